@@ -63,6 +63,26 @@ fn test_c_api_call_exec_program_with_print() {
     );
 }
 
+#[test]
+fn test_c_api_call_build_program_and_exec_artifact() {
+    test_c_api_without_wrapper::<BuildProgramArgs, BuildProgramResult>(
+        "KclService.BuildProgram",
+        "build-program.json",
+        "build-program.response.json",
+    );
+    test_c_api_without_wrapper::<ExecArtifactArgs, ExecProgramResult>(
+        "KclService.ExecArtifact",
+        "exec-artifact.json",
+        "exec-artifact.response.json",
+    );
+    fs::remove_file(
+        Path::new(TEST_DATA_PATH)
+            .join("option")
+            .join("main.artifact.json"),
+    )
+    .unwrap();
+}
+
 #[test]
 fn test_c_api_call_override_file() {
     let test_cases = [
@@ -94,6 +114,15 @@ fn test_c_api_call_override_file() {
     }
 }
 
+#[test]
+fn test_c_api_export_schema() {
+    test_c_api_without_wrapper::<ExportSchemaArgs, ExportSchemaResult>(
+        "KclService.ExportSchema",
+        "export-schema.json",
+        "export-schema.response.json",
+    );
+}
+
 #[test]
 fn test_c_api_get_schema_type_mapping() {
     test_c_api::<GetSchemaTypeMappingArgs, GetSchemaTypeMappingResult, _>(
@@ -171,6 +200,26 @@ fn test_c_api_validate_code() {
     );
 }
 
+#[test]
+fn test_c_api_validate_code_collect_check_failures() {
+    test_c_api::<ValidateCodeArgs, ValidateCodeResult, _>(
+        "KclService.ValidateCode",
+        "validate-code-collect-check-failures.json",
+        "validate-code-collect-check-failures.response.json",
+        |r| {
+            // The data is validated in a temporary file.
+            r.err_message.clear();
+            for error in &mut r.errors {
+                for msg in &mut error.messages {
+                    if let Some(pos) = &mut msg.pos {
+                        pos.filename.clear();
+                    }
+                }
+            }
+        },
+    );
+}
+
 #[test]
 fn test_c_api_validate_code_file() {
     test_c_api_without_wrapper::<ValidateCodeArgs, ValidateCodeResult>(
@@ -180,6 +229,16 @@ fn test_c_api_validate_code_file() {
     );
 }
 
+#[test]
+fn test_c_api_list_dep_files() {
+    test_c_api::<ListDepFilesArgs, ListDepFilesResult, _>(
+        "KclService.ListDepFiles",
+        "list-dep-files.json",
+        "list-dep-files.response.json",
+        |r| r.pkgroot.clear(),
+    );
+}
+
 #[test]
 fn test_c_api_load_settings_files() {
     test_c_api_without_wrapper::<LoadSettingsFilesArgs, LoadSettingsFilesResult>(
@@ -396,3 +455,53 @@ fn test_call_get_version() {
     let result = call(name, args).unwrap();
     assert!(!result.starts_with(b"ERROR"))
 }
+
+#[test]
+fn test_call_build_program_and_exec_artifact() {
+    let output = Path::new(TEST_DATA_PATH)
+        .join("option")
+        .join("main.call.artifact.json");
+    let args = BuildProgramArgs {
+        exec_args: Some(ExecProgramArgs {
+            work_dir: TEST_DATA_PATH.to_string(),
+            k_filename_list: vec!["option/main.k".to_string()],
+            ..Default::default()
+        }),
+        output: output.display().to_string(),
+    };
+    let result = call(b"KclService.BuildProgram", &args.encode_to_vec()).unwrap();
+    let artifact = BuildProgramResult::decode(result.as_slice())
+        .unwrap_or_else(|_| panic!("{}", String::from_utf8_lossy(&result)));
+    assert_eq!(artifact.path, output.display().to_string());
+
+    let args = ExecArtifactArgs {
+        path: artifact.path,
+        exec_args: Some(ExecProgramArgs {
+            work_dir: TEST_DATA_PATH.to_string(),
+            args: vec![
+                Argument {
+                    name: "key1".to_string(),
+                    value: "\"value1\"".to_string(),
+                },
+                Argument {
+                    name: "key2".to_string(),
+                    value: "2".to_string(),
+                },
+                Argument {
+                    name: "metadata-key".to_string(),
+                    value: "\"value3\"".to_string(),
+                },
+            ],
+            ..Default::default()
+        }),
+    };
+    let result = call(b"KclService.ExecArtifact", &args.encode_to_vec()).unwrap();
+    fs::remove_file(&output).unwrap();
+    let result = ExecProgramResult::decode(result.as_slice())
+        .unwrap_or_else(|_| panic!("{}", String::from_utf8_lossy(&result)));
+    assert_eq!(result.err_message, "");
+    assert_eq!(
+        result.yaml_result,
+        "a: value1\nb: 2\nc:\n  metadata:\n    key: value3"
+    );
+}
@@ -174,15 +174,19 @@ pub(crate) fn kcl_get_service_fn_ptr_by_name(name: &str) -> u64 {
         "KclService.ListOptions" => list_options as *const () as u64,
         "KclService.ListVariables" => list_variables as *const () as u64,
         "KclService.ExecProgram" => exec_program as *const () as u64,
+        "KclService.BuildProgram" => build_program as *const () as u64,
+        "KclService.ExecArtifact" => exec_artifact as *const () as u64,
         "KclService.OverrideFile" => override_file as *const () as u64,
         "KclService.GetSchemaTypeMapping" => get_schema_type_mapping as *const () as u64,
         "KclService.GetSchemaTypeMappingUnderPath" => {
             get_schema_type_mapping_under_path as *const () as u64
         }
+        "KclService.ExportSchema" => export_schema as *const () as u64,
         "KclService.FormatCode" => format_code as *const () as u64,
         "KclService.FormatPath" => format_path as *const () as u64,
         "KclService.LintPath" => lint_path as *const () as u64,
         "KclService.ValidateCode" => validate_code as *const () as u64,
+        "KclService.ListDepFiles" => list_dep_files as *const () as u64,
         "KclService.LoadSettingsFiles" => load_settings_files as *const () as u64,
         "KclService.Rename" => rename as *const () as u64,
         "KclService.RenameCode" => rename_code as *const () as u64,
@@ -409,6 +413,71 @@ pub(crate) fn exec_program(
     )
 }
 
+/// build_program provides users with the ability to build the KCL program to an artifact,
+/// which can be executed many times with different arguments by exec_artifact.
+///
+/// # Parameters
+///
+/// `serv`: [*mut kcl_service]
+///     The pointer of &\[[KclServiceImpl]]
+///
+///
+/// `args`: [*const c_char]
+///     the items and compile parameters selected by the user in the KCL CLI and the output
+///     path of the artifact serialized as protobuf byte sequence
+///
+/// # Returns
+///
+/// result: [*const c_char]
+///     Result of the call serialized as protobuf byte sequence
+pub(crate) fn build_program(
+    serv: *mut kcl_service,
+    args: *const c_char,
+    args_len: usize,
+    result_len: *mut usize,
+) -> *const c_char {
+    call!(
+        serv,
+        args,
+        args_len,
+        result_len,
+        BuildProgramArgs,
+        build_program
+    )
+}
+
+/// exec_artifact provides users with the ability to execute the KCL artifact built by
+/// build_program.
+///
+/// # Parameters
+///
+/// `serv`: [*mut kcl_service]
+///     The pointer of &\[[KclServiceImpl]]
+///
+///
+/// `args`: [*const c_char]
+///     the artifact path and the execute parameters serialized as protobuf byte sequence
+///
+/// # Returns
+///
+/// result: [*const c_char]
+///     Result of the call serialized as protobuf byte sequence
+pub(crate) fn exec_artifact(
+    serv: *mut kcl_service,
+    args: *const c_char,
+    args_len: usize,
+    result_len: *mut usize,
+) -> *const c_char {
+    call!(
+        serv,
+        args,
+        args_len,
+        result_len,
+        ExecArtifactArgs,
+        exec_artifact
+    )
+}
+
 /// override_file enable users override existing KCL file with specific KCl code
 ///
 /// # Parameters
@@ -489,6 +558,30 @@ pub(crate) fn get_schema_type_mapping_under_path(
     )
 }
 
+/// Export schemas as a JSON Schema 2020-12 or an OpenAPI v3 document.
+///
+/// # Parameters
+/// exec_args: [Option<ExecProgramArgs>]. The arguments of the program containing the schemas.
+///
+/// schema_name: [&str]. The schema name, when the schema name is empty, all schemas are exported.
+///
+/// format: [&str]. The document format, `jsonschema` (default) or `openapi`.
+pub(crate) fn export_schema(
+    serv: *mut kcl_service,
+    args: *const c_char,
+    args_len: usize,
+    result_len: *mut usize,
+) -> *const c_char {
+    call!(
+        serv,
+        args,
+        args_len,
+        result_len,
+        ExportSchemaArgs,
+        export_schema
+    )
+}
+
 /// Service for formatting a code source and returns the formatted source and
 /// whether the source is changed.
 pub(crate) fn format_code(
@@ -554,6 +647,23 @@ pub(crate) fn validate_code(
     )
 }
 
+/// Service for listing the dependency files of the KCL package in the work directory.
+pub(crate) fn list_dep_files(
+    serv: *mut kcl_service,
+    args: *const c_char,
+    args_len: usize,
+    result_len: *mut usize,
+) -> *const c_char {
+    call!(
+        serv,
+        args,
+        args_len,
+        result_len,
+        ListDepFilesArgs,
+        list_dep_files
+    )
+}
+
 /// Service for building setting file config from args.
 pub(crate) fn load_settings_files(
     serv: *mut kcl_service,
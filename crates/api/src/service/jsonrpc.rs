@@ -123,6 +123,22 @@ fn register_kcl_service(io: &mut IoHandler) {
         };
         futures::future::ready(catch!(kcl_service_impl, args, exec_program))
     });
+    io.add_method("KclService.BuildProgram", |params: Params| {
+        let kcl_service_impl = KclServiceImpl::default();
+        let args: BuildProgramArgs = match params.parse() {
+            Ok(val) => val,
+            Err(err) => return futures::future::ready(Err(err)),
+        };
+        futures::future::ready(catch!(kcl_service_impl, args, build_program))
+    });
+    io.add_method("KclService.ExecArtifact", |params: Params| {
+        let kcl_service_impl = KclServiceImpl::default();
+        let args: ExecArtifactArgs = match params.parse() {
+            Ok(val) => val,
+            Err(err) => return futures::future::ready(Err(err)),
+        };
+        futures::future::ready(catch!(kcl_service_impl, args, exec_artifact))
+    });
     io.add_method("KclService.OverrideFile", |params: Params| {
         let kcl_service_impl = KclServiceImpl::default();
         let args: OverrideFileArgs = match params.parse() {
@@ -154,6 +170,14 @@ fn register_kcl_service(io: &mut IoHandler) {
             ))
         },
     );
+    io.add_method("KclService.ExportSchema", |params: Params| {
+        let kcl_service_impl = KclServiceImpl::default();
+        let args: ExportSchemaArgs = match params.parse() {
+            Ok(val) => val,
+            Err(err) => return futures::future::ready(Err(err)),
+        };
+        futures::future::ready(catch!(kcl_service_impl, args, export_schema))
+    });
     io.add_method("KclService.FormatCode", |params: Params| {
         let kcl_service_impl = KclServiceImpl::default();
         let args: FormatCodeArgs = match params.parse() {
@@ -186,6 +210,14 @@ fn register_kcl_service(io: &mut IoHandler) {
         };
         futures::future::ready(catch!(kcl_service_impl, args, validate_code))
     });
+    io.add_method("KclService.ListDepFiles", |params: Params| {
+        let kcl_service_impl = KclServiceImpl::default();
+        let args: ListDepFilesArgs = match params.parse() {
+            Ok(val) => val,
+            Err(err) => return futures::future::ready(Err(err)),
+        };
+        futures::future::ready(catch!(kcl_service_impl, args, list_dep_files))
+    });
     io.add_method("KclService.LoadSettingsFiles", |params: Params| {
         let kcl_service_impl = KclServiceImpl::default();
         let args: LoadSettingsFilesArgs = match params.parse() {
@@ -248,10 +280,12 @@ fn register_builtin_service(io: &mut IoHandler) {
                 "KclService.GetSchemaType".to_owned(),
                 "KclService.GetFullSchemaType".to_owned(),
                 "KclService.GetSchemaTypeMapping".to_owned(),
+                "KclService.ExportSchema".to_owned(),
                 "KclService.FormatCode".to_owned(),
                 "KclService.FormatPath".to_owned(),
                 "KclService.LintPath".to_owned(),
                 "KclService.ValidateCode".to_owned(),
+                "KclService.ListDepFiles".to_owned(),
                 "KclService.LoadSettingsFiles".to_owned(),
                 "KclService.Rename".to_owned(),
                 "KclService.RenameCode".to_owned(),
@@ -7,6 +7,8 @@ use crate::gpyrpc::{self, *};
 
 use kcl_ast::ast::SerializeProgram;
 use kcl_config::settings::build_settings_pathbuf;
+use kcl_driver::deps::{ListDepFilesOptions, list_dep_files};
+use kcl_driver::toolchain;
 use kcl_language_server::rename;
 use kcl_loader::option::list_options;
 use kcl_loader::{LoadPackageOptions, load_packages_with_cache};
@@ -17,21 +19,21 @@ use kcl_parser::entry::{canonicalize_input_file, get_normalized_k_files_from_pat
 use kcl_parser::load_program;
 use kcl_parser::parse_single_file;
 use kcl_query::GetSchemaOption;
+use kcl_query::export::SchemaFormat;
 use kcl_query::override_file;
 use kcl_query::query::CompilationOptions;
 use kcl_query::query::{get_full_schema_type, get_full_schema_type_under_path};
 use kcl_query::selector::{ListOptions, list_variables};
-use kcl_runner::exec_program;
+use kcl_runner::{build_program, exec_artifact, exec_program};
 use kcl_sema::core::global_state::GlobalState;
 use kcl_sema::resolver::Options;
 use kcl_sema::resolver::scope::KCLScopeCache;
 use kcl_tools::format::{FormatOptions, format, format_source};
 use kcl_tools::lint::lint_files;
 use kcl_tools::testing;
-use kcl_tools::testing::TestRun;
 use kcl_tools::vet::validator::LoaderKind;
 use kcl_tools::vet::validator::ValidateOption;
-use kcl_tools::vet::validator::validate;
+use kcl_tools::vet::validator::validate_documents;
 use tempfile::NamedTempFile;
 
 use super::into::*;
@@ -221,9 +223,9 @@ impl KclServiceImpl {
     /// assert_eq!(result.type_errors.len(), 0);
     /// assert_eq!(result.symbols.len(), 12);
     /// assert_eq!(result.scopes.len(), 3);
-    /// assert_eq!(result.node_symbol_map.len(), 193);
-    /// assert_eq!(result.symbol_node_map.len(), 193);
-    /// assert_eq!(result.fully_qualified_name_map.len(), 204);
+    /// assert_eq!(result.node_symbol_map.len(), 230);
+    /// assert_eq!(result.symbol_node_map.len(), 230);
+    /// assert_eq!(result.fully_qualified_name_map.len(), 243);
     /// assert_eq!(result.pkg_scope_map.len(), 3);
     /// ```
     #[inline]
@@ -459,6 +461,27 @@ impl KclServiceImpl {
     /// let exec_result = serv.exec_program(args).unwrap();
     /// assert_eq!(exec_result.yaml_result, "alice:\n  age: 18");
     ///
+    /// // Collect all the check failures
+    /// let args = &ExecProgramArgs {
+    ///     k_filename_list: vec!["file.k".to_string()],
+    ///     k_code_list: vec![r#"
+    /// schema Person:
+    ///     name: str
+    ///     age: int
+    ///
+    ///     check:
+    ///         len(name) > 0
+    ///         age >= 0
+    ///
+    /// alice = Person {name = "", age = -1}
+    /// "#.to_string()],
+    ///     collect_check_failures: true,
+    ///     ..Default::default()
+    /// };
+    /// let exec_result = serv.exec_program(args).unwrap();
+    /// assert_eq!(exec_result.errors.len(), 2);
+    /// assert!(exec_result.yaml_result.is_empty());
+    ///
     /// // Error case
     /// let args = &ExecProgramArgs {
     ///     k_filename_list: vec!["invalid_file.k".to_string()],
@@ -485,6 +508,92 @@ impl KclServiceImpl {
             yaml_result: result.yaml_result,
             log_message: result.log_message,
             err_message: result.err_message,
+            errors: result
+                .diagnostics
+                .into_iter()
+                .map(|diag| diag.into_error())
+                .collect(),
+        })
+    }
+
+    /// Build the KCL program to an artifact, which can be executed many times with different
+    /// arguments using [KclServiceImpl::exec_artifact]. When the output path is empty, the
+    /// artifact is saved to a temporary file.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use kcl_api::service::service_impl::KclServiceImpl;
+    /// use kcl_api::gpyrpc::*;
+    /// use std::path::Path;
+    ///
+    /// let serv = KclServiceImpl::default();
+    /// let exec_args = ExecProgramArgs {
+    ///     work_dir: Path::new(".").join("src").join("testdata").canonicalize().unwrap().display().to_string(),
+    ///     k_filename_list: vec!["test.k".to_string()],
+    ///     ..Default::default()
+    /// };
+    /// let artifact = serv.build_program(&BuildProgramArgs {
+    ///     exec_args: Some(exec_args.clone()),
+    ///     output: "".to_string(),
+    /// }).unwrap();
+    /// assert!(Path::new(&artifact.path).exists());
+    ///
+    /// let exec_result = serv.exec_artifact(&ExecArtifactArgs {
+    ///     path: artifact.path.clone(),
+    ///     exec_args: Some(exec_args),
+    /// }).unwrap();
+    /// assert_eq!(exec_result.yaml_result, "alice:\n  age: 18");
+    /// std::fs::remove_file(&artifact.path).unwrap();
+    /// ```
+    pub fn build_program(&self, args: &BuildProgramArgs) -> anyhow::Result<BuildProgramResult> {
+        let exec_args = transform_exec_para(&args.exec_args, self.plugin_agent)?;
+        let output = if args.output.is_empty() {
+            let (_, path) = tempfile::Builder::new()
+                .prefix("kcl_artifact_")
+                .suffix(".json")
+                .tempfile()?
+                .keep()?;
+            path
+        } else {
+            PathBuf::from(&args.output)
+        };
+        let artifact = build_program(ParseSessionRef::default(), &exec_args, Some(output))?;
+        Ok(BuildProgramResult {
+            path: artifact.get_path().to_string(),
+        })
+    }
+
+    /// Execute the KCL artifact built by [KclServiceImpl::build_program] with args.
+    /// **Note that it is not thread safe.**
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use kcl_api::service::service_impl::KclServiceImpl;
+    /// use kcl_api::gpyrpc::*;
+    ///
+    /// let serv = KclServiceImpl::default();
+    /// let args = &ExecArtifactArgs {
+    ///     path: "./src/testdata/not_found_artifact.json".to_string(),
+    ///     ..Default::default()
+    /// };
+    /// let error = serv.exec_artifact(args).unwrap_err();
+    /// assert!(error.to_string().contains("Failed to read the artifact"), "{error}");
+    /// ```
+    pub fn exec_artifact(&self, args: &ExecArtifactArgs) -> anyhow::Result<ExecProgramResult> {
+        let exec_args = transform_exec_para(&args.exec_args, self.plugin_agent)?;
+        let result = exec_artifact(&args.path, &exec_args)?;
+        Ok(ExecProgramResult {
+            json_result: result.json_result,
+            yaml_result: result.yaml_result,
+            log_message: result.log_message,
+            err_message: result.err_message,
+            errors: result
+                .diagnostics
+                .into_iter()
+                .map(|diag| diag.into_error())
+                .collect(),
         })
     }
 
@@ -655,6 +764,52 @@ impl KclServiceImpl {
         })
     }
 
+    /// Service for exporting the schemas as a JSON Schema 2020-12 or an OpenAPI v3 document.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use kcl_api::service::service_impl::KclServiceImpl;
+    /// use kcl_api::gpyrpc::*;
+    /// use std::path::Path;
+    ///
+    /// let serv = KclServiceImpl::default();
+    /// let work_dir_parent = Path::new(".").join("src").join("testdata").join("export_schema");
+    /// let args = ExecProgramArgs {
+    ///     k_filename_list: vec![
+    ///         work_dir_parent.join("main.k").canonicalize().unwrap().display().to_string()
+    ///     ],
+    ///     ..Default::default()
+    /// };
+    ///
+    /// let result = serv.export_schema(&ExportSchemaArgs {
+    ///     exec_args: Some(args),
+    ///     schema_name: "Person".to_string(),
+    ///     format: "openapi".to_string(),
+    /// }).unwrap();
+    /// assert!(result.content.contains("\"openapi\": \"3.0.3\""));
+    /// assert!(result.content.contains("\"Person\""));
+    /// ```
+    pub fn export_schema(&self, args: &ExportSchemaArgs) -> anyhow::Result<ExportSchemaResult> {
+        let exec_args = transform_exec_para(&args.exec_args, self.plugin_agent)?;
+        let format: SchemaFormat = args.format.parse()?;
+        let schema_name = (!args.schema_name.is_empty()).then_some(args.schema_name.as_str());
+        let content = kcl_query::export::export_schema(
+            schema_name,
+            format,
+            CompilationOptions {
+                paths: exec_args.clone().k_filename_list,
+                loader_opts: Some(exec_args.get_load_program_options()),
+                resolve_opts: Options {
+                    resolve_val: true,
+                    ..Default::default()
+                },
+                get_schema_opts: GetSchemaOption::Definitions,
+            },
+        )?;
+        Ok(ExportSchemaResult { content })
+    }
+
     /// Service for formatting a code source and returns the formatted source and
     /// whether the source is changed.
     ///
@@ -816,25 +971,74 @@ impl KclServiceImpl {
             .map(|pkg| (pkg.pkg_name.clone(), pkg.pkg_path.clone()))
             .collect();
 
-        let (success, err_message) = match validate(ValidateOption::new(
-            transform_str_para(&args.schema),
-            args.attribute_name.clone(),
-            file_path,
-            match args.format.to_lowercase().as_str() {
-                "yaml" | "yml" => LoaderKind::YAML,
-                "json" => LoaderKind::JSON,
-                _ => LoaderKind::JSON,
+        let result = validate_documents(
+            ValidateOption::new(
+                transform_str_para(&args.schema),
+                args.attribute_name.clone(),
+                file_path,
+                match args.format.to_lowercase().as_str() {
+                    "yaml" | "yml" => LoaderKind::YAML,
+                    "json" => LoaderKind::JSON,
+                    "jsonl" | "ndjson" => LoaderKind::JSONL,
+                    "toml" => LoaderKind::TOML,
+                    "csv" => LoaderKind::CSV,
+                    _ => LoaderKind::JSON,
+                },
+                transform_str_para(&args.file),
+                transform_str_para(&args.code),
+                dep_pkgs_map,
+            )
+            .collect_check_failures(args.collect_check_failures),
+        );
+        Ok(match result {
+            Ok(result) => ValidateCodeResult {
+                success: result.is_valid(),
+                err_message: result.err_message().unwrap_or_default(),
+                errors: result
+                    .diagnostics()
+                    .into_iter()
+                    .map(|diag| diag.into_error())
+                    .collect(),
+            },
+            Err(err) => ValidateCodeResult {
+                success: false,
+                err_message: err.to_string(),
+                ..Default::default()
             },
-            transform_str_para(&args.file),
-            transform_str_para(&args.code),
-            dep_pkgs_map,
-        )) {
-            Ok(success) => (success, "".to_string()),
-            Err(err) => (false, err.to_string()),
-        };
-        Ok(ValidateCodeResult {
-            success,
-            err_message,
+        })
+    }
+
+    /// Service for listing the dependency files of the KCL package in the work directory,
+    /// which are the files of the package itself and of all the packages it imports.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use kcl_api::service::service_impl::KclServiceImpl;
+    /// use kcl_api::gpyrpc::*;
+    ///
+    /// let serv = KclServiceImpl::default();
+    /// let result = serv.list_dep_files(&ListDepFilesArgs {
+    ///     work_dir: "./src/testdata/list_dep_files".to_string(),
+    ///     ..Default::default()
+    /// }).unwrap();
+    /// assert_eq!(result.pkgpath, "");
+    /// assert_eq!(result.files.len(), 2);
+    /// ```
+    pub fn list_dep_files(&self, args: &ListDepFilesArgs) -> anyhow::Result<ListDepFilesResult> {
+        let dep_files = list_dep_files(
+            &toolchain::default(),
+            &args.work_dir,
+            &ListDepFilesOptions {
+                use_abs_path: args.use_abs_path,
+                include_all: args.include_all,
+                use_fast_parser: args.use_fast_parser,
+            },
+        )?;
+        Ok(ListDepFilesResult {
+            pkgroot: dep_files.pkgroot,
+            pkgpath: dep_files.pkgpath,
+            files: dep_files.files,
         })
     }
 
@@ -975,6 +1179,23 @@ impl KclServiceImpl {
     /// assert!(result.info[0].error.is_empty());
     /// // Failed case
     /// assert!(result.info[1].error.is_empty());
+    ///
+    /// // Collect the line and branch coverage of the non-test files.
+    /// let result = serv.test(&TestArgs {
+    ///     pkg_list: vec!["./src/testdata/testing/module/...".to_string()],
+    ///     coverage: true,
+    ///     ..TestArgs::default()
+    /// }).unwrap();
+    /// assert!(result.coverage_lcov.starts_with("TN:\nSF:"));
+    /// assert_eq!(result.coverage.len(), 1);
+    ///
+    /// // Emit a JUnit XML report for CI systems.
+    /// let result = serv.test(&TestArgs {
+    ///     pkg_list: vec!["./src/testdata/testing/module/...".to_string()],
+    ///     report_format: "junit".to_string(),
+    ///     ..TestArgs::default()
+    /// }).unwrap();
+    /// assert!(result.report.contains(r#"<testsuites name="kcl" tests="2" failures="0""#));
     /// ```
     pub fn test(&self, args: &TestArgs) -> anyhow::Result<TestResult> {
         let mut result = TestResult::default();
@@ -983,24 +1204,60 @@ impl KclServiceImpl {
             exec_args,
             run_regexp: args.run_regexp.clone(),
             fail_fast: args.fail_fast,
+            coverage: args.coverage,
+            jobs: args.jobs as usize,
+            update_golden: args.update_golden,
+        };
+        let report_format = if args.report_format.is_empty() {
+            None
+        } else {
+            Some(args.report_format.parse::<testing::TestReportFormat>()?)
         };
+        let mut suites = vec![];
         for pkg in &args.pkg_list {
-            let suites = testing::load_test_suites(pkg, &opts)?;
-            for suite in &suites {
-                let suite_result = suite.run(&opts)?;
-                for (name, info) in &suite_result.info {
-                    result.info.push(TestCaseInfo {
-                        name: name.clone(),
-                        error: info
-                            .error
-                            .as_ref()
-                            .map(|e| e.to_string())
-                            .unwrap_or_default(),
-                        duration: info.duration.as_micros() as u64,
-                        log_message: info.log_message.clone(),
-                    })
-                }
+            suites.append(&mut testing::load_test_suites(pkg, &opts)?);
+        }
+        let mut coverage: Option<testing::Coverage> = None;
+        let mut report = testing::TestReport::default();
+        for (suite, mut suite_result) in
+            suites.iter().zip(testing::run_test_suites(&suites, &opts)?)
+        {
+            if let Some(suite_coverage) = suite_result.coverage.take() {
+                coverage
+                    .get_or_insert_with(Default::default)
+                    .merge(suite_coverage);
+            }
+            for (name, info) in &suite_result.info {
+                result.info.push(TestCaseInfo {
+                    name: name.clone(),
+                    error: info
+                        .error
+                        .as_ref()
+                        .map(|e| e.to_string())
+                        .unwrap_or_default(),
+                    duration: info.duration.as_micros() as u64,
+                    log_message: info.log_message.clone(),
+                    pkg: suite.pkg.clone(),
+                })
             }
+            report.add(&suite.pkg, suite_result);
+        }
+        if let Some(format) = report_format {
+            result.report = report.emit(format)?;
+        }
+        if let Some(coverage) = coverage {
+            result.coverage_lcov = coverage.to_lcov();
+            result.coverage = coverage
+                .files
+                .iter()
+                .map(|(filename, file)| FileCoverage {
+                    filename: filename.clone(),
+                    lines_found: file.lines_found() as u64,
+                    lines_hit: file.lines_hit() as u64,
+                    branches_found: file.branches_found() as u64,
+                    branches_hit: file.branches_hit() as u64,
+                })
+                .collect();
         }
         Ok(result)
     }
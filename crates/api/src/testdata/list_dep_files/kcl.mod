@@ -0,0 +1,4 @@
+[package]
+name = "list_dep_files"
+edition = "*"
+version = "0.0.1"
@@ -32,6 +32,25 @@ macro_rules! walk_if {
     };
 }
 
+#[macro_export]
+macro_rules! walk_node_list {
+    ($walker: expr, $method: ident, $list: expr) => {
+        for elem in &$list {
+            $walker.$method(elem)
+        }
+    };
+}
+
+#[macro_export]
+macro_rules! walk_node_if {
+    ($walker: expr, $method: ident, $value: expr) => {
+        match &$value {
+            Some(v) => $walker.$method(v),
+            None => (),
+        }
+    };
+}
+
 #[macro_export]
 macro_rules! walk_list_mut {
     ($walker: ident, $method: ident, $list: expr) => {
@@ -1066,7 +1085,7 @@ pub fn walk_module<'ctx, V: Walker<'ctx>>(walker: &mut V, module: &'ctx ast::Mod
 pub trait MutSelfWalker {
     fn walk_expr_stmt(&mut self, expr_stmt: &ast::ExprStmt) {
         for expr in &expr_stmt.exprs {
-            self.walk_expr(&expr.node)
+            self.walk_expr_node(expr)
         }
     }
 
@@ -1081,28 +1100,28 @@ pub trait MutSelfWalker {
         for target in &assign_stmt.targets {
             self.walk_target(&target.node)
         }
-        self.walk_expr(&assign_stmt.value.node);
+        self.walk_expr_node(&assign_stmt.value);
     }
     fn walk_aug_assign_stmt(&mut self, aug_assign_stmt: &ast::AugAssignStmt) {
         self.walk_target(&aug_assign_stmt.target.node);
-        self.walk_expr(&aug_assign_stmt.value.node);
+        self.walk_expr_node(&aug_assign_stmt.value);
     }
     fn walk_assert_stmt(&mut self, assert_stmt: &ast::AssertStmt) {
-        self.walk_expr(&assert_stmt.test.node);
-        walk_if!(self, walk_expr, assert_stmt.if_cond);
-        walk_if!(self, walk_expr, assert_stmt.msg);
+        self.walk_expr_node(&assert_stmt.test);
+        walk_node_if!(self, walk_expr_node, assert_stmt.if_cond);
+        walk_node_if!(self, walk_expr_node, assert_stmt.msg);
     }
     fn walk_if_stmt(&mut self, if_stmt: &ast::IfStmt) {
-        self.walk_expr(&if_stmt.cond.node);
-        walk_list!(self, walk_stmt, if_stmt.body);
-        walk_list!(self, walk_stmt, if_stmt.orelse);
+        self.walk_expr_node(&if_stmt.cond);
+        walk_node_list!(self, walk_stmt_node, if_stmt.body);
+        walk_node_list!(self, walk_stmt_node, if_stmt.orelse);
     }
     fn walk_import_stmt(&mut self, _import_stmt: &ast::ImportStmt) {
         // Nothing to do
     }
     fn walk_schema_attr(&mut self, schema_attr: &ast::SchemaAttr) {
         walk_list!(self, walk_call_expr, schema_attr.decorators);
-        walk_if!(self, walk_expr, schema_attr.value);
+        walk_node_if!(self, walk_expr_node, schema_attr.value);
     }
     fn walk_schema_stmt(&mut self, schema_stmt: &ast::SchemaStmt) {
         walk_if!(self, walk_identifier, schema_stmt.parent_name);
@@ -1110,12 +1129,12 @@ pub trait MutSelfWalker {
         walk_if!(self, walk_arguments, schema_stmt.args);
         if let Some(schema_index_signature) = &schema_stmt.index_signature {
             let value = &schema_index_signature.node.value;
-            walk_if!(self, walk_expr, value);
+            walk_node_if!(self, walk_expr_node, value);
         }
         walk_list!(self, walk_identifier, schema_stmt.mixins);
         walk_list!(self, walk_call_expr, schema_stmt.decorators);
         walk_list!(self, walk_check_expr, schema_stmt.checks);
-        walk_list!(self, walk_stmt, schema_stmt.body);
+        walk_node_list!(self, walk_stmt_node, schema_stmt.body);
     }
     fn walk_rule_stmt(&mut self, rule_stmt: &ast::RuleStmt) {
         walk_list!(self, walk_identifier, rule_stmt.parent_rules);
@@ -1125,113 +1144,113 @@ pub trait MutSelfWalker {
         walk_if!(self, walk_identifier, rule_stmt.for_host_name);
     }
     fn walk_quant_expr(&mut self, quant_expr: &ast::QuantExpr) {
-        self.walk_expr(&quant_expr.target.node);
+        self.walk_expr_node(&quant_expr.target);
         walk_list!(self, walk_identifier, quant_expr.variables);
-        self.walk_expr(&quant_expr.test.node);
-        walk_if!(self, walk_expr, quant_expr.if_cond);
+        self.walk_expr_node(&quant_expr.test);
+        walk_node_if!(self, walk_expr_node, quant_expr.if_cond);
     }
     fn walk_if_expr(&mut self, if_expr: &ast::IfExpr) {
-        self.walk_expr(&if_expr.cond.node);
-        self.walk_expr(&if_expr.body.node);
-        self.walk_expr(&if_expr.orelse.node);
+        self.walk_expr_node(&if_expr.cond);
+        self.walk_expr_node(&if_expr.body);
+        self.walk_expr_node(&if_expr.orelse);
     }
     fn walk_unary_expr(&mut self, unary_expr: &ast::UnaryExpr) {
-        self.walk_expr(&unary_expr.operand.node);
+        self.walk_expr_node(&unary_expr.operand);
     }
     fn walk_binary_expr(&mut self, binary_expr: &ast::BinaryExpr) {
-        self.walk_expr(&binary_expr.left.node);
-        self.walk_expr(&binary_expr.right.node);
+        self.walk_expr_node(&binary_expr.left);
+        self.walk_expr_node(&binary_expr.right);
     }
     fn walk_selector_expr(&mut self, selector_expr: &ast::SelectorExpr) {
-        self.walk_expr(&selector_expr.value.node);
+        self.walk_expr_node(&selector_expr.value);
         self.walk_identifier(&selector_expr.attr.node);
     }
     fn walk_call_expr(&mut self, call_expr: &ast::CallExpr) {
-        self.walk_expr(&call_expr.func.node);
-        walk_list!(self, walk_expr, call_expr.args);
+        self.walk_expr_node(&call_expr.func);
+        walk_node_list!(self, walk_expr_node, call_expr.args);
         walk_list!(self, walk_keyword, call_expr.keywords);
     }
     fn walk_subscript(&mut self, subscript: &ast::Subscript) {
-        self.walk_expr(&subscript.value.node);
-        walk_if!(self, walk_expr, subscript.index);
-        walk_if!(self, walk_expr, subscript.lower);
-        walk_if!(self, walk_expr, subscript.upper);
-        walk_if!(self, walk_expr, subscript.step);
+        self.walk_expr_node(&subscript.value);
+        walk_node_if!(self, walk_expr_node, subscript.index);
+        walk_node_if!(self, walk_expr_node, subscript.lower);
+        walk_node_if!(self, walk_expr_node, subscript.upper);
+        walk_node_if!(self, walk_expr_node, subscript.step);
     }
     fn walk_paren_expr(&mut self, paren_expr: &ast::ParenExpr) {
-        self.walk_expr(&paren_expr.expr.node);
+        self.walk_expr_node(&paren_expr.expr);
     }
     fn walk_list_expr(&mut self, list_expr: &ast::ListExpr) {
-        walk_list!(self, walk_expr, list_expr.elts);
+        walk_node_list!(self, walk_expr_node, list_expr.elts);
     }
     fn walk_list_comp(&mut self, list_comp: &ast::ListComp) {
-        self.walk_expr(&list_comp.elt.node);
+        self.walk_expr_node(&list_comp.elt);
         walk_list!(self, walk_comp_clause, list_comp.generators);
     }
     fn walk_list_if_item_expr(&mut self, list_if_item_expr: &ast::ListIfItemExpr) {
-        self.walk_expr(&list_if_item_expr.if_cond.node);
-        walk_list!(self, walk_expr, list_if_item_expr.exprs);
-        walk_if!(self, walk_expr, list_if_item_expr.orelse);
+        self.walk_expr_node(&list_if_item_expr.if_cond);
+        walk_node_list!(self, walk_expr_node, list_if_item_expr.exprs);
+        walk_node_if!(self, walk_expr_node, list_if_item_expr.orelse);
     }
     fn walk_starred_expr(&mut self, starred_expr: &ast::StarredExpr) {
-        self.walk_expr(&starred_expr.value.node);
+        self.walk_expr_node(&starred_expr.value);
     }
     fn walk_dict_comp(&mut self, dict_comp: &ast::DictComp) {
         if let Some(key) = &dict_comp.entry.key {
-            self.walk_expr(&key.node);
+            self.walk_expr_node(key);
         }
-        self.walk_expr(&dict_comp.entry.value.node);
+        self.walk_expr_node(&dict_comp.entry.value);
         walk_list!(self, walk_comp_clause, dict_comp.generators);
     }
     fn walk_config_if_entry_expr(&mut self, config_if_entry_expr: &ast::ConfigIfEntryExpr) {
-        self.walk_expr(&config_if_entry_expr.if_cond.node);
+        self.walk_expr_node(&config_if_entry_expr.if_cond);
         for config_entry in &config_if_entry_expr.items {
-            walk_if!(self, walk_expr, config_entry.node.key);
-            self.walk_expr(&config_entry.node.value.node);
+            walk_node_if!(self, walk_expr_node, config_entry.node.key);
+            self.walk_expr_node(&config_entry.node.value);
         }
-        walk_if!(self, walk_expr, config_if_entry_expr.orelse);
+        walk_node_if!(self, walk_expr_node, config_if_entry_expr.orelse);
     }
     fn walk_comp_clause(&mut self, comp_clause: &ast::CompClause) {
         walk_list!(self, walk_identifier, comp_clause.targets);
-        self.walk_expr(&comp_clause.iter.node);
-        walk_list!(self, walk_expr, comp_clause.ifs);
+        self.walk_expr_node(&comp_clause.iter);
+        walk_node_list!(self, walk_expr_node, comp_clause.ifs);
     }
     fn walk_schema_expr(&mut self, schema_expr: &ast::SchemaExpr) {
         self.walk_identifier(&schema_expr.name.node);
-        walk_list!(self, walk_expr, schema_expr.args);
+        walk_node_list!(self, walk_expr_node, schema_expr.args);
         walk_list!(self, walk_keyword, schema_expr.kwargs);
-        self.walk_expr(&schema_expr.config.node);
+        self.walk_expr_node(&schema_expr.config);
     }
     fn walk_config_expr(&mut self, config_expr: &ast::ConfigExpr) {
         for config_entry in &config_expr.items {
-            walk_if!(self, walk_expr, config_entry.node.key);
-            self.walk_expr(&config_entry.node.value.node);
+            walk_node_if!(self, walk_expr_node, config_entry.node.key);
+            self.walk_expr_node(&config_entry.node.value);
         }
     }
     fn walk_check_expr(&mut self, check_expr: &ast::CheckExpr) {
-        self.walk_expr(&check_expr.test.node);
-        walk_if!(self, walk_expr, check_expr.if_cond);
-        walk_if!(self, walk_expr, check_expr.msg);
+        self.walk_expr_node(&check_expr.test);
+        walk_node_if!(self, walk_expr_node, check_expr.if_cond);
+        walk_node_if!(self, walk_expr_node, check_expr.msg);
     }
     fn walk_lambda_expr(&mut self, lambda_expr: &ast::LambdaExpr) {
         walk_if!(self, walk_arguments, lambda_expr.args);
-        walk_list!(self, walk_stmt, lambda_expr.body);
+        walk_node_list!(self, walk_stmt_node, lambda_expr.body);
     }
     fn walk_keyword(&mut self, keyword: &ast::Keyword) {
         self.walk_identifier(&keyword.arg.node);
         if let Some(v) = &keyword.value {
-            self.walk_expr(&v.node)
+            self.walk_expr_node(v)
         }
     }
     fn walk_arguments(&mut self, arguments: &ast::Arguments) {
         walk_list!(self, walk_identifier, arguments.args);
         for default in arguments.defaults.iter().flatten() {
-            self.walk_expr(&default.node)
+            self.walk_expr_node(default)
         }
     }
     fn walk_compare(&mut self, compare: &ast::Compare) {
-        self.walk_expr(&compare.left.node);
-        walk_list!(self, walk_expr, compare.comparators);
+        self.walk_expr_node(&compare.left);
+        walk_node_list!(self, walk_expr_node, compare.comparators);
     }
     fn walk_identifier(&mut self, identifier: &ast::Identifier) {
         // Nothing to do.
@@ -1240,7 +1259,7 @@ pub trait MutSelfWalker {
     fn walk_target(&mut self, target: &ast::Target) {
         for path in target.paths.iter() {
             if let ast::MemberOrIndex::Index(index) = path {
-                self.walk_expr(&index.node)
+                self.walk_expr_node(index)
             }
         }
     }
@@ -1256,10 +1275,10 @@ pub trait MutSelfWalker {
         let _ = name_constant_lit;
     }
     fn walk_joined_string(&mut self, joined_string: &ast::JoinedString) {
-        walk_list!(self, walk_expr, joined_string.values);
+        walk_node_list!(self, walk_expr_node, joined_string.values);
     }
     fn walk_formatted_value(&mut self, formatted_value: &ast::FormattedValue) {
-        self.walk_expr(&formatted_value.value.node);
+        self.walk_expr_node(&formatted_value.value);
     }
     fn walk_comment(&mut self, comment: &ast::Comment) {
         // Nothing to do.
@@ -1270,7 +1289,15 @@ pub trait MutSelfWalker {
         let _ = missing_expr;
     }
     fn walk_module(&mut self, module: &ast::Module) {
-        walk_list!(self, walk_stmt, module.body)
+        walk_node_list!(self, walk_stmt_node, module.body)
+    }
+    /// Walks the statement node, override it to get the span of each statement in the traversal.
+    fn walk_stmt_node(&mut self, stmt: &ast::Node<ast::Stmt>) {
+        self.walk_stmt(&stmt.node)
+    }
+    /// Walks the expression node, override it to get the span of each expression in the traversal.
+    fn walk_expr_node(&mut self, expr: &ast::Node<ast::Expr>) {
+        self.walk_expr(&expr.node)
     }
     fn walk_stmt(&mut self, stmt: &ast::Stmt) {
         match stmt {
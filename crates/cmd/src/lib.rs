@@ -3,8 +3,10 @@
 #[macro_use]
 extern crate clap;
 
+pub mod lint;
 pub mod run;
 pub mod settings;
+pub mod test;
 pub(crate) mod util;
 
 #[cfg(test)]
@@ -15,7 +17,9 @@ use clap::{ArgAction, Command};
 use std::io;
 
 use anyhow::Result;
+use lint::lint_command;
 use run::run_command;
+use test::test_command;
 
 /// Run the KCL main command.
 pub fn main(args: &[&str]) -> Result<()> {
@@ -23,6 +27,8 @@ pub fn main(args: &[&str]) -> Result<()> {
     // Sub commands
     match matches.subcommand() {
         Some(("run", sub_matches)) => run_command(sub_matches, &mut io::stdout()),
+        Some(("lint", sub_matches)) => lint_command(sub_matches, &mut io::stdout()),
+        Some(("test", sub_matches)) => test_command(sub_matches, &mut io::stdout()),
         Some(("version", _)) => {
             println!("{}", kcl_version::get_version_info());
             Ok(())
@@ -33,7 +39,7 @@ pub fn main(args: &[&str]) -> Result<()> {
     }
 }
 
-/// Get the CLI application including a run command and
+/// Get the CLI application including a run command, a lint command, a test command and
 /// a gPRC server command to interacting with external systems.
 pub fn app() -> Command {
     Command::new("libkcl")
@@ -44,7 +50,8 @@ pub fn app() -> Command {
             .about("run")
             .arg(arg!([input] ... "Specify the input files to run").num_args(0..))
             .arg(arg!(output: -o --output <output> "Specify the YAML output file path"))
-            .arg(arg!(format: -f --format <format> "Specify the output format (yaml or json)"))
+            .arg(arg!(format: -f --format <format> "Specify the output format (yaml, json or toml)"))
+            .arg(arg!(error_format: --error_format <error_format> "Specify the diagnostic output format (text, json or sarif)"))
             .arg(arg!(setting: -Y --setting <setting> ... "Specify the input setting file").num_args(1..))
             .arg(arg!(verbose: -v --verbose "Print test information verbosely").action(ArgAction::Count))
             .arg(arg!(disable_none: -n --disable_none "Disable dumping None values"))
@@ -53,6 +60,7 @@ pub fn app() -> Command {
             .arg(arg!(sort_keys: -k --sort_keys "Sort result keys"))
             .arg(arg!(show_hidden: -H --show_hidden "Display hidden attributes"))
             .arg(arg!(fast_eval: -K --fast_eval "Use the fast evaluation mode"))
+            .arg(arg!(collect_check_failures: --collect_check_failures "Report all the schema check failures instead of stopping at the first one"))
             .arg(arg!(arguments: -D --argument <arguments> ... "Specify the top-level argument").num_args(1..))
             .arg(arg!(path_selector: -S --path_selector <path_selector> ... "Specify the path selector").num_args(1..))
             .arg(arg!(overrides: -O --overrides <overrides> ... "Specify the configuration override path and value").num_args(1..))
@@ -60,6 +68,29 @@ pub fn app() -> Command {
             .arg(arg!(recursive: -R --recursive "Compile the files directory recursively"))
             .arg(arg!(package_map: -E --external <package_map> ... "Mapping of package name and path where the package is located").num_args(1..)),
         )
+    .subcommand(
+        Command::new("lint")
+            .about("lint")
+            .arg(arg!([input] ... "Specify the input files to lint").num_args(0..))
+            .arg(arg!(error_format: --error_format <error_format> "Specify the diagnostic output format (text, json or sarif)"))
+            .arg(arg!(fix: --fix "Apply all the machine-applicable fixes in place"))
+            .arg(arg!(dry_run: --dry_run "Print the fixes as unified diffs without changing the files")),
+    )
+    .subcommand(
+        Command::new("test")
+            .about("test")
+            .arg(arg!([input] ... "Specify the package paths to test, e.g. ./... for all the packages").num_args(0..))
+            .arg(arg!(run: --run <run> "Specify the regular expression to filter the test cases to run"))
+            .arg(arg!(fail_fast: --fail_fast "Stop the test run on the first failure"))
+            .arg(arg!(jobs: -j --jobs <jobs> "Specify the maximum number of test cases run concurrently, defaults to the available parallelism").value_parser(value_parser!(usize)))
+            .arg(arg!(update_golden: --update_golden "Update the golden files with the test values instead of comparing them"))
+            .arg(arg!(report_format: --report_format <report_format> "Specify the test report format (junit or json)"))
+            .arg(arg!(output: -o --output <output> "Specify the test report file path"))
+            .arg(arg!(coverage: --coverage "Collect the line and branch coverage of the non-test files"))
+            .arg(arg!(coverage_output: --coverage_output <coverage_output> "Specify the LCOV coverage file path, which enables the coverage"))
+            .arg(arg!(arguments: -D --argument <arguments> ... "Specify the top-level argument").num_args(1..))
+            .arg(arg!(package_map: -E --external <package_map> ... "Mapping of package name and path where the package is located").num_args(1..)),
+    )
     .subcommand(Command::new("server").about("Start a rpc server for APIs"))
     .subcommand(Command::new("version").about("Show the KCL version"))
 }
@@ -0,0 +1,72 @@
+use anyhow::Result;
+use clap::ArgMatches;
+use kcl_error::{Diagnostic, DiagnosticFormat, DiagnosticId, Handler};
+use kcl_tools::fix::apply_fixes;
+use kcl_tools::lint::lint_files;
+use std::io::Write;
+
+use crate::util::strings_from_matches;
+
+/// Run the KCL lint command.
+///
+/// - Without `--fix`, the lint diagnostics are written in the given error format.
+/// - With `--fix`, all the machine-applicable suggestions are applied to the files in place,
+///   and the remaining diagnostics are written.
+/// - With `--dry_run`, the fixes are written as unified diffs without changing any file.
+pub fn lint_command<W: Write>(matches: &ArgMatches, writer: &mut W) -> Result<()> {
+    let files = strings_from_matches(matches, "input").unwrap_or_default();
+    let files: Vec<&str> = files.iter().map(|f| f.as_str()).collect();
+    let error_format = match matches.get_one::<String>("error_format") {
+        Some(f) => f.parse::<DiagnosticFormat>()?,
+        None => DiagnosticFormat::Text,
+    };
+    let dry_run = matches.get_flag("dry_run");
+    let fix = matches.get_flag("fix") || dry_run;
+
+    let (mut errors, mut warnings) = lint_files(&files, None);
+    if fix {
+        // Only the lint diagnostics are fixed, compile errors are reported as they are.
+        let diags: Vec<Diagnostic> = errors
+            .iter()
+            .chain(warnings.iter())
+            .filter(|diag| matches!(diag.code, Some(DiagnosticId::Warning(_))))
+            .cloned()
+            .collect();
+        let fixes = apply_fixes(diags)?;
+        if dry_run {
+            for file_fix in &fixes {
+                write!(writer, "{}", file_fix.diff())?;
+            }
+            return Ok(());
+        }
+        for file_fix in &fixes {
+            std::fs::write(&file_fix.filename, &file_fix.fixed)?;
+            writeln!(
+                writer,
+                "Fixed {} problem(s) in {}",
+                file_fix.applied, file_fix.filename
+            )?;
+        }
+        if !fixes.is_empty() {
+            (errors, warnings) = lint_files(&files, None);
+        }
+    }
+
+    let has_errors = !errors.is_empty();
+    let mut handler = Handler::default();
+    for diag in errors.into_iter().chain(warnings) {
+        handler.add_diagnostic(diag);
+    }
+    if !handler.diagnostics.is_empty() {
+        writeln!(
+            writer,
+            "{}",
+            handler.emit_to_string_with_format(error_format)?
+        )?;
+    }
+    if has_errors {
+        Err(anyhow::anyhow!("Lint failed with errors"))
+    } else {
+        Ok(())
+    }
+}
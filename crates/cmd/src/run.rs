@@ -2,9 +2,10 @@
 
 use anyhow::Result;
 use clap::ArgMatches;
-use kcl_error::StringError;
+use kcl_error::{Diagnostic, DiagnosticFormat, Handler, StringError};
 use kcl_parser::ParseSession;
 use kcl_runner::exec_program;
+use kcl_runtime::{Context, PanicInfo, ValueRef};
 use std::io::Write;
 use std::sync::Arc;
 
@@ -16,6 +17,10 @@ pub fn run_command<W: Write>(matches: &ArgMatches, writer: &mut W) -> Result<()>
     let settings = must_build_settings(matches);
     let output = settings.output();
     let format_opt = matches.get_one::<String>("format").map(|s| s.as_str());
+    let error_format = match matches.get_one::<String>("error_format") {
+        Some(f) => f.parse::<DiagnosticFormat>()?,
+        None => DiagnosticFormat::Text,
+    };
     let sess = Arc::new(ParseSession::default());
     match exec_program(sess.clone(), &settings.try_into()?) {
         Ok(result) => {
@@ -25,6 +30,9 @@ pub fn run_command<W: Write>(matches: &ArgMatches, writer: &mut W) -> Result<()>
             }
             // Output execute error message
             if !result.err_message.is_empty() {
+                if error_format != DiagnosticFormat::Text {
+                    return emit_diagnostics(&sess, &result.err_message, error_format, writer);
+                }
                 if !sess.0.diag_handler.has_errors()? {
                     sess.0.add_err(StringError(result.err_message))?;
                 }
@@ -32,11 +40,12 @@ pub fn run_command<W: Write>(matches: &ArgMatches, writer: &mut W) -> Result<()>
             }
             // Select output based on format option
             let output_str = match format_opt {
-                Some("json") => &result.json_result,
-                Some("yaml") | None => &result.yaml_result,
+                Some("json") => result.json_result,
+                Some("yaml") | None => result.yaml_result,
+                Some("toml") => json_result_to_toml(&result.json_result)?,
                 Some(f) => {
                     return Err(anyhow::anyhow!(
-                        "Invalid format '{}', expected 'yaml' or 'json'",
+                        "Invalid format '{}', expected 'yaml', 'json' or 'toml'",
                         f
                     ));
                 }
@@ -52,6 +61,9 @@ pub fn run_command<W: Write>(matches: &ArgMatches, writer: &mut W) -> Result<()>
         }
         // Other error message
         Err(msg) => {
+            if error_format != DiagnosticFormat::Text {
+                return emit_diagnostics(&sess, &msg.to_string(), error_format, writer);
+            }
             if !sess.0.diag_handler.has_errors()? {
                 sess.0.add_err(StringError(msg.to_string()))?;
             }
@@ -60,3 +72,33 @@ pub fn run_command<W: Write>(matches: &ArgMatches, writer: &mut W) -> Result<()>
     }
     Ok(())
 }
+
+/// Write the compile and runtime error diagnostics recorded in the session to the writer in
+/// the machine-readable format, and return the error message as the command error.
+fn emit_diagnostics<W: Write>(
+    sess: &ParseSession,
+    err_message: &str,
+    format: DiagnosticFormat,
+    writer: &mut W,
+) -> Result<()> {
+    let mut handler = Handler::default();
+    for diag in &sess.1.read().diagnostics {
+        handler.add_diagnostic(diag.clone());
+    }
+    // Errors such as file not found are not recorded as diagnostics.
+    if !handler.has_errors() {
+        handler.add_diagnostic(Diagnostic::from(PanicInfo::from(err_message)));
+    }
+    writeln!(writer, "{}", handler.emit_to_string_with_format(format)?)?;
+    Err(anyhow::anyhow!(err_message.to_string()))
+}
+
+/// Convert the JSON execution result to a TOML document.
+fn json_result_to_toml(json_result: &str) -> Result<String> {
+    if json_result.is_empty() {
+        return Ok(String::new());
+    }
+    let mut ctx = Context::new();
+    let value = ValueRef::from_json(&mut ctx, json_result)?;
+    Ok(value.to_toml_string()?.trim_end().to_string())
+}
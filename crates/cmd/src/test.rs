@@ -0,0 +1,115 @@
+use anyhow::{Result, anyhow};
+use clap::ArgMatches;
+use kcl_config::settings::{Config, SettingsFile};
+use kcl_driver::arguments::parse_key_value_pair;
+use kcl_runner::ExecProgramArgs;
+use kcl_tools::testing::{
+    Coverage, TestOptions, TestReport, TestReportFormat, load_test_suites, run_test_suites,
+};
+use std::io::Write;
+
+use crate::util::{hashmaps_from_matches, strings_from_matches};
+
+/// Run the KCL test command.
+///
+/// - Without `--report_format`, the result of each test case and a summary are written.
+/// - With `--report_format`, a JUnit XML or JSON report is written to the `--output` file,
+///   or to the writer when no output file is given.
+/// - With `--coverage` or `--coverage_output`, the coverage summary of each file is written
+///   unless the report is written to the writer, and the LCOV tracefile is written to the
+///   `--coverage_output` file.
+///
+/// An error is returned when any test case fails, after the results are written.
+pub fn test_command<W: Write>(matches: &ArgMatches, writer: &mut W) -> Result<()> {
+    let pkg_list = strings_from_matches(matches, "input").unwrap_or_else(|| vec![".".to_string()]);
+    let report_format = matches
+        .get_one::<String>("report_format")
+        .map(|f| f.parse::<TestReportFormat>())
+        .transpose()?;
+    let report_output = matches.get_one::<String>("output");
+    let coverage_output = matches.get_one::<String>("coverage_output");
+    let mut kcl_options = vec![];
+    for arg in strings_from_matches(matches, "arguments").unwrap_or_default() {
+        kcl_options.push(parse_key_value_pair(&arg)?);
+    }
+    let exec_args: ExecProgramArgs = SettingsFile {
+        kcl_cli_configs: Some(Config {
+            package_maps: hashmaps_from_matches(matches, "package_map").transpose()?,
+            ..Default::default()
+        }),
+        kcl_options: Some(kcl_options),
+    }
+    .try_into()?;
+    let opts = TestOptions {
+        exec_args,
+        run_regexp: matches
+            .get_one::<String>("run")
+            .cloned()
+            .unwrap_or_default(),
+        fail_fast: matches.get_flag("fail_fast"),
+        update_golden: matches.get_flag("update_golden"),
+        coverage: matches.get_flag("coverage") || coverage_output.is_some(),
+        jobs: matches
+            .get_one::<usize>("jobs")
+            .copied()
+            .unwrap_or_default(),
+    };
+
+    let mut suites = vec![];
+    for pkg in &pkg_list {
+        suites.append(&mut load_test_suites(pkg, &opts)?);
+    }
+    let results = run_test_suites(&suites, &opts)?;
+    let mut report = TestReport::default();
+    let mut coverage: Option<Coverage> = None;
+    for (suite, mut result) in suites.into_iter().zip(results) {
+        if report_format.is_none() {
+            for (name, info) in &result.info {
+                write!(writer, "{}", info.log_message)?;
+                match &info.error {
+                    Some(err) => {
+                        writeln!(writer, "{name}: FAIL ({:?})", info.duration)?;
+                        writeln!(writer, "{err}")?;
+                    }
+                    None => writeln!(writer, "{name}: PASS ({:?})", info.duration)?,
+                }
+            }
+        }
+        if let Some(suite_coverage) = result.coverage.take() {
+            coverage
+                .get_or_insert_with(Default::default)
+                .merge(suite_coverage);
+        }
+        report.add(suite.pkg, result);
+    }
+
+    match report_format {
+        Some(format) => {
+            let content = report.emit(format)?;
+            match report_output {
+                Some(output) => std::fs::write(output, content)?,
+                None => write!(writer, "{content}")?,
+            }
+        }
+        None => writeln!(
+            writer,
+            "{} passed, {} failed",
+            report.tests() - report.failures(),
+            report.failures()
+        )?,
+    }
+    if opts.coverage {
+        let coverage = coverage.unwrap_or_default();
+        if report_format.is_none() || report_output.is_some() {
+            write!(writer, "{}", coverage.summary())?;
+        }
+        if let Some(output) = coverage_output {
+            std::fs::write(output, coverage.to_lcov())?;
+        }
+    }
+    if report.failures() > 0 {
+        Err(anyhow!("Test failed"))
+    } else {
+        Ok(())
+    }
+}
@@ -13,8 +13,10 @@ use kcl_runner::{MapErrorResult, exec_program};
 
 use crate::{
     app,
+    lint::lint_command,
     run::run_command,
     settings::{build_settings, must_build_settings},
+    test::test_command,
     util::hashmaps_from_matches,
 };
 
@@ -181,6 +183,9 @@ fn test_run_command() {
     test_load_cache_with_different_pkg();
     test_kcl_path_is_sym_link();
     test_compile_two_kcl_mod();
+    test_run_command_with_format();
+    test_run_command_with_error_format();
+    test_run_command_with_collect_check_failures();
     test_main_pkg_not_found();
     test_multi_mod_file();
     test_instances_with_yaml();
@@ -400,6 +405,142 @@ fn test_compile_two_kcl_mod() {
     );
 }
 
+fn test_run_command_with_format() {
+    let test_case_path = PathBuf::from("./src/test_data/multimod/kcl1/main.k");
+    let cases = [
+        ("yaml", "kcl1: hello 1\n"),
+        ("json", "{\"kcl1\": \"hello 1\"}\n"),
+        ("toml", "kcl1 = \"hello 1\"\n"),
+    ];
+    for (format, expected) in cases {
+        let matches = app().arg_required_else_help(true).get_matches_from([
+            ROOT_CMD,
+            "run",
+            &test_case_path.display().to_string(),
+            "-f",
+            format,
+        ]);
+        let mut buf = Vec::new();
+        run_command(matches.subcommand_matches("run").unwrap(), &mut buf).unwrap();
+        assert_eq!(expected, String::from_utf8(buf).unwrap());
+    }
+
+    let matches = app().arg_required_else_help(true).get_matches_from([
+        ROOT_CMD,
+        "run",
+        &test_case_path.display().to_string(),
+        "-f",
+        "xml",
+    ]);
+    let mut buf = Vec::new();
+    assert!(run_command(matches.subcommand_matches("run").unwrap(), &mut buf).is_err());
+}
+
+fn test_run_command_with_error_format() {
+    let cases = [
+        (
+            "./src/test_data/fuzz_match/main.k",
+            "attribute 'a' not found in 'Person'",
+        ),
+        (
+            "./src/test_data/failed/keyword_argument_error.k",
+            "keyword argument 'ID' not found",
+        ),
+    ];
+    for (path, expected) in cases {
+        let test_case_path = PathBuf::from(path).canonicalize().unwrap();
+        let file = test_case_path.display().to_string();
+        // JSON Lines
+        let matches = app().arg_required_else_help(true).get_matches_from([
+            ROOT_CMD,
+            "run",
+            &file,
+            "--error_format",
+            "json",
+        ]);
+        let mut buf = Vec::new();
+        assert!(run_command(matches.subcommand_matches("run").unwrap(), &mut buf).is_err());
+        let output = String::from_utf8(buf).unwrap();
+        let diag: serde_json::Value = serde_json::from_str(output.lines().next().unwrap()).unwrap();
+        assert_eq!(diag["level"], "error");
+        let message = &diag["messages"][0];
+        assert!(
+            message["message"].as_str().unwrap().contains(expected),
+            "{output}"
+        );
+        assert_eq!(message["range"]["start"]["filename"], file.as_str());
+        // SARIF
+        let matches = app().arg_required_else_help(true).get_matches_from([
+            ROOT_CMD,
+            "run",
+            &file,
+            "--error_format",
+            "sarif",
+        ]);
+        let mut buf = Vec::new();
+        assert!(run_command(matches.subcommand_matches("run").unwrap(), &mut buf).is_err());
+        let sarif: serde_json::Value = serde_json::from_slice(&buf).unwrap();
+        let result = &sarif["runs"][0]["results"][0];
+        assert_eq!(result["level"], "error");
+        assert!(
+            result["message"]["text"]
+                .as_str()
+                .unwrap()
+                .contains(expected)
+        );
+        assert_eq!(
+            result["locations"][0]["physicalLocation"]["region"]["startLine"],
+            message["range"]["start"]["line"]
+        );
+    }
+
+    let matches = app().arg_required_else_help(true).get_matches_from([
+        ROOT_CMD,
+        "run",
+        "./src/test_data/multimod/kcl1/main.k",
+        "--error_format",
+        "xml",
+    ]);
+    let mut buf = Vec::new();
+    assert!(run_command(matches.subcommand_matches("run").unwrap(), &mut buf).is_err());
+}
+
+fn test_run_command_with_collect_check_failures() {
+    let test_case_path = PathBuf::from("./src/test_data/failed/check_failures.k")
+        .canonicalize()
+        .unwrap();
+    let file = test_case_path.display().to_string();
+    let run = |args: &[&str]| {
+        let matches = app().arg_required_else_help(true).get_matches_from(
+            [ROOT_CMD, "run", &file, "--error_format", "json"]
+                .iter()
+                .chain(args),
+        );
+        let mut buf = Vec::new();
+        assert!(run_command(matches.subcommand_matches("run").unwrap(), &mut buf).is_err());
+        String::from_utf8(buf).unwrap()
+    };
+    // Stop at the first failing check by default.
+    assert_eq!(run(&[]).lines().count(), 1);
+    // Report all the failing checks in the evaluation order.
+    let output = run(&["--collect_check_failures"]);
+    let messages: Vec<String> = output
+        .lines()
+        .map(|line| {
+            let diag: serde_json::Value = serde_json::from_str(line).unwrap();
+            diag["messages"][1]["message"].as_str().unwrap().to_string()
+        })
+        .collect();
+    assert_eq!(
+        messages,
+        [
+            "Check failed on the condition: the name must not be empty",
+            "Check failed on the condition: the age must not be negative"
+        ],
+        "{output}"
+    );
+}
+
 fn test_instances_with_yaml() {
     let test_cases = [
         "test_inst_1",
@@ -568,3 +709,114 @@ fn test_keyword_argument_error_message() {
         }
     }
 }
+
+#[test]
+fn test_lint_command_with_fix() {
+    let source = fs::read_to_string("./src/test_data/lint/fix.k").unwrap();
+    let dir = env::temp_dir().join("kcl_test_lint_command_with_fix");
+    fs::create_dir_all(&dir).unwrap();
+    let file = dir.join("main.k").display().to_string();
+    fs::write(&file, &source).unwrap();
+    let fixed = "import math\n\na = None\nb = a is None\nc = math.log(10)\n";
+    // Dry run
+    let matches = app().get_matches_from([ROOT_CMD, "lint", &file, "--dry_run"]);
+    let mut buf = Vec::new();
+    lint_command(matches.subcommand_matches("lint").unwrap(), &mut buf).unwrap();
+    let output = String::from_utf8(buf).unwrap();
+    assert!(output.contains("-import math\n"), "{output}");
+    assert!(
+        output.contains("-b = a == None\n+b = a is None\n"),
+        "{output}"
+    );
+    assert_eq!(fs::read_to_string(&file).unwrap(), source);
+    // Fix in place
+    let matches = app().get_matches_from([ROOT_CMD, "lint", &file, "--fix"]);
+    let mut buf = Vec::new();
+    lint_command(matches.subcommand_matches("lint").unwrap(), &mut buf).unwrap();
+    let output = String::from_utf8(buf).unwrap();
+    assert_eq!(output, format!("Fixed 2 problem(s) in {file}\n"));
+    assert_eq!(fs::read_to_string(&file).unwrap(), fixed);
+    // Lint without fix
+    let matches = app().get_matches_from([ROOT_CMD, "lint", &file, "--error_format", "json"]);
+    let mut buf = Vec::new();
+    lint_command(matches.subcommand_matches("lint").unwrap(), &mut buf).unwrap();
+    assert!(buf.is_empty());
+    fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn test_test_command_with_report() {
+    let pkg = "./src/test_data/test";
+    // Text output
+    let matches = app().get_matches_from([ROOT_CMD, "test", pkg]);
+    let mut buf = Vec::new();
+    let err = test_command(matches.subcommand_matches("test").unwrap(), &mut buf).unwrap_err();
+    assert_eq!(err.to_string(), "Test failed");
+    let output = String::from_utf8(buf).unwrap();
+    assert!(
+        output.starts_with("add 1 + 1\ntest_add: PASS ("),
+        "{output}"
+    );
+    assert!(output.contains("test_add_fail: FAIL ("), "{output}");
+    assert!(output.contains("1 + 1 is not 3"), "{output}");
+    assert!(output.ends_with("1 passed, 1 failed\n"), "{output}");
+    // Filter the test cases and run them concurrently
+    let matches = app().get_matches_from([ROOT_CMD, "test", pkg, "--run", "test_add$", "-j", "2"]);
+    let mut buf = Vec::new();
+    test_command(matches.subcommand_matches("test").unwrap(), &mut buf).unwrap();
+    let output = String::from_utf8(buf).unwrap();
+    assert!(output.ends_with("1 passed, 0 failed\n"), "{output}");
+    // JUnit XML report file
+    let output = env::temp_dir().join("kcl_test_test_command_with_report.xml");
+    let output = output.to_str().unwrap();
+    let matches = app().get_matches_from([
+        ROOT_CMD,
+        "test",
+        pkg,
+        "--report_format",
+        "junit",
+        "-o",
+        output,
+    ]);
+    let mut buf = Vec::new();
+    assert!(test_command(matches.subcommand_matches("test").unwrap(), &mut buf).is_err());
+    assert!(buf.is_empty());
+    let junit = fs::read_to_string(output).unwrap();
+    assert!(junit.contains("<testsuites name=\"kcl\" tests=\"2\" failures=\"1\""));
+    assert!(junit.contains("<failure message=\""), "{junit}");
+    assert!(
+        junit.contains("<system-out>add 1 + 1\n</system-out>"),
+        "{junit}"
+    );
+    remove_file(output).unwrap();
+    // JSON report
+    let matches = app().get_matches_from([ROOT_CMD, "test", pkg, "--report_format", "json"]);
+    let mut buf = Vec::new();
+    assert!(test_command(matches.subcommand_matches("test").unwrap(), &mut buf).is_err());
+    let json: serde_json::Value = serde_json::from_slice(&buf).unwrap();
+    assert_eq!(json["suites"][0]["cases"][1]["name"], "test_add_fail");
+    assert_eq!(json["suites"][0]["cases"][1]["passed"], false);
+    // Coverage summary and LCOV file
+    let lcov = env::temp_dir().join("kcl_test_test_command_with_report.info");
+    let lcov = lcov.to_str().unwrap();
+    let matches = app().get_matches_from([
+        ROOT_CMD,
+        "test",
+        pkg,
+        "--run",
+        "test_add$",
+        "--coverage_output",
+        lcov,
+    ]);
+    let mut buf = Vec::new();
+    test_command(matches.subcommand_matches("test").unwrap(), &mut buf).unwrap();
+    let output = String::from_utf8(buf).unwrap();
+    assert!(output.contains("1 passed, 0 failed\nFilename"), "{output}");
+    assert!(output.contains("add.k"), "{output}");
+    assert!(!output.contains("add_test.k"), "{output}");
+    let tracefile = fs::read_to_string(lcov).unwrap();
+    assert!(tracefile.contains("add.k\n"), "{tracefile}");
+    assert!(tracefile.contains("DA:2,1\n"), "{tracefile}");
+    assert!(tracefile.ends_with("end_of_record\n"), "{tracefile}");
+    remove_file(lcov).unwrap();
+}
@@ -30,6 +30,7 @@ pub struct ModFile {
     pub package: Option<Package>,
     pub profile: Option<Profile>,
     pub dependencies: Option<Dependencies>,
+    pub lint: Option<Lint>,
 }
 
 /// ModLockFile is kcl package file 'kc.mod.lock'.
@@ -73,6 +74,18 @@ pub struct Profile {
     pub options: Option<Vec<String>>,
 }
 
+/// Lint is the lint section of 'kcl.mod'.
+/// It is used to change the levels of individual lint codes e.g., `W0411` for the current package.
+#[derive(Debug, Default, Serialize, Deserialize, Clone, PartialEq, Eq)]
+pub struct Lint {
+    /// A list of lint codes that are not reported.
+    pub allow: Option<Vec<String>>,
+    /// A list of lint codes that are reported as warnings.
+    pub warn: Option<Vec<String>>,
+    /// A list of lint codes that are reported as errors.
+    pub deny: Option<Vec<String>>,
+}
+
 /// A map of package names to their respective dependency specifications.
 pub type Dependencies = HashMap<String, Dependency>;
 pub type LockDependencies = HashMap<String, LockDependency>;
@@ -379,5 +392,13 @@ mod modfile_test {
                 path: "../pkg".to_string(),
             }))
         );
+        assert_eq!(
+            kcl_mod.lint,
+            Some(Lint {
+                allow: Some(vec!["W0413".to_string()]),
+                warn: None,
+                deny: Some(vec!["W0411".to_string()]),
+            })
+        );
     }
 }
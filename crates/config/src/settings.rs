@@ -68,6 +68,8 @@ pub struct Config {
     pub package_maps: Option<HashMap<String, String>>,
     /// Use the evaluator to execute the AST program instead of AOT.
     pub fast_eval: Option<bool>,
+    /// Collect all the schema check failures instead of stopping at the first one.
+    pub collect_check_failures: Option<bool>,
 }
 
 impl SettingsFile {
@@ -86,6 +88,7 @@ impl SettingsFile {
                 sort_keys: Some(false),
                 show_hidden: Some(false),
                 fast_eval: Some(false),
+                collect_check_failures: Some(false),
                 include_schema_type_path: Some(false),
                 package_maps: Some(HashMap::default()),
             }),
@@ -388,6 +391,11 @@ pub fn merge_settings(settings: &[SettingsFile]) -> SettingsFile {
                 set_if!(result_kcl_cli_configs, sort_keys, kcl_cli_configs);
                 set_if!(result_kcl_cli_configs, show_hidden, kcl_cli_configs);
                 set_if!(result_kcl_cli_configs, fast_eval, kcl_cli_configs);
+                set_if!(
+                    result_kcl_cli_configs,
+                    collect_check_failures,
+                    kcl_cli_configs
+                );
                 set_if!(
                     result_kcl_cli_configs,
                     include_schema_type_path,
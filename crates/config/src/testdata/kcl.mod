@@ -11,3 +11,7 @@ pkg3 = { path = "../pkg"}
 
 [profile]
 entries = ["main.k"]
+
+[lint]
+allow = ["W0413"]
+deny = ["W0411"]
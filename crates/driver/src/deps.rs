@@ -0,0 +1,210 @@
+//! List the dependency files of a KCL package.
+//!
+//! The dependency files of a package are the KCL files of the package itself and of all the
+//! packages it imports transitively, which can be used to decide whether a built artifact of
+//! the package is outdated.
+
+use std::collections::{BTreeSet, HashMap, HashSet};
+use std::path::{Path, PathBuf};
+
+use anyhow::Result;
+use kcl_config::modfile::{KCL_FILE_SUFFIX, KCL_MOD_FILE, KCL_MOD_LOCK_FILE};
+use kcl_config::vfs::fix_import_path;
+use kcl_parser::{LoadProgramOptions, ParseSessionRef, get_kcl_files, load_program};
+
+use crate::lookup_the_nearest_file_dir;
+use crate::toolchain::{Toolchain, fill_pkg_maps_for_k_file};
+
+/// Options of listing the dependency files of a KCL package.
+#[derive(Debug, Default, Clone)]
+pub struct ListDepFilesOptions {
+    /// Return absolute file paths instead of paths relative to the package root.
+    pub use_abs_path: bool,
+    /// Also return the files of external packages and the `kcl.mod` and `kcl.mod.lock`
+    /// files of the package root.
+    pub include_all: bool,
+    /// Only scan the import statements line by line instead of parsing the whole files,
+    /// which is faster and tolerates syntax errors in the rest of the files.
+    pub use_fast_parser: bool,
+}
+
+/// The dependency files of a KCL package.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct DepFiles {
+    /// The root path of the package, which is the directory containing `kcl.mod`
+    /// or the package path itself if there is no `kcl.mod`.
+    pub pkgroot: String,
+    /// The import path of the package relative to the package root e.g., `app.sub`.
+    pub pkgpath: String,
+    /// The sorted dependency files.
+    pub files: Vec<String>,
+}
+
+/// List the dependency files of the KCL package in the `work_dir`. The external packages
+/// are resolved with the metadata fetched by the toolchain.
+pub fn list_dep_files(
+    tool: &dyn Toolchain,
+    work_dir: &str,
+    opts: &ListDepFilesOptions,
+) -> Result<DepFiles> {
+    let work_dir = Path::new(work_dir).canonicalize()?;
+    let pkgroot = lookup_the_nearest_file_dir(work_dir.clone(), KCL_MOD_FILE)
+        .unwrap_or_else(|| work_dir.clone());
+    let pkgpath = work_dir
+        .strip_prefix(&pkgroot)
+        .map(|rel| {
+            rel.components()
+                .map(|c| c.as_os_str().to_string_lossy().to_string())
+                .collect::<Vec<String>>()
+                .join(".")
+        })
+        .unwrap_or_default();
+    let mut load_opts = LoadProgramOptions {
+        work_dir: work_dir.display().to_string(),
+        ..Default::default()
+    };
+    // The external packages are not resolvable without the metadata, but the local
+    // dependency files can still be listed.
+    let _ = fill_pkg_maps_for_k_file(tool, work_dir.clone(), &mut load_opts);
+
+    let entries = get_pkg_files(&work_dir)?;
+    let mut files = if opts.use_fast_parser {
+        scan_dep_files(&pkgroot, entries, &load_opts.package_maps)?
+    } else {
+        load_dep_files(entries, load_opts)?
+    };
+    if opts.include_all {
+        for file in [KCL_MOD_FILE, KCL_MOD_LOCK_FILE] {
+            let path = pkgroot.join(file);
+            if path.is_file() {
+                files.insert(path);
+            }
+        }
+    } else {
+        files.retain(|file| file.starts_with(&pkgroot));
+    }
+    let files = files
+        .iter()
+        .map(|file| match file.strip_prefix(&pkgroot) {
+            Ok(rel) if !opts.use_abs_path => rel.display().to_string(),
+            _ => file.display().to_string(),
+        })
+        .collect();
+    Ok(DepFiles {
+        pkgroot: pkgroot.display().to_string(),
+        pkgpath,
+        files,
+    })
+}
+
+/// Returns the KCL files of the package directory, excluding the test files and the
+/// hidden files starting with `_`.
+fn get_pkg_files(dir: &Path) -> Result<Vec<PathBuf>> {
+    Ok(get_kcl_files(dir, false)?
+        .into_iter()
+        .map(PathBuf::from)
+        .filter(|path| {
+            path.file_name()
+                .map(|name| {
+                    let name = name.to_string_lossy();
+                    !name.starts_with('_') && !name.ends_with("_test.k")
+                })
+                .unwrap_or(false)
+        })
+        .collect())
+}
+
+/// Load the program of the entry files and returns all the files of the loaded packages.
+fn load_dep_files(entries: Vec<PathBuf>, opts: LoadProgramOptions) -> Result<BTreeSet<PathBuf>> {
+    let entries: Vec<String> = entries.iter().map(|p| p.display().to_string()).collect();
+    let entries: Vec<&str> = entries.iter().map(|p| p.as_str()).collect();
+    let program = load_program(ParseSessionRef::default(), &entries, Some(opts), None)?.program;
+    Ok(program.pkgs.values().flatten().map(PathBuf::from).collect())
+}
+
+/// Follow the import statements scanned from the entry files and returns all the files
+/// of the imported packages.
+fn scan_dep_files(
+    pkgroot: &Path,
+    entries: Vec<PathBuf>,
+    package_maps: &HashMap<String, String>,
+) -> Result<BTreeSet<PathBuf>> {
+    let mut files = BTreeSet::new();
+    let mut visited = HashSet::new();
+    let mut queue: Vec<(PathBuf, PathBuf)> = entries
+        .into_iter()
+        .map(|file| (file, pkgroot.to_path_buf()))
+        .collect();
+    while let Some((file, root)) = queue.pop() {
+        if !visited.insert(file.clone()) {
+            continue;
+        }
+        let src = std::fs::read_to_string(&file)?;
+        for import_path in scan_imports(&src) {
+            let pkgpath = fix_import_path(
+                &root.display().to_string(),
+                &file.display().to_string(),
+                &import_path,
+            );
+            if let Some((dep_root, dep_files)) = find_pkg_files(&root, &pkgpath, package_maps)? {
+                queue.extend(dep_files.into_iter().map(|f| (f, dep_root.clone())));
+            }
+        }
+        files.insert(file);
+    }
+    Ok(files)
+}
+
+/// Returns the import paths of the top level import statements in the source code.
+fn scan_imports(src: &str) -> Vec<String> {
+    src.lines()
+        .filter_map(|line| line.strip_prefix("import "))
+        .filter_map(|rest| {
+            let rest = rest.split('#').next().unwrap_or_default();
+            rest.split_whitespace().next().map(|path| path.to_string())
+        })
+        .collect()
+}
+
+/// Find the files of the package `pkgpath` in the local package root first and then in the
+/// external packages. Returns the root of the found package and its files, or `None` for the
+/// system modules and the packages not found.
+fn find_pkg_files(
+    pkgroot: &Path,
+    pkgpath: &str,
+    package_maps: &HashMap<String, String>,
+) -> Result<Option<(PathBuf, Vec<PathBuf>)>> {
+    if pkgpath.is_empty() {
+        return Ok(None);
+    }
+    let local = pkgroot.join(pkgpath.replace('.', "/"));
+    if let Some(files) = get_pkg_or_file(&local)? {
+        return Ok(Some((pkgroot.to_path_buf(), files)));
+    }
+    let (name, subpath) = pkgpath.split_once('.').unwrap_or((pkgpath, ""));
+    if let Some(root) = package_maps.get(name) {
+        let root = PathBuf::from(root);
+        let path = if subpath.is_empty() {
+            root.clone()
+        } else {
+            root.join(subpath.replace('.', "/"))
+        };
+        if let Some(files) = get_pkg_or_file(&path)? {
+            return Ok(Some((root, files)));
+        }
+    }
+    Ok(None)
+}
+
+/// Returns the files of the package directory or the single KCL file of the path.
+fn get_pkg_or_file(path: &Path) -> Result<Option<Vec<PathBuf>>> {
+    if path.is_dir() {
+        return Ok(Some(get_pkg_files(path)?));
+    }
+    let file = PathBuf::from(format!("{}{}", path.display(), KCL_FILE_SUFFIX));
+    if file.is_file() {
+        Ok(Some(vec![file]))
+    } else {
+        Ok(None)
+    }
+}
@@ -1,6 +1,7 @@
 pub mod arguments;
 #[cfg(not(target_arch = "wasm32"))]
 pub mod client;
+pub mod deps;
 pub mod toolchain;
 
 #[cfg(test)]
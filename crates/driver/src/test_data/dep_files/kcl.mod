@@ -0,0 +1,4 @@
+[package]
+name = "dep_files"
+edition = "*"
+version = "0.0.1"
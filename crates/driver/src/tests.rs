@@ -4,8 +4,10 @@ use std::path::PathBuf;
 use kcl_config::settings::KeyValuePair;
 
 use crate::arguments::parse_key_value_pair;
+use crate::deps::{ListDepFilesOptions, list_dep_files};
 use crate::toolchain::NativeToolchain;
 use crate::toolchain::Toolchain;
+use crate::toolchain::{Metadata, Package};
 use crate::{get_pkg_list, lookup_the_nearest_file_dir, toolchain};
 
 #[test]
@@ -170,3 +172,75 @@ fn test_get_pkg_list() {
         3
     );
 }
+
+struct TestToolchain(Metadata);
+
+impl Toolchain for TestToolchain {
+    fn fetch_metadata(&self, _manifest_path: PathBuf) -> anyhow::Result<Metadata> {
+        Ok(self.0.clone())
+    }
+
+    fn update_dependencies(&self, _manifest_path: PathBuf) -> anyhow::Result<()> {
+        Ok(())
+    }
+}
+
+#[test]
+fn test_list_dep_files() {
+    let vendor = PathBuf::from(".")
+        .join("src")
+        .join("test_data")
+        .join("test_vendor")
+        .join("helloworld_0.1.0")
+        .canonicalize()
+        .unwrap();
+    let tool = TestToolchain(Metadata {
+        packages: [(
+            "helloworld".to_string(),
+            Package {
+                name: "helloworld".to_string(),
+                manifest_path: vendor.clone(),
+            },
+        )]
+        .into(),
+    });
+    let pkgroot = PathBuf::from(".")
+        .join("src")
+        .join("test_data")
+        .join("dep_files")
+        .canonicalize()
+        .unwrap();
+    let work_dir = pkgroot.join("app").display().to_string();
+    let local_files: Vec<String> = ["app/main.k", "app/sub/sub.k", "base.k", "utils/utils.k"]
+        .iter()
+        .map(|f| PathBuf::from(f).display().to_string())
+        .collect();
+    for use_fast_parser in [false, true] {
+        let opts = ListDepFilesOptions {
+            use_fast_parser,
+            ..Default::default()
+        };
+        let dep_files = list_dep_files(&tool, &work_dir, &opts).unwrap();
+        assert_eq!(dep_files.pkgroot, pkgroot.display().to_string());
+        assert_eq!(dep_files.pkgpath, "app");
+        assert_eq!(
+            dep_files.files, local_files,
+            "fast parser: {use_fast_parser}"
+        );
+
+        let opts = ListDepFilesOptions {
+            use_abs_path: true,
+            include_all: true,
+            use_fast_parser,
+        };
+        let dep_files = list_dep_files(&tool, &work_dir, &opts).unwrap();
+        let mut expected: Vec<String> = local_files
+            .iter()
+            .chain(["kcl.mod".to_string()].iter())
+            .map(|f| pkgroot.join(f).display().to_string())
+            .chain([vendor.join("main.k").display().to_string()])
+            .collect();
+        expected.sort();
+        assert_eq!(dep_files.files, expected, "fast parser: {use_fast_parser}");
+    }
+}
@@ -0,0 +1,383 @@
+//! Machine-readable emitters for diagnostics.
+//!
+//! Besides the human-readable text emitted by [`crate::Handler::emit`], diagnostics can
+//! be emitted as JSON Lines (one JSON object per diagnostic) or as a SARIF 2.1.0 log,
+//! which can be uploaded to code scanning systems in CI.
+
+use std::str::FromStr;
+
+use anyhow::{Result, bail};
+use serde::Serialize;
+
+use crate::diagnostic::{Diagnostic, DiagnosticId, Level, Message, Position};
+
+const SARIF_SCHEMA: &str = "https://json.schemastore.org/sarif-2.1.0.json";
+const SARIF_VERSION: &str = "2.1.0";
+const TOOL_NAME: &str = "kcl";
+const TOOL_INFORMATION_URI: &str = "https://kcl-lang.io";
+
+/// The output format of diagnostics.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub enum DiagnosticFormat {
+    /// Human-readable text with code snippets.
+    #[default]
+    Text,
+    /// JSON Lines, one JSON object per diagnostic.
+    Json,
+    /// SARIF 2.1.0 log.
+    Sarif,
+}
+
+impl FromStr for DiagnosticFormat {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s.to_lowercase().as_str() {
+            "text" | "human" => Ok(DiagnosticFormat::Text),
+            "json" | "jsonl" => Ok(DiagnosticFormat::Json),
+            "sarif" => Ok(DiagnosticFormat::Sarif),
+            _ => bail!("Invalid diagnostic format '{s}', expected 'text', 'json' or 'sarif'"),
+        }
+    }
+}
+
+impl DiagnosticId {
+    /// Returns the diagnostic code e.g., `E2G22` and `W1001`.
+    pub fn code(&self) -> String {
+        match self {
+            DiagnosticId::Error(kind) => kind.code(),
+            DiagnosticId::Warning(kind) => kind.code(),
+            DiagnosticId::Suggestions => "Suggestions".to_string(),
+        }
+    }
+
+    /// Returns the diagnostic name e.g., `TypeError` and `UnusedImportWarning`.
+    pub fn name(&self) -> String {
+        match self {
+            DiagnosticId::Error(kind) => kind.name(),
+            DiagnosticId::Warning(kind) => kind.name(),
+            DiagnosticId::Suggestions => "Suggestions".to_string(),
+        }
+    }
+}
+
+/// Emits diagnostics as JSON Lines, one JSON object per diagnostic and per line.
+pub fn emit_json_lines<'a>(diags: impl IntoIterator<Item = &'a Diagnostic>) -> Result<String> {
+    let mut lines = vec![];
+    for diag in diags {
+        lines.push(serde_json::to_string(&JsonDiagnostic::from(diag))?);
+    }
+    Ok(lines.join("\n"))
+}
+
+/// Emits diagnostics as a pretty printed SARIF 2.1.0 log with a single run.
+pub fn emit_sarif<'a>(diags: impl IntoIterator<Item = &'a Diagnostic>) -> Result<String> {
+    let mut rules: Vec<SarifRule> = vec![];
+    let mut results = vec![];
+    for diag in diags {
+        let rule_index = diag.code.as_ref().map(|id| {
+            let code = id.code();
+            match rules.iter().position(|r| r.id == code) {
+                Some(index) => index,
+                None => {
+                    rules.push(SarifRule {
+                        id: code,
+                        name: id.name(),
+                    });
+                    rules.len() - 1
+                }
+            }
+        });
+        results.push(SarifResult::new(diag, rule_index, &rules));
+    }
+    let log = SarifLog {
+        schema: SARIF_SCHEMA,
+        version: SARIF_VERSION,
+        runs: vec![SarifRun {
+            tool: SarifTool {
+                driver: SarifDriver {
+                    name: TOOL_NAME,
+                    information_uri: TOOL_INFORMATION_URI,
+                    version: env!("CARGO_PKG_VERSION"),
+                    rules,
+                },
+            },
+            results,
+        }],
+    };
+    Ok(serde_json::to_string_pretty(&log)?)
+}
+
+#[derive(Serialize)]
+struct JsonDiagnostic<'a> {
+    level: &'static str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    code: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    name: Option<String>,
+    messages: Vec<JsonMessage<'a>>,
+}
+
+impl<'a> From<&'a Diagnostic> for JsonDiagnostic<'a> {
+    fn from(diag: &'a Diagnostic) -> Self {
+        JsonDiagnostic {
+            level: diag.level.to_str(),
+            code: diag.code.as_ref().map(|id| id.code()),
+            name: diag.code.as_ref().map(|id| id.name()),
+            messages: diag.messages.iter().map(JsonMessage::from).collect(),
+        }
+    }
+}
+
+#[derive(Serialize)]
+struct JsonMessage<'a> {
+    message: &'a str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    note: Option<&'a str>,
+    range: JsonRange<'a>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    suggested_replacement: Option<&'a [String]>,
+}
+
+impl<'a> From<&'a Message> for JsonMessage<'a> {
+    fn from(msg: &'a Message) -> Self {
+        JsonMessage {
+            message: &msg.message,
+            note: msg.note.as_deref(),
+            range: JsonRange {
+                start: JsonPosition::from(&msg.range.0),
+                end: JsonPosition::from(&msg.range.1),
+            },
+            suggested_replacement: msg.suggested_replacement.as_deref(),
+        }
+    }
+}
+
+#[derive(Serialize)]
+struct JsonRange<'a> {
+    start: JsonPosition<'a>,
+    end: JsonPosition<'a>,
+}
+
+#[derive(Serialize)]
+struct JsonPosition<'a> {
+    filename: &'a str,
+    line: u64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    column: Option<u64>,
+}
+
+impl<'a> From<&'a Position> for JsonPosition<'a> {
+    fn from(pos: &'a Position) -> Self {
+        JsonPosition {
+            filename: &pos.filename,
+            line: pos.line,
+            column: pos.column,
+        }
+    }
+}
+
+#[derive(Serialize)]
+struct SarifLog {
+    #[serde(rename = "$schema")]
+    schema: &'static str,
+    version: &'static str,
+    runs: Vec<SarifRun>,
+}
+
+#[derive(Serialize)]
+struct SarifRun {
+    tool: SarifTool,
+    results: Vec<SarifResult>,
+}
+
+#[derive(Serialize)]
+struct SarifTool {
+    driver: SarifDriver,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct SarifDriver {
+    name: &'static str,
+    information_uri: &'static str,
+    version: &'static str,
+    rules: Vec<SarifRule>,
+}
+
+#[derive(Serialize)]
+struct SarifRule {
+    id: String,
+    name: String,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct SarifResult {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    rule_id: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    rule_index: Option<usize>,
+    level: &'static str,
+    message: SarifMessage,
+    locations: Vec<SarifLocation>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    related_locations: Vec<SarifLocation>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    fixes: Vec<SarifFix>,
+}
+
+impl SarifResult {
+    fn new(diag: &Diagnostic, rule_index: Option<usize>, rules: &[SarifRule]) -> Self {
+        let level = match diag.level {
+            Level::Error => "error",
+            Level::Warning => "warning",
+            Level::Note | Level::Suggestions => "note",
+        };
+        // The first message is the primary location, the others are related locations.
+        let (primary, related) = match diag.messages.split_first() {
+            Some((primary, related)) => (Some(primary), related),
+            None => (None, &[][..]),
+        };
+        let mut fixes = vec![];
+        for msg in &diag.messages {
+            let Some(location) = SarifLocation::new(msg) else {
+                continue;
+            };
+            for replacement in msg.suggested_replacement.iter().flatten() {
+                fixes.push(SarifFix {
+                    description: SarifMessage {
+                        text: format!("Replace with '{replacement}'"),
+                    },
+                    artifact_changes: vec![SarifArtifactChange {
+                        artifact_location: location.physical_location.artifact_location.clone(),
+                        replacements: vec![SarifReplacement {
+                            deleted_region: location.physical_location.region.clone(),
+                            inserted_content: SarifContent {
+                                text: replacement.clone(),
+                            },
+                        }],
+                    }],
+                });
+            }
+        }
+        SarifResult {
+            rule_id: rule_index.map(|i| rules[i].id.clone()),
+            rule_index,
+            level,
+            message: SarifMessage {
+                text: primary.map(message_text).unwrap_or_default(),
+            },
+            locations: primary.and_then(SarifLocation::new).into_iter().collect(),
+            related_locations: related
+                .iter()
+                .filter_map(|msg| {
+                    let mut location = SarifLocation::new(msg)?;
+                    location.message = Some(SarifMessage {
+                        text: message_text(msg),
+                    });
+                    Some(location)
+                })
+                .collect(),
+            fixes,
+        }
+    }
+}
+
+/// Returns the message text including its note.
+fn message_text(msg: &Message) -> String {
+    match &msg.note {
+        Some(note) => format!("{}\nnote: {}", msg.message, note),
+        None => msg.message.clone(),
+    }
+}
+
+#[derive(Serialize, Clone)]
+struct SarifMessage {
+    text: String,
+}
+
+#[derive(Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+struct SarifLocation {
+    physical_location: SarifPhysicalLocation,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    message: Option<SarifMessage>,
+}
+
+impl SarifLocation {
+    /// Returns the location of the message, or `None` if the message has no source file.
+    fn new(msg: &Message) -> Option<Self> {
+        let (start, end) = &msg.range;
+        if start.filename.is_empty() {
+            return None;
+        }
+        // SARIF lines and columns are 1-based.
+        let start_line = start.line.max(1);
+        let has_end = end.filename == start.filename && end.line >= start_line;
+        Some(SarifLocation {
+            physical_location: SarifPhysicalLocation {
+                artifact_location: SarifArtifactLocation {
+                    uri: start.filename.replace('\\', "/"),
+                },
+                region: SarifRegion {
+                    start_line,
+                    start_column: start.column.map(|c| c + 1),
+                    end_line: has_end.then_some(end.line),
+                    end_column: end.column.filter(|_| has_end).map(|c| c + 1),
+                },
+            },
+            message: None,
+        })
+    }
+}
+
+#[derive(Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+struct SarifPhysicalLocation {
+    artifact_location: SarifArtifactLocation,
+    region: SarifRegion,
+}
+
+#[derive(Serialize, Clone)]
+struct SarifArtifactLocation {
+    uri: String,
+}
+
+#[derive(Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+struct SarifRegion {
+    start_line: u64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    start_column: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    end_line: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    end_column: Option<u64>,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct SarifFix {
+    description: SarifMessage,
+    artifact_changes: Vec<SarifArtifactChange>,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct SarifArtifactChange {
+    artifact_location: SarifArtifactLocation,
+    replacements: Vec<SarifReplacement>,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct SarifReplacement {
+    deleted_region: SarifRegion,
+    inserted_content: SarifContent,
+}
+
+#[derive(Serialize)]
+struct SarifContent {
+    text: String,
+}
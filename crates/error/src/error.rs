@@ -57,6 +57,17 @@ register_errors! {
 // Error messages for WXXXX errors. Each message should start and end with a
 // new line.
 register_warnings! {
+    // W0XXX Lint Warnings
+    W0404: WarningKind::ReimportWarning, include_str!("./warning_codes/W0404.md"),
+    W0411: WarningKind::UnusedImportWarning, include_str!("./warning_codes/W0411.md"),
+    W0413: WarningKind::ImportPositionWarning, include_str!("./warning_codes/W0413.md"),
+    W0121: WarningKind::NoneComparisonWarning, include_str!("./warning_codes/W0121.md"),
+    W0125: WarningKind::ConstantCheckWarning, include_str!("./warning_codes/W0125.md"),
+    W0612: WarningKind::UnusedVariableWarning, include_str!("./warning_codes/W0612.md"),
+    W0613: WarningKind::UnusedSchemaAttrWarning, include_str!("./warning_codes/W0613.md"),
+    W0614: WarningKind::OverriddenSchemaAttrWarning, include_str!("./warning_codes/W0614.md"),
+    W0621: WarningKind::ShadowedLoopVariableWarning, include_str!("./warning_codes/W0621.md"),
+    // W1XXX Compile Warnings
     W1001: WarningKind::CompilerWarning, include_str!("./warning_codes/W1001.md"),
 }
 
@@ -144,6 +155,12 @@ pub enum WarningKind {
     UnusedImportWarning,
     ReimportWarning,
     ImportPositionWarning,
+    UnusedVariableWarning,
+    UnusedSchemaAttrWarning,
+    ShadowedLoopVariableWarning,
+    NoneComparisonWarning,
+    OverriddenSchemaAttrWarning,
+    ConstantCheckWarning,
 }
 
 /// Test warning `fmt`
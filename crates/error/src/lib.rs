@@ -4,6 +4,7 @@
 //! We can use `Handler` to create and emit diagnostics.
 
 pub mod diagnostic;
+pub mod emitter;
 mod error;
 
 use annotate_snippets::{
@@ -27,6 +28,7 @@ use std::{any::Any, sync::Arc};
 use thiserror::Error;
 
 pub use diagnostic::{Diagnostic, DiagnosticId, Level, Message, Position, Style};
+pub use emitter::DiagnosticFormat;
 pub use error::*;
 
 /// A handler deals with errors and other compiler output.
@@ -82,6 +84,27 @@ impl Handler {
         Ok(error_strings.join("\n"))
     }
 
+    /// Emit diagnostic to string in the given format.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use kcl_error::*;
+    /// let mut handler = Handler::default();
+    /// handler.add_compile_error("error message", (Position::dummy_pos(), Position::dummy_pos()));
+    /// let json = handler.emit_to_string_with_format(DiagnosticFormat::Json).unwrap();
+    /// assert!(json.starts_with(r#"{"level":"error","code":"E2L23""#));
+    /// let sarif = handler.emit_to_string_with_format(DiagnosticFormat::Sarif).unwrap();
+    /// assert!(sarif.contains(r#""ruleId": "E2L23""#));
+    /// ```
+    pub fn emit_to_string_with_format(&mut self, format: DiagnosticFormat) -> Result<String> {
+        match format {
+            DiagnosticFormat::Text => self.emit_to_string(),
+            DiagnosticFormat::Json => emitter::emit_json_lines(&self.diagnostics),
+            DiagnosticFormat::Sarif => emitter::emit_sarif(&self.diagnostics),
+        }
+    }
+
     /// Emit all diagnostics and abort if has any errors.
     pub fn abort_if_any_errors(&mut self) {
         match self.emit() {
@@ -491,6 +514,11 @@ impl SessionDiagnostic for Diagnostic {
                     diag.append_component(Box::new(Label::Error(error.code())));
                     diag.append_component(Box::new(format!(": {}\n", error.name())));
                 }
+                // Warnings such as denied lints may be reported as errors.
+                DiagnosticId::Warning(warning) if self.level == Level::Error => {
+                    diag.append_component(Box::new(Label::Error(warning.code())));
+                    diag.append_component(Box::new(format!(": {}\n", warning.name())));
+                }
                 DiagnosticId::Warning(warning) => {
                     diag.append_component(Box::new(Label::Warning(warning.code())));
                     diag.append_component(Box::new(format!(": {}\n", warning.name())));
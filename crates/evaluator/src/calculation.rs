@@ -1,10 +1,12 @@
 /* Calculation methods */
 
 use kcl_ast::ast;
-use kcl_runtime::{ConfigEntryOperationKind, DictValue, UnionOptions, Value, ValueRef};
+use kcl_runtime::{
+    ConfigEntryOperationKind, DictValue, RuntimeErrorType, UnionOptions, Value, ValueRef,
+};
 
 use crate::Evaluator;
-use crate::ty::{resolve_schema, type_pack_and_check};
+use crate::ty::{resolve_schema, try_type_pack_and_check, type_pack_and_check};
 use crate::union::union_entry;
 
 impl<'ctx> Evaluator<'ctx> {
@@ -175,10 +177,7 @@ impl<'ctx> Evaluator<'ctx> {
                 // Has type annotation
                 if let Some(ty) = attr_map.get(k) {
                     let value = lhs.dict_get_value(k).unwrap();
-                    lhs.dict_update_key_value(
-                        k,
-                        type_pack_and_check(self, &value, vec![ty], false),
-                    );
+                    lhs.dict_update_key_value(k, self.attr_type_pack_and_check(&value, ty));
                 }
             }
             lhs.clone()
@@ -243,13 +242,33 @@ impl<'ctx> Evaluator<'ctx> {
             }
         };
         if attr_map.contains_key(key) {
-            let v = type_pack_and_check(self, value, vec![attr_map.get(key).unwrap()], false);
+            let v = self.attr_type_pack_and_check(value, attr_map.get(key).unwrap());
             self.dict_merge_key_value_pair(dict, key, &v, op, insert_index, false);
         } else {
             self.dict_merge_key_value_pair(dict, key, value, op, insert_index, false);
         }
     }
 
+    /// Type pack and check the schema attribute value with the attribute type. When the
+    /// check failures are collected, the type mismatch is recorded and the value is kept
+    /// to check the rest of the schema.
+    pub(crate) fn attr_type_pack_and_check(&self, value: &ValueRef, ty: &str) -> ValueRef {
+        if !self.runtime_ctx.borrow().cfg.collect_check_failures {
+            return type_pack_and_check(self, value, vec![ty], false);
+        }
+        match try_type_pack_and_check(self, value, vec![ty], false) {
+            Ok(value) => value,
+            Err(msg) => {
+                let mut ctx = self.runtime_ctx.borrow_mut();
+                let prev_panic_info = ctx.panic_info.clone();
+                ctx.set_err_type(&RuntimeErrorType::TypeError);
+                ctx.panic_info.kcl_arg_msg.clear();
+                ctx.record_check_failure(prev_panic_info, &msg);
+                value.clone()
+            }
+        }
+    }
+
     /// Insert an entry including key and value into the dict.
     #[inline]
     pub(crate) fn dict_insert_value(&self, dict: &mut ValueRef, key: &str, value: &ValueRef) {
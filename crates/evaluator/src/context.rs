@@ -43,6 +43,30 @@ impl<'ctx> Evaluator<'ctx> {
         ctx.panic_info.kcl_line = node.line as i32;
     }
 
+    /// Record a coverage hit of the node if the coverage is enabled.
+    #[inline]
+    pub(crate) fn record_coverage<T>(&self, node: &'ctx ast::Node<T>) {
+        if let Some(coverage) = self.coverage.borrow_mut().as_mut() {
+            coverage.hit(node);
+        }
+    }
+
+    /// Record a coverage hit of the expression span if the coverage is enabled.
+    #[inline]
+    pub(crate) fn record_expr_coverage<T>(&self, node: &'ctx ast::Node<T>) {
+        if let Some(coverage) = self.coverage.borrow_mut().as_mut() {
+            coverage.hit_span(node);
+        }
+    }
+
+    /// Record the outcome of the branch condition if the coverage is enabled.
+    #[inline]
+    pub(crate) fn record_branch_coverage<T>(&self, cond: &'ctx ast::Node<T>, is_truth: bool) {
+        if let Some(coverage) = self.coverage.borrow_mut().as_mut() {
+            coverage.branch(cond, is_truth);
+        }
+    }
+
     /// Update current AST index.
     #[inline]
     pub(crate) fn update_ast_id<T>(&self, node: &'ctx ast::Node<T>) {
@@ -0,0 +1,417 @@
+//! Line, span and branch coverage of the KCL program evaluation.
+//!
+//! The executable lines are the lines of statements (including schema attributes) and check
+//! expressions in the program. The branch points are the conditions of if statements, if
+//! expressions, if entries of lists and configs, and the guards and tests of check expressions.
+//! When the coverage is enabled, the evaluator records the hit count of these lines, the hit
+//! count of each statement and expression span and the outcomes of each branch point, which
+//! can be emitted as an LCOV tracefile or as a summary per file.
+
+use std::collections::BTreeMap;
+use std::fmt::Write;
+
+use kcl_ast::ast;
+use kcl_ast::walker::MutSelfWalker;
+use kcl_ast::{walk_node_if, walk_node_list};
+
+/// The coverage of a set of KCL files.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct Coverage {
+    /// The coverage of each file sorted by the file name.
+    pub files: BTreeMap<String, FileCoverage>,
+}
+
+/// The start and end position of a statement or an expression.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct CoverageSpan {
+    pub line: u64,
+    pub column: u64,
+    pub end_line: u64,
+    pub end_column: u64,
+}
+
+impl<T> From<&ast::Node<T>> for CoverageSpan {
+    fn from(node: &ast::Node<T>) -> Self {
+        CoverageSpan {
+            line: node.line,
+            column: node.column,
+            end_line: node.end_line,
+            end_column: node.end_column,
+        }
+    }
+}
+
+/// The outcomes of a branch point.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct BranchCoverage {
+    /// The times the condition is true, e.g. the if body is taken or the check passes.
+    pub taken: u64,
+    /// The times the condition is false, e.g. the else body is taken or the check fails.
+    pub not_taken: u64,
+}
+
+impl BranchCoverage {
+    /// Returns whether the branch point has been evaluated.
+    #[inline]
+    pub fn is_evaluated(&self) -> bool {
+        self.taken > 0 || self.not_taken > 0
+    }
+}
+
+/// The coverage of a KCL file.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct FileCoverage {
+    /// The hit count of each executable line.
+    pub lines: BTreeMap<u64, u64>,
+    /// The hit count of each statement and expression span.
+    pub spans: BTreeMap<CoverageSpan, u64>,
+    /// The outcomes of each branch point keyed by the span of its condition.
+    pub branches: BTreeMap<CoverageSpan, BranchCoverage>,
+}
+
+impl FileCoverage {
+    /// Returns the number of executable lines.
+    #[inline]
+    pub fn lines_found(&self) -> usize {
+        self.lines.len()
+    }
+
+    /// Returns the number of executable lines hit at least once.
+    #[inline]
+    pub fn lines_hit(&self) -> usize {
+        self.lines.values().filter(|count| **count > 0).count()
+    }
+
+    /// Returns the percentage of the hit lines, which is 100 when there are no executable lines.
+    #[inline]
+    pub fn percent(&self) -> f64 {
+        percent(self.lines_hit(), self.lines_found())
+    }
+
+    /// Returns the number of branches, each branch point has a true and a false branch.
+    #[inline]
+    pub fn branches_found(&self) -> usize {
+        self.branches.len() * 2
+    }
+
+    /// Returns the number of branches taken at least once.
+    #[inline]
+    pub fn branches_hit(&self) -> usize {
+        self.branches
+            .values()
+            .map(|branch| (branch.taken > 0) as usize + (branch.not_taken > 0) as usize)
+            .sum()
+    }
+
+    /// Returns the percentage of the taken branches, which is 100 when there are no branches.
+    #[inline]
+    pub fn branch_percent(&self) -> f64 {
+        percent(self.branches_hit(), self.branches_found())
+    }
+}
+
+impl Coverage {
+    /// New a coverage with all the executable lines, spans and branches of the program
+    /// modules unhit.
+    pub fn new(program: &ast::Program) -> Self {
+        let mut coverage = Coverage::default();
+        for modules in program.pkgs.values() {
+            for module in modules {
+                if let Ok(Some(module)) = program.get_module(module) {
+                    let mut collector = CoverageCollector::default();
+                    collector.walk_module(&module);
+                    let file = coverage.files.entry(module.filename.clone()).or_default();
+                    for line in collector.lines {
+                        file.lines.entry(line).or_default();
+                    }
+                    for span in collector.spans {
+                        file.spans.entry(span).or_default();
+                    }
+                    for span in collector.branches {
+                        file.branches.entry(span).or_default();
+                    }
+                }
+            }
+        }
+        coverage
+    }
+
+    /// Records a hit of the statement or check expression node, which hits its line and span.
+    #[inline]
+    pub fn hit<T>(&mut self, node: &ast::Node<T>) {
+        if let Some(file) = self.files.get_mut(&node.filename) {
+            if let Some(count) = file.lines.get_mut(&node.line) {
+                *count += 1;
+            }
+            if let Some(count) = file.spans.get_mut(&CoverageSpan::from(node)) {
+                *count += 1;
+            }
+        }
+    }
+
+    /// Records a hit of the expression node span.
+    #[inline]
+    pub fn hit_span<T>(&mut self, node: &ast::Node<T>) {
+        if let Some(count) = self
+            .files
+            .get_mut(&node.filename)
+            .and_then(|file| file.spans.get_mut(&CoverageSpan::from(node)))
+        {
+            *count += 1;
+        }
+    }
+
+    /// Records the outcome of the branch point with the condition node.
+    #[inline]
+    pub fn branch<T>(&mut self, cond: &ast::Node<T>, is_truth: bool) {
+        if let Some(branch) = self
+            .files
+            .get_mut(&cond.filename)
+            .and_then(|file| file.branches.get_mut(&CoverageSpan::from(cond)))
+        {
+            if is_truth {
+                branch.taken += 1;
+            } else {
+                branch.not_taken += 1;
+            }
+        }
+    }
+
+    /// Merges the hit counts of another coverage into this coverage.
+    pub fn merge(&mut self, other: Coverage) {
+        for (filename, other_file) in other.files {
+            let file = self.files.entry(filename).or_default();
+            for (line, count) in other_file.lines {
+                *file.lines.entry(line).or_default() += count;
+            }
+            for (span, count) in other_file.spans {
+                *file.spans.entry(span).or_default() += count;
+            }
+            for (span, other_branch) in other_file.branches {
+                let branch = file.branches.entry(span).or_default();
+                branch.taken += other_branch.taken;
+                branch.not_taken += other_branch.not_taken;
+            }
+        }
+    }
+
+    /// Retains only the files specified by the predicate.
+    pub fn retain_files(&mut self, mut f: impl FnMut(&str) -> bool) {
+        self.files.retain(|filename, _| f(filename));
+    }
+
+    /// Returns the number of executable lines of all files.
+    pub fn lines_found(&self) -> usize {
+        self.files.values().map(|file| file.lines_found()).sum()
+    }
+
+    /// Returns the number of executable lines hit at least once of all files.
+    pub fn lines_hit(&self) -> usize {
+        self.files.values().map(|file| file.lines_hit()).sum()
+    }
+
+    /// Returns the percentage of the hit lines of all files, which is 100 when there are
+    /// no executable lines.
+    pub fn percent(&self) -> f64 {
+        percent(self.lines_hit(), self.lines_found())
+    }
+
+    /// Returns the number of branches of all files.
+    pub fn branches_found(&self) -> usize {
+        self.files.values().map(|file| file.branches_found()).sum()
+    }
+
+    /// Returns the number of branches taken at least once of all files.
+    pub fn branches_hit(&self) -> usize {
+        self.files.values().map(|file| file.branches_hit()).sum()
+    }
+
+    /// Returns the percentage of the taken branches of all files, which is 100 when there
+    /// are no branches.
+    pub fn branch_percent(&self) -> f64 {
+        percent(self.branches_hit(), self.branches_found())
+    }
+
+    /// Returns the coverage as an LCOV tracefile.
+    pub fn to_lcov(&self) -> String {
+        let mut lcov = String::new();
+        for (filename, file) in &self.files {
+            let _ = writeln!(lcov, "TN:");
+            let _ = writeln!(lcov, "SF:{filename}");
+            // The branch points on the same line are numbered as blocks by their columns,
+            // the branch 0 is the true branch and the branch 1 is the false branch.
+            let mut block = 0;
+            let mut last_line = None;
+            for (span, branch) in &file.branches {
+                block = if last_line == Some(span.line) {
+                    block + 1
+                } else {
+                    0
+                };
+                last_line = Some(span.line);
+                for (id, count) in [branch.taken, branch.not_taken].iter().enumerate() {
+                    if branch.is_evaluated() {
+                        let _ = writeln!(lcov, "BRDA:{},{block},{id},{count}", span.line);
+                    } else {
+                        let _ = writeln!(lcov, "BRDA:{},{block},{id},-", span.line);
+                    }
+                }
+            }
+            let _ = writeln!(lcov, "BRF:{}", file.branches_found());
+            let _ = writeln!(lcov, "BRH:{}", file.branches_hit());
+            for (line, count) in &file.lines {
+                let _ = writeln!(lcov, "DA:{line},{count}");
+            }
+            let _ = writeln!(lcov, "LF:{}", file.lines_found());
+            let _ = writeln!(lcov, "LH:{}", file.lines_hit());
+            let _ = writeln!(lcov, "end_of_record");
+        }
+        lcov
+    }
+
+    /// Returns the coverage summary table of each file and the total coverage.
+    pub fn summary(&self) -> String {
+        let width = self
+            .files
+            .keys()
+            .map(|filename| filename.len())
+            .chain(["Filename".len()])
+            .max()
+            .unwrap_or_default();
+        let mut summary = String::new();
+        let _ = writeln!(
+            summary,
+            "{:<width$}  {:>8}  {:>8}  {:>7}  {:>8}  {:>8}  {:>7}",
+            "Filename", "Lines", "Hit", "Cover", "Branches", "Taken", "Cover"
+        );
+        let rows = self
+            .files
+            .iter()
+            .map(|(filename, file)| {
+                (
+                    filename.as_str(),
+                    (file.lines_found(), file.lines_hit(), file.percent()),
+                    (
+                        file.branches_found(),
+                        file.branches_hit(),
+                        file.branch_percent(),
+                    ),
+                )
+            })
+            .chain([(
+                "TOTAL",
+                (self.lines_found(), self.lines_hit(), self.percent()),
+                (
+                    self.branches_found(),
+                    self.branches_hit(),
+                    self.branch_percent(),
+                ),
+            )]);
+        for (name, (found, hit, percent), (br_found, br_hit, br_percent)) in rows {
+            let _ = writeln!(
+                summary,
+                "{name:<width$}  {found:>8}  {hit:>8}  {percent:>6.1}%  {br_found:>8}  {br_hit:>8}  {br_percent:>6.1}%"
+            );
+        }
+        summary
+    }
+}
+
+#[inline]
+fn percent(hit: usize, found: usize) -> f64 {
+    if found == 0 {
+        100.0
+    } else {
+        hit as f64 * 100.0 / found as f64
+    }
+}
+
+/// Collects the lines of statements and check expressions, the spans of statements and
+/// expressions and the branch points.
+#[derive(Default)]
+struct CoverageCollector {
+    lines: Vec<u64>,
+    spans: Vec<CoverageSpan>,
+    branches: Vec<CoverageSpan>,
+}
+
+impl CoverageCollector {
+    fn checks(&mut self, checks: &[ast::NodeRef<ast::CheckExpr>]) {
+        for check in checks {
+            self.lines.push(check.line);
+            self.spans.push(CoverageSpan::from(check.as_ref()));
+            if let Some(if_cond) = &check.node.if_cond {
+                self.branches.push(CoverageSpan::from(if_cond.as_ref()));
+            }
+            self.branches
+                .push(CoverageSpan::from(check.node.test.as_ref()));
+            self.walk_check_expr(&check.node);
+        }
+    }
+}
+
+impl MutSelfWalker for CoverageCollector {
+    fn walk_stmt_node(&mut self, stmt: &ast::Node<ast::Stmt>) {
+        self.lines.push(stmt.line);
+        self.spans.push(CoverageSpan::from(stmt));
+        self.walk_stmt(&stmt.node);
+    }
+
+    fn walk_expr_node(&mut self, expr: &ast::Node<ast::Expr>) {
+        self.spans.push(CoverageSpan::from(expr));
+        self.walk_expr(&expr.node);
+    }
+
+    fn walk_if_stmt(&mut self, if_stmt: &ast::IfStmt) {
+        self.branches
+            .push(CoverageSpan::from(if_stmt.cond.as_ref()));
+        self.walk_expr_node(&if_stmt.cond);
+        walk_node_list!(self, walk_stmt_node, if_stmt.body);
+        walk_node_list!(self, walk_stmt_node, if_stmt.orelse);
+    }
+
+    fn walk_if_expr(&mut self, if_expr: &ast::IfExpr) {
+        self.branches
+            .push(CoverageSpan::from(if_expr.cond.as_ref()));
+        self.walk_expr_node(&if_expr.cond);
+        self.walk_expr_node(&if_expr.body);
+        self.walk_expr_node(&if_expr.orelse);
+    }
+
+    fn walk_list_if_item_expr(&mut self, list_if_item_expr: &ast::ListIfItemExpr) {
+        self.branches
+            .push(CoverageSpan::from(list_if_item_expr.if_cond.as_ref()));
+        self.walk_expr_node(&list_if_item_expr.if_cond);
+        walk_node_list!(self, walk_expr_node, list_if_item_expr.exprs);
+        walk_node_if!(self, walk_expr_node, list_if_item_expr.orelse);
+    }
+
+    fn walk_config_if_entry_expr(&mut self, config_if_entry_expr: &ast::ConfigIfEntryExpr) {
+        self.branches
+            .push(CoverageSpan::from(config_if_entry_expr.if_cond.as_ref()));
+        self.walk_expr_node(&config_if_entry_expr.if_cond);
+        for config_entry in &config_if_entry_expr.items {
+            walk_node_if!(self, walk_expr_node, config_entry.node.key);
+            self.walk_expr_node(&config_entry.node.value);
+        }
+        walk_node_if!(self, walk_expr_node, config_if_entry_expr.orelse);
+    }
+
+    fn walk_joined_string(&mut self, joined_string: &ast::JoinedString) {
+        // The string fragments are not evaluated as expressions.
+        for value in &joined_string.values {
+            if !matches!(value.node, ast::Expr::StringLit(_)) {
+                self.walk_expr_node(value);
+            }
+        }
+    }
+
+    fn walk_schema_stmt(&mut self, schema_stmt: &ast::SchemaStmt) {
+        walk_node_list!(self, walk_stmt_node, schema_stmt.body);
+        self.checks(&schema_stmt.checks);
+    }
+
+    fn walk_rule_stmt(&mut self, rule_stmt: &ast::RuleStmt) {
+        self.checks(&rule_stmt.checks);
+    }
+}
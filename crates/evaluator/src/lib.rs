@@ -5,6 +5,7 @@ mod tests;
 
 mod calculation;
 mod context;
+mod coverage;
 mod error;
 mod func;
 #[macro_use]
@@ -22,6 +23,7 @@ mod value;
 
 extern crate kcl_error;
 
+pub use coverage::{BranchCoverage, Coverage, CoverageSpan, FileCoverage};
 use func::FunctionEvalContextRef;
 use generational_arena::{Arena, Index};
 use kcl_primitives::IndexMap;
@@ -91,6 +93,8 @@ pub struct Evaluator<'ctx> {
     pub backtrack_meta: RefCell<Vec<BacktrackMeta>>,
     /// Current AST id for the evaluator walker.
     pub ast_id: RefCell<AstIndex>,
+    /// Line and branch coverage of the evaluation, which is recorded only when enabled.
+    pub coverage: RefCell<Option<Coverage>>,
 }
 
 #[derive(Clone)]
@@ -156,9 +160,20 @@ impl<'ctx> Evaluator<'ctx> {
             backtrack_meta: RefCell::new(Default::default()),
             ast_id: RefCell::new(AstIndex::default()),
             ctx_stack: RefCell::new(Default::default()),
+            coverage: RefCell::new(None),
         }
     }
 
+    /// Enable the line and branch coverage recording of the program evaluation.
+    pub fn enable_coverage(&self) {
+        *self.coverage.borrow_mut() = Some(Coverage::new(self.program));
+    }
+
+    /// Take the recorded coverage, returns `None` if the coverage is not enabled.
+    pub fn take_coverage(&self) -> Option<Coverage> {
+        self.coverage.borrow_mut().take()
+    }
+
     /// Evaluate the program and return the JSON and YAML result.
     pub fn run(self: &Evaluator<'ctx>) -> Result<(String, String)> {
         let modules = self.program.get_modules_for_pkg(kcl_ast::MAIN_PKG);
@@ -14,6 +14,7 @@ impl<'ctx> Evaluator<'_> {
         for stmt in &module.body {
             match &stmt.node {
                 ast::Stmt::Import(import_stmt) => {
+                    self.record_coverage(stmt);
                     self.walk_import_stmt(import_stmt)
                         .expect(kcl_error::RUNTIME_ERROR_MSG);
                 }
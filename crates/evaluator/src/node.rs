@@ -39,6 +39,7 @@ impl<'ctx> TypedResultWalker<'ctx> for Evaluator<'ctx> {
         backtrack_break_here!(self, stmt);
         self.update_ctx_panic_info(stmt);
         self.update_ast_id(stmt);
+        self.record_coverage(stmt);
         let value = match &stmt.node {
             ast::Stmt::TypeAlias(type_alias) => self.walk_type_alias_stmt(type_alias),
             ast::Stmt::Expr(expr_stmt) => self.walk_expr_stmt(expr_stmt),
@@ -191,6 +192,7 @@ impl<'ctx> TypedResultWalker<'ctx> for Evaluator<'ctx> {
     fn walk_if_stmt(&self, if_stmt: &'ctx ast::IfStmt) -> Self::Result {
         let cond = self.walk_expr(&if_stmt.cond)?;
         let is_truth = self.value_is_truthy(&cond);
+        self.record_branch_coverage(&if_stmt.cond, is_truth);
         // Is backtrack only orelse stmt?
         if self.is_backtrack_only_or_else() {
             if !is_truth {
@@ -302,6 +304,7 @@ impl<'ctx> TypedResultWalker<'ctx> for Evaluator<'ctx> {
 
     fn walk_expr(&self, expr: &'ctx ast::Node<ast::Expr>) -> Self::Result {
         self.update_ctx_panic_info(expr);
+        self.record_expr_coverage(expr);
         match &expr.node {
             ast::Expr::Target(target) => self.walk_target(target),
             ast::Expr::Identifier(identifier) => self.walk_identifier(identifier),
@@ -549,6 +552,7 @@ impl<'ctx> TypedResultWalker<'ctx> for Evaluator<'ctx> {
     fn walk_if_expr(&self, if_expr: &'ctx ast::IfExpr) -> Self::Result {
         let cond = self.walk_expr(&if_expr.cond)?;
         let is_truth = self.value_is_truthy(&cond);
+        self.record_branch_coverage(&if_expr.cond, is_truth);
         if is_truth {
             self.walk_expr(&if_expr.body)
         } else {
@@ -737,6 +741,7 @@ impl<'ctx> TypedResultWalker<'ctx> for Evaluator<'ctx> {
     fn walk_list_if_item_expr(&self, list_if_item_expr: &'ctx ast::ListIfItemExpr) -> Self::Result {
         let cond = self.walk_expr(&list_if_item_expr.if_cond)?;
         let is_truth = self.value_is_truthy(&cond);
+        self.record_branch_coverage(&list_if_item_expr.if_cond, is_truth);
         Ok(if is_truth {
             let mut then_value = self.list_value();
             for expr in &list_if_item_expr.exprs {
@@ -808,11 +813,13 @@ impl<'ctx> TypedResultWalker<'ctx> for Evaluator<'ctx> {
     ) -> Self::Result {
         let cond = self.walk_expr(&config_if_entry_expr.if_cond)?;
         let is_truth = self.value_is_truthy(&cond);
+        self.record_branch_coverage(&config_if_entry_expr.if_cond, is_truth);
         Ok(if is_truth {
             self.walk_config_entries(&config_if_entry_expr.items)?
         } else if let Some(orelse) = &config_if_entry_expr.orelse {
             // Config expr or config if entry expr.
             if let ast::Expr::Config(config_expr) = &orelse.node {
+                self.record_expr_coverage(orelse);
                 self.walk_config_entries(&config_expr.items)?
             } else {
                 self.walk_expr(orelse)?
@@ -925,11 +932,13 @@ impl<'ctx> TypedResultWalker<'ctx> for Evaluator<'ctx> {
         if let Some(if_cond) = &check_expr.if_cond {
             let if_value = self.walk_expr(if_cond)?;
             let is_truth = self.value_is_truthy(&if_value);
+            self.record_branch_coverage(if_cond, is_truth);
             if !is_truth {
                 return self.ok_result();
             }
         }
         let check_result = self.walk_expr(&check_expr.test)?;
+        self.record_branch_coverage(&check_expr.test, self.value_is_truthy(&check_result));
         let msg = {
             if let Some(msg) = &check_expr.msg {
                 self.walk_expr(msg).expect(kcl_error::INTERNAL_ERROR_MSG)
@@ -1706,7 +1715,10 @@ impl<'ctx> Evaluator<'ctx> {
                 };
                 // Store a local variable for every entry key.
                 let key = match &optional_name {
-                    Some(name) if !self.is_local_var(name) => self.string_value(name),
+                    Some(name) if !self.is_local_var(name) => {
+                        self.record_expr_coverage(key_node);
+                        self.string_value(name)
+                    }
                     _ => self.walk_expr(key_node)?,
                 };
                 self.dict_insert(
@@ -142,6 +142,7 @@ pub fn rule_check(
     }
     // Call self check function
     for check_expr in &ctx.borrow().node.checks {
+        s.record_coverage(check_expr);
         s.walk_check_expr(&check_expr.node)
             .expect(kcl_error::RUNTIME_ERROR_MSG);
     }
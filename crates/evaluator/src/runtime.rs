@@ -6,10 +6,13 @@ use std::{
 
 use kcl_runtime::{
     Context, SchemaTypeFunc, UnsafeWrapper, ValueRef, get_call_arg, kcl_builtin_reduce,
-    kcl_plugin_invoke, kcl_runtime_catch, ptr_as_ref,
+    kcl_collection_group_by, kcl_collection_merge, kcl_collection_partition,
+    kcl_collection_unique_by, kcl_plugin_invoke, kcl_runtime_catch, key_by_path, must_get_list_arg,
+    must_get_list_strategy_arg, ptr_as_ref,
 };
 
 use crate::Evaluator;
+use crate::union::union_entry;
 
 /// Macro to define evaluator builtins.
 /// Each entry maps: stub function pointer => handler function
@@ -33,6 +36,10 @@ fn get_evaluator_builtin_handler(
     evaluator_builtins!(ptr;
         kcl_runtime_catch => runtime_catch,
         kcl_builtin_reduce => runtime_reduce,
+        kcl_collection_group_by => collection_group_by,
+        kcl_collection_unique_by => collection_unique_by,
+        kcl_collection_partition => collection_partition,
+        kcl_collection_merge => collection_merge,
     )
 }
 
@@ -138,6 +145,69 @@ pub fn runtime_reduce(s: &Evaluator, args: &ValueRef, kwargs: &ValueRef) -> Valu
     acc
 }
 
+/// Group the list items by a key path or a key function.
+pub fn collection_group_by(s: &Evaluator, args: &ValueRef, kwargs: &ValueRef) -> ValueRef {
+    let items = must_get_list_arg("group_by", "items", args, kwargs, 0);
+    let key = get_call_arg(args, kwargs, 1, Some("key"))
+        .unwrap_or_else(|| panic!("group_by() missing the required positional argument: 'key'"));
+    items.list_group_by(&mut key_function(s, "group_by", "key", &key))
+}
+
+/// Returns the list items with the first occurrence of each key, and the items
+/// themselves are the keys when no key is given.
+pub fn collection_unique_by(s: &Evaluator, args: &ValueRef, kwargs: &ValueRef) -> ValueRef {
+    let items = must_get_list_arg("unique_by", "items", args, kwargs, 0);
+    match get_call_arg(args, kwargs, 1, Some("key")).filter(|v| !v.is_none_or_undefined()) {
+        Some(key) => items.list_unique_by(&mut key_function(s, "unique_by", "key", &key)),
+        None => items.list_unique_by(&mut |item| item.clone()),
+    }
+}
+
+/// Split the list items by a key path or a predicate function.
+pub fn collection_partition(s: &Evaluator, args: &ValueRef, kwargs: &ValueRef) -> ValueRef {
+    let items = must_get_list_arg("partition", "items", args, kwargs, 0);
+    let predicate = get_call_arg(args, kwargs, 1, Some("predicate")).unwrap_or_else(|| {
+        panic!("partition() missing the required positional argument: 'predicate'")
+    });
+    items.list_partition(&mut key_function(s, "partition", "predicate", &predicate))
+}
+
+/// Deep merge the values from left to right with the union semantics of the evaluator,
+/// which checks the merged schemas again.
+pub fn collection_merge(s: &Evaluator, args: &ValueRef, kwargs: &ValueRef) -> ValueRef {
+    let values = must_get_list_arg("merge", "values", args, kwargs, 0);
+    let strategy = must_get_list_strategy_arg(args, kwargs);
+    let opts = strategy.union_options();
+    let mut result = ValueRef::dict(None);
+    for (i, value) in values.as_list_ref().values.iter().enumerate() {
+        result = if i == 0 {
+            value.deep_copy()
+        } else {
+            let delta = result.list_merge_delta(value, strategy);
+            union_entry(s, &mut result.deep_copy(), &delta, false, &opts)
+        };
+    }
+    result
+}
+
+/// Returns the key function of a key path or a KCL function that takes the item.
+fn key_function<'a>(
+    s: &'a Evaluator,
+    name: &str,
+    arg: &str,
+    key: &'a ValueRef,
+) -> Box<dyn FnMut(&ValueRef) -> ValueRef + 'a> {
+    if key.is_str() {
+        let path = key.as_str();
+        return Box::new(move |item| key_by_path(&path)(item));
+    }
+    let proxy = key
+        .try_get_proxy()
+        .unwrap_or_else(|| panic!("{name}() argument '{arg}' must be a key path or a function"));
+    let kwargs = ValueRef::dict(None);
+    Box::new(move |item| s.invoke_proxy_function(proxy, &ValueRef::list(Some(&[item])), &kwargs))
+}
+
 /// Get the accumulator type from the reducer's first parameter, if available.
 fn get_reducer_acc_type(s: &Evaluator, proxy: generational_arena::Index) -> Option<String> {
     use crate::proxy::Proxy;
@@ -638,6 +638,7 @@ pub(crate) fn schema_check(
     {
         let ctx = ctx.borrow();
         for check_expr in &ctx.node.checks {
+            s.record_coverage(check_expr);
             s.walk_check_expr(&check_expr.node)
                 .expect(kcl_error::RUNTIME_ERROR_MSG);
         }
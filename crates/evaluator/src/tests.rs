@@ -1,4 +1,4 @@
-use crate::Evaluator;
+use crate::{BranchCoverage, CoverageSpan, Evaluator};
 use kcl_ast::MAIN_PKG;
 use kcl_loader::{LoadPackageOptions, load_packages};
 use kcl_parser::LoadProgramOptions;
@@ -846,3 +846,163 @@ sum = testing.add(1, 1)
     let evaluator = Evaluator::new_with_runtime_ctx(&p.program, context_with_plugin());
     insta::assert_snapshot!(format!("{}", evaluator.run().unwrap().1));
 }
+
+#[test]
+fn test_exec_with_coverage() {
+    let src = r#"schema Person:
+    name: str
+    age: int = 1
+    if age > 10:
+        kind = "adult"
+    else:
+        kind = "child"
+
+    check:
+        age >= 0
+
+alice = Person {name = "alice"}
+"#;
+    let p = load_packages(&LoadPackageOptions {
+        paths: vec!["test.k".to_string()],
+        load_opts: Some(LoadProgramOptions {
+            k_code_list: vec![src.to_string()],
+            ..Default::default()
+        }),
+        load_builtin: false,
+        ..Default::default()
+    })
+    .unwrap();
+    let evaluator = Evaluator::new(&p.program);
+    evaluator.enable_coverage();
+    evaluator.run().unwrap();
+    let coverage = evaluator.take_coverage().unwrap();
+    assert_eq!(coverage.files.len(), 1);
+    let file = coverage.files.values().next().unwrap();
+    let hit_lines: Vec<u64> = file
+        .lines
+        .iter()
+        .filter(|(_, count)| **count > 0)
+        .map(|(line, _)| *line)
+        .collect();
+    assert_eq!(
+        file.lines.keys().copied().collect::<Vec<u64>>(),
+        [1, 2, 3, 4, 5, 7, 10, 12]
+    );
+    assert_eq!(hit_lines, [1, 2, 3, 4, 7, 10, 12]);
+    let unhit_spans: Vec<CoverageSpan> = file
+        .spans
+        .iter()
+        .filter(|(_, count)| **count == 0)
+        .map(|(span, _)| *span)
+        .collect();
+    // Only the statement `kind = "adult"` and its value are not evaluated.
+    assert_eq!(
+        unhit_spans,
+        [
+            CoverageSpan {
+                line: 5,
+                column: 8,
+                end_line: 5,
+                end_column: 22
+            },
+            CoverageSpan {
+                line: 5,
+                column: 15,
+                end_line: 5,
+                end_column: 22
+            }
+        ]
+    );
+    // The if condition is false and the check passes.
+    let branches: Vec<(u64, BranchCoverage)> = file
+        .branches
+        .iter()
+        .map(|(span, branch)| (span.line, *branch))
+        .collect();
+    assert_eq!(
+        branches,
+        [
+            (
+                4,
+                BranchCoverage {
+                    taken: 0,
+                    not_taken: 1
+                }
+            ),
+            (
+                10,
+                BranchCoverage {
+                    taken: 1,
+                    not_taken: 0
+                }
+            )
+        ]
+    );
+    assert_eq!((file.branches_found(), file.branches_hit()), (4, 2));
+    let lcov = coverage.to_lcov();
+    assert!(lcov.contains("DA:5,0\n"), "{lcov}");
+    assert!(
+        lcov.contains("BRDA:4,0,0,0\nBRDA:4,0,1,1\nBRDA:10,0,0,1\nBRDA:10,0,1,0\nBRF:4\nBRH:2\n"),
+        "{lcov}"
+    );
+    assert!(lcov.ends_with("LF:8\nLH:7\nend_of_record\n"), "{lcov}");
+    let summary = coverage.summary();
+    assert!(summary.contains("Branches"), "{summary}");
+    assert!(summary.contains("TOTAL"), "{summary}");
+    assert!(evaluator.take_coverage().is_none());
+}
+
+#[test]
+fn test_exec_collect_check_failures() {
+    let src = r#"schema Person:
+    name: str
+    age: int
+    labels: {str:str} = {}
+
+    check:
+        len(name) > 0, "name must not be empty"
+        age >= 0, "age must be non-negative"
+
+schema Team:
+    leader: Person
+    members: [Person]
+
+team = Team {
+    leader = Person {name = "", age = -1}
+    members = [
+        Person {name = "bob", age = 1, labels = {key = 1}}
+        Person {name = "carol", age = -2}
+    ]
+}
+"#;
+    let p = load_packages(&LoadPackageOptions {
+        paths: vec!["test.k".to_string()],
+        load_opts: Some(LoadProgramOptions {
+            k_code_list: vec![src.to_string()],
+            ..Default::default()
+        }),
+        load_builtin: false,
+        ..Default::default()
+    })
+    .unwrap();
+    let mut ctx = Context::new();
+    ctx.cfg.collect_check_failures = true;
+    let ctx = Rc::new(RefCell::new(ctx));
+    let evaluator = Evaluator::new_with_runtime_ctx(&p.program, ctx.clone());
+    evaluator.run().unwrap();
+    let failures: Vec<(String, i32)> = ctx
+        .borrow()
+        .check_failures
+        .iter()
+        .map(|failure| (failure.message.clone(), failure.kcl_line))
+        .collect();
+    assert_eq!(
+        failures,
+        vec![
+            ("name must not be empty".to_string(), 7),
+            ("age must be non-negative".to_string(), 8),
+            ("expect {str:str}, got dict".to_string(), 4),
+            ("age must be non-negative".to_string(), 8),
+        ]
+    );
+}
@@ -65,8 +65,22 @@ pub fn type_pack_and_check(
     expected_types: Vec<&str>,
     strict: bool,
 ) -> ValueRef {
+    match try_type_pack_and_check(s, value, expected_types, strict) {
+        Ok(value) => value,
+        Err(msg) => panic!("{msg}"),
+    }
+}
+
+/// Type pack and check ValueRef with the expected type vector, and returns the type
+/// mismatch error message instead of panicking.
+pub fn try_type_pack_and_check(
+    s: &Evaluator,
+    value: &ValueRef,
+    expected_types: Vec<&str>,
+    strict: bool,
+) -> Result<ValueRef, String> {
     if value.is_none_or_undefined() || expected_types.is_empty() {
-        return value.clone();
+        return Ok(value.clone());
     }
     let is_schema = value.is_schema();
     let mut checked = false;
@@ -181,7 +195,7 @@ pub fn type_pack_and_check(
                 }
             }
         }
-        panic!(
+        return Err(format!(
             "expect {expected_type}, got {}{}",
             val_plan::type_of(value, true),
             if error_msgs.is_empty() {
@@ -189,9 +203,9 @@ pub fn type_pack_and_check(
             } else {
                 format!(". For details:\n{}", error_msgs.join("\n"))
             }
-        );
+        ));
     }
-    converted_value
+    Ok(converted_value)
 }
 
 /// Convert collection value including dict/list to the potential schema
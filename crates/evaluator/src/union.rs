@@ -233,6 +233,8 @@ fn do_union(
                         }
                     }
                 }
+            } else {
+                obj.values = delta.values.clone();
             }
         }
         (Value::dict_value(obj), Value::dict_value(delta)) => union_fn(obj, delta),
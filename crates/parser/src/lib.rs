@@ -23,9 +23,10 @@ use kcl_ast::ast::Module;
 use kcl_ast::{MAIN_PKG, ast};
 use kcl_config::modfile::{KCL_FILE_EXTENSION, KCL_FILE_SUFFIX, KCL_MOD_FILE, get_vendor_home};
 use kcl_error::diagnostic::{Errors, Range};
-use kcl_error::{ErrorKind, Message, Position, Style};
-use kcl_primitives::IndexMap;
+use kcl_error::{Diagnostic, ErrorKind, Message, Position, Style};
+use kcl_primitives::{IndexMap, IndexSet};
 use kcl_sema::plugin::PLUGIN_MODULE_PREFIX;
+use kcl_utils::parallel::{num_threads, par_map};
 use kcl_utils::path::PathPrefix;
 use kcl_utils::pkgpath::parse_external_pkg_name;
 use kcl_utils::pkgpath::rm_external_pkg_name;
@@ -690,6 +691,78 @@ fn is_external_pkg(pkg_path: &str, opts: &LoadProgramOptions) -> Result<Option<P
 pub type ASTCache = Arc<RwLock<IndexMap<PathBuf, Arc<ast::Module>>>>;
 pub type FileGraphCache = Arc<RwLock<PkgFileGraph>>;
 
+/// The parsed module and the parse errors of a file parsed with a separate parse session.
+type ParsedFile = Result<(ast::Module, IndexSet<Diagnostic>)>;
+
+/// Returns the source code of the file, which is the given source or the cached source.
+fn get_file_source(
+    file: &PkgFile,
+    src: Option<String>,
+    module_cache: &KCLModuleCache,
+) -> Option<String> {
+    match src {
+        Some(src) => Some(src),
+        None => match &module_cache.read() {
+            Ok(cache) => cache.source_code.get(file.get_path()),
+            Err(_) => None,
+        }
+        .cloned(),
+    }
+}
+
+/// Parse the files concurrently with a separate parse session for each file, which returns
+/// the parsed modules and the parse errors by the file paths. The parse errors are added into
+/// the main parse session when the files are loaded in order with [`load_file`], so that the
+/// diagnostic order is the same as parsing the files sequentially.
+///
+/// Returns an empty map when there are not enough files or threads, and the files are parsed
+/// with the main parse session by [`parse_file`].
+fn parse_files_concurrently(
+    files: &[(PkgFile, Option<String>)],
+    module_cache: &KCLModuleCache,
+) -> HashMap<PathBuf, ParsedFile> {
+    if files.len() <= 1 || num_threads() <= 1 {
+        return HashMap::new();
+    }
+    let mut paths = HashSet::new();
+    let files: Vec<(PathBuf, Option<String>)> = files
+        .iter()
+        .filter(|(file, _)| paths.insert(file.get_path().clone()))
+        .map(|(file, src)| {
+            (
+                file.get_path().clone(),
+                get_file_source(file, src.clone(), module_cache),
+            )
+        })
+        .collect();
+    let parsed = par_map(&files, |(path, src)| {
+        let sess = ParseSessionRef::default();
+        let m = parse_file_with_global_session(sess.clone(), path.to_str().unwrap(), src.clone())?;
+        let diagnostics = std::mem::take(&mut sess.1.write().diagnostics);
+        Ok((m, diagnostics))
+    });
+    files
+        .into_iter()
+        .map(|(path, _)| path)
+        .zip(parsed)
+        .collect()
+}
+
+/// Returns the files whose modules are not in the module cache.
+fn uncached_files(
+    files: &[PkgFile],
+    module_cache: &KCLModuleCache,
+) -> Vec<(PkgFile, Option<String>)> {
+    match module_cache.read() {
+        Ok(cache) => files
+            .iter()
+            .filter(|file| !cache.ast_cache.contains_key(file.get_path()))
+            .map(|file| (file.clone(), None))
+            .collect(),
+        Err(_) => vec![],
+    }
+}
+
 #[allow(clippy::too_many_arguments)]
 pub fn parse_file(
     sess: ParseSessionRef,
@@ -701,15 +774,57 @@ pub fn parse_file(
     file_graph: FileGraphCache,
     opts: &LoadProgramOptions,
 ) -> Result<Vec<PkgFile>> {
-    let src = match src {
-        Some(src) => Some(src),
-        None => match &module_cache.read() {
-            Ok(cache) => cache.source_code.get(file.get_path()),
-            Err(_) => None,
-        }
-        .cloned(),
-    };
+    let src = get_file_source(&file, src, &module_cache);
     let m = parse_file_with_session(sess.clone(), file.get_path().to_str().unwrap(), src)?;
+    register_file(sess, file, m, module_cache, pkgs, pkgmap, file_graph, opts)
+}
+
+/// Load the file with the module parsed by [`parse_files_concurrently`], or parse the file
+/// with the main parse session when it is not parsed.
+#[allow(clippy::too_many_arguments)]
+fn load_file(
+    sess: ParseSessionRef,
+    file: PkgFile,
+    src: Option<String>,
+    parsed: &mut HashMap<PathBuf, ParsedFile>,
+    module_cache: KCLModuleCache,
+    pkgs: &mut HashMap<String, Vec<String>>,
+    pkgmap: &mut PkgMap,
+    file_graph: FileGraphCache,
+    opts: &LoadProgramOptions,
+) -> Result<Vec<PkgFile>> {
+    match parsed.remove(file.get_path()) {
+        Some(parsed) => {
+            let (m, diagnostics) = parsed?;
+            sess.add_parse_diagnostics(diagnostics);
+            register_file(sess, file, m, module_cache, pkgs, pkgmap, file_graph, opts)
+        }
+        None => parse_file(
+            sess,
+            file,
+            src,
+            module_cache,
+            pkgs,
+            pkgmap,
+            file_graph,
+            opts,
+        ),
+    }
+}
+
+/// Record the parsed module and its dependencies into the module cache and the file graph,
+/// and returns the dependent files.
+#[allow(clippy::too_many_arguments)]
+fn register_file(
+    sess: ParseSessionRef,
+    file: PkgFile,
+    m: ast::Module,
+    module_cache: KCLModuleCache,
+    pkgs: &mut HashMap<String, Vec<String>>,
+    pkgmap: &mut PkgMap,
+    file_graph: FileGraphCache,
+    opts: &LoadProgramOptions,
+) -> Result<Vec<PkgFile>> {
     let deps = get_deps(&file, &m, pkgs, pkgmap, opts, sess)?;
     let dep_files = deps.keys().cloned().collect();
     pkgmap.extend(deps.clone());
@@ -801,12 +916,14 @@ pub fn parse_pkg(
     file_graph: FileGraphCache,
     opts: &LoadProgramOptions,
 ) -> Result<Vec<PkgFile>> {
+    let mut parsed = parse_files_concurrently(&files, &module_cache);
     let mut dependent = vec![];
     for (file, src) in files {
-        let deps = parse_file(
+        let deps = load_file(
             sess.clone(),
             file.clone(),
             src,
+            &mut parsed,
             module_cache.clone(),
             pkgs,
             pkgmap,
@@ -856,74 +973,80 @@ pub fn parse_entry(
     )?;
     let mut unparsed_file: VecDeque<PkgFile> = dependent_paths.into();
 
-    // Bfs unparsed and import files
-    while let Some(file) = unparsed_file.pop_front() {
-        match &mut module_cache.write() {
-            Ok(m_cache) => match m_cache.file_pkg.get_mut(file.get_path()) {
-                Some(s) => {
-                    // The module ast has been parsed, but does not belong to the same package
-                    if s.insert(file.clone()) {
+    // Bfs unparsed and import files, the unparsed files of the same level are parsed concurrently.
+    while !unparsed_file.is_empty() {
+        let level: Vec<PkgFile> = unparsed_file.drain(..).collect();
+        let mut parsed =
+            parse_files_concurrently(&uncached_files(&level, &module_cache), &module_cache);
+        for file in level {
+            match &mut module_cache.write() {
+                Ok(m_cache) => match m_cache.file_pkg.get_mut(file.get_path()) {
+                    Some(s) => {
+                        // The module ast has been parsed, but does not belong to the same package
+                        if s.insert(file.clone()) {
+                            new_files.insert(file.clone());
+                        }
+                    }
+                    None => {
+                        let mut s = HashSet::new();
+                        s.insert(file.clone());
+                        m_cache.file_pkg.insert(file.get_path().clone(), s);
                         new_files.insert(file.clone());
                     }
-                }
-                None => {
-                    let mut s = HashSet::new();
-                    s.insert(file.clone());
-                    m_cache.file_pkg.insert(file.get_path().clone(), s);
-                    new_files.insert(file.clone());
-                }
-            },
-            Err(e) => return Err(anyhow::anyhow!("Parse file failed: {e}")),
-        }
+                },
+                Err(e) => return Err(anyhow::anyhow!("Parse file failed: {e}")),
+            }
 
-        let module_cache_read = module_cache.read();
-        match &module_cache_read {
-            Ok(m_cache) => match m_cache.ast_cache.get(file.get_path()) {
-                Some(m) => {
-                    let deps = m_cache.dep_cache.get(&file).cloned().unwrap_or_else(|| {
-                        get_deps(&file, &m.read().unwrap(), pkgs, pkgmap, opts, sess.clone())
-                            .unwrap()
-                    });
-                    let dep_files: Vec<PkgFile> = deps.keys().cloned().collect();
-                    pkgmap.extend(deps.clone());
-
-                    match &mut file_graph.write() {
-                        Ok(file_graph) => {
-                            file_graph.update_file(&file, &dep_files);
-
-                            for dep in dep_files {
-                                if parsed_file.insert(dep.clone()) {
-                                    unparsed_file.push_back(dep.clone());
+            let module_cache_read = module_cache.read();
+            match &module_cache_read {
+                Ok(m_cache) => match m_cache.ast_cache.get(file.get_path()) {
+                    Some(m) => {
+                        let deps = m_cache.dep_cache.get(&file).cloned().unwrap_or_else(|| {
+                            get_deps(&file, &m.read().unwrap(), pkgs, pkgmap, opts, sess.clone())
+                                .unwrap()
+                        });
+                        let dep_files: Vec<PkgFile> = deps.keys().cloned().collect();
+                        pkgmap.extend(deps.clone());
+
+                        match &mut file_graph.write() {
+                            Ok(file_graph) => {
+                                file_graph.update_file(&file, &dep_files);
+
+                                for dep in dep_files {
+                                    if parsed_file.insert(dep.clone()) {
+                                        unparsed_file.push_back(dep.clone());
+                                    }
                                 }
-                            }
 
-                            continue;
+                                continue;
+                            }
+                            Err(e) => return Err(anyhow::anyhow!("Parse entry failed: {e}")),
                         }
-                        Err(e) => return Err(anyhow::anyhow!("Parse entry failed: {e}")),
                     }
-                }
-                None => {
-                    new_files.insert(file.clone());
-                    drop(module_cache_read);
-                    let deps = parse_file(
-                        sess.clone(),
-                        file,
-                        None,
-                        module_cache.clone(),
-                        pkgs,
-                        pkgmap,
-                        file_graph.clone(),
-                        opts,
-                    )?;
-                    for dep in deps {
-                        if parsed_file.insert(dep.clone()) {
-                            unparsed_file.push_back(dep.clone());
+                    None => {
+                        new_files.insert(file.clone());
+                        drop(module_cache_read);
+                        let deps = load_file(
+                            sess.clone(),
+                            file,
+                            None,
+                            &mut parsed,
+                            module_cache.clone(),
+                            pkgs,
+                            pkgmap,
+                            file_graph.clone(),
+                            opts,
+                        )?;
+                        for dep in deps {
+                            if parsed_file.insert(dep.clone()) {
+                                unparsed_file.push_back(dep.clone());
+                            }
                         }
                     }
-                }
-            },
-            Err(e) => return Err(anyhow::anyhow!("Parse entry failed: {e}")),
-        };
+                },
+                Err(e) => return Err(anyhow::anyhow!("Parse entry failed: {e}")),
+            };
+        }
     }
     Ok(new_files)
 }
@@ -1111,62 +1234,72 @@ pub fn load_all_files_under_paths(
                 let pkgs_not_imported = &mut res.program.pkgs_not_imported;
 
                 let mut new_files = HashSet::new();
-                // Bfs unparsed and import files
+                // Bfs unparsed and import files, the unparsed files of the same level are parsed concurrently.
                 loader.parsed_file.extend(unparsed_file.clone());
-                while let Some(file) = unparsed_file.pop_front() {
-                    new_files.insert(file.clone());
-                    let module_cache_read = module_cache.read();
-                    match &module_cache_read {
-                        Ok(m_cache) => match m_cache.ast_cache.get(file.get_path()) {
-                            Some(_) => continue,
-                            None => {
-                                drop(module_cache_read);
-                                let deps = parse_file(
-                                    sess.clone(),
-                                    file.clone(),
-                                    None,
-                                    module_cache.clone(),
-                                    pkgs_not_imported,
-                                    &mut loader.pkgmap,
-                                    loader.file_graph.clone(),
-                                    &loader.opts,
-                                )?;
-
-                                let m_ref = match module_cache.read() {
-                                    Ok(module_cache) => module_cache
-                                        .ast_cache
-                                        .get(file.get_path())
-                                        .unwrap_or_else(|| {
-                                            panic!(
-                                                "Module not found in module: {:?}",
-                                                file.get_path()
-                                            )
-                                        })
-                                        .clone(),
-                                    Err(e) => {
-                                        return Err(anyhow::anyhow!("Parse program failed: {e}"));
-                                    }
-                                };
-
-                                let pkg = loader.pkgmap.get(&file).expect("file not in pkgmap");
-                                let mut m = m_ref.write().unwrap();
-                                fix_rel_import_path_with_file(
-                                    &pkg.pkg_root,
-                                    &mut m,
-                                    &file,
-                                    &loader.pkgmap,
-                                    &loader.opts,
-                                    sess.clone(),
-                                );
-
-                                for dep in deps {
-                                    if loader.parsed_file.insert(dep.clone()) {
-                                        unparsed_file.push_back(dep.clone());
+                while !unparsed_file.is_empty() {
+                    let level: Vec<PkgFile> = unparsed_file.drain(..).collect();
+                    let mut parsed = parse_files_concurrently(
+                        &uncached_files(&level, &module_cache),
+                        &module_cache,
+                    );
+                    for file in level {
+                        new_files.insert(file.clone());
+                        let module_cache_read = module_cache.read();
+                        match &module_cache_read {
+                            Ok(m_cache) => match m_cache.ast_cache.get(file.get_path()) {
+                                Some(_) => continue,
+                                None => {
+                                    drop(module_cache_read);
+                                    let deps = load_file(
+                                        sess.clone(),
+                                        file.clone(),
+                                        None,
+                                        &mut parsed,
+                                        module_cache.clone(),
+                                        pkgs_not_imported,
+                                        &mut loader.pkgmap,
+                                        loader.file_graph.clone(),
+                                        &loader.opts,
+                                    )?;
+
+                                    let m_ref = match module_cache.read() {
+                                        Ok(module_cache) => module_cache
+                                            .ast_cache
+                                            .get(file.get_path())
+                                            .unwrap_or_else(|| {
+                                                panic!(
+                                                    "Module not found in module: {:?}",
+                                                    file.get_path()
+                                                )
+                                            })
+                                            .clone(),
+                                        Err(e) => {
+                                            return Err(anyhow::anyhow!(
+                                                "Parse program failed: {e}"
+                                            ));
+                                        }
+                                    };
+
+                                    let pkg = loader.pkgmap.get(&file).expect("file not in pkgmap");
+                                    let mut m = m_ref.write().unwrap();
+                                    fix_rel_import_path_with_file(
+                                        &pkg.pkg_root,
+                                        &mut m,
+                                        &file,
+                                        &loader.pkgmap,
+                                        &loader.opts,
+                                        sess.clone(),
+                                    );
+
+                                    for dep in deps {
+                                        if loader.parsed_file.insert(dep.clone()) {
+                                            unparsed_file.push_back(dep.clone());
+                                        }
                                     }
                                 }
-                            }
-                        },
-                        Err(e) => return Err(anyhow::anyhow!("Parse entry failed: {e}")),
+                            },
+                            Err(e) => return Err(anyhow::anyhow!("Parse entry failed: {e}")),
+                        }
                     }
                 }
 
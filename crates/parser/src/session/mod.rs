@@ -116,6 +116,21 @@ impl ParseSession {
         self
     }
 
+    /// Add the parse errors reported by another parse session e.g., the session of a file
+    /// parsed on another thread into the parse session.
+    pub fn add_parse_diagnostics(&self, diagnostics: IndexSet<Diagnostic>) -> &Self {
+        for diagnostic in diagnostics {
+            if let Err(err) = self.0.add_err(diagnostic.clone()) {
+                bug!(
+                    "compiler session internal error occurs: {}",
+                    err.to_string()
+                )
+            }
+            self.1.write().add_diagnostic(diagnostic);
+        }
+        self
+    }
+
     /// Classify diagnostics into errors and warnings.
     pub fn classification(&self) -> (IndexSet<Diagnostic>, IndexSet<Diagnostic>) {
         self.1.read().classification()
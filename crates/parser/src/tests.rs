@@ -872,3 +872,39 @@ fn parse_all_file_under_path() {
 
     assert_eq!(res.paths.len(), 1);
 }
+
+#[test]
+fn test_parse_errors_in_order() {
+    let testpath = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("testdata")
+        .join("parallel_parse");
+    let main = testpath.join("main.k").display().to_string();
+    let load_errors = || {
+        let sess = ParseSessionRef::default();
+        let res = load_program(sess.clone(), &[&main], None, None).unwrap();
+        assert!(sess.0.diag_handler.has_errors().unwrap());
+        res.errors
+            .iter()
+            .map(|diag| {
+                let pos = &diag.messages[0].range.0;
+                let filename = PathBuf::from(&pos.filename);
+                let filename = filename.strip_prefix(&testpath).unwrap();
+                format!("{}:{}", filename.display(), pos.line)
+            })
+            .collect::<Vec<String>>()
+    };
+    // The files of the packages are parsed concurrently, and the parse errors are reported
+    // in the order of loading the files.
+    let errors = [
+        "main.k:5",
+        "a/a_0.k:3",
+        "a/a_1.k:1",
+        "b/b_0.k:3",
+        "b/b_1.k:1",
+        "c/c_0.k:1",
+        "c/c_1.k:1",
+    ];
+    for _ in 0..10 {
+        assert_eq!(load_errors(), errors);
+    }
+}
@@ -0,0 +1,605 @@
+//! Export KCL schemas as JSON Schema 2020-12 or OpenAPI v3 documents.
+//!
+//! Schema attributes are exported as object properties with their types, documents, default
+//! values and the `@deprecated` decorator. The `check:` constraints written as comparisons
+//! with literals, `len(attr)`, `attr in [...]`, `regex.match`, `multiplyof` and `isunique`
+//! are mapped to the validation keywords such as `minimum`, `maxLength`, `enum` and `pattern`,
+//! the other constraints can't be expressed by the keywords and are skipped.
+
+use std::collections::HashSet;
+use std::str::FromStr;
+
+use anyhow::{Result, bail};
+use kcl_ast::{MAIN_PKG, ast};
+use kcl_parser::parse_expr;
+use kcl_primitives::IndexMap;
+use kcl_sema::ty::{DictType, SchemaAttr, SchemaType, Type, TypeKind, TypeRef};
+use serde_json::{Map, Number, Value, json};
+
+use crate::query::{CompilationOptions, resolve_file_with_program};
+
+const JSON_SCHEMA_DIALECT: &str = "https://json-schema.org/draft/2020-12/schema";
+const OPENAPI_VERSION: &str = "3.0.3";
+const DEPRECATED_DECORATOR: &str = "deprecated";
+
+/// The document format of the exported schemas.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub enum SchemaFormat {
+    /// JSON Schema 2020-12 with the schemas in `$defs`.
+    #[default]
+    JsonSchema,
+    /// OpenAPI v3 with the schemas in `components.schemas`.
+    OpenApi,
+}
+
+impl FromStr for SchemaFormat {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s.to_lowercase().as_str() {
+            "" | "jsonschema" | "json_schema" | "json-schema" => Ok(SchemaFormat::JsonSchema),
+            "openapi" | "oas" => Ok(SchemaFormat::OpenApi),
+            _ => bail!("Invalid schema format '{s}', expected 'jsonschema' or 'openapi'"),
+        }
+    }
+}
+
+/// Export the schema definitions in the main package of the program as a pretty printed
+/// JSON Schema or OpenAPI document. The schemas referenced by the attributes are exported
+/// as well. When `schema_name` is given, only the schema and the schemas it references are
+/// exported, and the JSON Schema document refers to the schema at the root.
+///
+/// # Examples
+///
+/// ```
+/// use kcl_parser::LoadProgramOptions;
+/// use kcl_query::export::{export_schema, SchemaFormat};
+/// use kcl_query::query::CompilationOptions;
+///
+/// let code = r#"
+/// schema Person:
+///     name: str
+///     age: int
+///
+///     check:
+///         0 <= age < 120
+/// "#;
+/// let doc = export_schema(
+///     Some("Person"),
+///     SchemaFormat::JsonSchema,
+///     CompilationOptions {
+///         paths: vec!["main.k".to_string()],
+///         loader_opts: Some(LoadProgramOptions {
+///             k_code_list: vec![code.to_string()],
+///             ..Default::default()
+///         }),
+///         ..Default::default()
+///     },
+/// ).unwrap();
+/// let doc: serde_json::Value = serde_json::from_str(&doc).unwrap();
+/// assert_eq!(doc["$ref"], "#/$defs/Person");
+/// assert_eq!(doc["$defs"]["Person"]["properties"]["age"]["minimum"], 0);
+/// assert_eq!(doc["$defs"]["Person"]["properties"]["age"]["exclusiveMaximum"], 120);
+/// ```
+pub fn export_schema(
+    schema_name: Option<&str>,
+    format: SchemaFormat,
+    mut opts: CompilationOptions,
+) -> Result<String> {
+    // The default values of attributes are only recorded when values are resolved.
+    opts.resolve_opts.resolve_val = true;
+    let (program, scope) = resolve_file_with_program(&opts)?;
+    let mut exporter = SchemaExporter::new(&program, format);
+    let mut root = None;
+    for (name, obj) in &scope.borrow().elems {
+        let ty = obj.borrow().ty.clone();
+        if !ty.is_schema() {
+            continue;
+        }
+        let schema_ty = ty.into_schema_type();
+        if schema_ty.is_instance
+            || schema_ty.is_rule
+            || schema_ty.is_mixin
+            || schema_ty.is_protocol
+            || schema_name.is_some_and(|schema_name| schema_name != name)
+        {
+            continue;
+        }
+        root = Some(exporter.schema_ref(&schema_ty));
+    }
+    let Some(root) = root else {
+        match schema_name {
+            Some(schema_name) => bail!("Schema '{schema_name}' not found"),
+            None => bail!("No schema definitions found"),
+        }
+    };
+    let defs = Value::Object(exporter.defs);
+    let doc = match format {
+        SchemaFormat::JsonSchema => {
+            let mut doc = json!({
+                "$schema": JSON_SCHEMA_DIALECT,
+                "$defs": defs,
+            });
+            if schema_name.is_some() {
+                doc["$ref"] = root["$ref"].clone();
+            }
+            doc
+        }
+        SchemaFormat::OpenApi => json!({
+            "openapi": OPENAPI_VERSION,
+            "info": {
+                "title": schema_name.unwrap_or("KCL Schemas"),
+                "version": "0.0.0",
+            },
+            "paths": {},
+            "components": {
+                "schemas": defs,
+            },
+        }),
+    };
+    Ok(serde_json::to_string_pretty(&doc)?)
+}
+
+/// The attribute operand of a check constraint.
+enum Operand<'a> {
+    /// The attribute itself e.g., `age`.
+    Attr(&'a str),
+    /// The length of the attribute e.g., `len(name)`.
+    Len(&'a str),
+}
+
+struct SchemaExporter<'a> {
+    program: &'a ast::Program,
+    format: SchemaFormat,
+    defs: Map<String, Value>,
+    visited: HashSet<String>,
+}
+
+impl<'a> SchemaExporter<'a> {
+    fn new(program: &'a ast::Program, format: SchemaFormat) -> Self {
+        Self {
+            program,
+            format,
+            defs: Map::new(),
+            visited: HashSet::new(),
+        }
+    }
+
+    /// Returns the reference to the schema definition and exports the definition if needed.
+    fn schema_ref(&mut self, schema_ty: &SchemaType) -> Value {
+        let name = if schema_ty.pkgpath.is_empty() || schema_ty.pkgpath == MAIN_PKG {
+            schema_ty.name.clone()
+        } else {
+            format!("{}.{}", schema_ty.pkgpath, schema_ty.name)
+        };
+        // Mark the schema as visited before exporting it to stop the recursive references.
+        if self.visited.insert(name.clone()) {
+            let def = self.schema_def(schema_ty);
+            self.defs.insert(name.clone(), def);
+        }
+        let prefix = match self.format {
+            SchemaFormat::JsonSchema => "#/$defs/",
+            SchemaFormat::OpenApi => "#/components/schemas/",
+        };
+        json!({ "$ref": format!("{prefix}{name}") })
+    }
+
+    fn schema_def(&mut self, schema_ty: &SchemaType) -> Value {
+        let mut attrs = IndexMap::default();
+        collect_attrs(schema_ty, &mut attrs);
+        let mut properties = Map::new();
+        let mut required = vec![];
+        for (name, attr) in &attrs {
+            // Private and function attributes are not a part of the schema data.
+            if name.starts_with('_') || matches!(attr.ty.kind, TypeKind::Function(_)) {
+                continue;
+            }
+            let mut property = self.type_schema(&attr.ty);
+            if let Some(doc) = attr.doc.as_ref().filter(|doc| !doc.is_empty()) {
+                property["description"] = json!(doc);
+            }
+            if let Some(default) = attr
+                .default
+                .as_ref()
+                .and_then(|default| parse_expr(default))
+                .and_then(|expr| literal_value(&expr.node))
+            {
+                property["default"] = default;
+            }
+            if attr
+                .decorators
+                .iter()
+                .any(|decorator| decorator.name == DEPRECATED_DECORATOR)
+            {
+                property["deprecated"] = json!(true);
+            }
+            if !attr.is_optional && !attr.has_default {
+                required.push(json!(name));
+            }
+            properties.insert(name.to_string(), property);
+        }
+        for check in self.checks(schema_ty) {
+            if check.node.if_cond.is_none() {
+                self.apply_check(&check.node.test.node, &attrs, &mut properties);
+            }
+        }
+
+        let mut def = json!({
+            "type": "object",
+            "title": schema_ty.name,
+        });
+        if !schema_ty.doc.is_empty() {
+            def["description"] = json!(schema_ty.doc);
+        }
+        def["properties"] = Value::Object(properties);
+        if !required.is_empty() {
+            def["required"] = Value::Array(required);
+        }
+        def["additionalProperties"] = match &schema_ty.index_signature {
+            Some(index_signature) => self.type_schema(&index_signature.val_ty),
+            None => json!(false),
+        };
+        def
+    }
+
+    /// Returns the checks of the schema and its base schemas.
+    fn checks(&self, schema_ty: &SchemaType) -> Vec<ast::NodeRef<ast::CheckExpr>> {
+        let mut checks = match &schema_ty.base {
+            Some(base) => self.checks(base),
+            None => vec![],
+        };
+        if let Ok(Some(module)) = self.program.get_module(&schema_ty.filename) {
+            for stmt in &module.body {
+                if let ast::Stmt::Schema(schema_stmt) = &stmt.node
+                    && schema_stmt.name.node == schema_ty.name
+                {
+                    checks.extend(schema_stmt.checks.iter().cloned());
+                }
+            }
+        }
+        checks
+    }
+
+    fn type_schema(&mut self, ty: &Type) -> Value {
+        match &ty.kind {
+            TypeKind::Bool => json!({"type": "boolean"}),
+            TypeKind::BoolLit(v) => self.literal_schema("boolean", json!(v)),
+            TypeKind::Int => json!({"type": "integer"}),
+            TypeKind::IntLit(v) => self.literal_schema("integer", json!(v)),
+            TypeKind::Float => json!({"type": "number"}),
+            TypeKind::FloatLit(v) => self.literal_schema("number", json!(v)),
+            TypeKind::Str => json!({"type": "string"}),
+            TypeKind::StrLit(v) => self.literal_schema("string", json!(v)),
+            // Number multipliers such as `1Mi` are output as strings.
+            TypeKind::NumberMultiplier(_) => json!({"type": "string"}),
+            TypeKind::List(item_ty) => json!({
+                "type": "array",
+                "items": self.type_schema(item_ty),
+            }),
+            TypeKind::Dict(DictType { val_ty, .. }) => json!({
+                "type": "object",
+                "additionalProperties": self.type_schema(val_ty),
+            }),
+            TypeKind::Union(types) => self.union_schema(types),
+            TypeKind::Schema(schema_ty) => self.schema_ref(schema_ty),
+            TypeKind::None => match self.format {
+                SchemaFormat::JsonSchema => json!({"type": "null"}),
+                SchemaFormat::OpenApi => json!({"nullable": true}),
+            },
+            _ => json!({}),
+        }
+    }
+
+    fn literal_schema(&self, ty: &str, value: Value) -> Value {
+        match self.format {
+            SchemaFormat::JsonSchema => json!({"type": ty, "const": value}),
+            // OpenAPI v3.0 has no `const` keyword.
+            SchemaFormat::OpenApi => json!({"type": ty, "enum": [value]}),
+        }
+    }
+
+    fn union_schema(&mut self, types: &[TypeRef]) -> Value {
+        // A union containing `any` accepts any value.
+        if types.iter().any(|ty| ty.is_any()) {
+            return json!({});
+        }
+        let nullable = types.iter().any(|ty| ty.is_none());
+        let types: Vec<&TypeRef> = types.iter().filter(|ty| !ty.is_none()).collect();
+        let literals: Option<Vec<Value>> = types
+            .iter()
+            .map(|ty| match &ty.kind {
+                TypeKind::BoolLit(v) => Some(json!(v)),
+                TypeKind::IntLit(v) => Some(json!(v)),
+                TypeKind::FloatLit(v) => Some(json!(v)),
+                TypeKind::StrLit(v) => Some(json!(v)),
+                _ => None,
+            })
+            .collect();
+        let mut schema = match literals {
+            Some(mut values) if !values.is_empty() => {
+                if nullable && self.format == SchemaFormat::JsonSchema {
+                    values.push(Value::Null);
+                }
+                json!({ "enum": values })
+            }
+            _ if types.len() == 1 => self.type_schema(types[0]),
+            _ => json!({
+                "anyOf": types.iter().map(|ty| self.type_schema(ty)).collect::<Vec<Value>>(),
+            }),
+        };
+        if !nullable {
+            return schema;
+        }
+        match self.format {
+            // The null value has been added to the enum values.
+            SchemaFormat::JsonSchema if schema.get("enum").is_some() => schema,
+            SchemaFormat::JsonSchema => json!({"anyOf": [schema, {"type": "null"}]}),
+            // The siblings of `$ref` are ignored in OpenAPI v3.0.
+            SchemaFormat::OpenApi if schema.get("$ref").is_some() => {
+                json!({"allOf": [schema], "nullable": true})
+            }
+            SchemaFormat::OpenApi => {
+                schema["nullable"] = json!(true);
+                schema
+            }
+        }
+    }
+
+    /// Map the check expression to the validation keywords of the properties.
+    fn apply_check(
+        &self,
+        expr: &ast::Expr,
+        attrs: &IndexMap<String, SchemaAttr>,
+        properties: &mut Map<String, Value>,
+    ) {
+        match expr {
+            ast::Expr::Paren(paren_expr) => {
+                self.apply_check(&paren_expr.expr.node, attrs, properties)
+            }
+            ast::Expr::Binary(binary_expr) if binary_expr.op == ast::BinOp::And => {
+                self.apply_check(&binary_expr.left.node, attrs, properties);
+                self.apply_check(&binary_expr.right.node, attrs, properties);
+            }
+            ast::Expr::Compare(compare) => {
+                // Chained comparisons e.g., `0 < age < 120` are split into pairs.
+                let operands: Vec<&ast::Expr> = std::iter::once(&compare.left.node)
+                    .chain(compare.comparators.iter().map(|expr| &expr.node))
+                    .collect();
+                for (i, op) in compare.ops.iter().enumerate() {
+                    let (left, right) = (operands[i], operands[i + 1]);
+                    if let (Some(operand), Some(value)) = (operand(left), literal_value(right)) {
+                        self.apply_compare(operand, op, value, attrs, properties);
+                    } else if let (Some(value), Some(operand)) =
+                        (literal_value(left), operand(right))
+                        && let Some(op) = reverse_cmp_op(op)
+                    {
+                        self.apply_compare(operand, &op, value, attrs, properties);
+                    }
+                }
+            }
+            ast::Expr::Call(call) => {
+                let (Some(func), Some(attr)) = (
+                    call_name(&call.func.node),
+                    call.args.first().and_then(|arg| attr_name(&arg.node)),
+                ) else {
+                    return;
+                };
+                let value = call.args.get(1).and_then(|arg| literal_value(&arg.node));
+                let Some(property) = self.property(attr, properties) else {
+                    return;
+                };
+                match (func.as_str(), value) {
+                    ("regex.match", Some(pattern @ Value::String(_))) => {
+                        property.insert("pattern".to_string(), pattern);
+                    }
+                    ("multiplyof", Some(value @ Value::Number(_))) => {
+                        property.insert("multipleOf".to_string(), value);
+                    }
+                    ("isunique", None) => {
+                        property.insert("uniqueItems".to_string(), json!(true));
+                    }
+                    _ => {}
+                }
+            }
+            _ => {}
+        }
+    }
+
+    fn apply_compare(
+        &self,
+        operand: Operand,
+        op: &ast::CmpOp,
+        value: Value,
+        attrs: &IndexMap<String, SchemaAttr>,
+        properties: &mut Map<String, Value>,
+    ) {
+        match operand {
+            Operand::Attr(attr) => {
+                let is_number = value.is_number();
+                let exclusive = self.format == SchemaFormat::JsonSchema;
+                let Some(property) = self.property(attr, properties) else {
+                    return;
+                };
+                match op {
+                    ast::CmpOp::Gt | ast::CmpOp::GtE if is_number => {
+                        if *op == ast::CmpOp::Gt && exclusive {
+                            property.insert("exclusiveMinimum".to_string(), value);
+                        } else {
+                            property.insert("minimum".to_string(), value);
+                            if *op == ast::CmpOp::Gt {
+                                property.insert("exclusiveMinimum".to_string(), json!(true));
+                            }
+                        }
+                    }
+                    ast::CmpOp::Lt | ast::CmpOp::LtE if is_number => {
+                        if *op == ast::CmpOp::Lt && exclusive {
+                            property.insert("exclusiveMaximum".to_string(), value);
+                        } else {
+                            property.insert("maximum".to_string(), value);
+                            if *op == ast::CmpOp::Lt {
+                                property.insert("exclusiveMaximum".to_string(), json!(true));
+                            }
+                        }
+                    }
+                    ast::CmpOp::Eq if !value.is_null() => match self.format {
+                        SchemaFormat::JsonSchema => {
+                            property.insert("const".to_string(), value);
+                        }
+                        SchemaFormat::OpenApi => {
+                            property.insert("enum".to_string(), json!([value]));
+                        }
+                    },
+                    ast::CmpOp::In if value.is_array() => {
+                        property.insert("enum".to_string(), value);
+                    }
+                    _ => {}
+                }
+            }
+            Operand::Len(attr) => {
+                let Some(n) = value.as_i64() else {
+                    return;
+                };
+                let (min_key, max_key) = match attrs.get(attr).map(|attr| &attr.ty.kind) {
+                    Some(TypeKind::Str) => ("minLength", "maxLength"),
+                    Some(TypeKind::List(_)) => ("minItems", "maxItems"),
+                    Some(TypeKind::Dict(_)) => ("minProperties", "maxProperties"),
+                    _ => return,
+                };
+                let (min, max) = match op {
+                    ast::CmpOp::Gt => (Some(n + 1), None),
+                    ast::CmpOp::GtE => (Some(n), None),
+                    ast::CmpOp::Lt => (None, Some(n - 1)),
+                    ast::CmpOp::LtE => (None, Some(n)),
+                    ast::CmpOp::Eq => (Some(n), Some(n)),
+                    _ => (None, None),
+                };
+                let Some(property) = self.property(attr, properties) else {
+                    return;
+                };
+                if let Some(min) = min.filter(|min| *min > 0) {
+                    property.insert(min_key.to_string(), json!(min));
+                }
+                if let Some(max) = max.filter(|max| *max >= 0) {
+                    property.insert(max_key.to_string(), json!(max));
+                }
+            }
+        }
+    }
+
+    /// Returns the property schema object which the validation keywords can be added to.
+    fn property<'m>(
+        &self,
+        attr: &str,
+        properties: &'m mut Map<String, Value>,
+    ) -> Option<&'m mut Map<String, Value>> {
+        let property = properties.get_mut(attr)?.as_object_mut()?;
+        // The siblings of `$ref` are ignored in OpenAPI v3.0.
+        if self.format == SchemaFormat::OpenApi && property.contains_key("$ref") {
+            None
+        } else {
+            Some(property)
+        }
+    }
+}
+
+/// Collect the attributes of the schema including the attributes of its base schemas.
+fn collect_attrs(schema_ty: &SchemaType, attrs: &mut IndexMap<String, SchemaAttr>) {
+    if let Some(base) = &schema_ty.base {
+        collect_attrs(base, attrs);
+    }
+    for (name, attr) in &schema_ty.attrs {
+        attrs.insert(name.clone(), attr.clone());
+    }
+}
+
+fn operand(expr: &ast::Expr) -> Option<Operand<'_>> {
+    match expr {
+        ast::Expr::Call(call)
+            if call_name(&call.func.node).as_deref() == Some("len") && call.args.len() == 1 =>
+        {
+            attr_name(&call.args[0].node).map(Operand::Len)
+        }
+        _ => attr_name(expr).map(Operand::Attr),
+    }
+}
+
+fn attr_name(expr: &ast::Expr) -> Option<&str> {
+    match expr {
+        ast::Expr::Identifier(identifier) if identifier.names.len() == 1 => {
+            Some(identifier.names[0].node.as_str())
+        }
+        _ => None,
+    }
+}
+
+fn call_name(expr: &ast::Expr) -> Option<String> {
+    match expr {
+        ast::Expr::Identifier(identifier) => Some(
+            identifier
+                .get_names()
+                .iter()
+                .map(|name| name.trim_start_matches('@'))
+                .collect::<Vec<&str>>()
+                .join("."),
+        ),
+        _ => None,
+    }
+}
+
+fn reverse_cmp_op(op: &ast::CmpOp) -> Option<ast::CmpOp> {
+    match op {
+        ast::CmpOp::Lt => Some(ast::CmpOp::Gt),
+        ast::CmpOp::LtE => Some(ast::CmpOp::GtE),
+        ast::CmpOp::Gt => Some(ast::CmpOp::Lt),
+        ast::CmpOp::GtE => Some(ast::CmpOp::LtE),
+        ast::CmpOp::Eq => Some(ast::CmpOp::Eq),
+        _ => None,
+    }
+}
+
+/// Returns the JSON value of the literal expression, or `None` if it is not a literal.
+fn literal_value(expr: &ast::Expr) -> Option<Value> {
+    match expr {
+        ast::Expr::NumberLit(number) if number.binary_suffix.is_none() => match number.value {
+            ast::NumberLitValue::Int(v) => Some(json!(v)),
+            ast::NumberLitValue::Float(v) => Number::from_f64(v).map(Value::Number),
+        },
+        ast::Expr::Unary(unary) if unary.op == ast::UnaryOp::USub => {
+            match literal_value(&unary.operand.node)? {
+                Value::Number(n) => match (n.as_i64(), n.as_f64()) {
+                    (Some(v), _) => Some(json!(-v)),
+                    (None, Some(v)) => Number::from_f64(-v).map(Value::Number),
+                    _ => None,
+                },
+                _ => None,
+            }
+        }
+        ast::Expr::StringLit(string) => Some(json!(string.value)),
+        ast::Expr::NameConstantLit(constant) => match constant.value {
+            ast::NameConstant::True => Some(json!(true)),
+            ast::NameConstant::False => Some(json!(false)),
+            ast::NameConstant::None => Some(Value::Null),
+            ast::NameConstant::Undefined => None,
+        },
+        ast::Expr::Paren(paren_expr) => literal_value(&paren_expr.expr.node),
+        ast::Expr::List(list) => list
+            .elts
+            .iter()
+            .map(|elt| literal_value(&elt.node))
+            .collect::<Option<Vec<Value>>>()
+            .map(Value::Array),
+        ast::Expr::Config(config) => {
+            let mut map = Map::new();
+            for item in &config.items {
+                let key = match &item.node.key.as_ref()?.node {
+                    ast::Expr::Identifier(identifier) => identifier.get_name(),
+                    ast::Expr::StringLit(string) => string.value.clone(),
+                    _ => return None,
+                };
+                map.insert(key, literal_value(&item.node.value.node)?);
+            }
+            Some(Value::Object(map))
+        }
+        _ => None,
+    }
+}
@@ -1,6 +1,7 @@
 use std::{cell::RefCell, rc::Rc, sync::Arc};
 
 use anyhow::Result;
+use kcl_ast::ast::Program;
 use kcl_parser::{LoadProgramOptions, ParseSession, load_all_files_under_paths, load_program};
 use kcl_primitives::{DefaultHashBuilder, IndexMap};
 use kcl_sema::{
@@ -242,8 +243,15 @@ fn get_full_schema_type_recursive(schema_ty: SchemaType) -> Result<SchemaType> {
     Ok(result)
 }
 
-#[allow(clippy::arc_with_non_send_sync)]
 fn resolve_file(opts: &CompilationOptions) -> Result<Rc<RefCell<Scope>>> {
+    resolve_file_with_program(opts).map(|(_, scope)| scope)
+}
+
+/// Resolve the files and return the loaded program and its main scope.
+#[allow(clippy::arc_with_non_send_sync)]
+pub(crate) fn resolve_file_with_program(
+    opts: &CompilationOptions,
+) -> Result<(Program, Rc<RefCell<Scope>>)> {
     let sess = Arc::new(ParseSession::default());
     let mut program = match load_program(
         sess,
@@ -258,7 +266,7 @@ fn resolve_file(opts: &CompilationOptions) -> Result<Rc<RefCell<Scope>>> {
     };
     let scope = resolve_program_with_opts(&mut program, opts.resolve_opts.clone(), None);
     match scope.main_scope() {
-        Some(scope) => Ok(scope.clone()),
+        Some(scope) => Ok((program, scope.clone())),
         None => Err(anyhow::anyhow!("main scope is not found")),
     }
 }
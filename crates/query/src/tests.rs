@@ -2,7 +2,11 @@ use std::{fs, path::PathBuf};
 
 use super::{r#override::apply_override_on_module, *};
 use crate::{
-    r#override::parse_override_spec, path::parse_attribute_path, selector::list_variables,
+    export::{SchemaFormat, export_schema},
+    r#override::parse_override_spec,
+    path::parse_attribute_path,
+    query::CompilationOptions,
+    selector::list_variables,
 };
 use kcl_error::{DiagnosticId, ErrorKind, Level};
 use kcl_parser::parse_file_force_errors;
@@ -1103,3 +1107,48 @@ fn test_list_merged_variables() {
         }
     }
 }
+
+#[test]
+fn test_export_schema() {
+    let path = PathBuf::from("./src/test_data/export/main.k")
+        .canonicalize()
+        .unwrap()
+        .adjust_canonicalization();
+    for (format, name) in [
+        (SchemaFormat::JsonSchema, "export_json_schema"),
+        (SchemaFormat::OpenApi, "export_openapi"),
+    ] {
+        let doc = export_schema(
+            None,
+            format,
+            CompilationOptions {
+                paths: vec![path.clone()],
+                ..Default::default()
+            },
+        )
+        .unwrap();
+        insta::assert_snapshot!(name, doc);
+    }
+    let doc = export_schema(
+        Some("Metadata"),
+        SchemaFormat::JsonSchema,
+        CompilationOptions {
+            paths: vec![path.clone()],
+            ..Default::default()
+        },
+    )
+    .unwrap();
+    let doc: serde_json::Value = serde_json::from_str(&doc).unwrap();
+    assert_eq!(doc["$ref"], "#/$defs/Metadata");
+    assert_eq!(doc["$defs"].as_object().unwrap().len(), 1);
+    let err = export_schema(
+        Some("Unknown"),
+        SchemaFormat::JsonSchema,
+        CompilationOptions {
+            paths: vec![path],
+            ..Default::default()
+        },
+    )
+    .unwrap_err();
+    assert_eq!(err.to_string(), "Schema 'Unknown' not found");
+}
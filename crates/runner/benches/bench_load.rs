@@ -0,0 +1,78 @@
+use std::path::Path;
+
+use criterion::{BenchmarkId, Criterion, criterion_group, criterion_main};
+
+use kcl_parser::{ParseSessionRef, load_program};
+use kcl_utils::parallel::KCL_THREADS_ENV;
+
+const PKG_COUNT: usize = 32;
+const FILE_COUNT: usize = 8;
+const SCHEMA_COUNT: usize = 8;
+
+/// Generate a program with independent packages imported by the main file.
+fn gen_program(root: &Path) -> String {
+    let mut main = String::new();
+    for pkg in 0..PKG_COUNT {
+        let pkg_dir = root.join(format!("pkg{pkg}"));
+        std::fs::create_dir_all(&pkg_dir).unwrap();
+        for file in 0..FILE_COUNT {
+            let mut code = String::new();
+            for schema in 0..SCHEMA_COUNT {
+                code.push_str(&format!(
+                    r#"schema Schema{file}_{schema}:
+    name: str = "schema{schema}"
+    replicas: int = {schema}
+    labels: {{str:str}} = {{app = name}}
+    ports: [int] = [80, 443]
+
+    check:
+        replicas >= 0, "replicas must be non-negative"
+
+"#
+                ));
+            }
+            code.push_str(&format!("value{file} = Schema{file}_0 {{}}\n"));
+            std::fs::write(pkg_dir.join(format!("file{file}.k")), code).unwrap();
+        }
+        main.push_str(&format!("import pkg{pkg}\n"));
+    }
+    for pkg in 0..PKG_COUNT {
+        main.push_str(&format!("value{pkg} = pkg{pkg}.value0\n"));
+    }
+    std::fs::write(
+        root.join("kcl.mod"),
+        "[package]\nname = \"bench\"\nversion = \"0.0.1\"\n",
+    )
+    .unwrap();
+    let main_file = root.join("main.k");
+    std::fs::write(&main_file, main).unwrap();
+    main_file.display().to_string()
+}
+
+pub fn criterion_benchmark(c: &mut Criterion) {
+    let root = tempfile::tempdir().unwrap();
+    let main_file = gen_program(root.path());
+    let mut group = c.benchmark_group("load_program");
+    group.sample_size(20);
+    // Limit the number of threads to 1 to load the files sequentially, or leave it unset to
+    // use the available parallelism.
+    for (name, threads) in [("sequential", Some("1")), ("parallel", None)] {
+        // SAFETY: the benchmarks run on the main thread without other threads reading the environment.
+        unsafe {
+            match threads {
+                Some(threads) => std::env::set_var(KCL_THREADS_ENV, threads),
+                None => std::env::remove_var(KCL_THREADS_ENV),
+            }
+        }
+        group.bench_with_input(BenchmarkId::new(name, PKG_COUNT), &main_file, |b, file| {
+            b.iter(|| {
+                let sess = ParseSessionRef::default();
+                load_program(sess, &[file], None, None).unwrap()
+            })
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(benches, criterion_benchmark);
+criterion_main!(benches);
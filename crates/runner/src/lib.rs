@@ -1,5 +1,6 @@
 use std::{
     collections::HashMap,
+    path::Path,
     sync::{Arc, RwLock},
 };
 
@@ -8,13 +9,15 @@ use kcl_ast::{
     MAIN_PKG,
     ast::{Module, Program},
 };
+use kcl_error::Level;
+pub use kcl_evaluator::{BranchCoverage, Coverage, CoverageSpan, FileCoverage};
 use kcl_parser::{KCLModuleCache, ParseSessionRef, load_program};
 use kcl_query::apply_overrides;
 use kcl_sema::resolver::{
     Options, resolve_program, resolve_program_with_opts, scope::ProgramScope,
 };
-pub use runner::{ExecProgramArgs, ExecProgramResult, MapErrorResult};
-use runner::{FastRunner, RunnerOptions};
+pub use runner::{Artifact, ExecProgramArgs, ExecProgramResult, MapErrorResult};
+use runner::{FastRunner, ProgramRunner, RunnerOptions};
 
 pub mod runner;
 
@@ -67,6 +70,56 @@ pub mod tests;
 /// let result = exec_program(sess, &args).unwrap();
 /// ```
 pub fn exec_program(sess: ParseSessionRef, args: &ExecProgramArgs) -> Result<ExecProgramResult> {
+    let program = load_program_with_overrides(sess.clone(), args)?;
+    execute(sess, program, args)
+}
+
+/// Build the KCL program to an [Artifact], which can be executed many times with
+/// different arguments such as the top level arguments `-D` using [exec_artifact]
+/// or [ProgramRunner::run]. When the `output` path is given, the artifact is also
+/// saved to it.
+///
+/// # Examples
+///
+/// ```
+/// use kcl_runner::{build_program, runner::ProgramRunner, ExecProgramArgs};
+/// use kcl_parser::ParseSession;
+/// use std::sync::Arc;
+///
+/// let sess = Arc::new(ParseSession::default());
+/// let mut args = ExecProgramArgs::default();
+/// args.k_filename_list = vec!["./src/test_datas/init_check_order_0/main.k".to_string()];
+///
+/// let artifact = build_program::<&str>(sess, &args, None).unwrap();
+/// let result = artifact.run(&args).unwrap();
+/// assert!(result.err_message.is_empty());
+/// ```
+pub fn build_program<P: AsRef<Path>>(
+    sess: ParseSessionRef,
+    args: &ExecProgramArgs,
+    output: Option<P>,
+) -> Result<Artifact> {
+    let mut program = load_program_with_overrides(sess.clone(), args)?;
+    // Resolve ast
+    let scope = resolve_program(&mut program);
+    // Emit parse and resolve errors if exists.
+    emit_compile_diag_to_string(sess, &scope, false)?;
+    let mut artifact = Artifact::new(program);
+    if let Some(output) = output {
+        artifact.save(output)?;
+    }
+    Ok(artifact)
+}
+
+/// Execute the [Artifact] saved at the `path` with the arguments.
+///
+/// **Note that it is not thread safe.**
+pub fn exec_artifact<P: AsRef<Path>>(path: P, args: &ExecProgramArgs) -> Result<ExecProgramResult> {
+    Artifact::from_path(path)?.run(args)
+}
+
+/// Load the program from the input files of the arguments and apply the overrides.
+fn load_program_with_overrides(sess: ParseSessionRef, args: &ExecProgramArgs) -> Result<Program> {
     // parse args from json string
     let opts = args.get_load_program_options();
     let kcl_paths_str = args
@@ -88,7 +141,7 @@ pub fn exec_program(sess: ParseSessionRef, args: &ExecProgramArgs) -> Result<Exe
         &[],
         args.print_override_ast || args.debug > 0,
     )?;
-    execute(sess, program, args)
+    Ok(program)
 }
 
 /// After the kcl program passed through kcl-parser in the compiler frontend,
@@ -160,11 +213,14 @@ pub fn execute(
     // Resolve ast
     let scope = resolve_program(&mut program);
     // Emit parse and resolve errors if exists.
-    emit_compile_diag_to_string(sess, &scope, false)?;
-    FastRunner::new(Some(RunnerOptions {
+    emit_compile_diag_to_string(sess.clone(), &scope, false)?;
+    let result = FastRunner::new(Some(RunnerOptions {
         plugin_agent_ptr: args.plugin_agent,
     }))
-    .run(&program, args)
+    .run(&program, args)?;
+    // Record runtime errors in the session to emit them in the machine-readable formats.
+    sess.append_diagnostic(result.diagnostics.iter().cloned().collect());
+    Ok(result)
 }
 
 /// `execute_module` can directly execute the ast `Module`.
@@ -202,6 +258,16 @@ fn emit_compile_diag_to_string(
 ) -> Result<()> {
     let mut res_str = sess.1.write().emit_to_string()?;
     let sema_err = scope.emit_diagnostics_to_string(sess.0.clone(), include_warnings);
+    // Record resolve diagnostics in the session to emit them in the machine-readable formats.
+    sess.append_diagnostic(
+        scope
+            .handler
+            .diagnostics
+            .iter()
+            .filter(|diag| include_warnings || diag.level != Level::Warning)
+            .cloned()
+            .collect(),
+    );
     if let Err(err) = &sema_err {
         #[cfg(not(target_os = "windows"))]
         res_str.push('\n');
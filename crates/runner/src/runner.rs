@@ -1,6 +1,10 @@
 use anyhow::{Result, anyhow};
-use kcl_evaluator::Evaluator;
+use kcl_evaluator::{Coverage, Evaluator};
 use std::collections::HashMap;
+use std::path::Path;
+#[cfg(not(target_arch = "wasm32"))]
+use std::sync::Mutex;
+use std::sync::{Arc, RwLock};
 use std::{cell::RefCell, rc::Rc};
 
 use kcl_ast::ast;
@@ -62,6 +66,10 @@ pub struct ExecProgramArgs {
     pub include_schema_type_path: bool,
     /// Whether to compile only.
     pub compile_only: bool,
+    /// Whether to collect all the schema check failures and attribute type mismatches
+    /// instead of stopping at the first one.
+    #[serde(default)]
+    pub collect_check_failures: bool,
     /// plugin_agent is the address of plugin.
     #[serde(skip)]
     pub plugin_agent: u64,
@@ -69,6 +77,9 @@ pub struct ExecProgramArgs {
     /// the result without any form of compilation.
     #[serde(skip)]
     pub fast_eval: bool,
+    /// coverage denotes recording the line and branch coverage of the evaluation.
+    #[serde(skip)]
+    pub coverage: bool,
 }
 
 impl ExecProgramArgs {
@@ -100,6 +111,14 @@ pub struct ExecProgramResult {
     pub yaml_result: String,
     pub log_message: String,
     pub err_message: String,
+    /// The runtime error diagnostics, which are used to emit the error message in the
+    /// machine-readable formats. All the check failures are here in the evaluation order
+    /// when [ExecProgramArgs::collect_check_failures] is set.
+    #[serde(skip)]
+    pub diagnostics: Vec<Diagnostic>,
+    /// The line and branch coverage of the evaluation, which is recorded when the coverage is enabled.
+    #[serde(skip)]
+    pub coverage: Option<Coverage>,
 }
 
 pub trait MapErrorResult {
@@ -184,6 +203,7 @@ impl TryFrom<SettingsFile> for ExecProgramArgs {
             args.sort_keys = cli_configs.sort_keys.unwrap_or_default();
             args.show_hidden = cli_configs.show_hidden.unwrap_or_default();
             args.fast_eval = cli_configs.fast_eval.unwrap_or_default();
+            args.collect_check_failures = cli_configs.collect_check_failures.unwrap_or_default();
             args.include_schema_type_path =
                 cli_configs.include_schema_type_path.unwrap_or_default();
             for override_str in cli_configs.overrides.unwrap_or_default() {
@@ -231,30 +251,63 @@ thread_local! {
     pub static KCL_RUNTIME_PANIC_RECORD: RefCell<RuntimePanicRecord> = RefCell::new(RuntimePanicRecord::default())
 }
 
+/// Record the runtime panic information in the thread local panic record.
+fn record_panic_info(info: &std::panic::PanicHookInfo) {
+    KCL_RUNTIME_PANIC_RECORD.with(|record| {
+        let mut record = record.borrow_mut();
+        record.kcl_panic_info = true;
+        record.message = if let Some(s) = info.payload().downcast_ref::<&str>() {
+            s.to_string()
+        } else if let Some(s) = info.payload().downcast_ref::<&String>() {
+            (*s).clone()
+        } else if let Some(s) = info.payload().downcast_ref::<String>() {
+            (*s).clone()
+        } else {
+            "unknown runtime error".to_string()
+        };
+        if let Some(location) = info.location() {
+            record.rust_file = location.file().to_string();
+            record.rust_line = location.line() as i32;
+            record.rust_col = location.column() as i32;
+        }
+    })
+}
+
 #[cfg(target_arch = "wasm32")]
 static ONCE_PANIC_HOOK: Lazy<()> = Lazy::new(|| {
-    std::panic::set_hook(Box::new(|info: &std::panic::PanicHookInfo| {
-        KCL_RUNTIME_PANIC_RECORD.with(|record| {
-            let mut record = record.borrow_mut();
-            record.kcl_panic_info = true;
-            record.message = if let Some(s) = info.payload().downcast_ref::<&str>() {
-                s.to_string()
-            } else if let Some(s) = info.payload().downcast_ref::<&String>() {
-                (*s).clone()
-            } else if let Some(s) = info.payload().downcast_ref::<String>() {
-                (*s).clone()
-            } else {
-                "unknown runtime error".to_string()
-            };
-            if let Some(location) = info.location() {
-                record.rust_file = location.file().to_string();
-                record.rust_line = location.line() as i32;
-                record.rust_col = location.column() as i32;
-            }
-        })
-    }));
+    std::panic::set_hook(Box::new(record_panic_info));
 });
 
+#[cfg(not(target_arch = "wasm32"))]
+type PanicHook = Box<dyn Fn(&std::panic::PanicHookInfo<'_>) + Sync + Send + 'static>;
+
+/// The number of the running programs and the panic hook before the first one started.
+/// Programs can run concurrently on different threads e.g., the test cases, so the panic
+/// hook is only replaced by the first program and restored by the last one.
+#[cfg(not(target_arch = "wasm32"))]
+static PANIC_HOOK: Mutex<(usize, Option<PanicHook>)> = Mutex::new((0, None));
+
+#[cfg(not(target_arch = "wasm32"))]
+fn install_panic_hook() {
+    let mut hook = PANIC_HOOK.lock().unwrap_or_else(|err| err.into_inner());
+    if hook.0 == 0 {
+        hook.1 = Some(std::panic::take_hook());
+        std::panic::set_hook(Box::new(record_panic_info));
+    }
+    hook.0 += 1;
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+fn restore_panic_hook() {
+    let mut hook = PANIC_HOOK.lock().unwrap_or_else(|err| err.into_inner());
+    hook.0 -= 1;
+    if hook.0 == 0
+        && let Some(prev_hook) = hook.1.take()
+    {
+        std::panic::set_hook(prev_hook);
+    }
+}
+
 pub struct FastRunner {
     opts: RunnerOptions,
 }
@@ -271,33 +324,15 @@ impl FastRunner {
     pub fn run(&self, program: &ast::Program, args: &ExecProgramArgs) -> Result<ExecProgramResult> {
         let ctx = Rc::new(RefCell::new(args_to_ctx(program, args)));
         let evaluator = Evaluator::new_with_runtime_ctx(program, ctx.clone());
+        if args.coverage {
+            evaluator.enable_coverage();
+        }
         #[cfg(target_arch = "wasm32")]
         // Ensure the panic hook is set (this will only happen once) for the WASM target,
         // because it is single threaded.
         Lazy::force(&ONCE_PANIC_HOOK);
         #[cfg(not(target_arch = "wasm32"))]
-        let prev_hook = std::panic::take_hook();
-        #[cfg(not(target_arch = "wasm32"))]
-        std::panic::set_hook(Box::new(|info: &std::panic::PanicHookInfo| {
-            KCL_RUNTIME_PANIC_RECORD.with(|record| {
-                let mut record = record.borrow_mut();
-                record.kcl_panic_info = true;
-                record.message = if let Some(s) = info.payload().downcast_ref::<&str>() {
-                    s.to_string()
-                } else if let Some(s) = info.payload().downcast_ref::<&String>() {
-                    (*s).clone()
-                } else if let Some(s) = info.payload().downcast_ref::<String>() {
-                    (*s).clone()
-                } else {
-                    "unknown runtime error".to_string()
-                };
-                if let Some(location) = info.location() {
-                    record.rust_file = location.file().to_string();
-                    record.rust_line = location.line() as i32;
-                    record.rust_col = location.column() as i32;
-                }
-            })
-        }));
+        install_panic_hook();
         let evaluator_result = std::panic::catch_unwind(|| {
             if self.opts.plugin_agent_ptr > 0 {
                 #[cfg(not(target_arch = "wasm32"))]
@@ -313,13 +348,14 @@ impl FastRunner {
             evaluator.run()
         });
         #[cfg(not(target_arch = "wasm32"))]
-        std::panic::set_hook(prev_hook);
+        restore_panic_hook();
         KCL_RUNTIME_PANIC_RECORD.with(|record| {
             let record = record.borrow();
             ctx.borrow_mut().set_panic_info(&record);
         });
         let mut result = ExecProgramResult {
             log_message: ctx.borrow().log_message.clone(),
+            coverage: evaluator.take_coverage(),
             ..Default::default()
         };
         let is_err = evaluator_result.is_err();
@@ -343,17 +379,42 @@ impl FastRunner {
                 };
             }
         }
-        // Wrap runtime JSON Panic error string into diagnostic style string.
-        if !result.err_message.is_empty() && std::env::var(KCL_DEBUG_ERROR_ENV_VAR).is_err() {
-            result.err_message = match Handler::default()
-                .add_diagnostic(<PanicInfo as Into<Diagnostic>>::into(PanicInfo::from(
-                    result.err_message.as_str(),
-                )))
-                .emit_to_string()
-            {
-                Ok(msg) => msg,
-                Err(err) => err.to_string(),
+        let check_failures = std::mem::take(&mut ctx.borrow_mut().check_failures);
+        if !check_failures.is_empty() {
+            // The recorded check failures are reported before the error which stops the
+            // evaluation if any, and the planning result of the invalid program is dropped.
+            result.json_result.clear();
+            result.yaml_result.clear();
+            let mut failures = check_failures;
+            if !result.err_message.is_empty() {
+                failures.push(PanicInfo::from(result.err_message.as_str()));
+            }
+            result.diagnostics = failures.iter().cloned().map(|p| p.into()).collect();
+            result.err_message = if std::env::var(KCL_DEBUG_ERROR_ENV_VAR).is_err() {
+                let mut handler = Handler::default();
+                for diag in &result.diagnostics {
+                    handler.add_diagnostic(diag.clone());
+                }
+                match handler.emit_to_string() {
+                    Ok(msg) => msg,
+                    Err(err) => err.to_string(),
+                }
+            } else {
+                serde_json::to_string(&failures).unwrap_or_default()
             };
+        } else if !result.err_message.is_empty() {
+            // Wrap runtime JSON Panic error string into diagnostic style string.
+            let diag: Diagnostic = PanicInfo::from(result.err_message.as_str()).into();
+            if std::env::var(KCL_DEBUG_ERROR_ENV_VAR).is_err() {
+                result.err_message = match Handler::default()
+                    .add_diagnostic(diag.clone())
+                    .emit_to_string()
+                {
+                    Ok(msg) => msg,
+                    Err(err) => err.to_string(),
+                };
+            }
+            result.diagnostics.push(diag);
         }
         // Free all value references at runtime. This is because the runtime context marks
         // all KCL objects and holds their copies, so it is necessary to actively GC them.
@@ -362,10 +423,102 @@ impl FastRunner {
     }
 }
 
+/// The artifact of a KCL program, which is the resolved AST of all the program modules.
+/// It is built once by [crate::build_program] and can be executed many times with
+/// different arguments e.g., the top level arguments `-D`, without parsing and resolving
+/// the program again.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct Artifact {
+    /// The KCL version which built the artifact.
+    version: String,
+    program: ast::SerializeProgram,
+    #[serde(skip)]
+    path: String,
+}
+
+impl Artifact {
+    /// New an artifact from the resolved program.
+    pub fn new(program: ast::Program) -> Self {
+        Self {
+            version: kcl_version::VERSION.to_string(),
+            program: program.into(),
+            path: String::new(),
+        }
+    }
+
+    /// Load the artifact from the file path.
+    pub fn from_path<P: AsRef<Path>>(path: P) -> Result<Self> {
+        let path = path.as_ref();
+        let data = std::fs::read(path)
+            .map_err(|err| anyhow!("Failed to read the artifact {}: {err}", path.display()))?;
+        let mut artifact: Artifact = serde_json::from_slice(&data)
+            .map_err(|err| anyhow!("Invalid artifact {}: {err}", path.display()))?;
+        if artifact.version != kcl_version::VERSION {
+            return Err(anyhow!(
+                "The artifact {} is built by KCL {}, but the current version is {}, please rebuild it",
+                path.display(),
+                artifact.version,
+                kcl_version::VERSION
+            ));
+        }
+        artifact.path = path.display().to_string();
+        Ok(artifact)
+    }
+
+    /// Save the artifact to the file path, the parent directories are created if not exist.
+    pub fn save<P: AsRef<Path>>(&mut self, path: P) -> Result<()> {
+        let path = path.as_ref();
+        if let Some(parent) = path.parent()
+            && !parent.as_os_str().is_empty()
+        {
+            std::fs::create_dir_all(parent)?;
+        }
+        std::fs::write(path, serde_json::to_vec(self)?)?;
+        self.path = path.display().to_string();
+        Ok(())
+    }
+
+    /// Get the file path of the artifact, which is empty if the artifact is not saved.
+    #[inline]
+    pub fn get_path(&self) -> &String {
+        &self.path
+    }
+
+    /// Get the program of the artifact.
+    pub fn program(&self) -> ast::Program {
+        let mut program = ast::Program {
+            root: self.program.root.clone(),
+            ..Default::default()
+        };
+        for (pkg, modules) in &self.program.pkgs {
+            let mut filenames = Vec::with_capacity(modules.len());
+            for module in modules {
+                filenames.push(module.filename.clone());
+                program.modules.insert(
+                    module.filename.clone(),
+                    Arc::new(RwLock::new(module.clone())),
+                );
+            }
+            program.pkgs.insert(pkg.clone(), filenames);
+        }
+        program
+    }
+}
+
+impl ProgramRunner for Artifact {
+    fn run(&self, args: &ExecProgramArgs) -> Result<ExecProgramResult> {
+        FastRunner::new(Some(RunnerOptions {
+            plugin_agent_ptr: args.plugin_agent,
+        }))
+        .run(&self.program(), args)
+    }
+}
+
 pub(crate) fn args_to_ctx(program: &ast::Program, args: &ExecProgramArgs) -> Context {
     let mut ctx = Context::new();
     ctx.cfg.strict_range_check = args.strict_range_check;
     ctx.cfg.debug_mode = args.debug != 0;
+    ctx.cfg.collect_check_failures = args.collect_check_failures;
     ctx.plan_opts.disable_none = args.disable_none;
     ctx.plan_opts.show_hidden = args.show_hidden;
     ctx.plan_opts.sort_keys = args.sort_keys;
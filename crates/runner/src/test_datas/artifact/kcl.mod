@@ -0,0 +1,4 @@
+[package]
+name = "artifact"
+edition = "*"
+version = "0.0.1"
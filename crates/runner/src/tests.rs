@@ -1,6 +1,6 @@
 #![allow(clippy::arc_with_non_send_sync)]
 
-use crate::exec_program;
+use crate::{build_program, exec_artifact, exec_program};
 use crate::{execute, runner::ExecProgramArgs};
 use anyhow::Result;
 use kcl_ast::ast::{Module, Program};
@@ -447,3 +447,37 @@ fn test_kcl_issue_1799() {
         )
     );
 }
+
+#[test]
+fn test_build_program_and_exec_artifact() {
+    let dir = tempfile::tempdir().unwrap();
+    let output = dir.path().join("build").join("main.json");
+    let mut args = ExecProgramArgs::default();
+    args.k_filename_list
+        .push("./src/test_datas/artifact/main.k".to_string());
+    let artifact = build_program(Arc::new(ParseSession::default()), &args, Some(&output)).unwrap();
+    assert_eq!(artifact.get_path(), &output.display().to_string());
+
+    for replicas in [2, 3] {
+        args.args = vec![kcl_ast::ast::Argument {
+            name: "replicas".to_string(),
+            value: replicas.to_string(),
+        }];
+        let result = exec_artifact(&output, &args).unwrap();
+        assert_eq!(
+            result.yaml_result,
+            format!("config:\n  name: app\n  replicas: {replicas}")
+        );
+    }
+    args.args = vec![kcl_ast::ast::Argument {
+        name: "replicas".to_string(),
+        value: "0".to_string(),
+    }];
+    let result = exec_artifact(&output, &args).unwrap();
+    assert!(
+        result.err_message.contains("replicas must be positive"),
+        "{}",
+        result.err_message
+    );
+    assert!(exec_artifact(dir.path().join("not_found.json"), &args).is_err());
+}
@@ -67,6 +67,17 @@ pub fn kcl_get_fn_ptr_by_name(name: &str) -> u64 {
         "kcl_builtin_sum" => crate::kcl_builtin_sum as *const () as u64,
         "kcl_builtin_typeof" => crate::kcl_builtin_typeof as *const () as u64,
         "kcl_builtin_zip" => crate::kcl_builtin_zip as *const () as u64,
+        "kcl_collection_chunk" => crate::kcl_collection_chunk as *const () as u64,
+        "kcl_collection_flatten" => crate::kcl_collection_flatten as *const () as u64,
+        "kcl_collection_get" => crate::kcl_collection_get as *const () as u64,
+        "kcl_collection_group_by" => crate::kcl_collection_group_by as *const () as u64,
+        "kcl_collection_merge" => crate::kcl_collection_merge as *const () as u64,
+        "kcl_collection_omit" => crate::kcl_collection_omit as *const () as u64,
+        "kcl_collection_partition" => crate::kcl_collection_partition as *const () as u64,
+        "kcl_collection_pick" => crate::kcl_collection_pick as *const () as u64,
+        "kcl_collection_set" => crate::kcl_collection_set as *const () as u64,
+        "kcl_collection_union_all" => crate::kcl_collection_union_all as *const () as u64,
+        "kcl_collection_unique_by" => crate::kcl_collection_unique_by as *const () as u64,
         "kcl_config_attr_map" => crate::kcl_config_attr_map as *const () as u64,
         "kcl_context_delete" => crate::kcl_context_delete as *const () as u64,
         "kcl_context_invoke" => crate::kcl_context_invoke as *const () as u64,
@@ -90,10 +101,15 @@ pub fn kcl_get_fn_ptr_by_name(name: &str) -> u64 {
             crate::kcl_context_set_strict_range_check as *const () as u64
         }
         "kcl_convert_collection_value" => crate::kcl_convert_collection_value as *const () as u64,
+        "kcl_crypto_argon2" => crate::kcl_crypto_argon2 as *const () as u64,
+        "kcl_crypto_argon2_verify" => crate::kcl_crypto_argon2_verify as *const () as u64,
+        "kcl_crypto_bcrypt" => crate::kcl_crypto_bcrypt as *const () as u64,
+        "kcl_crypto_bcrypt_verify" => crate::kcl_crypto_bcrypt_verify as *const () as u64,
         "kcl_crypto_blake3" => crate::kcl_crypto_blake3 as *const () as u64,
         "kcl_crypto_fileblake3" => crate::kcl_crypto_fileblake3 as *const () as u64,
         "kcl_crypto_filesha256" => crate::kcl_crypto_filesha256 as *const () as u64,
         "kcl_crypto_filesha512" => crate::kcl_crypto_filesha512 as *const () as u64,
+        "kcl_crypto_hmac" => crate::kcl_crypto_hmac as *const () as u64,
         "kcl_crypto_md5" => crate::kcl_crypto_md5 as *const () as u64,
         "kcl_crypto_sha1" => crate::kcl_crypto_sha1 as *const () as u64,
         "kcl_crypto_sha224" => crate::kcl_crypto_sha224 as *const () as u64,
@@ -101,9 +117,17 @@ pub fn kcl_get_fn_ptr_by_name(name: &str) -> u64 {
         "kcl_crypto_sha384" => crate::kcl_crypto_sha384 as *const () as u64,
         "kcl_crypto_sha512" => crate::kcl_crypto_sha512 as *const () as u64,
         "kcl_crypto_uuid" => crate::kcl_crypto_uuid as *const () as u64,
+        "kcl_crypto_verify_signature" => crate::kcl_crypto_verify_signature as *const () as u64,
+        "kcl_datetime_add" => crate::kcl_datetime_add as *const () as u64,
         "kcl_datetime_date" => crate::kcl_datetime_date as *const () as u64,
+        "kcl_datetime_diff" => crate::kcl_datetime_diff as *const () as u64,
+        "kcl_datetime_duration" => crate::kcl_datetime_duration as *const () as u64,
+        "kcl_datetime_format" => crate::kcl_datetime_format as *const () as u64,
         "kcl_datetime_now" => crate::kcl_datetime_now as *const () as u64,
+        "kcl_datetime_parse" => crate::kcl_datetime_parse as *const () as u64,
+        "kcl_datetime_subtract" => crate::kcl_datetime_subtract as *const () as u64,
         "kcl_datetime_ticks" => crate::kcl_datetime_ticks as *const () as u64,
+        "kcl_datetime_to_timezone" => crate::kcl_datetime_to_timezone as *const () as u64,
         "kcl_datetime_today" => crate::kcl_datetime_today as *const () as u64,
         "kcl_datetime_validate" => crate::kcl_datetime_validate as *const () as u64,
         "kcl_default_collection_insert_int_pointer" => {
@@ -224,9 +248,11 @@ pub fn kcl_get_fn_ptr_by_name(name: &str) -> u64 {
         "kcl_plugin_invoke_json" => crate::kcl_plugin_invoke_json as *const () as u64,
         "kcl_regex_compile" => crate::kcl_regex_compile as *const () as u64,
         "kcl_regex_findall" => crate::kcl_regex_findall as *const () as u64,
+        "kcl_regex_findall_groups" => crate::kcl_regex_findall_groups as *const () as u64,
         "kcl_regex_match" => crate::kcl_regex_match as *const () as u64,
         "kcl_regex_replace" => crate::kcl_regex_replace as *const () as u64,
         "kcl_regex_search" => crate::kcl_regex_search as *const () as u64,
+        "kcl_regex_search_groups" => crate::kcl_regex_search_groups as *const () as u64,
         "kcl_regex_split" => crate::kcl_regex_split as *const () as u64,
         "kcl_runtime_catch" => crate::kcl_runtime_catch as *const () as u64,
         "kcl_schema_assert" => crate::kcl_schema_assert as *const () as u64,
@@ -245,8 +271,20 @@ pub fn kcl_get_fn_ptr_by_name(name: &str) -> u64 {
         "kcl_scope_get" => crate::kcl_scope_get as *const () as u64,
         "kcl_scope_new" => crate::kcl_scope_new as *const () as u64,
         "kcl_scope_set" => crate::kcl_scope_set as *const () as u64,
+        "kcl_semver_bump_major" => crate::kcl_semver_bump_major as *const () as u64,
+        "kcl_semver_bump_minor" => crate::kcl_semver_bump_minor as *const () as u64,
+        "kcl_semver_bump_patch" => crate::kcl_semver_bump_patch as *const () as u64,
+        "kcl_semver_compare" => crate::kcl_semver_compare as *const () as u64,
+        "kcl_semver_parse" => crate::kcl_semver_parse as *const () as u64,
+        "kcl_semver_satisfies" => crate::kcl_semver_satisfies as *const () as u64,
+        "kcl_semver_validate" => crate::kcl_semver_validate as *const () as u64,
         "kcl_template_execute" => crate::kcl_template_execute as *const () as u64,
+        "kcl_template_execute_file" => crate::kcl_template_execute_file as *const () as u64,
         "kcl_template_html_escape" => crate::kcl_template_html_escape as *const () as u64,
+        "kcl_toml_decode" => crate::kcl_toml_decode as *const () as u64,
+        "kcl_toml_dump_to_file" => crate::kcl_toml_dump_to_file as *const () as u64,
+        "kcl_toml_encode" => crate::kcl_toml_encode as *const () as u64,
+        "kcl_toml_validate" => crate::kcl_toml_validate as *const () as u64,
         "kcl_units_to_G" => crate::kcl_units_to_G as *const () as u64,
         "kcl_units_to_Gi" => crate::kcl_units_to_Gi as *const () as u64,
         "kcl_units_to_K" => crate::kcl_units_to_K as *const () as u64,
@@ -350,7 +388,6 @@ pub fn kcl_get_fn_ptr_by_name(name: &str) -> u64 {
         "kcl_value_unary_not" => crate::kcl_value_unary_not as *const () as u64,
         "kcl_value_unary_plus" => crate::kcl_value_unary_plus as *const () as u64,
         "kcl_value_union" => crate::kcl_value_union as *const () as u64,
-        "kcl_value_union_all" => crate::kcl_value_union_all as *const () as u64,
         "kcl_yaml_decode" => crate::kcl_yaml_decode as *const () as u64,
         "kcl_yaml_decode_all" => crate::kcl_yaml_decode_all as *const () as u64,
         "kcl_yaml_dump_all_to_file" => crate::kcl_yaml_dump_all_to_file as *const () as u64,
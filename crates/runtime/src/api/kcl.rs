@@ -310,6 +310,9 @@ pub struct ContextConfig {
     pub debug_mode: bool,
     pub strict_range_check: bool,
     pub disable_schema_check: bool,
+    /// Record the failed schema checks and attribute type mismatches into
+    /// [Context::check_failures] and keep evaluating instead of stopping at the first one.
+    pub collect_check_failures: bool,
 }
 
 #[derive(PartialEq, Eq, Clone, Debug)]
@@ -365,10 +368,14 @@ pub struct Context {
     pub yaml_result: String,
     /// Panic information at runtime
     pub panic_info: PanicInfo,
+    /// Schema check failures recorded in order when [ContextConfig::collect_check_failures] is set.
+    pub check_failures: Vec<PanicInfo>,
     /// Planning options
     pub plan_opts: PlanOptions,
     /// Builtin plugin functions, the key of the map is the form <module_name>.<module_func> e.g., `hello.say_hello`
     pub plugin_functions: IndexMap<String, PluginFunction>,
+    /// Compiled patterns of the regex system module functions.
+    pub regex_cache: crate::RegexCache,
 }
 
 impl UnwindSafe for Context {}
@@ -0,0 +1,133 @@
+//! Copyright The KCL Authors. All rights reserved.
+
+use crate::*;
+
+impl ValueRef {
+    /// Group the list items into a dict by the key of each item, and the items of a
+    /// group are in the list order. Non-string keys are converted to their JSON strings.
+    pub fn list_group_by(&self, key: &mut dyn FnMut(&ValueRef) -> ValueRef) -> ValueRef {
+        let mut result = ValueRef::dict(None);
+        // Clone the items because the key function may be a KCL function.
+        let items = self.as_list_ref().values.clone();
+        for item in &items {
+            let key = group_key(&key(item));
+            match result.dict_get_value(&key) {
+                Some(mut group) => group.list_append(item),
+                None => result.dict_update_key_value(&key, ValueRef::list(Some(&[item]))),
+            }
+        }
+        result
+    }
+
+    /// Returns the list items with the first occurrence of each key.
+    pub fn list_unique_by(&self, key: &mut dyn FnMut(&ValueRef) -> ValueRef) -> ValueRef {
+        let mut keys: Vec<ValueRef> = vec![];
+        let mut result = ValueRef::list(None);
+        // Clone the items because the key function may be a KCL function.
+        let items = self.as_list_ref().values.clone();
+        for item in &items {
+            let key = key(item);
+            if !keys.contains(&key) {
+                keys.push(key);
+                result.list_append(item);
+            }
+        }
+        result
+    }
+
+    /// Split the list items into the items that match the predicate and the items that
+    /// don't, and returns a list of the two lists.
+    pub fn list_partition(&self, predicate: &mut dyn FnMut(&ValueRef) -> ValueRef) -> ValueRef {
+        let mut matched = ValueRef::list(None);
+        let mut unmatched = ValueRef::list(None);
+        // Clone the items because the key function may be a KCL function.
+        let items = self.as_list_ref().values.clone();
+        for item in &items {
+            if predicate(item).is_truthy() {
+                matched.list_append(item);
+            } else {
+                unmatched.list_append(item);
+            }
+        }
+        ValueRef::list(Some(&[&matched, &unmatched]))
+    }
+
+    /// Flatten the nested lists up to the `depth`, and a negative depth flattens all the
+    /// nested lists.
+    pub fn list_flatten(&self, depth: i64) -> ValueRef {
+        let mut result = ValueRef::list(None);
+        for item in &self.as_list_ref().values {
+            if item.is_list() && depth != 0 {
+                for value in &item.list_flatten(depth - 1).as_list_ref().values {
+                    result.list_append(value);
+                }
+            } else {
+                result.list_append(item);
+            }
+        }
+        result
+    }
+
+    /// Split the list into the chunks of the `size`, and the last chunk may be shorter.
+    pub fn list_chunk(&self, size: usize) -> ValueRef {
+        let mut result = ValueRef::list(None);
+        for chunk in self.as_list_ref().values.chunks(size) {
+            let chunk: Vec<&ValueRef> = chunk.iter().collect();
+            result.list_append(&ValueRef::list(Some(&chunk)));
+        }
+        result
+    }
+}
+
+/// Returns the key function that gets the value at the dotted key path of the items,
+/// and None when the path is not found.
+pub fn key_by_path(path: &str) -> impl FnMut(&ValueRef) -> ValueRef + '_ {
+    move |item| item.get_by_path(path).unwrap_or_else(ValueRef::none)
+}
+
+fn group_key(key: &ValueRef) -> String {
+    if key.is_str() {
+        key.as_str()
+    } else {
+        key.to_json_string()
+    }
+}
+
+#[cfg(test)]
+mod test_value_list_collection {
+    use crate::*;
+
+    #[test]
+    fn test_group_and_unique_by() {
+        let mut ctx = Context::new();
+        let value = ValueRef::from_json(
+            &mut ctx,
+            r#"[{"n": "a", "v": 1}, {"n": "b", "v": 2}, {"n": "a", "v": 3}]"#,
+        )
+        .unwrap();
+        assert_eq!(
+            value.list_group_by(&mut key_by_path("n")).to_json_string(),
+            r#"{"a": [{"n": "a", "v": 1}, {"n": "a", "v": 3}], "b": [{"n": "b", "v": 2}]}"#
+        );
+        assert_eq!(
+            value.list_unique_by(&mut key_by_path("n")).to_json_string(),
+            r#"[{"n": "a", "v": 1}, {"n": "b", "v": 2}]"#
+        );
+    }
+
+    #[test]
+    fn test_flatten_and_chunk() {
+        let mut ctx = Context::new();
+        let value = ValueRef::from_json(&mut ctx, r#"[1, [2, [3, [4]]], 5]"#).unwrap();
+        assert_eq!(
+            value.list_flatten(1).to_json_string(),
+            "[1, 2, [3, [4]], 5]"
+        );
+        assert_eq!(value.list_flatten(-1).to_json_string(), "[1, 2, 3, 4, 5]");
+        let value = ValueRef::list_int(&[1, 2, 3, 4, 5]);
+        assert_eq!(
+            value.list_chunk(2).to_json_string(),
+            "[[1, 2], [3, 4], [5]]"
+        );
+    }
+}
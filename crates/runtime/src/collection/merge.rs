@@ -0,0 +1,171 @@
+//! Copyright The KCL Authors. All rights reserved.
+
+use std::str::FromStr;
+
+use crate::*;
+
+/// ListMergeStrategy denotes how two list values are merged in [`ValueRef::deep_merge`].
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum ListMergeStrategy {
+    /// The list on the right replaces the list on the left.
+    #[default]
+    Override,
+    /// The items of the list on the right are appended to the list on the left.
+    Append,
+    /// Like `Append`, but the items that are already in the list are skipped.
+    Unique,
+    /// The items at the same index are deep merged, which is the same as the
+    /// list union `|` semantics.
+    Merge,
+}
+
+impl FromStr for ListMergeStrategy {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "override" => Ok(ListMergeStrategy::Override),
+            "append" => Ok(ListMergeStrategy::Append),
+            "unique" => Ok(ListMergeStrategy::Unique),
+            "merge" => Ok(ListMergeStrategy::Merge),
+            _ => Err(format!(
+                "invalid list merge strategy '{s}', expected 'override', 'append', 'unique' or 'merge'"
+            )),
+        }
+    }
+}
+
+impl ListMergeStrategy {
+    /// Returns the union options of the strategy, the lists of the `append` and `unique`
+    /// strategies are combined by [`ValueRef::list_merge_delta`] and then overridden.
+    pub fn union_options(&self) -> UnionOptions {
+        UnionOptions {
+            list_override: *self != ListMergeStrategy::Merge,
+            idempotent_check: false,
+            config_resolve: true,
+        }
+    }
+}
+
+impl ValueRef {
+    /// Deep merge the value `x` into a copy of the value and return the result.
+    ///
+    /// The values are merged with the union `|` semantics, which respects the attribute
+    /// operators of `x` and resolves the schemas again, but the values on the right win
+    /// instead of conflicting. Lists are merged with the list merge `strategy`.
+    pub fn deep_merge(
+        &self,
+        ctx: &mut Context,
+        x: &ValueRef,
+        strategy: ListMergeStrategy,
+    ) -> ValueRef {
+        let delta = self.list_merge_delta(x, strategy);
+        self.deep_copy()
+            .union_entry(ctx, &delta, false, &strategy.union_options())
+    }
+
+    /// Returns a copy of the value `x` to union into the value, where the lists of `x` are
+    /// combined with the lists at the same path of the value for the `append` and `unique`
+    /// strategies. The attributes of `x` with the `=` and `+=` operators are kept.
+    pub fn list_merge_delta(&self, x: &ValueRef, strategy: ListMergeStrategy) -> ValueRef {
+        if !matches!(
+            strategy,
+            ListMergeStrategy::Append | ListMergeStrategy::Unique
+        ) {
+            return x.deep_copy();
+        }
+        if self.is_list() && x.is_list() {
+            let mut result = self.deep_copy();
+            for value in &x.as_list_ref().values {
+                if strategy == ListMergeStrategy::Append || result.list_find(value) < 0 {
+                    result.list_append(&value.deep_copy());
+                }
+            }
+            return result;
+        }
+        let mut result = x.deep_copy();
+        if self.is_config() && x.is_config() {
+            let merged: Vec<(String, ValueRef)> = {
+                let delta = x.as_dict_ref();
+                delta
+                    .values
+                    .iter()
+                    .filter(|(key, _)| {
+                        matches!(
+                            delta.ops.get(*key),
+                            None | Some(ConfigEntryOperationKind::Union)
+                        ) && !delta.insert_indexs.contains_key(*key)
+                    })
+                    .filter_map(|(key, value)| {
+                        self.dict_get_value(key)
+                            .map(|origin| (key.clone(), origin.list_merge_delta(value, strategy)))
+                    })
+                    .collect()
+            };
+            let mut dict = result.as_dict_mut_ref();
+            for (key, value) in merged {
+                dict.values.insert(key, value);
+            }
+        }
+        result
+    }
+}
+
+#[cfg(test)]
+mod test_value_deep_merge {
+    use crate::*;
+
+    #[test]
+    fn test_deep_merge() {
+        let mut ctx = Context::new();
+        let base = r#"{"a": {"b": 1, "c": [1, 2]}, "d": [{"e": 1}]}"#;
+        let delta = r#"{"a": {"b": 2, "c": [2, 3]}, "d": [{"f": 2}], "g": null}"#;
+        let cases = [
+            (
+                "override",
+                r#"{"a": {"b": 2, "c": [2, 3]}, "d": [{"f": 2}], "g": null}"#,
+            ),
+            (
+                "append",
+                r#"{"a": {"b": 2, "c": [1, 2, 2, 3]}, "d": [{"e": 1}, {"f": 2}], "g": null}"#,
+            ),
+            (
+                "unique",
+                r#"{"a": {"b": 2, "c": [1, 2, 3]}, "d": [{"e": 1}, {"f": 2}], "g": null}"#,
+            ),
+            (
+                "merge",
+                r#"{"a": {"b": 2, "c": [2, 3]}, "d": [{"e": 1, "f": 2}], "g": null}"#,
+            ),
+        ];
+        for (strategy, expected) in cases {
+            let base = ValueRef::from_json(&mut ctx, base).unwrap();
+            let delta = ValueRef::from_json(&mut ctx, delta).unwrap();
+            let value = base.deep_merge(&mut ctx, &delta, strategy.parse().unwrap());
+            let expected = ValueRef::from_json(&mut ctx, expected).unwrap();
+            assert_eq!(value.to_json_string(), expected.to_json_string());
+        }
+    }
+
+    #[test]
+    fn test_deep_merge_with_operators() {
+        let mut ctx = Context::new();
+        let base = ValueRef::from_json(&mut ctx, r#"{"a": [1, 2], "b": [1]}"#).unwrap();
+        let mut delta = ValueRef::dict(None);
+        delta.dict_update_entry(
+            "a",
+            &ValueRef::from_json(&mut ctx, "[3]").unwrap(),
+            &ConfigEntryOperationKind::Override,
+            None,
+        );
+        delta.dict_update_entry(
+            "b",
+            &ValueRef::from_json(&mut ctx, "[2]").unwrap(),
+            &ConfigEntryOperationKind::Union,
+            None,
+        );
+        // The attribute with the `=` operator is overridden regardless of the strategy.
+        let value = base.deep_merge(&mut ctx, &delta, ListMergeStrategy::Append);
+        assert_eq!(value.to_json_string(), r#"{"a": [3], "b": [1, 2]}"#);
+    }
+}
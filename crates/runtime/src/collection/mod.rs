@@ -1,11 +1,21 @@
 //! Copyright The KCL Authors. All rights reserved.
 
+pub mod list;
+pub use list::*;
+
+pub mod merge;
+pub use merge::*;
+
+pub mod path;
+
 use crate::*;
 
+// union_all(num: [any]) -> any
+
 /// # Safety
 /// The caller must ensure that `ctx`, `args`, and `kwargs` are valid pointers
 #[unsafe(no_mangle)]
-pub unsafe extern "C-unwind" fn kcl_value_union_all(
+pub unsafe extern "C-unwind" fn kcl_collection_union_all(
     ctx: *mut kcl_context_t,
     args: *const kcl_value_ref_t,
     _kwargs: *const kcl_value_ref_t,
@@ -30,3 +40,302 @@ pub unsafe extern "C-unwind" fn kcl_value_union_all(
     }
     panic!("union_all() takes at least 1 argument (0 given)")
 }
+
+// merge(values: [any], list_strategy: str = "override") -> any
+
+/// # Safety
+/// The caller must ensure that `ctx`, `args`, and `kwargs` are valid pointers
+#[unsafe(no_mangle)]
+pub unsafe extern "C-unwind" fn kcl_collection_merge(
+    ctx: *mut kcl_context_t,
+    args: *const kcl_value_ref_t,
+    kwargs: *const kcl_value_ref_t,
+) -> *const kcl_value_ref_t {
+    let args = unsafe { ptr_as_ref(args) };
+    let kwargs = unsafe { ptr_as_ref(kwargs) };
+    let ctx = unsafe { mut_ptr_as_ref(ctx) };
+    let values = must_get_list_arg("merge", "values", args, kwargs, 0);
+    let strategy = must_get_list_strategy_arg(args, kwargs);
+    let mut result = ValueRef::dict(None);
+    for (i, value) in values.as_list_ref().values.iter().enumerate() {
+        result = if i == 0 {
+            value.deep_copy()
+        } else {
+            result.deep_merge(ctx, value, strategy)
+        };
+    }
+    result.into_raw(ctx)
+}
+
+// group_by(items: [any], key: str | (any) -> any) -> {str:[any]}
+
+/// # Safety
+/// The caller must ensure that `ctx`, `args`, and `kwargs` are valid pointers
+#[unsafe(no_mangle)]
+pub unsafe extern "C-unwind" fn kcl_collection_group_by(
+    ctx: *mut kcl_context_t,
+    args: *const kcl_value_ref_t,
+    kwargs: *const kcl_value_ref_t,
+) -> *const kcl_value_ref_t {
+    let args = unsafe { ptr_as_ref(args) };
+    let kwargs = unsafe { ptr_as_ref(kwargs) };
+    let ctx = unsafe { mut_ptr_as_ref(ctx) };
+    let items = must_get_list_arg("group_by", "items", args, kwargs, 0);
+    let key = must_get_key_path_arg("group_by", "key", args, kwargs, 1);
+    items.list_group_by(&mut key_by_path(&key)).into_raw(ctx)
+}
+
+// unique_by(items: [any], key: str | (any) -> any = None) -> [any]
+
+/// # Safety
+/// The caller must ensure that `ctx`, `args`, and `kwargs` are valid pointers
+#[unsafe(no_mangle)]
+pub unsafe extern "C-unwind" fn kcl_collection_unique_by(
+    ctx: *mut kcl_context_t,
+    args: *const kcl_value_ref_t,
+    kwargs: *const kcl_value_ref_t,
+) -> *const kcl_value_ref_t {
+    let args = unsafe { ptr_as_ref(args) };
+    let kwargs = unsafe { ptr_as_ref(kwargs) };
+    let ctx = unsafe { mut_ptr_as_ref(ctx) };
+    let items = must_get_list_arg("unique_by", "items", args, kwargs, 0);
+    let result = if get_call_arg(args, kwargs, 1, Some("key"))
+        .filter(|v| !v.is_none_or_undefined())
+        .is_some()
+    {
+        let key = must_get_key_path_arg("unique_by", "key", args, kwargs, 1);
+        items.list_unique_by(&mut key_by_path(&key))
+    } else {
+        items.list_unique_by(&mut |item| item.clone())
+    };
+    result.into_raw(ctx)
+}
+
+// partition(items: [any], predicate: str | (any) -> any) -> [[any]]
+
+/// # Safety
+/// The caller must ensure that `ctx`, `args`, and `kwargs` are valid pointers
+#[unsafe(no_mangle)]
+pub unsafe extern "C-unwind" fn kcl_collection_partition(
+    ctx: *mut kcl_context_t,
+    args: *const kcl_value_ref_t,
+    kwargs: *const kcl_value_ref_t,
+) -> *const kcl_value_ref_t {
+    let args = unsafe { ptr_as_ref(args) };
+    let kwargs = unsafe { ptr_as_ref(kwargs) };
+    let ctx = unsafe { mut_ptr_as_ref(ctx) };
+    let items = must_get_list_arg("partition", "items", args, kwargs, 0);
+    let predicate = must_get_key_path_arg("partition", "predicate", args, kwargs, 1);
+    items
+        .list_partition(&mut key_by_path(&predicate))
+        .into_raw(ctx)
+}
+
+// flatten(items: [any], depth: int = 1) -> [any]
+
+/// # Safety
+/// The caller must ensure that `ctx`, `args`, and `kwargs` are valid pointers
+#[unsafe(no_mangle)]
+pub unsafe extern "C-unwind" fn kcl_collection_flatten(
+    ctx: *mut kcl_context_t,
+    args: *const kcl_value_ref_t,
+    kwargs: *const kcl_value_ref_t,
+) -> *const kcl_value_ref_t {
+    let args = unsafe { ptr_as_ref(args) };
+    let kwargs = unsafe { ptr_as_ref(kwargs) };
+    let ctx = unsafe { mut_ptr_as_ref(ctx) };
+    let items = must_get_list_arg("flatten", "items", args, kwargs, 0);
+    let depth = get_call_arg_int(args, kwargs, 1, Some("depth")).unwrap_or(1);
+    items.list_flatten(depth).into_raw(ctx)
+}
+
+// chunk(items: [any], size: int) -> [[any]]
+
+/// # Safety
+/// The caller must ensure that `ctx`, `args`, and `kwargs` are valid pointers
+#[unsafe(no_mangle)]
+pub unsafe extern "C-unwind" fn kcl_collection_chunk(
+    ctx: *mut kcl_context_t,
+    args: *const kcl_value_ref_t,
+    kwargs: *const kcl_value_ref_t,
+) -> *const kcl_value_ref_t {
+    let args = unsafe { ptr_as_ref(args) };
+    let kwargs = unsafe { ptr_as_ref(kwargs) };
+    let ctx = unsafe { mut_ptr_as_ref(ctx) };
+    let items = must_get_list_arg("chunk", "items", args, kwargs, 0);
+    let size = get_call_arg_int(args, kwargs, 1, Some("size"))
+        .unwrap_or_else(|| panic!("chunk() missing the required positional argument: 'size'"));
+    if size <= 0 {
+        panic!("chunk() argument 'size' must be a positive integer, got {size}");
+    }
+    items.list_chunk(size as usize).into_raw(ctx)
+}
+
+// pick(value: {str:}, paths: str | [str]) -> {str:}
+
+/// # Safety
+/// The caller must ensure that `ctx`, `args`, and `kwargs` are valid pointers
+#[unsafe(no_mangle)]
+pub unsafe extern "C-unwind" fn kcl_collection_pick(
+    ctx: *mut kcl_context_t,
+    args: *const kcl_value_ref_t,
+    kwargs: *const kcl_value_ref_t,
+) -> *const kcl_value_ref_t {
+    let args = unsafe { ptr_as_ref(args) };
+    let kwargs = unsafe { ptr_as_ref(kwargs) };
+    let ctx = unsafe { mut_ptr_as_ref(ctx) };
+    let value = must_get_config_arg("pick", args, kwargs);
+    let paths = must_get_paths_arg("pick", args, kwargs);
+    match value.pick_paths(&paths) {
+        Ok(result) => result.into_raw(ctx),
+        Err(err) => panic!("pick() {err}"),
+    }
+}
+
+// omit(value: {str:}, paths: str | [str]) -> {str:}
+
+/// # Safety
+/// The caller must ensure that `ctx`, `args`, and `kwargs` are valid pointers
+#[unsafe(no_mangle)]
+pub unsafe extern "C-unwind" fn kcl_collection_omit(
+    ctx: *mut kcl_context_t,
+    args: *const kcl_value_ref_t,
+    kwargs: *const kcl_value_ref_t,
+) -> *const kcl_value_ref_t {
+    let args = unsafe { ptr_as_ref(args) };
+    let kwargs = unsafe { ptr_as_ref(kwargs) };
+    let ctx = unsafe { mut_ptr_as_ref(ctx) };
+    let value = must_get_config_arg("omit", args, kwargs);
+    let paths = must_get_paths_arg("omit", args, kwargs);
+    value.omit_paths(&paths).into_raw(ctx)
+}
+
+// get(value: any, path: str, default: any = None) -> any
+
+/// # Safety
+/// The caller must ensure that `ctx`, `args`, and `kwargs` are valid pointers
+#[unsafe(no_mangle)]
+pub unsafe extern "C-unwind" fn kcl_collection_get(
+    ctx: *mut kcl_context_t,
+    args: *const kcl_value_ref_t,
+    kwargs: *const kcl_value_ref_t,
+) -> *const kcl_value_ref_t {
+    let args = unsafe { ptr_as_ref(args) };
+    let kwargs = unsafe { ptr_as_ref(kwargs) };
+    let ctx = unsafe { mut_ptr_as_ref(ctx) };
+    let value = get_call_arg(args, kwargs, 0, Some("value"))
+        .unwrap_or_else(|| panic!("get() missing the required positional argument: 'value'"));
+    let path = get_call_arg_str(args, kwargs, 1, Some("path"))
+        .unwrap_or_else(|| panic!("get() missing the required positional argument: 'path'"));
+    match value.get_by_path(&path) {
+        Some(result) => result.into_raw(ctx),
+        None => get_call_arg(args, kwargs, 2, Some("default"))
+            .unwrap_or_else(ValueRef::none)
+            .into_raw(ctx),
+    }
+}
+
+// set(value: {str:}, path: str, new_value: any) -> {str:}
+
+/// # Safety
+/// The caller must ensure that `ctx`, `args`, and `kwargs` are valid pointers
+#[unsafe(no_mangle)]
+pub unsafe extern "C-unwind" fn kcl_collection_set(
+    ctx: *mut kcl_context_t,
+    args: *const kcl_value_ref_t,
+    kwargs: *const kcl_value_ref_t,
+) -> *const kcl_value_ref_t {
+    let args = unsafe { ptr_as_ref(args) };
+    let kwargs = unsafe { ptr_as_ref(kwargs) };
+    let ctx = unsafe { mut_ptr_as_ref(ctx) };
+    let mut value = must_get_config_arg("set", args, kwargs).deep_copy();
+    let path = get_call_arg_str(args, kwargs, 1, Some("path"))
+        .unwrap_or_else(|| panic!("set() missing the required positional argument: 'path'"));
+    let new_value = get_call_arg(args, kwargs, 2, Some("new_value"))
+        .unwrap_or_else(|| panic!("set() missing the required positional argument: 'new_value'"));
+    match value.set_by_path(&path, &new_value) {
+        Ok(()) => value.into_raw(ctx),
+        Err(err) => panic!("set() {err}"),
+    }
+}
+
+/// Returns the list argument of the collection function `name`.
+pub fn must_get_list_arg(
+    name: &str,
+    arg: &str,
+    args: &ValueRef,
+    kwargs: &ValueRef,
+    index: usize,
+) -> ValueRef {
+    match get_call_arg(args, kwargs, index, Some(arg)) {
+        Some(value) if value.is_list() => value,
+        Some(value) => panic!(
+            "{name}() argument '{arg}' must be a list, not '{}'",
+            value.type_str()
+        ),
+        None => panic!("{name}() missing the required positional argument: '{arg}'"),
+    }
+}
+
+/// Returns the `list_strategy` argument of `merge()`, which is `override` by default.
+pub fn must_get_list_strategy_arg(args: &ValueRef, kwargs: &ValueRef) -> ListMergeStrategy {
+    match get_call_arg_str(args, kwargs, 1, Some("list_strategy")) {
+        Some(strategy) => strategy
+            .parse::<ListMergeStrategy>()
+            .unwrap_or_else(|err| panic!("merge() {err}")),
+        None => ListMergeStrategy::default(),
+    }
+}
+
+/// Returns the key path argument of the collection function `name`. The key functions
+/// are called by the evaluator, so they are not expected here.
+fn must_get_key_path_arg(
+    name: &str,
+    arg: &str,
+    args: &ValueRef,
+    kwargs: &ValueRef,
+    index: usize,
+) -> String {
+    match get_call_arg(args, kwargs, index, Some(arg)) {
+        Some(value) if value.is_str() => value.as_str(),
+        Some(value) if value.is_func() => {
+            panic!("kcl_collection_{name} with a function should be handled by the evaluator")
+        }
+        Some(value) => panic!(
+            "{name}() argument '{arg}' must be a key path or a function, not '{}'",
+            value.type_str()
+        ),
+        None => panic!("{name}() missing the required positional argument: '{arg}'"),
+    }
+}
+
+fn must_get_config_arg(name: &str, args: &ValueRef, kwargs: &ValueRef) -> ValueRef {
+    match get_call_arg(args, kwargs, 0, Some("value")) {
+        Some(value) if value.is_config() => value,
+        Some(value) => panic!(
+            "{name}() argument 'value' must be a dict or schema, not '{}'",
+            value.type_str()
+        ),
+        None => panic!("{name}() missing the required positional argument: 'value'"),
+    }
+}
+
+fn must_get_paths_arg(name: &str, args: &ValueRef, kwargs: &ValueRef) -> Vec<String> {
+    match get_call_arg(args, kwargs, 1, Some("paths")) {
+        Some(paths) if paths.is_str() => vec![paths.as_str()],
+        Some(paths) if paths.is_list() => paths
+            .as_list_ref()
+            .values
+            .iter()
+            .map(|path| {
+                if path.is_str() {
+                    path.as_str()
+                } else {
+                    panic!("{name}() argument 'paths' must be a list of key paths")
+                }
+            })
+            .collect(),
+        Some(_) => panic!("{name}() argument 'paths' must be a key path or a list of key paths"),
+        None => panic!("{name}() missing the required positional argument: 'paths'"),
+    }
+}
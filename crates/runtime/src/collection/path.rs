@@ -0,0 +1,148 @@
+//! Copyright The KCL Authors. All rights reserved.
+
+use crate::*;
+
+impl ValueRef {
+    /// Set the value at the dotted key path e.g., `spec.replicas` or `containers.0.image`
+    /// in place, the same path form as [`ValueRef::get_by_path`]. The missing dicts on the
+    /// path are created, and the list indexes on the path must exist.
+    pub fn set_by_path(&mut self, path: &str, value: &ValueRef) -> Result<(), String> {
+        let keys: Vec<&str> = path.split('.').collect();
+        let (last, parents) = keys.split_last().unwrap();
+        let mut current = self.clone();
+        for (i, key) in parents.iter().enumerate() {
+            let child = match current.get_by_key(key) {
+                Some(child) if child.is_config() || child.is_list() => child,
+                // The existing values except None can't be replaced by the dicts.
+                Some(child) if !child.is_none_or_undefined() => {
+                    return Err(invalid_path_error(path, keys[i + 1], &child));
+                }
+                _ if current.is_config() => {
+                    let child = ValueRef::dict(None);
+                    current.dict_update_key_value(key, child.clone());
+                    child
+                }
+                _ => return Err(invalid_path_error(path, key, &current)),
+            };
+            current = child;
+        }
+        if current.is_config() {
+            current.dict_update_key_value(last, value.clone());
+        } else if current.is_list() {
+            match last.parse::<usize>() {
+                Ok(index) if index < current.len() => current.list_set(index, value),
+                _ => return Err(invalid_path_error(path, last, &current)),
+            }
+        } else {
+            return Err(invalid_path_error(path, last, &current));
+        }
+        Ok(())
+    }
+
+    /// Remove the value at the dotted key path in place, and returns whether the value
+    /// is found and removed. The order of the remaining dict keys is kept.
+    pub fn remove_by_path(&mut self, path: &str) -> bool {
+        let (parent, last) = match path.rsplit_once('.') {
+            Some((parent, last)) => match self.get_by_path(parent) {
+                Some(parent) => (parent, last),
+                None => return false,
+            },
+            None => (self.clone(), path),
+        };
+        let mut parent = parent;
+        if parent.is_config() {
+            let mut dict = parent.as_dict_mut_ref();
+            dict.ops.shift_remove(last);
+            dict.insert_indexs.shift_remove(last);
+            dict.attr_map.shift_remove(last);
+            dict.values.shift_remove(last).is_some()
+        } else if parent.is_list() {
+            match last.parse::<usize>() {
+                Ok(index) if index < parent.len() => {
+                    parent.list_remove_at(index);
+                    true
+                }
+                _ => false,
+            }
+        } else {
+            false
+        }
+    }
+
+    /// Returns a new dict that only contains the values at the dotted key paths, and
+    /// the paths that are not found are ignored.
+    pub fn pick_paths(&self, paths: &[String]) -> Result<ValueRef, String> {
+        let mut result = ValueRef::dict(None);
+        for path in paths {
+            if let Some(value) = self.get_by_path(path) {
+                result.set_by_path(path, &value.deep_copy())?;
+            }
+        }
+        Ok(result)
+    }
+
+    /// Returns a copy of the value without the values at the dotted key paths.
+    pub fn omit_paths(&self, paths: &[String]) -> ValueRef {
+        let mut result = self.deep_copy();
+        for path in paths {
+            result.remove_by_path(path);
+        }
+        result
+    }
+}
+
+fn invalid_path_error(path: &str, key: &str, value: &ValueRef) -> String {
+    if value.is_list() {
+        format!(
+            "invalid key path '{path}': the list index '{key}' is out of range or not an integer"
+        )
+    } else {
+        format!(
+            "invalid key path '{path}': the key '{key}' can't be set on the {} value",
+            value.type_str()
+        )
+    }
+}
+
+#[cfg(test)]
+mod test_value_path {
+    use crate::*;
+
+    #[test]
+    fn test_set_and_remove_by_path() {
+        let mut ctx = Context::new();
+        let mut value =
+            ValueRef::from_json(&mut ctx, r#"{"a": {"b": 1}, "c": [{"d": 1}], "e": 1}"#).unwrap();
+        value.set_by_path("a.x.y", &ValueRef::int(2)).unwrap();
+        value.set_by_path("c.0.d", &ValueRef::int(3)).unwrap();
+        assert!(value.set_by_path("c.1.d", &ValueRef::int(3)).is_err());
+        assert!(value.set_by_path("e.f", &ValueRef::int(3)).is_err());
+        assert_eq!(
+            value.to_json_string(),
+            r#"{"a": {"b": 1, "x": {"y": 2}}, "c": [{"d": 3}], "e": 1}"#
+        );
+        assert!(value.remove_by_path("a.b"));
+        assert!(value.remove_by_path("c.0"));
+        assert!(!value.remove_by_path("x.y"));
+        assert_eq!(
+            value.to_json_string(),
+            r#"{"a": {"x": {"y": 2}}, "c": [], "e": 1}"#
+        );
+    }
+
+    #[test]
+    fn test_pick_and_omit_paths() {
+        let mut ctx = Context::new();
+        let value =
+            ValueRef::from_json(&mut ctx, r#"{"a": {"b": 1, "c": 2}, "d": 3, "e": 4}"#).unwrap();
+        let paths = ["a.b".to_string(), "e".to_string(), "x".to_string()];
+        assert_eq!(
+            value.pick_paths(&paths).unwrap().to_json_string(),
+            r#"{"a": {"b": 1}, "e": 4}"#
+        );
+        assert_eq!(
+            value.omit_paths(&paths).to_json_string(),
+            r#"{"a": {"c": 2}, "d": 3}"#
+        );
+    }
+}
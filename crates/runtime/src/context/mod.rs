@@ -155,6 +155,18 @@ impl crate::Context {
         self.panic_info.err_type_code = *err_type as i32;
     }
 
+    /// Record the current panic information as a check failure with the message and restore
+    /// the panic information to `prev`, so that the evaluation can go on. A failure which
+    /// is the same as a recorded one e.g., a nested schema checked again is ignored.
+    pub fn record_check_failure(&mut self, prev: PanicInfo, message: &str) {
+        let mut failure = std::mem::replace(&mut self.panic_info, prev);
+        failure.__kcl_PanicInfo__ = true;
+        failure.message = message.to_string();
+        if !self.check_failures.contains(&failure) {
+            self.check_failures.push(failure);
+        }
+    }
+
     pub fn set_warning_message(&mut self, msg: &str) {
         self.panic_info.__kcl_PanicInfo__ = true;
         self.panic_info.message = msg.to_string();
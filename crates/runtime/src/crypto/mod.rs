@@ -9,12 +9,224 @@ use core::panic;
 use std::{fs::File, io::Read};
 
 use crate::encoding::encode_text;
+use argon2::{
+    Argon2, PasswordHash, PasswordHasher, PasswordVerifier,
+    password_hash::{SaltString, rand_core::OsRng},
+};
+use ed25519_dalek::{VerifyingKey, pkcs8::DecodePublicKey};
+use hmac::{Hmac, Mac};
+use rsa::{Pkcs1v15Sign, Pss, RsaPublicKey, pkcs1::DecodeRsaPublicKey};
 use sha2::{Digest, Sha224, Sha256, Sha384, Sha512};
 
 use crate::*;
 use uuid::Uuid;
 
-// md5(value: str, encoding: str = "utf-8") -> str
+/// The default cost of `bcrypt()`.
+const BCRYPT_DEFAULT_COST: u32 = 12;
+
+/// Encode the digest bytes in the output format `hex` (the default), `base64` or
+/// `base64url` (the URL-safe alphabet without padding).
+pub fn encode_digest(bytes: &[u8], output: &str) -> Result<String, String> {
+    match output {
+        "" | "hex" => Ok(bytes.iter().map(|byte| format!("{byte:02x}")).collect()),
+        "base64" => Ok(::base64::encode(bytes)),
+        "base64url" => Ok(::base64::encode_config(bytes, ::base64::URL_SAFE_NO_PAD)),
+        _ => Err(format!(
+            "invalid output format '{output}', expected 'hex', 'base64' or 'base64url'"
+        )),
+    }
+}
+
+/// Decode the bytes encoded in the format `hex`, `base64` or `base64url`. Both base64
+/// alphabets are accepted with or without padding.
+pub fn decode_digest(value: &str, format: &str) -> Result<Vec<u8>, String> {
+    let value = value.trim();
+    match format {
+        "hex" => {
+            if !value.len().is_multiple_of(2) || !value.is_ascii() {
+                return Err("invalid hex string".to_string());
+            }
+            (0..value.len())
+                .step_by(2)
+                .map(|i| u8::from_str_radix(&value[i..i + 2], 16).map_err(|err| err.to_string()))
+                .collect()
+        }
+        "" | "base64" | "base64url" => {
+            let value = value
+                .trim_end_matches('=')
+                .replace('-', "+")
+                .replace('_', "/");
+            ::base64::decode_config(value, ::base64::STANDARD_NO_PAD).map_err(|err| err.to_string())
+        }
+        _ => Err(format!(
+            "invalid format '{format}', expected 'hex', 'base64' or 'base64url'"
+        )),
+    }
+}
+
+/// Returns the HMAC of the value with the key, and the algorithm is `sha256` or `sha512`.
+pub fn hmac_digest(value: &[u8], key: &[u8], algorithm: &str) -> Result<Vec<u8>, String> {
+    match algorithm {
+        "" | "sha256" => {
+            let mut mac = Hmac::<Sha256>::new_from_slice(key).map_err(|err| err.to_string())?;
+            mac.update(value);
+            Ok(mac.finalize().into_bytes().to_vec())
+        }
+        "sha512" => {
+            let mut mac = Hmac::<Sha512>::new_from_slice(key).map_err(|err| err.to_string())?;
+            mac.update(value);
+            Ok(mac.finalize().into_bytes().to_vec())
+        }
+        _ => Err(format!(
+            "invalid HMAC algorithm '{algorithm}', expected 'sha256' or 'sha512'"
+        )),
+    }
+}
+
+/// Returns the bcrypt hash of the password in the modular crypt format e.g.,
+/// `$2b$12$...`. The version prefix is one of `2a`, `2b` (the default), `2x` and `2y`
+/// (used by the Apache htpasswd files), and a random salt is used when the 16 bytes
+/// salt is not given.
+pub fn bcrypt_hash(
+    password: &str,
+    cost: u32,
+    salt: Option<&str>,
+    prefix: &str,
+) -> Result<String, String> {
+    let version = match prefix.trim_matches('$') {
+        "2a" => bcrypt::Version::TwoA,
+        "" | "2b" => bcrypt::Version::TwoB,
+        "2x" => bcrypt::Version::TwoX,
+        "2y" => bcrypt::Version::TwoY,
+        _ => {
+            return Err(format!(
+                "invalid bcrypt prefix '{prefix}', expected '2a', '2b', '2x' or '2y'"
+            ));
+        }
+    };
+    let parts = match salt {
+        Some(salt) => {
+            let salt: [u8; 16] = salt.as_bytes().try_into().map_err(|_| {
+                format!("the bcrypt salt must be 16 bytes, got {} bytes", salt.len())
+            })?;
+            bcrypt::hash_with_salt(password, cost, salt)
+        }
+        None => bcrypt::hash_with_result(password, cost),
+    }
+    .map_err(|err| err.to_string())?;
+    Ok(parts.format_for_version(version))
+}
+
+/// Returns the Argon2id hash of the password in the PHC string format e.g.,
+/// `$argon2id$v=19$m=19456,t=2,p=1$...`. A random salt is used when the salt is not
+/// given, and the salt must be at least 8 bytes.
+pub fn argon2_hash(
+    password: &str,
+    salt: Option<&str>,
+    memory_cost: u32,
+    time_cost: u32,
+    parallelism: u32,
+) -> Result<String, String> {
+    let params = argon2::Params::new(memory_cost, time_cost, parallelism, None)
+        .map_err(|err| err.to_string())?;
+    let argon2 = Argon2::new(argon2::Algorithm::Argon2id, argon2::Version::V0x13, params);
+    let salt = match salt {
+        Some(salt) => SaltString::encode_b64(salt.as_bytes()).map_err(|err| err.to_string())?,
+        None => SaltString::generate(&mut OsRng),
+    };
+    argon2
+        .hash_password(password.as_bytes(), &salt)
+        .map(|hash| hash.to_string())
+        .map_err(|err| err.to_string())
+}
+
+/// Verify the password against the Argon2 (`argon2i`, `argon2d` or `argon2id`) hash
+/// in the PHC string format.
+pub fn argon2_verify(password: &str, hash: &str) -> Result<bool, String> {
+    let hash = PasswordHash::new(hash).map_err(|err| err.to_string())?;
+    match Argon2::default().verify_password(password.as_bytes(), &hash) {
+        Ok(()) => Ok(true),
+        Err(argon2::password_hash::Error::Password) => Ok(false),
+        Err(err) => Err(err.to_string()),
+    }
+}
+
+/// Verify the signature of the message with the PEM encoded public key. Ed25519 keys
+/// and RSA keys in the `PUBLIC KEY` (SPKI) or `RSA PUBLIC KEY` (PKCS#1) PEM blocks are
+/// supported.
+///
+/// The algorithm of the RSA signatures is one of `rsa-sha256` (the default),
+/// `rsa-sha384`, `rsa-sha512` for PKCS#1 v1.5 and `rsa-pss-sha256`, `rsa-pss-sha384`,
+/// `rsa-pss-sha512` for PSS, and the algorithm of the Ed25519 signatures is `ed25519`.
+pub fn verify_signature(
+    message: &[u8],
+    signature: &[u8],
+    public_key: &str,
+    algorithm: &str,
+) -> Result<bool, String> {
+    if let Ok(key) = VerifyingKey::from_public_key_pem(public_key) {
+        if !matches!(algorithm, "" | "ed25519") {
+            return Err(format!(
+                "invalid algorithm '{algorithm}' for the Ed25519 public key, expected 'ed25519'"
+            ));
+        }
+        let Ok(signature) = ed25519_dalek::Signature::from_slice(signature) else {
+            return Ok(false);
+        };
+        return Ok(key.verify_strict(message, &signature).is_ok());
+    }
+    let key = RsaPublicKey::from_public_key_pem(public_key)
+        .or_else(|_| RsaPublicKey::from_pkcs1_pem(public_key))
+        .map_err(|_| {
+            "invalid public key, expected an Ed25519 or RSA public key in the PEM format"
+                .to_string()
+        })?;
+    let result = match algorithm {
+        "" | "rsa-sha256" => key.verify(
+            Pkcs1v15Sign::new::<Sha256>(),
+            &Sha256::digest(message),
+            signature,
+        ),
+        "rsa-sha384" => key.verify(
+            Pkcs1v15Sign::new::<Sha384>(),
+            &Sha384::digest(message),
+            signature,
+        ),
+        "rsa-sha512" => key.verify(
+            Pkcs1v15Sign::new::<Sha512>(),
+            &Sha512::digest(message),
+            signature,
+        ),
+        "rsa-pss-sha256" => key.verify(Pss::new::<Sha256>(), &Sha256::digest(message), signature),
+        "rsa-pss-sha384" => key.verify(Pss::new::<Sha384>(), &Sha384::digest(message), signature),
+        "rsa-pss-sha512" => key.verify(Pss::new::<Sha512>(), &Sha512::digest(message), signature),
+        _ => {
+            return Err(format!(
+                "invalid algorithm '{algorithm}' for the RSA public key, expected 'rsa-sha256', 'rsa-sha384', 'rsa-sha512', 'rsa-pss-sha256', 'rsa-pss-sha384' or 'rsa-pss-sha512'"
+            ));
+        }
+    };
+    Ok(result.is_ok())
+}
+
+fn must_encode_digest(bytes: &[u8], output: Option<String>) -> String {
+    encode_digest(bytes, output.as_deref().unwrap_or_default())
+        .unwrap_or_else(|err| panic!("{}", err))
+}
+
+#[inline]
+fn get_optional_str(
+    args: &ValueRef,
+    kwargs: &ValueRef,
+    index: usize,
+    key: Option<&str>,
+) -> Option<String> {
+    get_call_arg(args, kwargs, index, key)
+        .filter(|v| !v.is_none_or_undefined())
+        .map(|v| v.as_str())
+}
+
+// md5(value: str, encoding: str = "utf-8", output: str = "hex") -> str
 
 /// # Safety
 /// The caller must ensure that `ctx`, `args`, and `kwargs` are valid pointers
@@ -31,13 +243,14 @@ pub unsafe extern "C-unwind" fn kcl_crypto_md5(
     if let Some(s) = get_call_arg_str(args, kwargs, 0, Some("value")) {
         let encoding = get_call_arg_str(args, kwargs, 1, Some("encoding"));
         let bytes = encode_text(&s, encoding).unwrap();
-        let hex = format!("{:x}", md5::compute(bytes));
-        return ValueRef::str(hex.as_ref()).into_raw(ctx);
+        let output = get_call_arg_str(args, kwargs, 2, Some("output"));
+        let digest = md5::compute(bytes);
+        return ValueRef::str(&must_encode_digest(&digest.0, output)).into_raw(ctx);
     }
     panic!("md5() missing 1 required positional argument: 'value'");
 }
 
-// sha1(value: str, encoding: str = "utf-8") -> str
+// sha1(value: str, encoding: str = "utf-8", output: str = "hex") -> str
 
 /// # Safety
 /// The caller must ensure that `ctx`, `args`, and `kwargs` are valid pointers
@@ -54,13 +267,14 @@ pub unsafe extern "C-unwind" fn kcl_crypto_sha1(
     if let Some(s) = get_call_arg_str(args, kwargs, 0, Some("value")) {
         let encoding = get_call_arg_str(args, kwargs, 1, Some("encoding"));
         let bytes = encode_text(&s, encoding).unwrap();
-        let hex = sha1::Sha1::from(bytes).digest().to_string();
-        return ValueRef::str(hex.as_ref()).into_raw(ctx);
+        let output = get_call_arg_str(args, kwargs, 2, Some("output"));
+        let digest = sha1::Sha1::from(bytes).digest().bytes();
+        return ValueRef::str(&must_encode_digest(&digest, output)).into_raw(ctx);
     }
     panic!("sha1() missing 1 required positional argument: 'value'");
 }
 
-// sha224(value: str, encoding: str = "utf-8") -> str
+// sha224(value: str, encoding: str = "utf-8", output: str = "hex") -> str
 
 /// # Safety
 /// The caller must ensure that `ctx`, `args`, and `kwargs` are valid pointers
@@ -80,20 +294,13 @@ pub unsafe extern "C-unwind" fn kcl_crypto_sha224(
         let mut hasher = Sha224::new();
         hasher.update(bytes);
         let result = hasher.finalize();
-
-        let mut hex = String::with_capacity(2 * Sha256::output_size());
-        use std::fmt::Write;
-
-        for byte in result {
-            let _ = write!(&mut hex, "{byte:02x}");
-        }
-
-        return ValueRef::str(hex.as_ref()).into_raw(ctx);
+        let output = get_call_arg_str(args, kwargs, 2, Some("output"));
+        return ValueRef::str(&must_encode_digest(&result, output)).into_raw(ctx);
     }
     panic!("sha224() missing 1 required positional argument: 'value'");
 }
 
-// sha256(value: str, encoding: str = "utf-8") -> str
+// sha256(value: str, encoding: str = "utf-8", output: str = "hex") -> str
 
 /// # Safety
 /// The caller must ensure that `ctx`, `args`, and `kwargs` are valid pointers
@@ -113,20 +320,13 @@ pub unsafe extern "C-unwind" fn kcl_crypto_sha256(
         let mut hasher = Sha256::new();
         hasher.update(bytes);
         let result = hasher.finalize();
-
-        let mut hex = String::with_capacity(2 * Sha256::output_size());
-        use std::fmt::Write;
-
-        for byte in result {
-            let _ = write!(&mut hex, "{byte:02x}");
-        }
-
-        return ValueRef::str(hex.as_ref()).into_raw(ctx);
+        let output = get_call_arg_str(args, kwargs, 2, Some("output"));
+        return ValueRef::str(&must_encode_digest(&result, output)).into_raw(ctx);
     }
     panic!("sha256() missing 1 required positional argument: 'value'");
 }
 
-// sha384(value: str, encoding: str = "utf-8") -> str
+// sha384(value: str, encoding: str = "utf-8", output: str = "hex") -> str
 
 /// # Safety
 /// The caller must ensure that `ctx`, `args`, and `kwargs` are valid pointers
@@ -146,20 +346,13 @@ pub unsafe extern "C-unwind" fn kcl_crypto_sha384(
         let mut hasher = Sha384::new();
         hasher.update(bytes);
         let result = hasher.finalize();
-
-        let mut hex = String::with_capacity(2 * Sha256::output_size());
-        use std::fmt::Write;
-
-        for byte in result {
-            let _ = write!(&mut hex, "{byte:02x}");
-        }
-
-        return ValueRef::str(hex.as_ref()).into_raw(ctx);
+        let output = get_call_arg_str(args, kwargs, 2, Some("output"));
+        return ValueRef::str(&must_encode_digest(&result, output)).into_raw(ctx);
     }
     panic!("sha384() missing 1 required positional argument: 'value'");
 }
 
-// sha512(value: str, encoding: str = "utf-8") -> str
+// sha512(value: str, encoding: str = "utf-8", output: str = "hex") -> str
 
 /// # Safety
 /// The caller must ensure that `ctx`, `args`, and `kwargs` are valid pointers
@@ -179,20 +372,13 @@ pub unsafe extern "C-unwind" fn kcl_crypto_sha512(
         let mut hasher = Sha512::new();
         hasher.update(bytes);
         let result = hasher.finalize();
-
-        let mut hex = String::with_capacity(2 * Sha256::output_size());
-        use std::fmt::Write;
-
-        for byte in result {
-            let _ = write!(&mut hex, "{byte:02x}");
-        }
-
-        return ValueRef::str(hex.as_ref()).into_raw(ctx);
+        let output = get_call_arg_str(args, kwargs, 2, Some("output"));
+        return ValueRef::str(&must_encode_digest(&result, output)).into_raw(ctx);
     }
     panic!("sha512() missing 1 required positional argument: 'value'");
 }
 
-// blake3(value: str, encoding: str = "utf-8") -> str
+// blake3(value: str, encoding: str = "utf-8", output: str = "hex") -> str
 
 /// # Safety
 /// The caller must ensure that `ctx`, `args`, and `kwargs` are valid pointers
@@ -210,15 +396,8 @@ pub unsafe extern "C-unwind" fn kcl_crypto_blake3(
         let encoding = get_call_arg_str(args, kwargs, 1, Some("encoding"));
         let bytes = encode_text(&s, encoding).unwrap();
         let hasher = blake3::hash(&bytes);
-
-        let mut hex = String::with_capacity(2 * blake3::OUT_LEN);
-        use std::fmt::Write;
-
-        for byte in hasher.as_bytes() {
-            let _ = write!(&mut hex, "{byte:02x}");
-        }
-
-        return ValueRef::str(hex.as_ref()).into_raw(ctx);
+        let output = get_call_arg_str(args, kwargs, 2, Some("output"));
+        return ValueRef::str(&must_encode_digest(hasher.as_bytes(), output)).into_raw(ctx);
     }
     panic!("blake3() missing 1 required positional argument: 'value'");
 }
@@ -348,3 +527,195 @@ pub unsafe extern "C-unwind" fn kcl_crypto_fileblake3(
     }
     panic!("fileblake3() missing 1 required positional argument: 'filepath'");
 }
+
+// hmac(value: str, key: str, algorithm: str = "sha256", output: str = "hex", encoding: str = "utf-8") -> str
+
+/// # Safety
+/// The caller must ensure that `ctx`, `args`, and `kwargs` are valid pointers
+#[unsafe(no_mangle)]
+pub unsafe extern "C-unwind" fn kcl_crypto_hmac(
+    ctx: *mut kcl_context_t,
+    args: *const kcl_value_ref_t,
+    kwargs: *const kcl_value_ref_t,
+) -> *const kcl_value_ref_t {
+    let args = unsafe { ptr_as_ref(args) };
+    let kwargs = unsafe { ptr_as_ref(kwargs) };
+    let ctx = unsafe { mut_ptr_as_ref(ctx) };
+
+    if let Some(s) = get_call_arg_str(args, kwargs, 0, Some("value")) {
+        if let Some(key) = get_call_arg_str(args, kwargs, 1, Some("key")) {
+            let algorithm = get_optional_str(args, kwargs, 2, Some("algorithm"));
+            let output = get_optional_str(args, kwargs, 3, Some("output"));
+            let encoding = get_optional_str(args, kwargs, 4, Some("encoding"));
+            let value = encode_text(&s, encoding.clone()).unwrap();
+            let key = encode_text(&key, encoding).unwrap();
+            let digest = hmac_digest(&value, &key, algorithm.as_deref().unwrap_or_default())
+                .unwrap_or_else(|err| panic!("{}", err));
+            return ValueRef::str(&must_encode_digest(&digest, output)).into_raw(ctx);
+        }
+        panic!("hmac() missing 1 required positional argument: 'key'");
+    }
+    panic!("hmac() missing 2 required positional arguments: 'value' and 'key'");
+}
+
+// bcrypt(password: str, cost: int = 12, salt: str = None, prefix: str = "2b") -> str
+
+/// # Safety
+/// The caller must ensure that `ctx`, `args`, and `kwargs` are valid pointers
+#[unsafe(no_mangle)]
+pub unsafe extern "C-unwind" fn kcl_crypto_bcrypt(
+    ctx: *mut kcl_context_t,
+    args: *const kcl_value_ref_t,
+    kwargs: *const kcl_value_ref_t,
+) -> *const kcl_value_ref_t {
+    let args = unsafe { ptr_as_ref(args) };
+    let kwargs = unsafe { ptr_as_ref(kwargs) };
+    let ctx = unsafe { mut_ptr_as_ref(ctx) };
+
+    if let Some(password) = get_call_arg_str(args, kwargs, 0, Some("password")) {
+        let cost = get_call_arg(args, kwargs, 1, Some("cost"))
+            .filter(|v| !v.is_none_or_undefined())
+            .map(|v| v.must_as_strict_int())
+            .unwrap_or(BCRYPT_DEFAULT_COST as i64);
+        let cost = u32::try_from(cost).unwrap_or_else(|_| panic!("invalid bcrypt cost {}", cost));
+        let salt = get_optional_str(args, kwargs, 2, Some("salt"));
+        let prefix = get_optional_str(args, kwargs, 3, Some("prefix"));
+        let hash = bcrypt_hash(
+            &password,
+            cost,
+            salt.as_deref(),
+            prefix.as_deref().unwrap_or_default(),
+        )
+        .unwrap_or_else(|err| panic!("{}", err));
+        return ValueRef::str(&hash).into_raw(ctx);
+    }
+    panic!("bcrypt() missing 1 required positional argument: 'password'");
+}
+
+// bcrypt_verify(password: str, hash: str) -> bool
+
+/// # Safety
+/// The caller must ensure that `ctx`, `args`, and `kwargs` are valid pointers
+#[unsafe(no_mangle)]
+pub unsafe extern "C-unwind" fn kcl_crypto_bcrypt_verify(
+    ctx: *mut kcl_context_t,
+    args: *const kcl_value_ref_t,
+    kwargs: *const kcl_value_ref_t,
+) -> *const kcl_value_ref_t {
+    let args = unsafe { ptr_as_ref(args) };
+    let kwargs = unsafe { ptr_as_ref(kwargs) };
+    let ctx = unsafe { mut_ptr_as_ref(ctx) };
+
+    if let Some(password) = get_call_arg_str(args, kwargs, 0, Some("password")) {
+        if let Some(hash) = get_call_arg_str(args, kwargs, 1, Some("hash")) {
+            let result = bcrypt::verify(&password, &hash)
+                .unwrap_or_else(|err| panic!("invalid bcrypt hash '{}': {}", hash, err));
+            return ValueRef::bool(result).into_raw(ctx);
+        }
+        panic!("bcrypt_verify() missing 1 required positional argument: 'hash'");
+    }
+    panic!("bcrypt_verify() missing 2 required positional arguments: 'password' and 'hash'");
+}
+
+// argon2(password: str, salt: str = None, memory_cost: int = 19456, time_cost: int = 2, parallelism: int = 1) -> str
+
+/// # Safety
+/// The caller must ensure that `ctx`, `args`, and `kwargs` are valid pointers
+#[unsafe(no_mangle)]
+pub unsafe extern "C-unwind" fn kcl_crypto_argon2(
+    ctx: *mut kcl_context_t,
+    args: *const kcl_value_ref_t,
+    kwargs: *const kcl_value_ref_t,
+) -> *const kcl_value_ref_t {
+    let args = unsafe { ptr_as_ref(args) };
+    let kwargs = unsafe { ptr_as_ref(kwargs) };
+    let ctx = unsafe { mut_ptr_as_ref(ctx) };
+
+    if let Some(password) = get_call_arg_str(args, kwargs, 0, Some("password")) {
+        let salt = get_optional_str(args, kwargs, 1, Some("salt"));
+        let get_param = |index: usize, key: &str, default: u32| -> u32 {
+            let value = get_call_arg(args, kwargs, index, Some(key))
+                .filter(|v| !v.is_none_or_undefined())
+                .map(|v| v.must_as_strict_int())
+                .unwrap_or(default as i64);
+            u32::try_from(value).unwrap_or_else(|_| panic!("invalid argon2 {} {}", key, value))
+        };
+        let memory_cost = get_param(2, "memory_cost", argon2::Params::DEFAULT_M_COST);
+        let time_cost = get_param(3, "time_cost", argon2::Params::DEFAULT_T_COST);
+        let parallelism = get_param(4, "parallelism", argon2::Params::DEFAULT_P_COST);
+        let hash = argon2_hash(
+            &password,
+            salt.as_deref(),
+            memory_cost,
+            time_cost,
+            parallelism,
+        )
+        .unwrap_or_else(|err| panic!("{}", err));
+        return ValueRef::str(&hash).into_raw(ctx);
+    }
+    panic!("argon2() missing 1 required positional argument: 'password'");
+}
+
+// argon2_verify(password: str, hash: str) -> bool
+
+/// # Safety
+/// The caller must ensure that `ctx`, `args`, and `kwargs` are valid pointers
+#[unsafe(no_mangle)]
+pub unsafe extern "C-unwind" fn kcl_crypto_argon2_verify(
+    ctx: *mut kcl_context_t,
+    args: *const kcl_value_ref_t,
+    kwargs: *const kcl_value_ref_t,
+) -> *const kcl_value_ref_t {
+    let args = unsafe { ptr_as_ref(args) };
+    let kwargs = unsafe { ptr_as_ref(kwargs) };
+    let ctx = unsafe { mut_ptr_as_ref(ctx) };
+
+    if let Some(password) = get_call_arg_str(args, kwargs, 0, Some("password")) {
+        if let Some(hash) = get_call_arg_str(args, kwargs, 1, Some("hash")) {
+            let result = argon2_verify(&password, &hash)
+                .unwrap_or_else(|err| panic!("invalid argon2 hash '{}': {}", hash, err));
+            return ValueRef::bool(result).into_raw(ctx);
+        }
+        panic!("argon2_verify() missing 1 required positional argument: 'hash'");
+    }
+    panic!("argon2_verify() missing 2 required positional arguments: 'password' and 'hash'");
+}
+
+// verify_signature(value: str, signature: str, public_key: str, algorithm: str = None, format: str = "base64", encoding: str = "utf-8") -> bool
+
+/// # Safety
+/// The caller must ensure that `ctx`, `args`, and `kwargs` are valid pointers
+#[unsafe(no_mangle)]
+pub unsafe extern "C-unwind" fn kcl_crypto_verify_signature(
+    ctx: *mut kcl_context_t,
+    args: *const kcl_value_ref_t,
+    kwargs: *const kcl_value_ref_t,
+) -> *const kcl_value_ref_t {
+    let args = unsafe { ptr_as_ref(args) };
+    let kwargs = unsafe { ptr_as_ref(kwargs) };
+    let ctx = unsafe { mut_ptr_as_ref(ctx) };
+
+    let (Some(s), Some(signature), Some(public_key)) = (
+        get_call_arg_str(args, kwargs, 0, Some("value")),
+        get_call_arg_str(args, kwargs, 1, Some("signature")),
+        get_call_arg_str(args, kwargs, 2, Some("public_key")),
+    ) else {
+        panic!(
+            "verify_signature() missing required positional arguments: 'value', 'signature' and 'public_key'"
+        );
+    };
+    let algorithm = get_optional_str(args, kwargs, 3, Some("algorithm"));
+    let format = get_optional_str(args, kwargs, 4, Some("format"));
+    let encoding = get_optional_str(args, kwargs, 5, Some("encoding"));
+    let value = encode_text(&s, encoding).unwrap();
+    let signature = decode_digest(&signature, format.as_deref().unwrap_or_default())
+        .unwrap_or_else(|err| panic!("invalid signature '{}': {}", signature, err));
+    let result = verify_signature(
+        &value,
+        &signature,
+        &public_key,
+        algorithm.as_deref().unwrap_or_default(),
+    )
+    .unwrap_or_else(|err| panic!("{}", err));
+    ValueRef::bool(result).into_raw(ctx)
+}
@@ -1,11 +1,22 @@
 //! Copyright The KCL Authors. All rights reserved.
 
-extern crate chrono;
+extern crate jiff;
 
-use chrono::{NaiveDate, NaiveDateTime, NaiveTime, prelude::Local};
+use jiff::{
+    Span, SpanTotal, Timestamp, Unit, Zoned,
+    civil::{DateTime, Time},
+    fmt::{
+        strtime,
+        temporal::{Pieces, PiecesOffset},
+    },
+    tz::TimeZone,
+};
 
 use crate::*;
 
+/// The default format of `format()`, an RFC 3339 timestamp.
+const RFC3339_FORMAT: &str = "%Y-%m-%dT%H:%M:%S%.f%:z";
+
 /// Return the "%Y-%m-%d %H:%M:%S.%{ticks}" format date.
 /// `today() -> str`
 /// # Safety
@@ -16,10 +27,14 @@ pub unsafe extern "C-unwind" fn kcl_datetime_today(
     _args: *const kcl_value_ref_t,
     _kwargs: *const kcl_value_ref_t,
 ) -> *const kcl_value_ref_t {
-    let s = Local::now();
+    let s = Zoned::now();
     let ctx = unsafe { mut_ptr_as_ref(ctx) };
-    ValueRef::str(&(s.format("%Y-%m-%d %H:%M:%S").to_string() + "." + &s.timestamp().to_string()))
-        .into_raw(ctx)
+    ValueRef::str(
+        &(must_format_datetime(&s, "%Y-%m-%d %H:%M:%S")
+            + "."
+            + &s.timestamp().as_second().to_string()),
+    )
+    .into_raw(ctx)
 }
 
 /// Return the local time format. e.g. 'Sat Jun 06 16:26:11 1998' or format the combined date and time per the specified format string,
@@ -33,13 +48,13 @@ pub unsafe extern "C-unwind" fn kcl_datetime_now(
     args: *const kcl_value_ref_t,
     kwargs: *const kcl_value_ref_t,
 ) -> *const kcl_value_ref_t {
-    let s = Local::now();
+    let s = Zoned::now();
     let ctx = unsafe { mut_ptr_as_ref(ctx) };
     let args = unsafe { ptr_as_ref(args) };
     let kwargs = unsafe { ptr_as_ref(kwargs) };
     let format = get_call_arg_str(args, kwargs, 0, Some("format"))
         .unwrap_or_else(|| "%a %b %d %H:%M:%S %Y".to_string());
-    ValueRef::str(&s.format(&format).to_string()).into_raw(ctx)
+    ValueRef::str(&must_format_datetime(&s, &format)).into_raw(ctx)
 }
 
 /// Return the current time in seconds since the Epoch. Fractions of a second may be present if the system clock provides them.
@@ -53,7 +68,7 @@ pub unsafe extern "C-unwind" fn kcl_datetime_ticks(
     _kwargs: *const kcl_value_ref_t,
 ) -> *const kcl_value_ref_t {
     let ctx = unsafe { mut_ptr_as_ref(ctx) };
-    let x = Local::now().timestamp();
+    let x = Timestamp::now().as_second();
     ValueRef::float(x as f64).into_raw(ctx)
 }
 
@@ -67,9 +82,9 @@ pub unsafe extern "C-unwind" fn kcl_datetime_date(
     _args: *const kcl_value_ref_t,
     _kwargs: *const kcl_value_ref_t,
 ) -> *const kcl_value_ref_t {
-    let s = Local::now();
+    let s = Zoned::now();
     let ctx = unsafe { mut_ptr_as_ref(ctx) };
-    ValueRef::str(&s.format("%Y-%m-%d %H:%M:%S").to_string()).into_raw(ctx)
+    ValueRef::str(&must_format_datetime(&s, "%Y-%m-%d %H:%M:%S")).into_raw(ctx)
 }
 
 /// Validates whether the provided date string matches the specified format.
@@ -106,9 +121,297 @@ pub unsafe extern "C-unwind" fn kcl_datetime_validate(
 ///   otherwise, returns `false`.
 #[inline]
 fn validate_date(date: &str, format: &str) -> bool {
-    NaiveDateTime::parse_from_str(date, format)
-        .map(|_| true)
-        .or_else(|_| NaiveDate::parse_from_str(date, format).map(|_| true))
-        .or_else(|_| NaiveTime::parse_from_str(date, format).map(|_| true))
-        .is_ok()
+    let Ok(tm) = strtime::parse(format, date) else {
+        return false;
+    };
+    // The time is midnight without the hour, so the date must be parsed then, and the
+    // time formats without the date fields e.g., `%H:%M:%S` have no date to validate.
+    match tm.hour() {
+        Some(_) => {
+            tm.to_time().is_ok()
+                && (tm.year().is_none() && tm.month().is_none() && tm.day().is_none()
+                    || tm.to_date().is_ok())
+        }
+        None => tm.to_date().is_ok(),
+    }
+}
+
+/// Parse the date string with the strftime `format`, or as an ISO 8601 / RFC 3339 date
+/// when the format is empty. A date without a UTC offset or time zone is interpreted in
+/// the time zone `tz`, and UTC when it is empty.
+pub fn parse_datetime(date: &str, format: &str, tz: &str) -> Result<Zoned, String> {
+    if format.is_empty() {
+        return parse_iso_datetime(date, tz);
+    }
+    let tm = strtime::parse(format, date).map_err(|err| err.to_string())?;
+    if tm.offset().is_some() || tm.iana_time_zone().is_some() {
+        return tm.to_zoned().map_err(|err| err.to_string());
+    }
+    let datetime = match tm.to_datetime() {
+        Ok(datetime) => datetime,
+        // Date-only formats e.g., `%Y-%m-%d` start at midnight.
+        Err(_) => tm
+            .to_date()
+            .map_err(|err| err.to_string())?
+            .to_datetime(Time::midnight()),
+    };
+    datetime
+        .to_zoned(get_time_zone(tz)?)
+        .map_err(|err| err.to_string())
+}
+
+/// Format the date with the strftime `format`.
+pub fn format_datetime(date: &Zoned, format: &str) -> Result<String, String> {
+    strtime::format(format, date).map_err(|err| err.to_string())
+}
+
+/// Parse the ISO 8601 duration e.g., `P1Y2M3DT4H5M6S`, `PT90M` or `-P1D`.
+pub fn parse_duration(duration: &str) -> Result<Span, String> {
+    duration
+        .trim()
+        .parse::<Span>()
+        .map_err(|err| err.to_string())
+}
+
+/// Returns the time zone of the IANA time zone name e.g., `Asia/Shanghai`, or UTC
+/// when the name is empty.
+pub fn get_time_zone(tz: &str) -> Result<TimeZone, String> {
+    if tz.is_empty() {
+        Ok(TimeZone::UTC)
+    } else {
+        TimeZone::get(tz).map_err(|err| err.to_string())
+    }
+}
+
+fn parse_iso_datetime(date: &str, tz: &str) -> Result<Zoned, String> {
+    let pieces = Pieces::parse(date.trim()).map_err(|err| err.to_string())?;
+    if pieces.time_zone_annotation().is_some() {
+        return date.trim().parse::<Zoned>().map_err(|err| err.to_string());
+    }
+    let datetime = DateTime::from_parts(pieces.date(), pieces.time().unwrap_or_default());
+    let tz = match pieces.offset() {
+        Some(PiecesOffset::Numeric(offset)) => TimeZone::fixed(offset.offset()),
+        Some(_) => TimeZone::UTC,
+        None => get_time_zone(tz)?,
+    };
+    datetime.to_zoned(tz).map_err(|err| err.to_string())
+}
+
+/// The date argument is a date string or the seconds since the Epoch e.g., `ticks()`,
+/// the dates without a UTC offset and the Epoch seconds are in UTC.
+fn must_get_datetime(value: &ValueRef) -> Zoned {
+    if value.is_number() {
+        let seconds = value.as_num();
+        return Timestamp::from_nanosecond((seconds * 1e9) as i128)
+            .map(|ts| ts.to_zoned(TimeZone::UTC))
+            .unwrap_or_else(|err| panic!("invalid timestamp {}: {}", seconds, err));
+    }
+    let date = value.as_str();
+    parse_datetime(&date, "", "").unwrap_or_else(|err| panic!("invalid date '{}': {}", date, err))
+}
+
+fn must_parse_duration(duration: &str) -> Span {
+    parse_duration(duration)
+        .unwrap_or_else(|err| panic!("invalid duration '{}': {}", duration, err))
+}
+
+fn must_get_time_zone(tz: &str) -> TimeZone {
+    get_time_zone(tz).unwrap_or_else(|err| panic!("invalid time zone '{}': {}", tz, err))
+}
+
+fn must_format_datetime(date: &Zoned, format: &str) -> String {
+    format_datetime(date, format)
+        .unwrap_or_else(|err| panic!("invalid date format '{}': {}", format, err))
+}
+
+#[inline]
+fn get_optional_str(args: &ValueRef, kwargs: &ValueRef, index: usize, key: Option<&str>) -> String {
+    get_call_arg(args, kwargs, index, key)
+        .filter(|v| !v.is_none_or_undefined())
+        .map(|v| v.as_str())
+        .unwrap_or_default()
+}
+
+/// Parse the date string with the strftime format and return the RFC 3339 timestamp.
+/// `parse(date: str, format: str = None, tz: str = None) -> str`
+/// # Safety
+/// The caller must ensure that `ctx`, `args`, and `kwargs` are valid pointers
+#[unsafe(no_mangle)]
+pub unsafe extern "C-unwind" fn kcl_datetime_parse(
+    ctx: *mut kcl_context_t,
+    args: *const kcl_value_ref_t,
+    kwargs: *const kcl_value_ref_t,
+) -> *const kcl_value_ref_t {
+    let ctx = unsafe { mut_ptr_as_ref(ctx) };
+    let args = unsafe { ptr_as_ref(args) };
+    let kwargs = unsafe { ptr_as_ref(kwargs) };
+    if let Some(date) = get_call_arg_str(args, kwargs, 0, Some("date")) {
+        let format = get_optional_str(args, kwargs, 1, Some("format"));
+        let tz = get_optional_str(args, kwargs, 2, Some("tz"));
+        let result = parse_datetime(&date, &format, &tz)
+            .unwrap_or_else(|err| panic!("invalid date '{}': {}", date, err));
+        return ValueRef::str(&must_format_datetime(&result, RFC3339_FORMAT)).into_raw(ctx);
+    }
+    panic!("parse() missing 1 required positional argument: 'date'");
+}
+
+/// Format the date string or the seconds since the Epoch with the strftime format,
+/// in the time zone `tz` when it is given.
+/// `format(date: str | int | float, format: str = "%Y-%m-%dT%H:%M:%S%.f%:z", tz: str = None) -> str`
+/// # Safety
+/// The caller must ensure that `ctx`, `args`, and `kwargs` are valid pointers
+#[unsafe(no_mangle)]
+pub unsafe extern "C-unwind" fn kcl_datetime_format(
+    ctx: *mut kcl_context_t,
+    args: *const kcl_value_ref_t,
+    kwargs: *const kcl_value_ref_t,
+) -> *const kcl_value_ref_t {
+    let ctx = unsafe { mut_ptr_as_ref(ctx) };
+    let args = unsafe { ptr_as_ref(args) };
+    let kwargs = unsafe { ptr_as_ref(kwargs) };
+    if let Some(date) = get_call_arg(args, kwargs, 0, Some("date")) {
+        let format = get_optional_str(args, kwargs, 1, Some("format"));
+        let format = if format.is_empty() {
+            RFC3339_FORMAT
+        } else {
+            &format
+        };
+        let tz = get_optional_str(args, kwargs, 2, Some("tz"));
+        let mut date = must_get_datetime(&date);
+        if !tz.is_empty() {
+            date = date.with_time_zone(must_get_time_zone(&tz));
+        }
+        return ValueRef::str(&must_format_datetime(&date, format)).into_raw(ctx);
+    }
+    panic!("format() missing 1 required positional argument: 'date'");
+}
+
+/// Convert the date to the IANA time zone and return the RFC 3339 timestamp.
+/// `to_timezone(date: str | int | float, tz: str) -> str`
+/// # Safety
+/// The caller must ensure that `ctx`, `args`, and `kwargs` are valid pointers
+#[unsafe(no_mangle)]
+pub unsafe extern "C-unwind" fn kcl_datetime_to_timezone(
+    ctx: *mut kcl_context_t,
+    args: *const kcl_value_ref_t,
+    kwargs: *const kcl_value_ref_t,
+) -> *const kcl_value_ref_t {
+    let ctx = unsafe { mut_ptr_as_ref(ctx) };
+    let args = unsafe { ptr_as_ref(args) };
+    let kwargs = unsafe { ptr_as_ref(kwargs) };
+    if let Some(date) = get_call_arg(args, kwargs, 0, Some("date")) {
+        if let Some(tz) = get_call_arg_str(args, kwargs, 1, Some("tz")) {
+            let date = must_get_datetime(&date).with_time_zone(must_get_time_zone(&tz));
+            return ValueRef::str(&must_format_datetime(&date, RFC3339_FORMAT)).into_raw(ctx);
+        }
+        panic!("to_timezone() missing 1 required positional argument: 'tz'");
+    }
+    panic!("to_timezone() missing 2 required positional arguments: 'date' and 'tz'");
+}
+
+/// Return the seconds of the ISO 8601 duration, a day is 24 hours and the years and
+/// months are not allowed.
+/// `duration(duration: str) -> float`
+/// # Safety
+/// The caller must ensure that `ctx`, `args`, and `kwargs` are valid pointers
+#[unsafe(no_mangle)]
+pub unsafe extern "C-unwind" fn kcl_datetime_duration(
+    ctx: *mut kcl_context_t,
+    args: *const kcl_value_ref_t,
+    kwargs: *const kcl_value_ref_t,
+) -> *const kcl_value_ref_t {
+    let ctx = unsafe { mut_ptr_as_ref(ctx) };
+    let args = unsafe { ptr_as_ref(args) };
+    let kwargs = unsafe { ptr_as_ref(kwargs) };
+    if let Some(duration) = get_call_arg_str(args, kwargs, 0, Some("duration")) {
+        let seconds = must_parse_duration(&duration)
+            .total(SpanTotal::from(Unit::Second).days_are_24_hours())
+            .unwrap_or_else(|err| panic!("invalid duration '{}': {}", duration, err));
+        return ValueRef::float(seconds).into_raw(ctx);
+    }
+    panic!("duration() missing 1 required positional argument: 'duration'");
+}
+
+/// Add the ISO 8601 duration to the date and return the RFC 3339 timestamp.
+/// `add(date: str | int | float, duration: str) -> str`
+/// # Safety
+/// The caller must ensure that `ctx`, `args`, and `kwargs` are valid pointers
+#[unsafe(no_mangle)]
+pub unsafe extern "C-unwind" fn kcl_datetime_add(
+    ctx: *mut kcl_context_t,
+    args: *const kcl_value_ref_t,
+    kwargs: *const kcl_value_ref_t,
+) -> *const kcl_value_ref_t {
+    let ctx = unsafe { mut_ptr_as_ref(ctx) };
+    let args = unsafe { ptr_as_ref(args) };
+    let kwargs = unsafe { ptr_as_ref(kwargs) };
+    ValueRef::str(&add_duration("add", args, kwargs, false)).into_raw(ctx)
+}
+
+/// Subtract the ISO 8601 duration from the date and return the RFC 3339 timestamp.
+/// `subtract(date: str | int | float, duration: str) -> str`
+/// # Safety
+/// The caller must ensure that `ctx`, `args`, and `kwargs` are valid pointers
+#[unsafe(no_mangle)]
+pub unsafe extern "C-unwind" fn kcl_datetime_subtract(
+    ctx: *mut kcl_context_t,
+    args: *const kcl_value_ref_t,
+    kwargs: *const kcl_value_ref_t,
+) -> *const kcl_value_ref_t {
+    let ctx = unsafe { mut_ptr_as_ref(ctx) };
+    let args = unsafe { ptr_as_ref(args) };
+    let kwargs = unsafe { ptr_as_ref(kwargs) };
+    ValueRef::str(&add_duration("subtract", args, kwargs, true)).into_raw(ctx)
+}
+
+fn add_duration(name: &str, args: &ValueRef, kwargs: &ValueRef, negate: bool) -> String {
+    if let Some(date) = get_call_arg(args, kwargs, 0, Some("date")) {
+        if let Some(duration) = get_call_arg_str(args, kwargs, 1, Some("duration")) {
+            let date = must_get_datetime(&date);
+            let mut span = must_parse_duration(&duration);
+            if negate {
+                span = span.negate();
+            }
+            // The calendar units are added in the time zone of the date, so adding
+            // `P1D` keeps the wall clock time across the DST transitions.
+            let result = date
+                .checked_add(span)
+                .unwrap_or_else(|err| panic!("{}() failed: {}", name, err));
+            return must_format_datetime(&result, RFC3339_FORMAT);
+        }
+        panic!(
+            "{}() missing 1 required positional argument: 'duration'",
+            name
+        );
+    }
+    panic!(
+        "{}() missing 2 required positional arguments: 'date' and 'duration'",
+        name
+    );
+}
+
+/// Return the seconds from the date `start` to the date `end`, which is negative when
+/// `end` is earlier than `start`.
+/// `diff(start: str | int | float, end: str | int | float) -> float`
+/// # Safety
+/// The caller must ensure that `ctx`, `args`, and `kwargs` are valid pointers
+#[unsafe(no_mangle)]
+pub unsafe extern "C-unwind" fn kcl_datetime_diff(
+    ctx: *mut kcl_context_t,
+    args: *const kcl_value_ref_t,
+    kwargs: *const kcl_value_ref_t,
+) -> *const kcl_value_ref_t {
+    let ctx = unsafe { mut_ptr_as_ref(ctx) };
+    let args = unsafe { ptr_as_ref(args) };
+    let kwargs = unsafe { ptr_as_ref(kwargs) };
+    if let Some(start) = get_call_arg(args, kwargs, 0, Some("start")) {
+        if let Some(end) = get_call_arg(args, kwargs, 1, Some("end")) {
+            let start = must_get_datetime(&start).timestamp();
+            let end = must_get_datetime(&end).timestamp();
+            let seconds = end.duration_since(start).as_secs_f64();
+            return ValueRef::float(seconds).into_raw(ctx);
+        }
+        panic!("diff() missing 1 required positional argument: 'end'");
+    }
+    panic!("diff() missing 2 required positional arguments: 'start' and 'end'");
 }
@@ -197,6 +197,28 @@ kcl_value_ref_t* kcl_builtin_typeof(kcl_context_t* ctx, kcl_value_ref_t* args, k
 
 kcl_value_ref_t* kcl_builtin_zip(kcl_context_t* ctx, kcl_value_ref_t* args, kcl_value_ref_t* _kwargs);
 
+kcl_value_ref_t* kcl_collection_chunk(kcl_context_t* ctx, kcl_value_ref_t* args, kcl_value_ref_t* kwargs);
+
+kcl_value_ref_t* kcl_collection_flatten(kcl_context_t* ctx, kcl_value_ref_t* args, kcl_value_ref_t* kwargs);
+
+kcl_value_ref_t* kcl_collection_get(kcl_context_t* ctx, kcl_value_ref_t* args, kcl_value_ref_t* kwargs);
+
+kcl_value_ref_t* kcl_collection_group_by(kcl_context_t* ctx, kcl_value_ref_t* args, kcl_value_ref_t* kwargs);
+
+kcl_value_ref_t* kcl_collection_merge(kcl_context_t* ctx, kcl_value_ref_t* args, kcl_value_ref_t* kwargs);
+
+kcl_value_ref_t* kcl_collection_omit(kcl_context_t* ctx, kcl_value_ref_t* args, kcl_value_ref_t* kwargs);
+
+kcl_value_ref_t* kcl_collection_partition(kcl_context_t* ctx, kcl_value_ref_t* args, kcl_value_ref_t* kwargs);
+
+kcl_value_ref_t* kcl_collection_pick(kcl_context_t* ctx, kcl_value_ref_t* args, kcl_value_ref_t* kwargs);
+
+kcl_value_ref_t* kcl_collection_set(kcl_context_t* ctx, kcl_value_ref_t* args, kcl_value_ref_t* kwargs);
+
+kcl_value_ref_t* kcl_collection_union_all(kcl_context_t* ctx, kcl_value_ref_t* args, kcl_value_ref_t* _kwargs);
+
+kcl_value_ref_t* kcl_collection_unique_by(kcl_context_t* ctx, kcl_value_ref_t* args, kcl_value_ref_t* kwargs);
+
 void kcl_config_attr_map(kcl_value_ref_t* value, kcl_char_t* name, kcl_char_t* type_str);
 
 void kcl_context_delete(kcl_context_t* p);
@@ -231,6 +253,14 @@ void kcl_context_set_strict_range_check(kcl_context_t* p, kcl_bool_t v);
 
 kcl_value_ref_t* kcl_convert_collection_value(kcl_context_t* ctx, kcl_value_ref_t* value, kcl_char_t* tpe, kcl_value_ref_t* is_in_schema);
 
+kcl_value_ref_t* kcl_crypto_argon2(kcl_context_t* ctx, kcl_value_ref_t* args, kcl_value_ref_t* kwargs);
+
+kcl_value_ref_t* kcl_crypto_argon2_verify(kcl_context_t* ctx, kcl_value_ref_t* args, kcl_value_ref_t* kwargs);
+
+kcl_value_ref_t* kcl_crypto_bcrypt(kcl_context_t* ctx, kcl_value_ref_t* args, kcl_value_ref_t* kwargs);
+
+kcl_value_ref_t* kcl_crypto_bcrypt_verify(kcl_context_t* ctx, kcl_value_ref_t* args, kcl_value_ref_t* kwargs);
+
 kcl_value_ref_t* kcl_crypto_blake3(kcl_context_t* ctx, kcl_value_ref_t* args, kcl_value_ref_t* kwargs);
 
 kcl_value_ref_t* kcl_crypto_fileblake3(kcl_context_t* ctx, kcl_value_ref_t* args, kcl_value_ref_t* kwargs);
@@ -239,6 +269,8 @@ kcl_value_ref_t* kcl_crypto_filesha256(kcl_context_t* ctx, kcl_value_ref_t* args
 
 kcl_value_ref_t* kcl_crypto_filesha512(kcl_context_t* ctx, kcl_value_ref_t* args, kcl_value_ref_t* kwargs);
 
+kcl_value_ref_t* kcl_crypto_hmac(kcl_context_t* ctx, kcl_value_ref_t* args, kcl_value_ref_t* kwargs);
+
 kcl_value_ref_t* kcl_crypto_md5(kcl_context_t* ctx, kcl_value_ref_t* args, kcl_value_ref_t* kwargs);
 
 kcl_value_ref_t* kcl_crypto_sha1(kcl_context_t* ctx, kcl_value_ref_t* args, kcl_value_ref_t* kwargs);
@@ -253,12 +285,28 @@ kcl_value_ref_t* kcl_crypto_sha512(kcl_context_t* ctx, kcl_value_ref_t* args, kc
 
 kcl_value_ref_t* kcl_crypto_uuid(kcl_context_t* ctx, kcl_value_ref_t* _args, kcl_value_ref_t* _kwargs);
 
+kcl_value_ref_t* kcl_crypto_verify_signature(kcl_context_t* ctx, kcl_value_ref_t* args, kcl_value_ref_t* kwargs);
+
+kcl_value_ref_t* kcl_datetime_add(kcl_context_t* ctx, kcl_value_ref_t* args, kcl_value_ref_t* kwargs);
+
 kcl_value_ref_t* kcl_datetime_date(kcl_context_t* ctx, kcl_value_ref_t* _args, kcl_value_ref_t* _kwargs);
 
+kcl_value_ref_t* kcl_datetime_diff(kcl_context_t* ctx, kcl_value_ref_t* args, kcl_value_ref_t* kwargs);
+
+kcl_value_ref_t* kcl_datetime_duration(kcl_context_t* ctx, kcl_value_ref_t* args, kcl_value_ref_t* kwargs);
+
+kcl_value_ref_t* kcl_datetime_format(kcl_context_t* ctx, kcl_value_ref_t* args, kcl_value_ref_t* kwargs);
+
 kcl_value_ref_t* kcl_datetime_now(kcl_context_t* ctx, kcl_value_ref_t* args, kcl_value_ref_t* kwargs);
 
+kcl_value_ref_t* kcl_datetime_parse(kcl_context_t* ctx, kcl_value_ref_t* args, kcl_value_ref_t* kwargs);
+
+kcl_value_ref_t* kcl_datetime_subtract(kcl_context_t* ctx, kcl_value_ref_t* args, kcl_value_ref_t* kwargs);
+
 kcl_value_ref_t* kcl_datetime_ticks(kcl_context_t* ctx, kcl_value_ref_t* _args, kcl_value_ref_t* _kwargs);
 
+kcl_value_ref_t* kcl_datetime_to_timezone(kcl_context_t* ctx, kcl_value_ref_t* args, kcl_value_ref_t* kwargs);
+
 kcl_value_ref_t* kcl_datetime_today(kcl_context_t* ctx, kcl_value_ref_t* _args, kcl_value_ref_t* _kwargs);
 
 kcl_value_ref_t* kcl_datetime_validate(kcl_context_t* ctx, kcl_value_ref_t* args, kcl_value_ref_t* kwargs);
@@ -477,12 +525,16 @@ kcl_value_ref_t* kcl_regex_compile(kcl_context_t* ctx, kcl_value_ref_t* args, kc
 
 kcl_value_ref_t* kcl_regex_findall(kcl_context_t* ctx, kcl_value_ref_t* args, kcl_value_ref_t* kwargs);
 
+kcl_value_ref_t* kcl_regex_findall_groups(kcl_context_t* ctx, kcl_value_ref_t* args, kcl_value_ref_t* kwargs);
+
 kcl_value_ref_t* kcl_regex_match(kcl_context_t* ctx, kcl_value_ref_t* args, kcl_value_ref_t* kwargs);
 
 kcl_value_ref_t* kcl_regex_replace(kcl_context_t* ctx, kcl_value_ref_t* args, kcl_value_ref_t* kwargs);
 
 kcl_value_ref_t* kcl_regex_search(kcl_context_t* ctx, kcl_value_ref_t* args, kcl_value_ref_t* kwargs);
 
+kcl_value_ref_t* kcl_regex_search_groups(kcl_context_t* ctx, kcl_value_ref_t* args, kcl_value_ref_t* kwargs);
+
 kcl_value_ref_t* kcl_regex_split(kcl_context_t* ctx, kcl_value_ref_t* args, kcl_value_ref_t* kwargs);
 
 kcl_value_ref_t* kcl_runtime_catch(kcl_context_t* ctx, kcl_value_ref_t* args, kcl_value_ref_t* kwargs);
@@ -515,10 +567,34 @@ kcl_eval_scope_t* kcl_scope_new();
 
 void kcl_scope_set(kcl_context_t* _ctx, kcl_eval_scope_t* scope, char* pkg, char* name, kcl_value_ref_t* value);
 
+kcl_value_ref_t* kcl_semver_bump_major(kcl_context_t* ctx, kcl_value_ref_t* args, kcl_value_ref_t* kwargs);
+
+kcl_value_ref_t* kcl_semver_bump_minor(kcl_context_t* ctx, kcl_value_ref_t* args, kcl_value_ref_t* kwargs);
+
+kcl_value_ref_t* kcl_semver_bump_patch(kcl_context_t* ctx, kcl_value_ref_t* args, kcl_value_ref_t* kwargs);
+
+kcl_value_ref_t* kcl_semver_compare(kcl_context_t* ctx, kcl_value_ref_t* args, kcl_value_ref_t* kwargs);
+
+kcl_value_ref_t* kcl_semver_parse(kcl_context_t* ctx, kcl_value_ref_t* args, kcl_value_ref_t* kwargs);
+
+kcl_value_ref_t* kcl_semver_satisfies(kcl_context_t* ctx, kcl_value_ref_t* args, kcl_value_ref_t* kwargs);
+
+kcl_value_ref_t* kcl_semver_validate(kcl_context_t* ctx, kcl_value_ref_t* args, kcl_value_ref_t* kwargs);
+
 kcl_value_ref_t* kcl_template_execute(kcl_context_t* ctx, kcl_value_ref_t* args, kcl_value_ref_t* kwargs);
 
+kcl_value_ref_t* kcl_template_execute_file(kcl_context_t* ctx, kcl_value_ref_t* args, kcl_value_ref_t* kwargs);
+
 kcl_value_ref_t* kcl_template_html_escape(kcl_context_t* ctx, kcl_value_ref_t* args, kcl_value_ref_t* kwargs);
 
+kcl_value_ref_t* kcl_toml_decode(kcl_context_t* ctx, kcl_value_ref_t* args, kcl_value_ref_t* kwargs);
+
+kcl_value_ref_t* kcl_toml_dump_to_file(kcl_context_t* ctx, kcl_value_ref_t* args, kcl_value_ref_t* kwargs);
+
+kcl_value_ref_t* kcl_toml_encode(kcl_context_t* ctx, kcl_value_ref_t* args, kcl_value_ref_t* kwargs);
+
+kcl_value_ref_t* kcl_toml_validate(kcl_context_t* ctx, kcl_value_ref_t* args, kcl_value_ref_t* kwargs);
+
 kcl_value_ref_t* kcl_units_to_G(kcl_context_t* ctx, kcl_value_ref_t* args, kcl_value_ref_t* kwargs);
 
 kcl_value_ref_t* kcl_units_to_Gi(kcl_context_t* ctx, kcl_value_ref_t* args, kcl_value_ref_t* kwargs);
@@ -715,8 +791,6 @@ kcl_value_ref_t* kcl_value_unary_plus(kcl_context_t* ctx, kcl_value_ref_t* a);
 
 kcl_value_ref_t* kcl_value_union(kcl_context_t* ctx, kcl_value_ref_t* schema, kcl_value_ref_t* b);
 
-kcl_value_ref_t* kcl_value_union_all(kcl_context_t* ctx, kcl_value_ref_t* args, kcl_value_ref_t* _kwargs);
-
 kcl_value_ref_t* kcl_yaml_decode(kcl_context_t* ctx, kcl_value_ref_t* args, kcl_value_ref_t* kwargs);
 
 kcl_value_ref_t* kcl_yaml_decode_all(kcl_context_t* ctx, kcl_value_ref_t* args, kcl_value_ref_t* kwargs);
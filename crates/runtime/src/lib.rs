@@ -104,6 +104,9 @@ pub use self::net::*;
 pub mod regex;
 pub use self::regex::*;
 
+pub mod semver;
+pub use self::semver::*;
+
 pub mod stdlib;
 pub use self::stdlib::*;
 
@@ -113,6 +116,9 @@ pub use self::units::*;
 pub mod yaml;
 pub use self::yaml::*;
 
+pub mod toml;
+pub use self::toml::*;
+
 pub mod file;
 pub use self::file::*;
 
@@ -2,8 +2,86 @@
 
 extern crate fancy_regex;
 
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use fancy_regex::{Captures, Regex};
+
 use crate::*;
 
+/// The default number of the compiled patterns kept in the [RegexCache].
+pub const DEFAULT_REGEX_CACHE_CAPACITY: usize = 256;
+
+/// RegexCache keeps the compiled patterns of the regex system module functions in the
+/// runtime context, so a pattern used in a large list comprehension is compiled only once.
+/// The cache is cleared when it is full, and the zero capacity disables the cache.
+#[derive(Clone, Debug)]
+pub struct RegexCache {
+    pub capacity: usize,
+    regexes: HashMap<String, Arc<Regex>>,
+}
+
+impl Default for RegexCache {
+    fn default() -> Self {
+        Self::new(DEFAULT_REGEX_CACHE_CAPACITY)
+    }
+}
+
+impl RegexCache {
+    /// New a regex cache that keeps at most `capacity` compiled patterns.
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            capacity,
+            regexes: HashMap::default(),
+        }
+    }
+
+    /// Returns the compiled regex of the pattern from the cache, or compiles and caches it.
+    pub fn compile(&mut self, pattern: &str) -> Result<Arc<Regex>, fancy_regex::Error> {
+        if let Some(re) = self.regexes.get(pattern) {
+            return Ok(re.clone());
+        }
+        let re = Arc::new(Regex::new(pattern)?);
+        if self.capacity > 0 {
+            if self.regexes.len() >= self.capacity {
+                self.regexes.clear();
+            }
+            self.regexes.insert(pattern.to_string(), re.clone());
+        }
+        Ok(re)
+    }
+
+    /// Returns the number of the cached patterns.
+    pub fn len(&self) -> usize {
+        self.regexes.len()
+    }
+
+    /// Returns whether the cache is empty.
+    pub fn is_empty(&self) -> bool {
+        self.regexes.is_empty()
+    }
+}
+
+fn must_compile_regex(ctx: &mut Context, name: &str, pattern: &str) -> Arc<Regex> {
+    ctx.regex_cache
+        .compile(pattern)
+        .unwrap_or_else(|err| panic!("{name}() invalid regex pattern '{pattern}': {err}"))
+}
+
+/// Returns the named capture groups of the regex match as a dict, and the groups that
+/// don't participate in the match are None.
+fn named_groups(re: &Regex, caps: &Captures) -> ValueRef {
+    let mut groups = ValueRef::dict(None);
+    for name in re.capture_names().flatten() {
+        let value = match caps.name(name) {
+            Some(m) => ValueRef::str(m.as_str()),
+            None => ValueRef::none(),
+        };
+        groups.dict_update_key_value(name, value);
+    }
+    groups
+}
+
 // match(string: str, pattern: str) -> bool:
 
 /// # Safety
@@ -16,11 +94,12 @@ pub unsafe extern "C-unwind" fn kcl_regex_match(
 ) -> *const kcl_value_ref_t {
     let args = unsafe { ptr_as_ref(args) };
     let kwargs = unsafe { ptr_as_ref(kwargs) };
+    let ctx = unsafe { mut_ptr_as_ref(ctx) };
 
     if let Some(string) = get_call_arg_str(args, kwargs, 0, Some("string"))
         && let Some(pattern) = get_call_arg_str(args, kwargs, 1, Some("pattern"))
     {
-        let re = fancy_regex::Regex::new(pattern.as_ref()).unwrap();
+        let re = must_compile_regex(ctx, "match", &pattern);
         match re.is_match(string.as_ref()) {
             Ok(ok) => {
                 if ok {
@@ -53,7 +132,9 @@ pub unsafe extern "C-unwind" fn kcl_regex_replace(
         if let Some(pattern) = get_call_arg_str(args, kwargs, 1, Some("pattern")) {
             if let Some(replace) = get_call_arg_str(args, kwargs, 2, Some("replace")) {
                 let count = get_call_arg_int(args, kwargs, 3, Some("count")).unwrap_or(0);
-                let re = fancy_regex::Regex::new(pattern.as_ref()).unwrap();
+                let re = must_compile_regex(ctx, "replace", &pattern);
+                // The `$name`, `${name}` and `$1` in the replacement are expanded to the
+                // capture groups, and `$$` is a literal `$`.
                 let s = re.replacen(string.as_ref(), count as usize, replace.as_ref() as &str);
                 return ValueRef::str(&s).into_raw(ctx);
             }
@@ -76,9 +157,10 @@ pub unsafe extern "C-unwind" fn kcl_regex_compile(
 ) -> *mut kcl_value_ref_t {
     let args = unsafe { ptr_as_ref(args) };
     let kwargs = unsafe { ptr_as_ref(kwargs) };
+    let ctx = unsafe { mut_ptr_as_ref(ctx) };
 
     if let Some(pattern) = get_call_arg_str(args, kwargs, 0, Some("pattern")) {
-        match fancy_regex::Regex::new(pattern.as_ref()) {
+        match ctx.regex_cache.compile(&pattern) {
             Ok(_) => return unsafe { kcl_value_Bool(ctx, 1) },
             _ => return unsafe { kcl_value_Bool(ctx, 0) },
         }
@@ -103,8 +185,7 @@ pub unsafe extern "C-unwind" fn kcl_regex_findall(
         if let Some(pattern) = get_call_arg_str(args, kwargs, 1, Some("pattern")) {
             let mut list = ValueRef::list(None);
 
-            for x in fancy_regex::Regex::new(pattern.as_ref())
-                .unwrap()
+            for x in must_compile_regex(ctx, "findall", &pattern)
                 .captures_iter(string.as_ref())
                 .flatten()
             {
@@ -139,10 +220,11 @@ pub unsafe extern "C-unwind" fn kcl_regex_search(
 ) -> *mut kcl_value_ref_t {
     let args = unsafe { ptr_as_ref(args) };
     let kwargs = unsafe { ptr_as_ref(kwargs) };
+    let ctx = unsafe { mut_ptr_as_ref(ctx) };
 
     if let Some(string) = get_call_arg_str(args, kwargs, 0, Some("string")) {
         if let Some(pattern) = get_call_arg_str(args, kwargs, 1, Some("pattern")) {
-            let re = fancy_regex::Regex::new(pattern.as_ref()).unwrap();
+            let re = must_compile_regex(ctx, "search", &pattern);
 
             if let Ok(Some(..)) = re.find(string.as_ref()) {
                 return unsafe { kcl_value_Bool(ctx, 1) };
@@ -172,7 +254,7 @@ pub unsafe extern "C-unwind" fn kcl_regex_split(
             let maxsplit = get_call_arg_int(args, kwargs, 2, Some("maxsplit")).unwrap_or(0);
             let mut list = ValueRef::list(None);
 
-            let re = fancy_regex::Regex::new(pattern.as_ref()).unwrap();
+            let re = must_compile_regex(ctx, "split", &pattern);
 
             let mut fields: Vec<String> = Vec::new();
             let mut current_pos = 0;
@@ -201,3 +283,99 @@ pub unsafe extern "C-unwind" fn kcl_regex_split(
     }
     panic!("split() missing 2 required positional arguments: 'string' and 'pattern'");
 }
+
+// search_groups(string: str, pattern: str) -> {str:str}:
+
+/// # Safety
+/// The caller must ensure that `ctx`, `args`, and `kwargs` are valid pointers
+#[unsafe(no_mangle)]
+pub unsafe extern "C-unwind" fn kcl_regex_search_groups(
+    ctx: *mut kcl_context_t,
+    args: *const kcl_value_ref_t,
+    kwargs: *const kcl_value_ref_t,
+) -> *mut kcl_value_ref_t {
+    let args = unsafe { ptr_as_ref(args) };
+    let kwargs = unsafe { ptr_as_ref(kwargs) };
+    let ctx = unsafe { mut_ptr_as_ref(ctx) };
+    if let Some(string) = get_call_arg_str(args, kwargs, 0, Some("string")) {
+        if let Some(pattern) = get_call_arg_str(args, kwargs, 1, Some("pattern")) {
+            let re = must_compile_regex(ctx, "search_groups", &pattern);
+            return match re.captures(string.as_ref()) {
+                Ok(Some(caps)) => named_groups(&re, &caps).into_raw(ctx),
+                Ok(None) => ValueRef::none().into_raw(ctx),
+                Err(err) => {
+                    panic!("search_groups() failed to match the pattern '{pattern}': {err}")
+                }
+            };
+        }
+        panic!("search_groups() missing the required positional argument: 'pattern'");
+    }
+    panic!("search_groups() missing 2 required positional arguments: 'string' and 'pattern'");
+}
+
+// findall_groups(string: str, pattern: str) -> [{str:str}]:
+
+/// # Safety
+/// The caller must ensure that `ctx`, `args`, and `kwargs` are valid pointers
+#[unsafe(no_mangle)]
+pub unsafe extern "C-unwind" fn kcl_regex_findall_groups(
+    ctx: *mut kcl_context_t,
+    args: *const kcl_value_ref_t,
+    kwargs: *const kcl_value_ref_t,
+) -> *mut kcl_value_ref_t {
+    let args = unsafe { ptr_as_ref(args) };
+    let kwargs = unsafe { ptr_as_ref(kwargs) };
+    let ctx = unsafe { mut_ptr_as_ref(ctx) };
+    if let Some(string) = get_call_arg_str(args, kwargs, 0, Some("string")) {
+        if let Some(pattern) = get_call_arg_str(args, kwargs, 1, Some("pattern")) {
+            let re = must_compile_regex(ctx, "findall_groups", &pattern);
+            let mut list = ValueRef::list(None);
+            for caps in re.captures_iter(string.as_ref()) {
+                match caps {
+                    Ok(caps) => list.list_append(&named_groups(&re, &caps)),
+                    Err(err) => {
+                        panic!("findall_groups() failed to match the pattern '{pattern}': {err}")
+                    }
+                }
+            }
+            return list.into_raw(ctx);
+        }
+        panic!("findall_groups() missing the required positional argument: 'pattern'");
+    }
+    panic!("findall_groups() missing 2 required positional arguments: 'string' and 'pattern'");
+}
+
+#[cfg(test)]
+mod test_regex {
+    use super::*;
+
+    #[test]
+    fn test_regex_cache() {
+        let mut cache = RegexCache::new(2);
+        let re = cache.compile("a+").unwrap();
+        assert!(Arc::ptr_eq(&re, &cache.compile("a+").unwrap()));
+        assert!(cache.compile("(").is_err());
+        cache.compile("b+").unwrap();
+        assert_eq!(cache.len(), 2);
+        cache.compile("c+").unwrap();
+        assert_eq!(cache.len(), 1);
+        let mut cache = RegexCache::new(0);
+        cache.compile("a+").unwrap();
+        assert!(cache.is_empty());
+    }
+
+    #[test]
+    fn test_named_groups() {
+        let re = Regex::new(r"(?P<registry>[^/]+)/(?P<name>[^:]+)(:(?P<tag>.+))?").unwrap();
+        let caps = re.captures("ghcr.io/kcl:v1").unwrap().unwrap();
+        assert_eq!(
+            named_groups(&re, &caps).to_json_string(),
+            r#"{"registry": "ghcr.io", "name": "kcl", "tag": "v1"}"#
+        );
+        let caps = re.captures("ghcr.io/kcl").unwrap().unwrap();
+        assert_eq!(
+            named_groups(&re, &caps).to_json_string(),
+            r#"{"registry": "ghcr.io", "name": "kcl", "tag": null}"#
+        );
+    }
+}
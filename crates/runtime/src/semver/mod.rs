@@ -0,0 +1,380 @@
+//! Copyright The KCL Authors. All rights reserved.
+
+extern crate semver;
+
+use std::cmp::Ordering;
+
+use crate::*;
+use semver::{Version, VersionReq};
+
+/// Parse a semantic version string such as `1.2.3`, `1.2.3-alpha.1+build.5`.
+/// A leading `v` or `V` (e.g., `v1.2.3`) is allowed and ignored.
+pub fn parse_version(version: &str) -> Result<Version, semver::Error> {
+    Version::parse(strip_version_prefix(version.trim()))
+}
+
+/// Parse a version range. The range is a `||` separated list of comparator sets,
+/// and the comparators in one set can be separated by commas (`>=1.2, <2`) or
+/// whitespaces (`>=1.2 <2`). Hyphen ranges (`1.2.3 - 2.3.4`) are also supported.
+pub fn parse_version_range(range: &str) -> Result<Vec<VersionReq>, semver::Error> {
+    range
+        .split("||")
+        .map(|set| VersionReq::parse(&normalize_comparator_set(set)))
+        .collect()
+}
+
+/// Compare two versions by the semantic versioning precedence, the build
+/// metadata is ignored.
+pub fn compare_versions(lhs: &Version, rhs: &Version) -> Ordering {
+    (lhs.major, lhs.minor, lhs.patch, &lhs.pre).cmp(&(rhs.major, rhs.minor, rhs.patch, &rhs.pre))
+}
+
+/// Whether the version satisfies any of the version requirements.
+pub fn version_satisfies(version: &Version, ranges: &[VersionReq]) -> bool {
+    ranges.iter().any(|req| req.matches(version))
+}
+
+/// Increment the major version. A pre-release of a major version
+/// (e.g., `2.0.0-rc.1`) is bumped to its release version.
+pub fn bump_major(version: &Version) -> Version {
+    let major = if !version.pre.is_empty() && version.minor == 0 && version.patch == 0 {
+        version.major
+    } else {
+        version.major + 1
+    };
+    Version::new(major, 0, 0)
+}
+
+/// Increment the minor version. A pre-release of a minor version
+/// (e.g., `1.2.0-rc.1`) is bumped to its release version.
+pub fn bump_minor(version: &Version) -> Version {
+    let minor = if !version.pre.is_empty() && version.patch == 0 {
+        version.minor
+    } else {
+        version.minor + 1
+    };
+    Version::new(version.major, minor, 0)
+}
+
+/// Increment the patch version. A pre-release version (e.g., `1.2.3-rc.1`)
+/// is bumped to its release version.
+pub fn bump_patch(version: &Version) -> Version {
+    let patch = if !version.pre.is_empty() {
+        version.patch
+    } else {
+        version.patch + 1
+    };
+    Version::new(version.major, version.minor, patch)
+}
+
+fn strip_version_prefix(version: &str) -> &str {
+    match version.strip_prefix(['v', 'V']) {
+        Some(rest) if rest.starts_with(|c: char| c.is_ascii_digit()) => rest,
+        _ => version,
+    }
+}
+
+/// Convert a comparator set to the comma separated form.
+fn normalize_comparator_set(set: &str) -> String {
+    if let Some((lower, upper)) = set.split_once(" - ") {
+        return format!(
+            ">={}, <={}",
+            strip_version_prefix(lower.trim()),
+            strip_version_prefix(upper.trim())
+        );
+    }
+    let mut comparators: Vec<String> = vec![];
+    let mut op = String::new();
+    for token in set.split(|c: char| c.is_whitespace() || c == ',') {
+        if token.is_empty() {
+            continue;
+        }
+        let version_start = token
+            .find(|c: char| !matches!(c, '<' | '>' | '=' | '~' | '^'))
+            .unwrap_or(token.len());
+        let (token_op, version) = token.split_at(version_start);
+        op.push_str(token_op);
+        if !version.is_empty() {
+            comparators.push(format!("{}{}", op, strip_version_prefix(version)));
+            op.clear();
+        }
+    }
+    if comparators.is_empty() {
+        "*".to_string()
+    } else {
+        comparators.join(", ")
+    }
+}
+
+fn must_parse_version(version: &str) -> Version {
+    parse_version(version)
+        .unwrap_or_else(|err| panic!("invalid semantic version '{}': {}", version, err))
+}
+
+// parse(version: str) -> {str:}
+
+/// # Safety
+/// The caller must ensure that `ctx`, `args`, and `kwargs` are valid pointers
+#[unsafe(no_mangle)]
+pub unsafe extern "C-unwind" fn kcl_semver_parse(
+    ctx: *mut kcl_context_t,
+    args: *const kcl_value_ref_t,
+    kwargs: *const kcl_value_ref_t,
+) -> *const kcl_value_ref_t {
+    let args = unsafe { ptr_as_ref(args) };
+    let kwargs = unsafe { ptr_as_ref(kwargs) };
+    let ctx = unsafe { mut_ptr_as_ref(ctx) };
+
+    if let Some(version) = get_call_arg_str(args, kwargs, 0, Some("version")) {
+        let version = must_parse_version(&version);
+        let Version {
+            major,
+            minor,
+            patch,
+            pre,
+            build,
+        } = version;
+        return ValueRef::dict(Some(&[
+            ("major", &ValueRef::int(major as i64)),
+            ("minor", &ValueRef::int(minor as i64)),
+            ("patch", &ValueRef::int(patch as i64)),
+            ("pre", &ValueRef::str(pre.as_str())),
+            ("build", &ValueRef::str(build.as_str())),
+        ]))
+        .into_raw(ctx);
+    }
+    panic!("parse() missing 1 required positional argument: 'version'")
+}
+
+// validate(version: str) -> bool
+
+/// # Safety
+/// The caller must ensure that `ctx`, `args`, and `kwargs` are valid pointers
+#[unsafe(no_mangle)]
+pub unsafe extern "C-unwind" fn kcl_semver_validate(
+    ctx: *mut kcl_context_t,
+    args: *const kcl_value_ref_t,
+    kwargs: *const kcl_value_ref_t,
+) -> *const kcl_value_ref_t {
+    let args = unsafe { ptr_as_ref(args) };
+    let kwargs = unsafe { ptr_as_ref(kwargs) };
+    let ctx = unsafe { mut_ptr_as_ref(ctx) };
+
+    if let Some(version) = get_call_arg_str(args, kwargs, 0, Some("version")) {
+        return ValueRef::bool(parse_version(&version).is_ok()).into_raw(ctx);
+    }
+    panic!("validate() missing 1 required positional argument: 'version'")
+}
+
+// compare(v1: str, v2: str) -> int
+
+/// # Safety
+/// The caller must ensure that `ctx`, `args`, and `kwargs` are valid pointers
+#[unsafe(no_mangle)]
+pub unsafe extern "C-unwind" fn kcl_semver_compare(
+    ctx: *mut kcl_context_t,
+    args: *const kcl_value_ref_t,
+    kwargs: *const kcl_value_ref_t,
+) -> *const kcl_value_ref_t {
+    let args = unsafe { ptr_as_ref(args) };
+    let kwargs = unsafe { ptr_as_ref(kwargs) };
+    let ctx = unsafe { mut_ptr_as_ref(ctx) };
+
+    if let Some(v1) = get_call_arg_str(args, kwargs, 0, Some("v1"))
+        && let Some(v2) = get_call_arg_str(args, kwargs, 1, Some("v2"))
+    {
+        let result = match compare_versions(&must_parse_version(&v1), &must_parse_version(&v2)) {
+            Ordering::Less => -1,
+            Ordering::Equal => 0,
+            Ordering::Greater => 1,
+        };
+        return ValueRef::int(result).into_raw(ctx);
+    }
+    panic!("compare() missing 2 required positional arguments: 'v1' and 'v2'")
+}
+
+// satisfies(version: str, range: str) -> bool
+
+/// # Safety
+/// The caller must ensure that `ctx`, `args`, and `kwargs` are valid pointers
+#[unsafe(no_mangle)]
+pub unsafe extern "C-unwind" fn kcl_semver_satisfies(
+    ctx: *mut kcl_context_t,
+    args: *const kcl_value_ref_t,
+    kwargs: *const kcl_value_ref_t,
+) -> *const kcl_value_ref_t {
+    let args = unsafe { ptr_as_ref(args) };
+    let kwargs = unsafe { ptr_as_ref(kwargs) };
+    let ctx = unsafe { mut_ptr_as_ref(ctx) };
+
+    if let Some(version) = get_call_arg_str(args, kwargs, 0, Some("version"))
+        && let Some(range) = get_call_arg_str(args, kwargs, 1, Some("range"))
+    {
+        let version = must_parse_version(&version);
+        let ranges = parse_version_range(&range)
+            .unwrap_or_else(|err| panic!("invalid version range '{}': {}", range, err));
+        return ValueRef::bool(version_satisfies(&version, &ranges)).into_raw(ctx);
+    }
+    panic!("satisfies() missing 2 required positional arguments: 'version' and 'range'")
+}
+
+// bump_major(version: str) -> str
+
+/// # Safety
+/// The caller must ensure that `ctx`, `args`, and `kwargs` are valid pointers
+#[unsafe(no_mangle)]
+pub unsafe extern "C-unwind" fn kcl_semver_bump_major(
+    ctx: *mut kcl_context_t,
+    args: *const kcl_value_ref_t,
+    kwargs: *const kcl_value_ref_t,
+) -> *const kcl_value_ref_t {
+    let args = unsafe { ptr_as_ref(args) };
+    let kwargs = unsafe { ptr_as_ref(kwargs) };
+    let ctx = unsafe { mut_ptr_as_ref(ctx) };
+
+    if let Some(version) = get_call_arg_str(args, kwargs, 0, Some("version")) {
+        let version = bump_major(&must_parse_version(&version));
+        return ValueRef::str(&version.to_string()).into_raw(ctx);
+    }
+    panic!("bump_major() missing 1 required positional argument: 'version'")
+}
+
+// bump_minor(version: str) -> str
+
+/// # Safety
+/// The caller must ensure that `ctx`, `args`, and `kwargs` are valid pointers
+#[unsafe(no_mangle)]
+pub unsafe extern "C-unwind" fn kcl_semver_bump_minor(
+    ctx: *mut kcl_context_t,
+    args: *const kcl_value_ref_t,
+    kwargs: *const kcl_value_ref_t,
+) -> *const kcl_value_ref_t {
+    let args = unsafe { ptr_as_ref(args) };
+    let kwargs = unsafe { ptr_as_ref(kwargs) };
+    let ctx = unsafe { mut_ptr_as_ref(ctx) };
+
+    if let Some(version) = get_call_arg_str(args, kwargs, 0, Some("version")) {
+        let version = bump_minor(&must_parse_version(&version));
+        return ValueRef::str(&version.to_string()).into_raw(ctx);
+    }
+    panic!("bump_minor() missing 1 required positional argument: 'version'")
+}
+
+// bump_patch(version: str) -> str
+
+/// # Safety
+/// The caller must ensure that `ctx`, `args`, and `kwargs` are valid pointers
+#[unsafe(no_mangle)]
+pub unsafe extern "C-unwind" fn kcl_semver_bump_patch(
+    ctx: *mut kcl_context_t,
+    args: *const kcl_value_ref_t,
+    kwargs: *const kcl_value_ref_t,
+) -> *const kcl_value_ref_t {
+    let args = unsafe { ptr_as_ref(args) };
+    let kwargs = unsafe { ptr_as_ref(kwargs) };
+    let ctx = unsafe { mut_ptr_as_ref(ctx) };
+
+    if let Some(version) = get_call_arg_str(args, kwargs, 0, Some("version")) {
+        let version = bump_patch(&must_parse_version(&version));
+        return ValueRef::str(&version.to_string()).into_raw(ctx);
+    }
+    panic!("bump_patch() missing 1 required positional argument: 'version'")
+}
+
+#[cfg(test)]
+mod test_semver {
+    use super::*;
+
+    #[test]
+    fn test_parse_version() {
+        let cases = [
+            ("1.2.3", Some((1, 2, 3, "", ""))),
+            ("v1.2.3", Some((1, 2, 3, "", ""))),
+            (
+                "1.2.3-alpha.1+build.5",
+                Some((1, 2, 3, "alpha.1", "build.5")),
+            ),
+            (" 0.0.1 ", Some((0, 0, 1, "", ""))),
+            ("1.2", None),
+            ("version", None),
+            ("v", None),
+        ];
+        for (version, expected) in cases {
+            let result = parse_version(version).ok().map(|v| {
+                (
+                    v.major,
+                    v.minor,
+                    v.patch,
+                    v.pre.as_str().to_string(),
+                    v.build.as_str().to_string(),
+                )
+            });
+            let expected = expected.map(|(major, minor, patch, pre, build)| {
+                (major, minor, patch, pre.to_string(), build.to_string())
+            });
+            assert_eq!(result, expected, "{version}");
+        }
+    }
+
+    #[test]
+    fn test_compare_versions() {
+        let cases = [
+            ("1.2.3", "1.2.3", Ordering::Equal),
+            ("1.2.3", "1.10.0", Ordering::Less),
+            ("2.0.0", "1.99.99", Ordering::Greater),
+            ("1.0.0-alpha", "1.0.0", Ordering::Less),
+            ("1.0.0-alpha.2", "1.0.0-alpha.10", Ordering::Less),
+            ("1.0.0+build.1", "1.0.0+build.2", Ordering::Equal),
+        ];
+        for (lhs, rhs, expected) in cases {
+            let result =
+                compare_versions(&parse_version(lhs).unwrap(), &parse_version(rhs).unwrap());
+            assert_eq!(result, expected, "{lhs} vs {rhs}");
+        }
+    }
+
+    #[test]
+    fn test_version_satisfies() {
+        let cases = [
+            ("1.2.3", "^1.2", true),
+            ("2.0.0", "^1.2", false),
+            ("1.2.3", ">=1.2.0, <1.3.0", true),
+            ("1.2.3", ">=1.2.0 <1.2.3", false),
+            ("1.2.3", ">= 1.2.0 < 2", true),
+            ("3.1.0", "^1 || ^3", true),
+            ("1.5.0", "1.2.3 - 1.5.0", true),
+            ("1.5.1", "1.2.3 - 1.5.0", false),
+            ("1.2.3", "~1.2", true),
+            ("1.3.0", "~1.2", false),
+            ("v1.2.3", ">=v1.0.0", true),
+            ("1.2.3", "*", true),
+            ("1.2.3", "", true),
+            ("1.2.3-rc.1", ">=1.0.0", false),
+            ("1.2.3-rc.1", ">=1.2.3-rc.0", true),
+        ];
+        for (version, range, expected) in cases {
+            let result = version_satisfies(
+                &parse_version(version).unwrap(),
+                &parse_version_range(range).unwrap(),
+            );
+            assert_eq!(result, expected, "{version} satisfies {range}");
+        }
+        assert!(parse_version_range(">>1.0").is_err());
+    }
+
+    #[test]
+    fn test_bump_version() {
+        let cases = [
+            ("1.2.3", "2.0.0", "1.3.0", "1.2.4"),
+            ("1.2.3-rc.1+build", "2.0.0", "1.3.0", "1.2.3"),
+            ("1.2.0-rc.1", "2.0.0", "1.2.0", "1.2.0"),
+            ("2.0.0-rc.1", "2.0.0", "2.0.0", "2.0.0"),
+        ];
+        for (version, major, minor, patch) in cases {
+            let version = parse_version(version).unwrap();
+            assert_eq!(bump_major(&version).to_string(), major);
+            assert_eq!(bump_minor(&version).to_string(), minor);
+            assert_eq!(bump_patch(&version).to_string(), patch);
+        }
+    }
+}
@@ -1,11 +1,36 @@
-use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
 
 use crate::*;
 use handlebars::{
-    Context, Handlebars, Helper, HelperResult, Output, RenderContext, RenderErrorReason,
-    html_escape,
+    Context, Handlebars, Helper, HelperDef, HelperResult, Output, RenderContext, RenderError,
+    RenderErrorReason, ScopedJson, html_escape,
 };
 
+/// The template engine of the template system module functions.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum TemplateEngine {
+    /// Handlebars templates, see https://handlebarsjs.com/.
+    #[default]
+    Handlebars,
+    /// Jinja2 compatible templates rendered by MiniJinja, see https://docs.rs/minijinja.
+    Jinja,
+}
+
+impl FromStr for TemplateEngine {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "handlebars" => Ok(TemplateEngine::Handlebars),
+            "jinja" => Ok(TemplateEngine::Jinja),
+            _ => Err(format!(
+                "invalid template engine '{s}', expected 'handlebars' or 'jinja'"
+            )),
+        }
+    }
+}
+
 /// Custom helper that renders a value without HTML escaping.
 /// Usage: {{raw var}}
 fn raw_helper(
@@ -29,6 +54,286 @@ fn raw_helper(
     Ok(())
 }
 
+/// A helper that computes a value from its parameters. The value is written without HTML
+/// escaping like [raw_helper], and is passed to the outer helper in subexpressions
+/// e.g., `{{indent (toYaml spec) 2}}`.
+struct ValueHelper(fn(&Helper) -> Result<serde_json::Value, RenderError>);
+
+impl HelperDef for ValueHelper {
+    fn call_inner<'reg: 'rc, 'rc>(
+        &self,
+        h: &Helper<'rc>,
+        _: &'reg Handlebars<'reg>,
+        _: &'rc Context,
+        _: &mut RenderContext<'reg, 'rc>,
+    ) -> Result<ScopedJson<'rc>, RenderError> {
+        Ok(ScopedJson::Derived((self.0)(h)?))
+    }
+
+    fn call<'reg: 'rc, 'rc>(
+        &self,
+        h: &Helper<'rc>,
+        _: &'reg Handlebars<'reg>,
+        _: &'rc Context,
+        _: &mut RenderContext<'reg, 'rc>,
+        out: &mut dyn Output,
+    ) -> HelperResult {
+        out.write(&json_text(&(self.0)(h)?))?;
+        Ok(())
+    }
+}
+
+/// Usage: {{indent text 2}}
+fn indent_helper(h: &Helper) -> Result<serde_json::Value, RenderError> {
+    let text = json_text(helper_param(h, "indent", 0)?);
+    let width =
+        helper_param(h, "indent", 1)?
+            .as_u64()
+            .ok_or(RenderErrorReason::InvalidParamType(
+                "the indent width must be a non-negative integer",
+            ))?;
+    Ok(serde_json::Value::String(indent(&text, width as usize)))
+}
+
+/// Usage: {{toYaml value}}
+fn to_yaml_helper(h: &Helper) -> Result<serde_json::Value, RenderError> {
+    let value = helper_param(h, "toYaml", 0)?;
+    let yaml =
+        serde_yaml::to_string(value).map_err(|err| RenderErrorReason::Other(err.to_string()))?;
+    Ok(serde_json::Value::String(yaml.trim_end().to_string()))
+}
+
+/// Usage: {{toJson value}}
+fn to_json_helper(h: &Helper) -> Result<serde_json::Value, RenderError> {
+    let value = helper_param(h, "toJson", 0)?;
+    let json =
+        serde_json::to_string(value).map_err(|err| RenderErrorReason::Other(err.to_string()))?;
+    Ok(serde_json::Value::String(json))
+}
+
+/// Usage: {{default value "fallback"}}, the fallback is used when the value is missing,
+/// None or an empty string, list or dict.
+fn default_helper(h: &Helper) -> Result<serde_json::Value, RenderError> {
+    let fallback = helper_param(h, "default", 1)?;
+    let value = match h.param(0) {
+        Some(param) if !param.is_value_missing() => param.value(),
+        _ => return Ok(fallback.clone()),
+    };
+    let is_empty = match value {
+        serde_json::Value::Null => true,
+        serde_json::Value::String(s) => s.is_empty(),
+        serde_json::Value::Array(a) => a.is_empty(),
+        serde_json::Value::Object(o) => o.is_empty(),
+        _ => false,
+    };
+    Ok(if is_empty { fallback } else { value }.clone())
+}
+
+/// Usage: {{upper text}}
+fn upper_helper(h: &Helper) -> Result<serde_json::Value, RenderError> {
+    let text = json_text(helper_param(h, "upper", 0)?);
+    Ok(serde_json::Value::String(text.to_uppercase()))
+}
+
+fn helper_param<'a>(
+    h: &'a Helper,
+    name: &'static str,
+    index: usize,
+) -> Result<&'a serde_json::Value, RenderError> {
+    h.param(index)
+        .map(|param| param.value())
+        .ok_or_else(|| RenderErrorReason::ParamNotFoundForIndex(name, index).into())
+}
+
+/// Returns the text of a JSON value, strings are not quoted and null is empty.
+fn json_text(value: &serde_json::Value) -> String {
+    match value {
+        serde_json::Value::Null => String::new(),
+        serde_json::Value::String(s) => s.clone(),
+        _ => value.to_string(),
+    }
+}
+
+/// Indent the non-empty lines of the text with `width` spaces.
+fn indent(text: &str, width: usize) -> String {
+    let pad = " ".repeat(width);
+    text.split('\n')
+        .map(|line| {
+            if line.is_empty() {
+                line.to_string()
+            } else {
+                format!("{pad}{line}")
+            }
+        })
+        .collect::<Vec<String>>()
+        .join("\n")
+}
+
+/// Returns a Handlebars registry with the builtin helpers `raw`, `indent`, `toYaml`,
+/// `toJson`, `default` and `upper`.
+fn new_handlebars() -> Handlebars<'static> {
+    let mut handlebars = Handlebars::new();
+    // Register helper for raw (unescaped) output
+    handlebars.register_helper("raw", Box::new(raw_helper));
+    handlebars.register_helper("indent", Box::new(ValueHelper(indent_helper)));
+    handlebars.register_helper("toYaml", Box::new(ValueHelper(to_yaml_helper)));
+    handlebars.register_helper("toJson", Box::new(ValueHelper(to_json_helper)));
+    handlebars.register_helper("default", Box::new(ValueHelper(default_helper)));
+    handlebars.register_helper("upper", Box::new(ValueHelper(upper_helper)));
+    handlebars
+}
+
+/// Render the template named `name` with the data and the partial templates, and returns
+/// the error message with the template location on failure.
+pub fn render_template(
+    engine: TemplateEngine,
+    name: &str,
+    template: &str,
+    data: &ValueRef,
+    partials: &[(String, String)],
+) -> Result<String, String> {
+    let data = data.build_json(&Default::default());
+    match engine {
+        TemplateEngine::Handlebars => {
+            let mut handlebars = new_handlebars();
+            for (partial_name, partial) in partials {
+                handlebars
+                    .register_partial(partial_name, partial)
+                    .map_err(|err| template_error(partial_name, err.pos(), err.reason()))?;
+            }
+            handlebars
+                .register_template_string(name, template)
+                .map_err(|err| template_error(name, err.pos(), err.reason()))?;
+            handlebars.render(name, &data).map_err(|err| {
+                let pos = err.line_no.zip(err.column_no);
+                template_error(
+                    err.template_name.as_deref().unwrap_or(name),
+                    pos,
+                    err.reason(),
+                )
+            })
+        }
+        TemplateEngine::Jinja => {
+            let mut env = minijinja::Environment::new();
+            // Keep the trailing newline like Handlebars.
+            env.set_keep_trailing_newline(true);
+            env.add_filter("toYaml", |value: minijinja::Value| {
+                serde_yaml::to_string(&value)
+                    .map(|yaml| yaml.trim_end().to_string())
+                    .map_err(|err| {
+                        minijinja::Error::new(
+                            minijinja::ErrorKind::InvalidOperation,
+                            err.to_string(),
+                        )
+                    })
+            });
+            env.add_filter("toJson", |value: minijinja::Value| {
+                serde_json::to_string(&value).map_err(|err| {
+                    minijinja::Error::new(minijinja::ErrorKind::InvalidOperation, err.to_string())
+                })
+            });
+            for (partial_name, partial) in partials {
+                env.add_template_owned(partial_name.clone(), partial.clone())
+                    .map_err(|err| jinja_error(&err, partial_name))?;
+            }
+            env.add_template_owned(name.to_string(), template.to_string())
+                .map_err(|err| jinja_error(&err, name))?;
+            env.get_template(name)
+                .and_then(|template| template.render(&data))
+                .map_err(|err| jinja_error(&err, name))
+        }
+    }
+}
+
+fn jinja_error(err: &minijinja::Error, name: &str) -> String {
+    // The column is computed from the byte range of the error in the template source.
+    let column = match (err.range(), err.template_source()) {
+        (Some(range), Some(source)) => source
+            .get(..range.start)
+            .and_then(|s| s.rsplit('\n').next())
+            .map_or(1, |s| s.chars().count() + 1),
+        _ => 1,
+    };
+    let reason = match err.detail() {
+        Some(detail) => format!("{}: {}", err.kind(), detail),
+        None => err.kind().to_string(),
+    };
+    let pos = err.line().map(|line| (line, column));
+    template_error(err.name().unwrap_or(name), pos, reason)
+}
+
+fn template_error(
+    name: &str,
+    pos: Option<(usize, usize)>,
+    reason: impl std::fmt::Display,
+) -> String {
+    match pos {
+        Some((line, column)) => {
+            format!("template error in '{name}' at line {line}, column {column}: {reason}")
+        }
+        None => format!("template error in '{name}': {reason}"),
+    }
+}
+
+/// Resolve the template file path relative to the directory of the KCL file that is
+/// being executed.
+fn resolve_template_path(ctx: &crate::Context, filepath: &str) -> PathBuf {
+    let path = Path::new(filepath);
+    if path.is_absolute() {
+        return path.to_path_buf();
+    }
+    match Path::new(&ctx.panic_info.kcl_file).parent() {
+        Some(dir) => dir.join(path),
+        None => path.to_path_buf(),
+    }
+}
+
+/// Returns the data, partials and engine arguments starting from the argument `index`.
+fn get_render_args(
+    name: &str,
+    args: &ValueRef,
+    kwargs: &ValueRef,
+    index: usize,
+) -> (ValueRef, Vec<(String, String)>, TemplateEngine) {
+    let data = get_call_arg(args, kwargs, index, Some("data"))
+        .filter(|v| !v.is_none_or_undefined())
+        .unwrap_or(ValueRef::dict(None));
+    if !data.is_config() {
+        panic!(
+            "{name}() argument 'data' must be a dict, not '{}'",
+            data.type_str()
+        );
+    }
+    let partials = match get_call_arg(args, kwargs, index + 1, Some("partials"))
+        .filter(|v| !v.is_none_or_undefined())
+    {
+        Some(partials) if partials.is_config() => partials
+            .as_dict_ref()
+            .values
+            .iter()
+            .map(|(k, v)| {
+                if v.is_str() {
+                    (k.to_string(), v.as_str())
+                } else {
+                    panic!("{name}() the partial '{k}' must be a template string")
+                }
+            })
+            .collect(),
+        Some(partials) => panic!(
+            "{name}() argument 'partials' must be a dict, not '{}'",
+            partials.type_str()
+        ),
+        None => vec![],
+    };
+    let engine = match get_call_arg_str(args, kwargs, index + 2, Some("engine")) {
+        Some(engine) => engine
+            .parse::<TemplateEngine>()
+            .unwrap_or_else(|err| panic!("{name}() {err}")),
+        None => TemplateEngine::default(),
+    };
+    (data, partials, engine)
+}
+
 /// Applies a parsed template to the specified data object and
 /// returns the string output.
 /// # Safety
@@ -44,27 +349,45 @@ pub unsafe extern "C-unwind" fn kcl_template_execute(
     let ctx = unsafe { mut_ptr_as_ref(ctx) };
 
     if let Some(template) = get_call_arg_str(args, kwargs, 0, Some("template")) {
-        let mut handlebars = Handlebars::new();
+        let (data, partials, engine) = get_render_args("execute", args, kwargs, 1);
+        return match render_template(engine, "template", &template, &data, &partials) {
+            Ok(result) => ValueRef::str(&result).into_raw(ctx),
+            Err(err) => panic!("{err}"),
+        };
+    }
+    panic!("execute() takes exactly one argument (0 given)");
+}
 
-        // Register helper for raw (unescaped) output
-        handlebars.register_helper("raw", Box::new(raw_helper));
+/// Applies the template in the file to the specified data object and returns the
+/// string output. The relative file path is resolved from the directory of the KCL file.
+/// # Safety
+/// The caller must ensure that `ctx`, `args`, and `kwargs` are valid pointers.
+#[unsafe(no_mangle)]
+pub unsafe extern "C-unwind" fn kcl_template_execute_file(
+    ctx: *mut kcl_context_t,
+    args: *const kcl_value_ref_t,
+    kwargs: *const kcl_value_ref_t,
+) -> *const kcl_value_ref_t {
+    let args = unsafe { ptr_as_ref(args) };
+    let kwargs = unsafe { ptr_as_ref(kwargs) };
+    let ctx = unsafe { mut_ptr_as_ref(ctx) };
 
-        handlebars
-            .register_template_string("template", template)
-            .expect("register template failed");
-        let data = get_call_arg(args, kwargs, 1, Some("data")).unwrap_or(ValueRef::dict(None));
-        let data: HashMap<String, JsonValue> = HashMap::from_iter(
-            data.as_dict_ref()
-                .values
-                .iter()
-                .map(|(k, v)| (k.to_string(), v.build_json(&Default::default()))),
-        );
-        let result = handlebars
-            .render("template", &data)
-            .expect("render template failed");
-        return ValueRef::str(&result).into_raw(ctx);
+    if let Some(filepath) = get_call_arg_str(args, kwargs, 0, Some("filepath")) {
+        let (data, partials, engine) = get_render_args("execute_file", args, kwargs, 1);
+        let path = resolve_template_path(ctx, &filepath);
+        let template = std::fs::read_to_string(&path).unwrap_or_else(|err| {
+            panic!(
+                "failed to access the template file '{}': {}",
+                path.display(),
+                err
+            )
+        });
+        return match render_template(engine, &filepath, &template, &data, &partials) {
+            Ok(result) => ValueRef::str(&result).into_raw(ctx),
+            Err(err) => panic!("{err}"),
+        };
     }
-    panic!("execute() takes exactly one argument (0 given)");
+    panic!("execute_file() missing the required positional argument: 'filepath'");
 }
 
 /// Replaces the characters `&"<>` with the equivalent html / xml entities.
@@ -219,4 +542,69 @@ mod tests {
             r#"normal: &quot;test&quot;, raw: "test", triple: "test""#
         );
     }
+
+    fn render(engine: TemplateEngine, template: &str, data: &str) -> Result<String, String> {
+        let mut ctx = crate::Context::new();
+        let data = ValueRef::from_json(&mut ctx, data).unwrap();
+        let partials = [("item".to_string(), "- {{ name }}".to_string())];
+        render_template(engine, "test", template, &data, &partials)
+    }
+
+    #[test]
+    fn test_builtin_helpers() {
+        let data = r#"{"name": "app", "spec": {"replicas": 2, "ports": [80]}, "empty": ""}"#;
+        let cases = [
+            ("{{upper name}}", "APP"),
+            (
+                "{{default empty \"none\"}} {{default missing 1}} {{default name 1}}",
+                "none 1 app",
+            ),
+            ("{{toJson spec}}", r#"{"ports":[80],"replicas":2}"#),
+            (
+                "spec:\n{{indent (toYaml spec) 2}}",
+                "spec:\n  ports:\n  - 80\n  replicas: 2",
+            ),
+            ("{{> item}}", "- app"),
+        ];
+        for (template, expected) in cases {
+            assert_eq!(
+                render(TemplateEngine::Handlebars, template, data).unwrap(),
+                expected
+            );
+        }
+    }
+
+    #[test]
+    fn test_jinja_engine() {
+        let data = r#"{"name": "app", "spec": {"replicas": 2}}"#;
+        assert_eq!(
+            render(
+                TemplateEngine::Jinja,
+                "{{ name | upper }} {{ spec | toJson }}\n",
+                data
+            )
+            .unwrap(),
+            "APP {\"replicas\":2}\n"
+        );
+        assert_eq!(
+            render(TemplateEngine::Jinja, "{% include 'item' %}", data).unwrap(),
+            "- app"
+        );
+    }
+
+    #[test]
+    fn test_template_errors() {
+        assert!(
+            render(TemplateEngine::Handlebars, "a\n{{#if name}}", "{}")
+                .unwrap_err()
+                .starts_with(
+                    "template error in 'test' at line 2, column 13: invalid handlebars syntax"
+                )
+        );
+        assert!(
+            render(TemplateEngine::Jinja, "a\n  {{ name | unknown }}", "{}")
+                .unwrap_err()
+                .starts_with("template error in 'test' at line 2, column 13: unknown filter")
+        );
+    }
 }
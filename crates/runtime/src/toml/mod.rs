@@ -0,0 +1,111 @@
+//! Copyright The KCL Authors. All rights reserved.
+use crate::*;
+
+/// encode(data, sort_keys=False, ignore_private=False, ignore_none=False)
+/// # Safety
+/// The caller must ensure that `ctx`, `args`, and `kwargs` are valid pointers
+#[unsafe(no_mangle)]
+pub unsafe extern "C-unwind" fn kcl_toml_encode(
+    ctx: *mut kcl_context_t,
+    args: *const kcl_value_ref_t,
+    kwargs: *const kcl_value_ref_t,
+) -> *const kcl_value_ref_t {
+    let args = unsafe { ptr_as_ref(args) };
+    let kwargs = unsafe { ptr_as_ref(kwargs) };
+
+    if let Some(arg0) = get_call_arg(args, kwargs, 0, Some("data")) {
+        match arg0.to_toml_string_with_options(&args_to_opts(args, kwargs, 1)) {
+            Ok(s) => return ValueRef::str(&s).into_raw(unsafe { mut_ptr_as_ref(ctx) }),
+            Err(err) => panic!("{}", err),
+        }
+    }
+    panic!("encode() missing 1 required positional argument: 'data'")
+}
+
+/// decode(value)
+/// # Safety
+/// The caller must ensure that `ctx`, `args`, and `kwargs` are valid pointers
+#[unsafe(no_mangle)]
+pub unsafe extern "C-unwind" fn kcl_toml_decode(
+    ctx: *mut kcl_context_t,
+    args: *const kcl_value_ref_t,
+    kwargs: *const kcl_value_ref_t,
+) -> *const kcl_value_ref_t {
+    let args = unsafe { ptr_as_ref(args) };
+    let kwargs = unsafe { ptr_as_ref(kwargs) };
+    let ctx = unsafe { mut_ptr_as_ref(ctx) };
+
+    if let Some(arg0) = get_call_arg(args, kwargs, 0, Some("value")) {
+        match ValueRef::from_toml(ctx, arg0.as_str().as_ref()) {
+            Ok(x) => return x.into_raw(ctx),
+            Err(err) => panic!("{}", err),
+        }
+    }
+    panic!("decode() missing 1 required positional argument: 'value'")
+}
+
+/// dump_to_file(data, filename, sort_keys=False, ignore_private=False, ignore_none=False)
+/// # Safety
+/// The caller must ensure that `ctx`, `args`, and `kwargs` are valid pointers
+#[unsafe(no_mangle)]
+pub unsafe extern "C-unwind" fn kcl_toml_dump_to_file(
+    ctx: *mut kcl_context_t,
+    args: *const kcl_value_ref_t,
+    kwargs: *const kcl_value_ref_t,
+) -> *const kcl_value_ref_t {
+    let args = unsafe { ptr_as_ref(args) };
+    let kwargs = unsafe { ptr_as_ref(kwargs) };
+    let data = args.arg_i(0).or(kwargs.get_by_key("data"));
+    let filename = args.arg_i(1).or(kwargs.get_by_key("filename"));
+    match (data, filename) {
+        (Some(data), Some(filename)) => {
+            let filename = filename.as_str();
+            let toml = data
+                .to_toml_string_with_options(&args_to_opts(args, kwargs, 2))
+                .unwrap_or_else(|e| panic!("{}", e));
+            std::fs::write(&filename, toml)
+                .unwrap_or_else(|e| panic!("Unable to write file '{}': {}", filename, e));
+            unsafe { kcl_value_Undefined(ctx) }
+        }
+        _ => {
+            panic!("dump_to_file() missing 2 required positional arguments: 'data' and 'filename'")
+        }
+    }
+}
+
+/// validate(value: str) -> bool
+/// # Safety
+/// The caller must ensure that `ctx`, `args`, and `kwargs` are valid pointers
+#[unsafe(no_mangle)]
+pub unsafe extern "C-unwind" fn kcl_toml_validate(
+    ctx: *mut kcl_context_t,
+    args: *const kcl_value_ref_t,
+    kwargs: *const kcl_value_ref_t,
+) -> *const kcl_value_ref_t {
+    let args = unsafe { ptr_as_ref(args) };
+    let kwargs = unsafe { ptr_as_ref(kwargs) };
+    let ctx = unsafe { mut_ptr_as_ref(ctx) };
+
+    if let Some(arg0) = get_call_arg(args, kwargs, 0, Some("value")) {
+        match ValueRef::from_toml(ctx, arg0.as_str().as_ref()) {
+            Ok(_) => return unsafe { kcl_value_True(ctx) },
+            Err(_) => return unsafe { kcl_value_False(ctx) },
+        }
+    }
+    panic!("validate() missing 1 required positional argument: 'value'")
+}
+
+fn args_to_opts(args: &ValueRef, kwargs: &ValueRef, index: usize) -> TomlEncodeOptions {
+    let mut opts = TomlEncodeOptions::default();
+    if let Some(sort_keys) = get_call_arg_bool(args, kwargs, index, Some("sort_keys")) {
+        opts.sort_keys = sort_keys;
+    }
+    if let Some(ignore_private) = get_call_arg_bool(args, kwargs, index + 1, Some("ignore_private"))
+    {
+        opts.ignore_private = ignore_private;
+    }
+    if let Some(ignore_none) = get_call_arg_bool(args, kwargs, index + 2, Some("ignore_none")) {
+        opts.ignore_none = ignore_none;
+    }
+    opts
+}
@@ -72,4 +72,7 @@ pub use val_union::*;
 pub mod val_yaml;
 pub use val_yaml::*;
 
+pub mod val_toml;
+pub use val_toml::*;
+
 pub mod walker;
@@ -37,6 +37,10 @@ pub fn schema_config_meta(filename: &str, line: u64, column: u64) -> ValueRef {
 
 pub fn schema_assert(ctx: &mut Context, value: &ValueRef, msg: &str, config_meta: &ValueRef) {
     if !value.is_truthy() {
+        let prev_panic_info = ctx
+            .cfg
+            .collect_check_failures
+            .then(|| ctx.panic_info.clone());
         ctx.set_err_type(&RuntimeErrorType::SchemaCheckFailure);
         if let Some(config_meta_file) = config_meta.get_by_key(CONFIG_META_FILENAME) {
             let config_meta_line = config_meta.get_by_key(CONFIG_META_LINE).unwrap();
@@ -58,6 +62,10 @@ pub fn schema_assert(ctx: &mut Context, value: &ValueRef, msg: &str, config_meta
             }
         );
         ctx.set_kcl_location_info(Some(arg_msg.as_str()), None, None, None);
+        if let Some(prev_panic_info) = prev_panic_info {
+            ctx.record_check_failure(prev_panic_info, msg);
+            return;
+        }
 
         panic!("{}", msg);
     }
@@ -176,6 +184,10 @@ impl ValueRef {
                     let undefined = ValueRef::undefined();
                     let value = attr_map.get(attr).unwrap_or(&undefined);
                     if is_required && value.is_none_or_undefined() {
+                        let prev_panic_info = ctx
+                            .cfg
+                            .collect_check_failures
+                            .then(|| ctx.panic_info.clone());
                         let filename = config_meta.get_by_key(CONFIG_META_FILENAME);
                         let line = config_meta.get_by_key(CONFIG_META_LINE);
                         if let Some(filename) = filename {
@@ -184,11 +196,17 @@ impl ValueRef {
                         if let Some(line) = line {
                             ctx.panic_info.kcl_line = line.as_int() as i32;
                         }
-                        panic!(
+                        let msg = format!(
                             "attribute '{}' of {} is required and can't be None or Undefined",
                             attr,
                             self.schema_name()
                         );
+                        if let Some(prev_panic_info) = prev_panic_info {
+                            ctx.panic_info.kcl_arg_msg.clear();
+                            ctx.record_check_failure(prev_panic_info, &msg);
+                            continue;
+                        }
+                        panic!("{}", msg);
                     }
                 }
                 // Recursive check schema values for every attributes.
@@ -394,6 +412,51 @@ mod test_value_schema {
         assert!(err.is_err())
     }
 
+    #[test]
+    fn test_schema_collect_check_failures() {
+        let mut ctx = Context::new();
+        ctx.cfg.collect_check_failures = true;
+        ctx.set_kcl_location_info(None, Some("main.k"), Some(3), None);
+        let config_meta = ValueRef::dict(None);
+        schema_assert(&mut ctx, &ValueRef::bool(false), "a > 0", &config_meta);
+        schema_assert(&mut ctx, &ValueRef::bool(true), "b > 0", &config_meta);
+        schema_assert(&mut ctx, &ValueRef::bool(false), "c > 0", &config_meta);
+        let optional_mapping = ValueRef::dict_bool(&[("name", false)]);
+        let schema = ValueRef::dict(None).dict_to_schema(
+            TEST_SCHEMA_NAME,
+            MAIN_PKG_PATH,
+            &[],
+            &config_meta,
+            &optional_mapping,
+            None,
+            None,
+        );
+        schema.schema_check_attr_optional(&mut ctx, true);
+        schema.schema_check_attr_optional(&mut ctx, true);
+        let messages: Vec<&str> = ctx
+            .check_failures
+            .iter()
+            .map(|failure| failure.message.as_str())
+            .collect();
+        assert_eq!(
+            messages,
+            vec![
+                "a > 0",
+                "c > 0",
+                "attribute 'name' of Data is required and can't be None or Undefined"
+            ]
+        );
+        assert_eq!(
+            ctx.check_failures[0].kcl_arg_msg,
+            "Check failed on the condition: a > 0"
+        );
+        assert_eq!(ctx.check_failures[0].kcl_file, "main.k");
+        assert_eq!(ctx.check_failures[0].kcl_line, 3);
+        // The panic information is restored after recording the failures.
+        assert!(!ctx.panic_info.__kcl_PanicInfo__);
+        assert!(ctx.panic_info.kcl_arg_msg.is_empty());
+    }
+
     #[test]
     fn test_schema_attr_map() {
         let mut schema = get_test_schema_value();
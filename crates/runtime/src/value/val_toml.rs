@@ -0,0 +1,193 @@
+//! Copyright The KCL Authors. All rights reserved.
+
+extern crate toml;
+
+use crate::*;
+
+/// TOML encode options.
+/// - sort_keys: Sort the encode result by keys (defaults to false).
+/// - ignore_private: Whether to ignore the attribute whose name starts with
+///   a character `_` (defaults to false).
+/// - ignore_none: Whether to ignore the attribute whose value is `None` (defaults to false).
+///
+/// Note that TOML has no null value, so `None` and `Undefined` values are always
+/// omitted from the encode result whatever the `ignore_none` option is.
+#[derive(Debug, Default, Clone)]
+pub struct TomlEncodeOptions {
+    pub sort_keys: bool,
+    pub ignore_private: bool,
+    pub ignore_none: bool,
+}
+
+impl ValueRef {
+    /// Decode a TOML document string to a ValueRef.
+    /// Returns [toml::de::Error] when decoding fails.
+    pub fn from_toml(ctx: &mut Context, s: &str) -> Result<Self, toml::de::Error> {
+        let toml_value: toml::Value = toml::from_str(s)?;
+        Ok(Self::parse_toml(ctx, &toml_value))
+    }
+
+    fn parse_toml(ctx: &mut Context, value: &toml::Value) -> Self {
+        match value {
+            toml::Value::Table(values) => {
+                let mut dict = Self::dict(None);
+                for (name, value) in values {
+                    let v = Self::parse_toml(ctx, value);
+                    dict.dict_insert(
+                        ctx,
+                        name.as_ref(),
+                        &v,
+                        ConfigEntryOperationKind::Union,
+                        None,
+                    );
+                }
+                dict
+            }
+            toml::Value::Array(values) => {
+                let mut list = Self::list(None);
+                for value in values {
+                    list.list_append(&Self::parse_toml(ctx, value));
+                }
+                list
+            }
+            toml::Value::String(val) => Self::str(val),
+            toml::Value::Integer(val) => Self::int(*val),
+            toml::Value::Float(val) => Self::float(*val),
+            toml::Value::Boolean(val) => Self::bool(*val),
+            // KCL has no datetime type, use the RFC 3339 string form instead.
+            toml::Value::Datetime(val) => Self::str(&val.to_string()),
+        }
+    }
+
+    pub fn to_toml_string(&self) -> Result<String, toml::ser::Error> {
+        self.to_toml_string_with_options(&Default::default())
+    }
+
+    /// Encode the value to a TOML document string. Only dict and schema values
+    /// can be encoded as a TOML document.
+    pub fn to_toml_string_with_options(
+        &self,
+        opts: &TomlEncodeOptions,
+    ) -> Result<String, toml::ser::Error> {
+        // Reuse the JSON value builder to deal with the encode options.
+        let json_opts = JsonEncodeOptions {
+            sort_keys: opts.sort_keys,
+            indent: 0,
+            ignore_private: opts.ignore_private,
+            ignore_none: opts.ignore_none,
+        };
+        match build_toml(&self.build_json(&json_opts)) {
+            Some(value @ toml::Value::Table(_)) => toml::to_string(&value),
+            _ => Err(<toml::ser::Error as serde::ser::Error>::custom(format!(
+                "only dict and schema can be encoded to TOML, got {}",
+                self.type_str()
+            ))),
+        }
+    }
+}
+
+/// Convert the JSON value to the TOML value, null values are dropped.
+fn build_toml(value: &JsonValue) -> Option<toml::Value> {
+    match value {
+        JsonValue::Null => None,
+        JsonValue::Bool(v) => Some(toml::Value::Boolean(*v)),
+        JsonValue::Number(v) => match v.as_i64() {
+            Some(v) => Some(toml::Value::Integer(v)),
+            None => v.as_f64().map(toml::Value::Float),
+        },
+        JsonValue::String(v) => Some(toml::Value::String(v.clone())),
+        JsonValue::Array(values) => Some(toml::Value::Array(
+            values.iter().filter_map(build_toml).collect(),
+        )),
+        JsonValue::Object(values) => Some(toml::Value::Table(
+            values
+                .iter()
+                .filter_map(|(k, v)| build_toml(v).map(|v| (k.clone(), v)))
+                .collect(),
+        )),
+    }
+}
+
+#[cfg(test)]
+mod test_value_toml {
+    use crate::*;
+
+    #[test]
+    fn test_value_from_toml() {
+        let mut ctx = Context::new();
+        let cases = [
+            ("a = 1\n", ValueRef::dict(Some(&[("a", &ValueRef::int(1))]))),
+            (
+                "a = 1\nb = 2\n",
+                ValueRef::dict(Some(&[("a", &ValueRef::int(1)), ("b", &ValueRef::int(2))])),
+            ),
+            (
+                "a = [1, 2, 3]\nb = \"s\"\n",
+                ValueRef::dict(Some(&[
+                    ("a", &ValueRef::list_int(&[1, 2, 3])),
+                    ("b", &ValueRef::str("s")),
+                ])),
+            ),
+            (
+                "[a]\nb = 1979-05-27T07:32:00Z\n",
+                ValueRef::dict(Some(&[(
+                    "a",
+                    &ValueRef::dict(Some(&[("b", &ValueRef::str("1979-05-27T07:32:00Z"))])),
+                )])),
+            ),
+            ("", ValueRef::dict(Some(&[]))),
+        ];
+        for (toml_str, expected) in cases {
+            let result = ValueRef::from_toml(&mut ctx, toml_str).unwrap();
+            assert_eq!(result, expected);
+        }
+    }
+
+    #[test]
+    fn test_value_from_err_toml() {
+        let mut ctx = Context::new();
+        let cases = ["a = ", "[a\n", "a = 1\na = 2\n"];
+        for toml_str in cases {
+            assert!(ValueRef::from_toml(&mut ctx, toml_str).is_err());
+        }
+    }
+
+    #[test]
+    fn test_value_to_toml_string() {
+        let cases = [
+            (ValueRef::dict(Some(&[("a", &ValueRef::int(1))])), "a = 1\n"),
+            (
+                ValueRef::dict(Some(&[
+                    ("c", &ValueRef::dict(Some(&[("d", &ValueRef::float(1.5))]))),
+                    ("a", &ValueRef::list_int(&[1, 2, 3])),
+                    ("b", &ValueRef::none()),
+                ])),
+                "a = [1, 2, 3]\n\n[c]\nd = 1.5\n",
+            ),
+        ];
+        for (value, expected) in cases {
+            let result = value.to_toml_string().unwrap();
+            assert_eq!(result, expected);
+        }
+    }
+
+    #[test]
+    fn test_value_to_toml_string_with_options() {
+        let value = ValueRef::dict(Some(&[("b", &ValueRef::int(1)), ("_a", &ValueRef::int(2))]));
+        let opts = TomlEncodeOptions {
+            sort_keys: true,
+            ignore_private: false,
+            ignore_none: false,
+        };
+        assert_eq!(
+            value.to_toml_string_with_options(&opts).unwrap(),
+            "_a = 2\nb = 1\n"
+        );
+        let opts = TomlEncodeOptions {
+            ignore_private: true,
+            ..Default::default()
+        };
+        assert_eq!(value.to_toml_string_with_options(&opts).unwrap(), "b = 1\n");
+        assert!(ValueRef::list_int(&[1]).to_toml_string().is_err());
+    }
+}
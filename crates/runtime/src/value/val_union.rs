@@ -282,6 +282,8 @@ impl ValueRef {
                             }
                         }
                     }
+                } else {
+                    obj.values = delta.values.clone();
                 }
             }
             (Value::dict_value(obj), Value::dict_value(delta)) => union_fn(obj, delta),
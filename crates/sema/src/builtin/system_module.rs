@@ -949,6 +949,174 @@ register_datetime_member! {
         false,
         None,
     )
+    parse => Type::function(
+        None,
+        Type::str_ref(),
+        &[
+            Parameter {
+                name: "date".to_string(),
+                ty: Type::str_ref(),
+                has_default: false,
+                default_value: None,
+                range: dummy_range(),
+            },
+            Parameter {
+                name: "format".to_string(),
+                ty: Type::str_ref(),
+                has_default: true,
+                default_value: None,
+                range: dummy_range(),
+            },
+            Parameter {
+                name: "tz".to_string(),
+                ty: Type::str_ref(),
+                has_default: true,
+                default_value: None,
+                range: dummy_range(),
+            },
+        ],
+        r#"Parse the date string with the strftime `format` e.g., `%d/%m/%Y %H:%M`, or as an ISO 8601 date when the format is not given, and return the RFC 3339 timestamp. A date without a UTC offset is in the IANA time zone `tz`, and UTC by default."#,
+        false,
+        None,
+    )
+    format => Type::function(
+        None,
+        Type::str_ref(),
+        &[
+            Parameter {
+                name: "date".to_string(),
+                ty: Type::union_ref(&[Type::str_ref(), Type::int_ref(), Type::float_ref()]),
+                has_default: false,
+                default_value: None,
+                range: dummy_range(),
+            },
+            Parameter {
+                name: "format".to_string(),
+                ty: Type::str_ref(),
+                has_default: true,
+                default_value: None,
+                range: dummy_range(),
+            },
+            Parameter {
+                name: "tz".to_string(),
+                ty: Type::str_ref(),
+                has_default: true,
+                default_value: None,
+                range: dummy_range(),
+            },
+        ],
+        r#"Format the date string or the seconds since the Epoch with the strftime `format`, and the default format is the RFC 3339 format "%Y-%m-%dT%H:%M:%S%.f%:z". The date is converted to the IANA time zone `tz` when it is given."#,
+        false,
+        None,
+    )
+    to_timezone => Type::function(
+        None,
+        Type::str_ref(),
+        &[
+            Parameter {
+                name: "date".to_string(),
+                ty: Type::union_ref(&[Type::str_ref(), Type::int_ref(), Type::float_ref()]),
+                has_default: false,
+                default_value: None,
+                range: dummy_range(),
+            },
+            Parameter {
+                name: "tz".to_string(),
+                ty: Type::str_ref(),
+                has_default: false,
+                default_value: None,
+                range: dummy_range(),
+            },
+        ],
+        r#"Convert the date string or the seconds since the Epoch to the IANA time zone `tz` e.g., `Asia/Shanghai`, and return the RFC 3339 timestamp."#,
+        false,
+        None,
+    )
+    duration => Type::function(
+        None,
+        Type::float_ref(),
+        &[
+            Parameter {
+                name: "duration".to_string(),
+                ty: Type::str_ref(),
+                has_default: false,
+                default_value: None,
+                range: dummy_range(),
+            },
+        ],
+        r#"Return the seconds of the ISO 8601 duration e.g., `P1DT12H` or `PT90M`. A day is 24 hours, and the years and months are not allowed because their lengths depend on the date."#,
+        false,
+        None,
+    )
+    add => Type::function(
+        None,
+        Type::str_ref(),
+        &[
+            Parameter {
+                name: "date".to_string(),
+                ty: Type::union_ref(&[Type::str_ref(), Type::int_ref(), Type::float_ref()]),
+                has_default: false,
+                default_value: None,
+                range: dummy_range(),
+            },
+            Parameter {
+                name: "duration".to_string(),
+                ty: Type::str_ref(),
+                has_default: false,
+                default_value: None,
+                range: dummy_range(),
+            },
+        ],
+        r#"Add the ISO 8601 duration e.g., `P1Y2M3DT4H` to the date string or the seconds since the Epoch, and return the RFC 3339 timestamp. The calendar units are added in the time zone of the date."#,
+        false,
+        None,
+    )
+    subtract => Type::function(
+        None,
+        Type::str_ref(),
+        &[
+            Parameter {
+                name: "date".to_string(),
+                ty: Type::union_ref(&[Type::str_ref(), Type::int_ref(), Type::float_ref()]),
+                has_default: false,
+                default_value: None,
+                range: dummy_range(),
+            },
+            Parameter {
+                name: "duration".to_string(),
+                ty: Type::str_ref(),
+                has_default: false,
+                default_value: None,
+                range: dummy_range(),
+            },
+        ],
+        r#"Subtract the ISO 8601 duration e.g., `P90D` from the date string or the seconds since the Epoch, and return the RFC 3339 timestamp. The calendar units are subtracted in the time zone of the date."#,
+        false,
+        None,
+    )
+    diff => Type::function(
+        None,
+        Type::float_ref(),
+        &[
+            Parameter {
+                name: "start".to_string(),
+                ty: Type::union_ref(&[Type::str_ref(), Type::int_ref(), Type::float_ref()]),
+                has_default: false,
+                default_value: None,
+                range: dummy_range(),
+            },
+            Parameter {
+                name: "end".to_string(),
+                ty: Type::union_ref(&[Type::str_ref(), Type::int_ref(), Type::float_ref()]),
+                has_default: false,
+                default_value: None,
+                range: dummy_range(),
+            },
+        ],
+        r#"Return the seconds from the date `start` to the date `end`, which is negative when `end` is earlier than `start`."#,
+        false,
+        None,
+    )
 }
 
 // ------------------------------
@@ -1002,7 +1170,7 @@ register_regex_member! {
                 range: dummy_range(),
             },
         ],
-        r#"Return the string obtained by replacing the leftmost non-overlapping occurrences of the pattern in string by the replacement."#,
+        r#"Return the string obtained by replacing the leftmost non-overlapping occurrences of the pattern in string by the replacement. The `$name` and `$1` in the replacement are replaced by the named and numbered capture groups, `$$` is a literal `$`, and `${name}` in a raw string e.g., `r"${name}_suffix"` delimits the group name."#,
         false,
         None,
     )
@@ -1121,6 +1289,52 @@ register_regex_member! {
         false,
         None,
     )
+    search_groups => Type::function(
+        None,
+        Type::dict_ref(Type::str_ref(), Type::str_ref()),
+        &[
+            Parameter {
+                name: "string".to_string(),
+                ty: Type::str_ref(),
+                has_default: false,
+                default_value: None,
+                range: dummy_range(),
+            },
+            Parameter {
+                name: "pattern".to_string(),
+                ty: Type::str_ref(),
+                has_default: false,
+                default_value: None,
+                range: dummy_range(),
+            },
+        ],
+        r#"Scan through string looking for the first match to the pattern, returning a dict of the named capture groups e.g., `(?P<name>...)` of the match, or `None` if no match was found. The groups that don't participate in the match are `None`."#,
+        false,
+        None,
+    )
+    findall_groups => Type::function(
+        None,
+        Type::list_ref(Type::dict_ref(Type::str_ref(), Type::str_ref())),
+        &[
+            Parameter {
+                name: "string".to_string(),
+                ty: Type::str_ref(),
+                has_default: false,
+                default_value: None,
+                range: dummy_range(),
+            },
+            Parameter {
+                name: "pattern".to_string(),
+                ty: Type::str_ref(),
+                has_default: false,
+                default_value: None,
+                range: dummy_range(),
+            },
+        ],
+        r#"Return a list of the dicts of the named capture groups of all non-overlapping matches in the string."#,
+        false,
+        None,
+    )
 }
 
 // ------------------------------
@@ -1527,72 +1741,63 @@ Otherwise, replace the value."#,
 }
 
 // ------------------------------
-// crypto system package
+// toml system package
 // ------------------------------
 
-pub const CRYPTO: &str = "crypto";
-macro_rules! register_crypto_member {
+pub const TOML: &str = "toml";
+macro_rules! register_toml_member {
     ($($name:ident => $ty:expr)*) => (
-        pub static CRYPTO_FUNCTION_TYPES: Lazy<IndexMap<String, Type>> = Lazy::new(|| {
+        pub static TOML_FUNCTION_TYPES: Lazy<IndexMap<String, Type>> = Lazy::new(|| {
             let mut builtin_mapping = IndexMap::default();
             $( builtin_mapping.insert(stringify!($name).to_string(), $ty); )*
             builtin_mapping
         });
-        pub const CRYPTO_FUNCTION_NAMES: &[&str] = &[
+        pub const TOML_FUNCTION_NAMES: &[&str] = &[
             $( stringify!($name), )*
         ];
     )
 }
-register_crypto_member! {
-    md5 => Type::function(
+register_toml_member! {
+    encode => Type::function(
         None,
         Type::str_ref(),
         &[
             Parameter {
-                name: "value".to_string(),
-                ty: Type::str_ref(),
+                name: "data".to_string(),
+                ty: Type::any_ref(),
                 has_default: false,
                 default_value: None,
                 range: dummy_range(),
             },
             Parameter {
-                name: "encoding".to_string(),
-                ty: Type::str_ref(),
+                name: "sort_keys".to_string(),
+                ty: Type::bool_ref(),
                 has_default: true,
                 default_value: None,
                 range: dummy_range(),
             },
-        ],
-        r#"Encrypt the string `value` using `MD5` and the codec registered for encoding."#,
-        false,
-        None,
-    )
-    sha1 => Type::function(
-        None,
-        Type::str_ref(),
-        &[
             Parameter {
-                name: "value".to_string(),
-                ty: Type::str_ref(),
-                has_default: false,
+                name: "ignore_private".to_string(),
+                ty: Type::bool_ref(),
+                has_default: true,
                 default_value: None,
                 range: dummy_range(),
             },
             Parameter {
-                name: "encoding".to_string(),
-                ty: Type::str_ref(),
+                name: "ignore_none".to_string(),
+                ty: Type::bool_ref(),
                 has_default: true,
                 default_value: None,
                 range: dummy_range(),
             },
         ],
-        r#"Encrypt the string `value` using `SHA1` and the codec registered for encoding."#,
+        r#"Serialize a KCL object `data` to a TOML formatted str. `None` values are omitted because TOML has no null value."#,
         false,
-        None,
+        Some(1),
     )
-    sha224 => Type::function(
+    decode => Type::function(
         None,
-        Type::str_ref(),
+        Type::dict_ref(Type::str_ref(), Type::any_ref()),
         &[
             Parameter {
                 name: "value".to_string(),
@@ -1601,90 +1806,499 @@ register_crypto_member! {
                 default_value: None,
                 range: dummy_range(),
             },
-            Parameter {
-                name: "encoding".to_string(),
-                ty: Type::str_ref(),
-                has_default: true,
-                default_value: None,
-                range: dummy_range(),
-            },
         ],
-        r#"Encrypt the string `value` using `SHA224` and the codec registered for encoding."#,
+        r#"Deserialize `value` (a string instance containing a TOML document) to a KCL object."#,
         false,
         None,
     )
-    sha256 => Type::function(
+    dump_to_file => Type::function(
         None,
         Type::str_ref(),
         &[
             Parameter {
-                name: "value".to_string(),
-                ty: Type::str_ref(),
+                name: "data".to_string(),
+                ty: Type::any_ref(),
                 has_default: false,
                 default_value: None,
                 range: dummy_range(),
             },
             Parameter {
-                name: "encoding".to_string(),
+                name: "filename".to_string(),
                 ty: Type::str_ref(),
-                has_default: true,
+                has_default: false,
                 default_value: None,
                 range: dummy_range(),
             },
-        ],
-        r#"Encrypt the string `value` using `SHA256` and the codec registered for encoding."#,
-        false,
-        None,
-    )
-    sha384 => Type::function(
-        None,
-        Type::str_ref(),
-        &[
             Parameter {
-                name: "value".to_string(),
-                ty: Type::str_ref(),
-                has_default: false,
+                name: "sort_keys".to_string(),
+                ty: Type::bool_ref(),
+                has_default: true,
                 default_value: None,
                 range: dummy_range(),
             },
             Parameter {
-                name: "encoding".to_string(),
-                ty: Type::str_ref(),
+                name: "ignore_private".to_string(),
+                ty: Type::bool_ref(),
                 has_default: true,
                 default_value: None,
                 range: dummy_range(),
             },
-        ],
-        r#"Encrypt the string `value` using `SHA384` and the codec registered for encoding."#,
+            Parameter {
+                name: "ignore_none".to_string(),
+                ty: Type::bool_ref(),
+                has_default: true,
+                default_value: None,
+                range: dummy_range(),
+            },
+        ],
+        r#"Serialize a KCL object `data` to a TOML formatted str and write it into the file `filename`."#,
+        false,
+        Some(2),
+    )
+    validate => Type::function(
+        None,
+        Type::bool_ref(),
+        &[
+            Parameter {
+                name: "value".to_string(),
+                ty: Type::str_ref(),
+                has_default: false,
+                default_value: None,
+                range: dummy_range(),
+            },
+        ],
+        r#"Validate whether the given string is a valid TOML document."#,
+        false,
+        None,
+    )
+}
+
+// ------------------------------
+// crypto system package
+// ------------------------------
+
+pub const CRYPTO: &str = "crypto";
+macro_rules! register_crypto_member {
+    ($($name:ident => $ty:expr)*) => (
+        pub static CRYPTO_FUNCTION_TYPES: Lazy<IndexMap<String, Type>> = Lazy::new(|| {
+            let mut builtin_mapping = IndexMap::default();
+            $( builtin_mapping.insert(stringify!($name).to_string(), $ty); )*
+            builtin_mapping
+        });
+        pub const CRYPTO_FUNCTION_NAMES: &[&str] = &[
+            $( stringify!($name), )*
+        ];
+    )
+}
+register_crypto_member! {
+    md5 => Type::function(
+        None,
+        Type::str_ref(),
+        &[
+            Parameter {
+                name: "value".to_string(),
+                ty: Type::str_ref(),
+                has_default: false,
+                default_value: None,
+                range: dummy_range(),
+            },
+            Parameter {
+                name: "encoding".to_string(),
+                ty: Type::str_ref(),
+                has_default: true,
+                default_value: None,
+                range: dummy_range(),
+            },
+            Parameter {
+                name: "output".to_string(),
+                ty: Type::str_ref(),
+                has_default: true,
+                default_value: None,
+                range: dummy_range(),
+            },
+        ],
+        r#"Encrypt the string `value` using `MD5` and the codec registered for encoding. The `output` format of the digest is `hex` (the default), `base64` or `base64url`."#,
+        false,
+        None,
+    )
+    sha1 => Type::function(
+        None,
+        Type::str_ref(),
+        &[
+            Parameter {
+                name: "value".to_string(),
+                ty: Type::str_ref(),
+                has_default: false,
+                default_value: None,
+                range: dummy_range(),
+            },
+            Parameter {
+                name: "encoding".to_string(),
+                ty: Type::str_ref(),
+                has_default: true,
+                default_value: None,
+                range: dummy_range(),
+            },
+            Parameter {
+                name: "output".to_string(),
+                ty: Type::str_ref(),
+                has_default: true,
+                default_value: None,
+                range: dummy_range(),
+            },
+        ],
+        r#"Encrypt the string `value` using `SHA1` and the codec registered for encoding. The `output` format of the digest is `hex` (the default), `base64` or `base64url`."#,
+        false,
+        None,
+    )
+    sha224 => Type::function(
+        None,
+        Type::str_ref(),
+        &[
+            Parameter {
+                name: "value".to_string(),
+                ty: Type::str_ref(),
+                has_default: false,
+                default_value: None,
+                range: dummy_range(),
+            },
+            Parameter {
+                name: "encoding".to_string(),
+                ty: Type::str_ref(),
+                has_default: true,
+                default_value: None,
+                range: dummy_range(),
+            },
+            Parameter {
+                name: "output".to_string(),
+                ty: Type::str_ref(),
+                has_default: true,
+                default_value: None,
+                range: dummy_range(),
+            },
+        ],
+        r#"Encrypt the string `value` using `SHA224` and the codec registered for encoding. The `output` format of the digest is `hex` (the default), `base64` or `base64url`."#,
+        false,
+        None,
+    )
+    sha256 => Type::function(
+        None,
+        Type::str_ref(),
+        &[
+            Parameter {
+                name: "value".to_string(),
+                ty: Type::str_ref(),
+                has_default: false,
+                default_value: None,
+                range: dummy_range(),
+            },
+            Parameter {
+                name: "encoding".to_string(),
+                ty: Type::str_ref(),
+                has_default: true,
+                default_value: None,
+                range: dummy_range(),
+            },
+            Parameter {
+                name: "output".to_string(),
+                ty: Type::str_ref(),
+                has_default: true,
+                default_value: None,
+                range: dummy_range(),
+            },
+        ],
+        r#"Encrypt the string `value` using `SHA256` and the codec registered for encoding. The `output` format of the digest is `hex` (the default), `base64` or `base64url`."#,
+        false,
+        None,
+    )
+    sha384 => Type::function(
+        None,
+        Type::str_ref(),
+        &[
+            Parameter {
+                name: "value".to_string(),
+                ty: Type::str_ref(),
+                has_default: false,
+                default_value: None,
+                range: dummy_range(),
+            },
+            Parameter {
+                name: "encoding".to_string(),
+                ty: Type::str_ref(),
+                has_default: true,
+                default_value: None,
+                range: dummy_range(),
+            },
+            Parameter {
+                name: "output".to_string(),
+                ty: Type::str_ref(),
+                has_default: true,
+                default_value: None,
+                range: dummy_range(),
+            },
+        ],
+        r#"Encrypt the string `value` using `SHA384` and the codec registered for encoding. The `output` format of the digest is `hex` (the default), `base64` or `base64url`."#,
+        false,
+        None,
+    )
+    sha512 => Type::function(
+        None,
+        Type::str_ref(),
+        &[
+            Parameter {
+                name: "value".to_string(),
+                ty: Type::str_ref(),
+                has_default: false,
+                default_value: None,
+                range: dummy_range(),
+            },
+            Parameter {
+                name: "encoding".to_string(),
+                ty: Type::str_ref(),
+                has_default: true,
+                default_value: None,
+                range: dummy_range(),
+            },
+            Parameter {
+                name: "output".to_string(),
+                ty: Type::str_ref(),
+                has_default: true,
+                default_value: None,
+                range: dummy_range(),
+            },
+        ],
+        r#"Encrypt the string `value` using `SHA512` and the codec registered for encoding. The `output` format of the digest is `hex` (the default), `base64` or `base64url`."#,
+        false,
+        None,
+    )
+    blake3 => Type::function(
+        None,
+        Type::str_ref(),
+        &[
+            Parameter {
+                name: "value".to_string(),
+                ty: Type::str_ref(),
+                has_default: false,
+                default_value: None,
+                range: dummy_range(),
+            },
+            Parameter {
+                name: "encoding".to_string(),
+                ty: Type::str_ref(),
+                has_default: true,
+                default_value: None,
+                range: dummy_range(),
+            },
+            Parameter {
+                name: "output".to_string(),
+                ty: Type::str_ref(),
+                has_default: true,
+                default_value: None,
+                range: dummy_range(),
+            },
+        ],
+        r#"Encrypt the string `value` using `BLAKE3` and the codec registered for encoding. The `output` format of the digest is `hex` (the default), `base64` or `base64url`."#,
+        false,
+        None,
+    )
+    uuid => Type::function(
+        None,
+        Type::str_ref(),
+        &[],
+        r#"Generate a random UUIDv4."#,
+        false,
+        None,
+    )
+    filesha256 => Type::function(
+        None,
+        Type::str_ref(),
+        &[
+            Parameter {
+                name: "filepath".to_string(),
+                ty: Type::str_ref(),
+                has_default: false,
+                default_value: None,
+                range: dummy_range(),
+            },
+        ],
+        r#"Calculate the SHA256 hash of the file `filepath`."#,
+        false,
+        None,
+    )
+    hmac => Type::function(
+        None,
+        Type::str_ref(),
+        &[
+            Parameter {
+                name: "value".to_string(),
+                ty: Type::str_ref(),
+                has_default: false,
+                default_value: None,
+                range: dummy_range(),
+            },
+            Parameter {
+                name: "key".to_string(),
+                ty: Type::str_ref(),
+                has_default: false,
+                default_value: None,
+                range: dummy_range(),
+            },
+            Parameter {
+                name: "algorithm".to_string(),
+                ty: Type::str_ref(),
+                has_default: true,
+                default_value: None,
+                range: dummy_range(),
+            },
+            Parameter {
+                name: "output".to_string(),
+                ty: Type::str_ref(),
+                has_default: true,
+                default_value: None,
+                range: dummy_range(),
+            },
+            Parameter {
+                name: "encoding".to_string(),
+                ty: Type::str_ref(),
+                has_default: true,
+                default_value: None,
+                range: dummy_range(),
+            },
+        ],
+        r#"Return the HMAC of the string `value` with the `key`, the `algorithm` is `sha256` (the default) or `sha512`, and the `output` format is `hex` (the default), `base64` or `base64url`. The value and the key are encoded with the codec registered for encoding."#,
+        false,
+        None,
+    )
+    bcrypt => Type::function(
+        None,
+        Type::str_ref(),
+        &[
+            Parameter {
+                name: "password".to_string(),
+                ty: Type::str_ref(),
+                has_default: false,
+                default_value: None,
+                range: dummy_range(),
+            },
+            Parameter {
+                name: "cost".to_string(),
+                ty: Type::int_ref(),
+                has_default: true,
+                default_value: None,
+                range: dummy_range(),
+            },
+            Parameter {
+                name: "salt".to_string(),
+                ty: Type::str_ref(),
+                has_default: true,
+                default_value: None,
+                range: dummy_range(),
+            },
+            Parameter {
+                name: "prefix".to_string(),
+                ty: Type::str_ref(),
+                has_default: true,
+                default_value: None,
+                range: dummy_range(),
+            },
+        ],
+        r#"Return the bcrypt hash of the `password` e.g., `$2b$12$...`. The `cost` defaults to 12, the version `prefix` is `2a`, `2b` (the default), `2x` or `2y` used by the Apache htpasswd files, and a random salt is used when the 16 bytes `salt` is not given."#,
+        false,
+        None,
+    )
+    bcrypt_verify => Type::function(
+        None,
+        Type::bool_ref(),
+        &[
+            Parameter {
+                name: "password".to_string(),
+                ty: Type::str_ref(),
+                has_default: false,
+                default_value: None,
+                range: dummy_range(),
+            },
+            Parameter {
+                name: "hash".to_string(),
+                ty: Type::str_ref(),
+                has_default: false,
+                default_value: None,
+                range: dummy_range(),
+            },
+        ],
+        r#"Verify whether the `password` matches the bcrypt `hash`."#,
+        false,
+        None,
+    )
+    argon2 => Type::function(
+        None,
+        Type::str_ref(),
+        &[
+            Parameter {
+                name: "password".to_string(),
+                ty: Type::str_ref(),
+                has_default: false,
+                default_value: None,
+                range: dummy_range(),
+            },
+            Parameter {
+                name: "salt".to_string(),
+                ty: Type::str_ref(),
+                has_default: true,
+                default_value: None,
+                range: dummy_range(),
+            },
+            Parameter {
+                name: "memory_cost".to_string(),
+                ty: Type::int_ref(),
+                has_default: true,
+                default_value: None,
+                range: dummy_range(),
+            },
+            Parameter {
+                name: "time_cost".to_string(),
+                ty: Type::int_ref(),
+                has_default: true,
+                default_value: None,
+                range: dummy_range(),
+            },
+            Parameter {
+                name: "parallelism".to_string(),
+                ty: Type::int_ref(),
+                has_default: true,
+                default_value: None,
+                range: dummy_range(),
+            },
+        ],
+        r#"Return the Argon2id hash of the `password` in the PHC string format e.g., `$argon2id$v=19$m=19456,t=2,p=1$...`. A random salt is used when the `salt` of at least 8 bytes is not given, and the `memory_cost` in KiB, `time_cost` and `parallelism` default to 19456, 2 and 1."#,
         false,
         None,
     )
-    sha512 => Type::function(
+    argon2_verify => Type::function(
         None,
-        Type::str_ref(),
+        Type::bool_ref(),
         &[
             Parameter {
-                name: "value".to_string(),
+                name: "password".to_string(),
                 ty: Type::str_ref(),
                 has_default: false,
                 default_value: None,
                 range: dummy_range(),
             },
             Parameter {
-                name: "encoding".to_string(),
+                name: "hash".to_string(),
                 ty: Type::str_ref(),
-                has_default: true,
+                has_default: false,
                 default_value: None,
                 range: dummy_range(),
             },
         ],
-        r#"Encrypt the string `value` using `SHA512` and the codec registered for encoding."#,
+        r#"Verify whether the `password` matches the Argon2 `hash` in the PHC string format."#,
         false,
         None,
     )
-    blake3 => Type::function(
+    verify_signature => Type::function(
         None,
-        Type::str_ref(),
+        Type::bool_ref(),
         &[
             Parameter {
                 name: "value".to_string(),
@@ -1694,38 +2308,42 @@ register_crypto_member! {
                 range: dummy_range(),
             },
             Parameter {
-                name: "encoding".to_string(),
+                name: "signature".to_string(),
                 ty: Type::str_ref(),
-                has_default: true,
+                has_default: false,
                 default_value: None,
                 range: dummy_range(),
             },
-        ],
-        r#"Encrypt the string `value` using `BLAKE3` and the codec registered for encoding."#,
-        false,
-        None,
-    )
-    uuid => Type::function(
-        None,
-        Type::str_ref(),
-        &[],
-        r#"Generate a random UUIDv4."#,
-        false,
-        None,
-    )
-    filesha256 => Type::function(
-        None,
-        Type::str_ref(),
-        &[
             Parameter {
-                name: "filepath".to_string(),
+                name: "public_key".to_string(),
                 ty: Type::str_ref(),
                 has_default: false,
                 default_value: None,
                 range: dummy_range(),
             },
+            Parameter {
+                name: "algorithm".to_string(),
+                ty: Type::str_ref(),
+                has_default: true,
+                default_value: None,
+                range: dummy_range(),
+            },
+            Parameter {
+                name: "format".to_string(),
+                ty: Type::str_ref(),
+                has_default: true,
+                default_value: None,
+                range: dummy_range(),
+            },
+            Parameter {
+                name: "encoding".to_string(),
+                ty: Type::str_ref(),
+                has_default: true,
+                default_value: None,
+                range: dummy_range(),
+            },
         ],
-        r#"Calculate the SHA256 hash of the file `filepath`."#,
+        r#"Verify the `signature` of the string `value` with the Ed25519 or RSA `public_key` in the PEM format. The `algorithm` is `ed25519` for Ed25519 keys, and `rsa-sha256` (the default), `rsa-sha384`, `rsa-sha512`, `rsa-pss-sha256`, `rsa-pss-sha384` or `rsa-pss-sha512` for RSA keys. The signature `format` is `base64` (the default), `base64url` or `hex`."#,
         false,
         None,
     )
@@ -1931,98 +2549,364 @@ register_units_member! {
         Type::str_ref(),
         &[
             Parameter {
-                name: "num".to_string(),
-                ty: Type::number(),
+                name: "num".to_string(),
+                ty: Type::number(),
+                has_default: false,
+                default_value: None,
+                range: dummy_range(),
+            },
+        ],
+        r#"Int literal to string with `Mi` suffix."#,
+        false,
+        None,
+    )
+    to_Gi => Type::function(
+        None,
+        Type::str_ref(),
+        &[
+            Parameter {
+                name: "num".to_string(),
+                ty: Type::number(),
+                has_default: false,
+                default_value: None,
+                range: dummy_range(),
+            },
+        ],
+        r#"Int literal to string with `Gi` suffix."#,
+        false,
+        None,
+    )
+    to_Ti => Type::function(
+        None,
+        Type::str_ref(),
+        &[
+            Parameter {
+                name: "num".to_string(),
+                ty: Type::number(),
+                has_default: false,
+                default_value: None,
+                range: dummy_range(),
+            },
+        ],
+        r#"Int literal to string with `Ti` suffix."#,
+        false,
+        None,
+    )
+    to_Pi => Type::function(
+        None,
+        Type::str_ref(),
+        &[
+            Parameter {
+                name: "num".to_string(),
+                ty: Type::number(),
+                has_default: false,
+                default_value: None,
+                range: dummy_range(),
+            },
+        ],
+        r#"Int literal to string with `Pi` suffix."#,
+        false,
+        None,
+    )
+}
+
+// ------------------------------
+// collection system package
+// ------------------------------
+
+pub const COLLECTION: &str = "collection";
+
+/// The type of the collection function keys: a dotted key path or a function that takes
+/// an item and returns the key.
+fn collection_key_ty() -> TypeRef {
+    Type::union_ref(&[
+        Type::str_ref(),
+        Arc::new(Type::function(
+            None,
+            Type::any_ref(),
+            &[Parameter {
+                name: "item".to_string(),
+                ty: Type::any_ref(),
+                has_default: false,
+                default_value: None,
+                range: dummy_range(),
+            }],
+            "",
+            false,
+            None,
+        )),
+    ])
+}
+macro_rules! register_collection_member {
+    ($($name:ident => $ty:expr)*) => (
+        pub static COLLECTION_FUNCTION_TYPES: Lazy<IndexMap<String, Type>> = Lazy::new(|| {
+            let mut builtin_mapping = IndexMap::default();
+            $( builtin_mapping.insert(stringify!($name).to_string(), $ty); )*
+            builtin_mapping
+        });
+        pub const COLLECTION_FUNCTION_NAMES: &[&str] = &[
+            $( stringify!($name), )*
+        ];
+    )
+}
+register_collection_member! {
+    union_all => Type::function(
+        None,
+        Type::any_ref(),
+        &[
+            Parameter {
+                name: "num".to_string(),
+                ty: Type::list_ref(Type::any_ref()),
+                has_default: false,
+                default_value: None,
+                range: dummy_range(),
+            },
+        ],
+        r#"Union all object to one object."#,
+        false,
+        None,
+    )
+    merge => Type::function(
+        None,
+        Type::any_ref(),
+        &[
+            Parameter {
+                name: "values".to_string(),
+                ty: Type::list_ref(Type::any_ref()),
+                has_default: false,
+                default_value: None,
+                range: dummy_range(),
+            },
+            Parameter {
+                name: "list_strategy".to_string(),
+                ty: Type::str_ref(),
+                has_default: true,
+                default_value: None,
+                range: dummy_range(),
+            },
+        ],
+        r#"Deep merge a list of values from left to right with the union `|` semantics, where the attribute operators of the values on the right are respected, the merged schemas are checked again and the other values are replaced by the values on the right instead of conflicting. The `list_strategy` denotes how two lists are merged: 'override' (default) replaces the list, 'append' appends the items, 'unique' appends the items that are not in the list and 'merge' deep merges the items at the same index."#,
+        false,
+        None,
+    )
+    group_by => Type::function(
+        None,
+        Type::dict_ref(Type::str_ref(), Type::list_ref(Type::any_ref())),
+        &[
+            Parameter {
+                name: "items".to_string(),
+                ty: Type::list_ref(Type::any_ref()),
+                has_default: false,
+                default_value: None,
+                range: dummy_range(),
+            },
+            Parameter {
+                name: "key".to_string(),
+                ty: collection_key_ty(),
+                has_default: false,
+                default_value: None,
+                range: dummy_range(),
+            },
+        ],
+        r#"Group the list items into a dict by the key of each item. The `key` is a dotted key path e.g., `metadata.name` or a function that takes an item and returns the key. Non-string keys are converted to their JSON strings."#,
+        false,
+        None,
+    )
+    unique_by => Type::function(
+        None,
+        Type::list_ref(Type::any_ref()),
+        &[
+            Parameter {
+                name: "items".to_string(),
+                ty: Type::list_ref(Type::any_ref()),
+                has_default: false,
+                default_value: None,
+                range: dummy_range(),
+            },
+            Parameter {
+                name: "key".to_string(),
+                ty: collection_key_ty(),
+                has_default: true,
+                default_value: None,
+                range: dummy_range(),
+            },
+        ],
+        r#"Return the list items with the first occurrence of each key. The `key` is a dotted key path or a function that takes an item and returns the key, and the items themselves are the keys when no key is given."#,
+        false,
+        None,
+    )
+    partition => Type::function(
+        None,
+        Type::list_ref(Type::list_ref(Type::any_ref())),
+        &[
+            Parameter {
+                name: "items".to_string(),
+                ty: Type::list_ref(Type::any_ref()),
+                has_default: false,
+                default_value: None,
+                range: dummy_range(),
+            },
+            Parameter {
+                name: "predicate".to_string(),
+                ty: collection_key_ty(),
+                has_default: false,
+                default_value: None,
+                range: dummy_range(),
+            },
+        ],
+        r#"Split the list items into two lists: the items that match the predicate and the items that don't. The `predicate` is a dotted key path or a function that takes an item, and its result is tested for truthiness."#,
+        false,
+        None,
+    )
+    flatten => Type::function(
+        None,
+        Type::list_ref(Type::any_ref()),
+        &[
+            Parameter {
+                name: "items".to_string(),
+                ty: Type::list_ref(Type::any_ref()),
+                has_default: false,
+                default_value: None,
+                range: dummy_range(),
+            },
+            Parameter {
+                name: "depth".to_string(),
+                ty: Type::int_ref(),
+                has_default: true,
+                default_value: None,
+                range: dummy_range(),
+            },
+        ],
+        r#"Flatten the nested lists up to the `depth` (default 1), and a negative depth flattens all the nested lists."#,
+        false,
+        None,
+    )
+    chunk => Type::function(
+        None,
+        Type::list_ref(Type::list_ref(Type::any_ref())),
+        &[
+            Parameter {
+                name: "items".to_string(),
+                ty: Type::list_ref(Type::any_ref()),
+                has_default: false,
+                default_value: None,
+                range: dummy_range(),
+            },
+            Parameter {
+                name: "size".to_string(),
+                ty: Type::int_ref(),
                 has_default: false,
                 default_value: None,
                 range: dummy_range(),
             },
         ],
-        r#"Int literal to string with `Mi` suffix."#,
+        r#"Split the list into chunks of the `size`, and the last chunk may be shorter."#,
         false,
         None,
     )
-    to_Gi => Type::function(
+    pick => Type::function(
         None,
-        Type::str_ref(),
+        Type::dict_ref(Type::str_ref(), Type::any_ref()),
         &[
             Parameter {
-                name: "num".to_string(),
-                ty: Type::number(),
+                name: "value".to_string(),
+                ty: Type::dict_ref(Type::str_ref(), Type::any_ref()),
+                has_default: false,
+                default_value: None,
+                range: dummy_range(),
+            },
+            Parameter {
+                name: "paths".to_string(),
+                ty: Type::union_ref(&[Type::str_ref(), Type::list_ref(Type::str_ref())]),
                 has_default: false,
                 default_value: None,
                 range: dummy_range(),
             },
         ],
-        r#"Int literal to string with `Gi` suffix."#,
+        r#"Return a new dict that only contains the values at the dotted key paths e.g., `spec.replicas`. The paths that are not found are ignored."#,
         false,
         None,
     )
-    to_Ti => Type::function(
+    omit => Type::function(
         None,
-        Type::str_ref(),
+        Type::dict_ref(Type::str_ref(), Type::any_ref()),
         &[
             Parameter {
-                name: "num".to_string(),
-                ty: Type::number(),
+                name: "value".to_string(),
+                ty: Type::dict_ref(Type::str_ref(), Type::any_ref()),
+                has_default: false,
+                default_value: None,
+                range: dummy_range(),
+            },
+            Parameter {
+                name: "paths".to_string(),
+                ty: Type::union_ref(&[Type::str_ref(), Type::list_ref(Type::str_ref())]),
                 has_default: false,
                 default_value: None,
                 range: dummy_range(),
             },
         ],
-        r#"Int literal to string with `Ti` suffix."#,
+        r#"Return a copy of the dict without the values at the dotted key paths."#,
         false,
         None,
     )
-    to_Pi => Type::function(
+    get => Type::function(
         None,
-        Type::str_ref(),
+        Type::any_ref(),
         &[
             Parameter {
-                name: "num".to_string(),
-                ty: Type::number(),
+                name: "value".to_string(),
+                ty: Type::any_ref(),
+                has_default: false,
+                default_value: None,
+                range: dummy_range(),
+            },
+            Parameter {
+                name: "path".to_string(),
+                ty: Type::str_ref(),
                 has_default: false,
                 default_value: None,
                 range: dummy_range(),
             },
+            Parameter {
+                name: "default".to_string(),
+                ty: Type::any_ref(),
+                has_default: true,
+                default_value: None,
+                range: dummy_range(),
+            },
         ],
-        r#"Int literal to string with `Pi` suffix."#,
+        r#"Get the value at the dotted key path e.g., `spec.containers.0.image`, where the numeric keys index lists. Return the `default` (default None) when the path is not found."#,
         false,
         None,
     )
-}
-
-// ------------------------------
-// collection system package
-// ------------------------------
-
-pub const COLLECTION: &str = "collection";
-macro_rules! register_collection_member {
-    ($($name:ident => $ty:expr)*) => (
-        pub static COLLECTION_FUNCTION_TYPES: Lazy<IndexMap<String, Type>> = Lazy::new(|| {
-            let mut builtin_mapping = IndexMap::default();
-            $( builtin_mapping.insert(stringify!($name).to_string(), $ty); )*
-            builtin_mapping
-        });
-        pub const COLLECTION_FUNCTION_NAMES: &[&str] = &[
-            $( stringify!($name), )*
-        ];
-    )
-}
-register_collection_member! {
-    union_all => Type::function(
+    set => Type::function(
         None,
-        Type::any_ref(),
+        Type::dict_ref(Type::str_ref(), Type::any_ref()),
         &[
             Parameter {
-                name: "num".to_string(),
-                ty: Type::list_ref(Type::any_ref()),
+                name: "value".to_string(),
+                ty: Type::dict_ref(Type::str_ref(), Type::any_ref()),
+                has_default: false,
+                default_value: None,
+                range: dummy_range(),
+            },
+            Parameter {
+                name: "path".to_string(),
+                ty: Type::str_ref(),
+                has_default: false,
+                default_value: None,
+                range: dummy_range(),
+            },
+            Parameter {
+                name: "new_value".to_string(),
+                ty: Type::any_ref(),
                 has_default: false,
                 default_value: None,
                 range: dummy_range(),
             },
         ],
-        r#"Union all object to one object."#,
+        r#"Return a copy of the dict with the `new_value` set at the dotted key path. The missing dicts on the path are created, and the list indexes on the path must exist."#,
         false,
         None,
     )
@@ -2335,8 +3219,59 @@ register_template_member! {
                 default_value: None,
                 range: dummy_range(),
             },
+            Parameter {
+                name: "partials".to_string(),
+                ty: Type::dict_ref(Type::str_ref(), Type::str_ref()),
+                has_default: true,
+                default_value: None,
+                range: dummy_range(),
+            },
+            Parameter {
+                name: "engine".to_string(),
+                ty: Type::str_ref(),
+                has_default: true,
+                default_value: None,
+                range: dummy_range(),
+            },
+        ],
+        r#"Applies a parsed template to the specified data object and returns the string output. See https://handlebarsjs.com/ for more documents and examples. The `partials` are the named templates that can be used in the template e.g., `{{> name}}`. The `engine` is 'handlebars' (default) or 'jinja' for Jinja2 compatible templates rendered by MiniJinja, where the partials are used with `{% include 'name' %}`. Besides the `raw` helper, the `indent`, `toYaml`, `toJson`, `default` and `upper` helpers are available in Handlebars templates e.g., `{{indent (toYaml spec) 2}}`, and the `toYaml` and `toJson` filters are available in Jinja templates."#,
+        false,
+        None,
+    )
+    execute_file => Type::function(
+        None,
+        Type::str_ref(),
+        &[
+            Parameter {
+                name: "filepath".to_string(),
+                ty: Type::str_ref(),
+                has_default: false,
+                default_value: None,
+                range: dummy_range(),
+            },
+            Parameter {
+                name: "data".to_string(),
+                ty: Type::dict_ref(Type::str_ref(), Type::any_ref()),
+                has_default: true,
+                default_value: None,
+                range: dummy_range(),
+            },
+            Parameter {
+                name: "partials".to_string(),
+                ty: Type::dict_ref(Type::str_ref(), Type::str_ref()),
+                has_default: true,
+                default_value: None,
+                range: dummy_range(),
+            },
+            Parameter {
+                name: "engine".to_string(),
+                ty: Type::str_ref(),
+                has_default: true,
+                default_value: None,
+                range: dummy_range(),
+            },
         ],
-        r#"Applies a parsed template to the specified data object and returns the string output. See https://handlebarsjs.com/ for more documents and examples."#,
+        r#"Applies the template in the file to the specified data object and returns the string output. The relative file path is resolved from the directory of the current KCL file. The `data`, `partials` and `engine` arguments are the same as `execute`."#,
         false,
         None,
     )
@@ -2358,6 +3293,152 @@ register_template_member! {
     )
 }
 
+// ------------------------------
+// semver system package
+// ------------------------------
+
+pub const SEMVER: &str = "semver";
+macro_rules! register_semver_member {
+    ($($name:ident => $ty:expr)*) => (
+        pub static SEMVER_FUNCTION_TYPES: Lazy<IndexMap<String, Type>> = Lazy::new(|| {
+            let mut builtin_mapping = IndexMap::default();
+            $( builtin_mapping.insert(stringify!($name).to_string(), $ty); )*
+            builtin_mapping
+        });
+        pub const SEMVER_FUNCTION_NAMES: &[&str] = &[
+            $( stringify!($name), )*
+        ];
+    )
+}
+register_semver_member! {
+    parse => Type::function(
+        None,
+        Type::dict_ref(Type::str_ref(), Type::any_ref()),
+        &[
+            Parameter {
+                name: "version".to_string(),
+                ty: Type::str_ref(),
+                has_default: false,
+                default_value: None,
+                range: dummy_range(),
+            },
+        ],
+        r#"Parse the semantic version string `version` into a dict with the `major`, `minor`, `patch`, `pre` and `build` keys. A leading `v` is allowed."#,
+        false,
+        None,
+    )
+    validate => Type::function(
+        None,
+        Type::bool_ref(),
+        &[
+            Parameter {
+                name: "version".to_string(),
+                ty: Type::str_ref(),
+                has_default: false,
+                default_value: None,
+                range: dummy_range(),
+            },
+        ],
+        r#"Validate whether the given string is a valid semantic version."#,
+        false,
+        None,
+    )
+    compare => Type::function(
+        None,
+        Type::int_ref(),
+        &[
+            Parameter {
+                name: "v1".to_string(),
+                ty: Type::str_ref(),
+                has_default: false,
+                default_value: None,
+                range: dummy_range(),
+            },
+            Parameter {
+                name: "v2".to_string(),
+                ty: Type::str_ref(),
+                has_default: false,
+                default_value: None,
+                range: dummy_range(),
+            },
+        ],
+        r#"Compare two semantic versions and return -1, 0 or 1 when `v1` is lower than, equal to or greater than `v2`. Build metadata is ignored."#,
+        false,
+        None,
+    )
+    satisfies => Type::function(
+        None,
+        Type::bool_ref(),
+        &[
+            Parameter {
+                name: "version".to_string(),
+                ty: Type::str_ref(),
+                has_default: false,
+                default_value: None,
+                range: dummy_range(),
+            },
+            Parameter {
+                name: "range".to_string(),
+                ty: Type::str_ref(),
+                has_default: false,
+                default_value: None,
+                range: dummy_range(),
+            },
+        ],
+        r#"Whether the semantic version `version` satisfies the version range `range`, e.g., `^1.2`, `>=1.2.0, <2.0.0`, `~1.2 || ^2` or `1.2.3 - 1.5.0`."#,
+        false,
+        None,
+    )
+    bump_major => Type::function(
+        None,
+        Type::str_ref(),
+        &[
+            Parameter {
+                name: "version".to_string(),
+                ty: Type::str_ref(),
+                has_default: false,
+                default_value: None,
+                range: dummy_range(),
+            },
+        ],
+        r#"Increment the major version of `version` and reset the minor and patch versions, e.g., `1.2.3` to `2.0.0`."#,
+        false,
+        None,
+    )
+    bump_minor => Type::function(
+        None,
+        Type::str_ref(),
+        &[
+            Parameter {
+                name: "version".to_string(),
+                ty: Type::str_ref(),
+                has_default: false,
+                default_value: None,
+                range: dummy_range(),
+            },
+        ],
+        r#"Increment the minor version of `version` and reset the patch version, e.g., `1.2.3` to `1.3.0`."#,
+        false,
+        None,
+    )
+    bump_patch => Type::function(
+        None,
+        Type::str_ref(),
+        &[
+            Parameter {
+                name: "version".to_string(),
+                ty: Type::str_ref(),
+                has_default: false,
+                default_value: None,
+                range: dummy_range(),
+            },
+        ],
+        r#"Increment the patch version of `version`, e.g., `1.2.3` to `1.2.4`."#,
+        false,
+        None,
+    )
+}
+
 // ------------------------------
 // runtime system package
 // ------------------------------
@@ -2396,7 +3477,7 @@ register_runtime_member! {
 
 pub const STANDARD_SYSTEM_MODULES: &[&str] = &[
     COLLECTION, NET, MANIFESTS, MATH, DATETIME, REGEX, YAML, JSON, CRYPTO, BASE64, UNITS, FILE,
-    TEMPLATE, RUNTIME, BASE32,
+    TEMPLATE, RUNTIME, BASE32, TOML, SEMVER,
 ];
 
 pub const STANDARD_SYSTEM_MODULE_NAMES_WITH_AT: &[&str] = &[
@@ -2415,6 +3496,8 @@ pub const STANDARD_SYSTEM_MODULE_NAMES_WITH_AT: &[&str] = &[
     "@template",
     "@runtime",
     "@base32",
+    "@toml",
+    "@semver",
 ];
 
 /// Get the system module members
@@ -2429,6 +3512,7 @@ pub fn get_system_module_members(name: &str) -> Vec<&str> {
         REGEX => REGEX_FUNCTION_NAMES.to_vec(),
         YAML => YAML_FUNCTION_NAMES.to_vec(),
         JSON => JSON_FUNCTION_NAMES.to_vec(),
+        TOML => TOML_FUNCTION_NAMES.to_vec(),
         CRYPTO => CRYPTO_FUNCTION_NAMES.to_vec(),
         UNITS => {
             let mut members = UNITS_FUNCTION_NAMES.to_vec();
@@ -2439,6 +3523,7 @@ pub fn get_system_module_members(name: &str) -> Vec<&str> {
         FILE => FILE_FUNCTION_NAMES.to_vec(),
         TEMPLATE => TEMPLATE_FUNCTION_NAMES.to_vec(),
         RUNTIME => RUNTIME_FUNCTION_NAMES.to_vec(),
+        SEMVER => SEMVER_FUNCTION_NAMES.to_vec(),
         _ => bug!("invalid system module name '{}'", name),
     }
 }
@@ -2482,6 +3567,10 @@ pub fn get_system_member_function_ty(name: &str, func: &str) -> TypeRef {
             let types = &JSON_FUNCTION_TYPES;
             types.get(func).cloned()
         }
+        TOML => {
+            let types = &TOML_FUNCTION_TYPES;
+            types.get(func).cloned()
+        }
         CRYPTO => {
             let types = &CRYPTO_FUNCTION_TYPES;
             types.get(func).cloned()
@@ -2506,6 +3595,10 @@ pub fn get_system_member_function_ty(name: &str, func: &str) -> TypeRef {
             let types = &RUNTIME_FUNCTION_TYPES;
             types.get(func).cloned()
         }
+        SEMVER => {
+            let types = &SEMVER_FUNCTION_TYPES;
+            types.get(func).cloned()
+        }
         _ => None,
     };
     optional_ty.map(Arc::new).unwrap_or(Type::any_ref())
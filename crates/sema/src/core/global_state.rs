@@ -1,5 +1,6 @@
-use std::collections::HashSet;
+use std::collections::{HashSet, VecDeque};
 
+use kcl_ast::ast::Program;
 use kcl_error::Position;
 use kcl_primitives::{IndexMap, IndexSet};
 
@@ -720,6 +721,53 @@ impl GlobalState {
         self.sema_db.file_sema_map = file_sema_map_cache;
     }
 
+    /// Returns the packages of the module and all the packages that import them directly or
+    /// indirectly, which are the packages affected by a change of the module.
+    pub fn get_reverse_dependencies(&self, filename: &str) -> HashSet<String> {
+        match self.packages.get_module_info(filename) {
+            Some(module_info) => self.get_pkg_reverse_dependencies(&module_info.pkgpath),
+            None => HashSet::new(),
+        }
+    }
+
+    /// Returns the package and all the packages that import it directly or indirectly.
+    fn get_pkg_reverse_dependencies(&self, pkgpath: &str) -> HashSet<String> {
+        let mut pkgs = HashSet::new();
+        let mut queue = VecDeque::from([pkgpath.to_string()]);
+        while let Some(pkg) = queue.pop_front() {
+            if !pkgs.insert(pkg.clone()) {
+                continue;
+            }
+            for module_info in self.packages.module_info.values() {
+                if !pkgs.contains(&module_info.pkgpath)
+                    && module_info
+                        .imports
+                        .values()
+                        .any(|import| import.fully_qualified_name == pkg)
+                {
+                    queue.push_back(module_info.pkgpath.clone());
+                }
+            }
+        }
+        pkgs
+    }
+
+    /// Invalidates the packages affected by the change of the module, the cache of the other
+    /// packages is kept by the next [GlobalState::clear_cache]. A new module, which is not
+    /// resolved yet, invalidates its package in the program and the reverse dependencies.
+    pub fn invalidate_module(&mut self, filename: &str, program: &Program) {
+        let pkgs = match self.packages.get_module_info(filename) {
+            Some(module_info) => self.get_pkg_reverse_dependencies(&module_info.pkgpath),
+            None => program
+                .pkgs
+                .iter()
+                .filter(|(_, modules)| modules.iter().any(|module| module == filename))
+                .flat_map(|(pkg, _)| self.get_pkg_reverse_dependencies(pkg))
+                .collect(),
+        };
+        self.new_or_invalidate_pkgs.extend(pkgs);
+    }
+
     pub fn clear_cache(&mut self) {
         let invalidate_pkgs = self.new_or_invalidate_pkgs.clone();
         self.clear_sema_db_cache(&invalidate_pkgs);
@@ -1,12 +1,18 @@
 use crate::lint::lintpass::LintPass;
+use crate::lint::lints_def::ConstantCheck;
 use crate::lint::lints_def::ImportPosition;
+use crate::lint::lints_def::NoneComparison;
+use crate::lint::lints_def::OverriddenSchemaAttr;
 use crate::lint::lints_def::ReImport;
+use crate::lint::lints_def::ShadowedLoopVariable;
 use crate::lint::lints_def::UnusedImport;
-use crate::lint::types::{LintArray, LintContext};
+use crate::lint::lints_def::UnusedSchemaAttr;
+use crate::lint::lints_def::UnusedVariable;
+use crate::lint::types::{LintArray, LintContext, LintLevel};
 use crate::lint_methods;
 use crate::resolver::scope::Scope;
 use kcl_ast::ast;
-use kcl_error::Handler;
+use kcl_error::{Handler, Level};
 
 /// Call the `check_*` method of each lintpass in CombinedLintLass.check_*.
 /// ```ignore
@@ -32,11 +38,35 @@ macro_rules! expand_combined_lint_pass_method {
 macro_rules! expand_combined_lint_pass_methods {
     ($handler:ty, $ctx:ty, $passes:tt, [$($(#[$attr:meta])* fn $name:ident($($param:ident: $arg:ty),*);)*]) => (
         $(fn $name(&mut self, handler: &mut $handler, ctx: &mut $ctx, $($param: $arg),*) {
-            expand_combined_lint_pass_method!($passes, self, $name, (handler, ctx, $($param),*));
+            let mut lint_handler = <$handler>::default();
+            expand_combined_lint_pass_method!($passes, self, $name, (&mut lint_handler, ctx, $($param),*));
+            apply_lint_levels(handler, ctx, lint_handler);
         })*
     )
 }
 
+/// Applies the configured lint levels and the inline suppression comments to the diagnostics
+/// reported by the lint passes, and adds the remaining diagnostics into the handler.
+fn apply_lint_levels(handler: &mut Handler, ctx: &LintContext, lint_handler: Handler) {
+    for mut diag in lint_handler.diagnostics {
+        let code = diag.code.as_ref().map(|id| id.code()).unwrap_or_default();
+        if diag
+            .messages
+            .first()
+            .is_some_and(|msg| ctx.is_suppressed(&code, &msg.range.0))
+        {
+            continue;
+        }
+        match ctx.levels.get(&code) {
+            Some(LintLevel::Allow) => continue,
+            Some(LintLevel::Warn) => diag.level = Level::Warning,
+            Some(LintLevel::Deny) => diag.level = Level::Error,
+            None => {}
+        }
+        handler.add_diagnostic(diag);
+    }
+}
+
 /// Expand all definitions of `CombinedLintPass`. The results are as follows：
 ///
 /// ```ignore
@@ -118,6 +148,12 @@ macro_rules! default_lint_passes {
                 ImportPosition: ImportPosition,
                 UnusedImport: UnusedImport,
                 ReImport: ReImport,
+                UnusedVariable: UnusedVariable::default(),
+                UnusedSchemaAttr: UnusedSchemaAttr::default(),
+                ShadowedLoopVariable: ShadowedLoopVariable,
+                NoneComparison: NoneComparison,
+                OverriddenSchemaAttr: OverriddenSchemaAttr,
+                ConstantCheck: ConstantCheck,
             ]
         );
     };
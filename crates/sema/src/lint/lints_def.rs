@@ -1,11 +1,13 @@
 use crate::lint::lintpass::LintPass;
 use crate::lint::types::{Lint, LintArray, LintContext};
-use crate::resolver::scope::Scope;
-use crate::{declare_lint_pass, resolver::scope::ScopeObjectKind};
+use crate::resolver::scope::{Scope, ScopeKind};
+use crate::{declare_lint_pass, impl_lint_pass, resolver::scope::ScopeObjectKind};
 use kcl_ast::ast;
 use kcl_ast::pos::GetPos;
+use kcl_ast::walker::MutSelfWalker;
+use kcl_error::diagnostic::Range;
 use kcl_error::{Handler, Level, Message, Style, WarningKind};
-use kcl_primitives::IndexSet;
+use kcl_primitives::{IndexMap, IndexSet};
 
 /// The 'import_position' lint detects import statements that are not declared at the top of file.
 /// ### Example
@@ -109,7 +111,7 @@ impl LintPass for UnusedImport {
                                 style: Style::Line,
                                 message: format!("Module '{}' imported but unused", scope_obj.name),
                                 note: Some("Consider removing this statement".to_string()),
-                                suggested_replacement: None,
+                                suggested_replacement: Some(vec!["".to_string()]),
                             }],
                         );
                     }
@@ -164,7 +166,7 @@ impl LintPass for ReImport {
                                 &import_stmt.name
                             ),
                             note: Some("Consider removing this statement".to_string()),
-                            suggested_replacement: None,
+                            suggested_replacement: Some(vec!["".to_string()]),
                         }],
                     );
                 } else {
@@ -174,3 +176,581 @@ impl LintPass for ReImport {
         }
     }
 }
+
+/// Collects the names referenced by identifiers and assignment targets with member paths
+/// or augmented operators, which are regarded as usages of the variables.
+#[derive(Default)]
+struct NameCollector {
+    names: IndexSet<String>,
+}
+
+impl MutSelfWalker for NameCollector {
+    fn walk_identifier(&mut self, identifier: &ast::Identifier) {
+        for name in &identifier.names {
+            self.names.insert(name.node.clone());
+        }
+    }
+
+    fn walk_aug_assign_stmt(&mut self, aug_assign_stmt: &ast::AugAssignStmt) {
+        self.names
+            .insert(aug_assign_stmt.target.node.name.node.clone());
+        self.walk_target(&aug_assign_stmt.target.node);
+        self.walk_expr(&aug_assign_stmt.value.node);
+    }
+
+    fn walk_target(&mut self, target: &ast::Target) {
+        if !target.paths.is_empty() {
+            self.names.insert(target.name.node.clone());
+        }
+        for path in &target.paths {
+            if let ast::MemberOrIndex::Index(index) = path {
+                self.walk_expr(&index.node);
+            }
+        }
+    }
+}
+
+/// The 'unused_variable' lint detects private top-level variables that are defined but never used.
+///
+/// ### Example
+///
+/// ```kcl
+/// _name = "kcl"
+/// age = 1
+///
+/// ```
+/// ### Explanation
+///
+/// Private variables starting with `_` are not output, so a private variable that is never
+/// used in the program is dead code and is often a typo of another variable name.
+pub static UNUSED_VARIABLE: &Lint = &Lint {
+    name: stringify!("UNUSED_VARIABLE"),
+    level: Level::Warning,
+    desc: "Check for unused private variables",
+    code: "W0612",
+    note: Some("Consider removing this variable"),
+};
+
+/// The variable definitions are collected in `check_module` and reported in `check_scope`
+/// of the package scope, because private variables can be referenced by other files.
+#[derive(Default)]
+pub struct UnusedVariable {
+    /// Private variable definitions in each file.
+    defs: IndexMap<String, Vec<(String, Range)>>,
+    /// Used names in the whole program.
+    used_names: IndexSet<String>,
+}
+
+impl_lint_pass!(UnusedVariable => [UNUSED_VARIABLE]);
+
+impl LintPass for UnusedVariable {
+    fn check_module(
+        &mut self,
+        _handler: &mut Handler,
+        _ctx: &mut LintContext,
+        module: &ast::Module,
+    ) {
+        let mut defs: Vec<(String, Range)> = vec![];
+        for stmt in &module.body {
+            if let ast::Stmt::Assign(assign_stmt) = &stmt.node {
+                for target in &assign_stmt.targets {
+                    let name = &target.node.name.node;
+                    if target.node.paths.is_empty()
+                        && is_private_name(name)
+                        && !defs.iter().any(|(n, _)| n == name)
+                    {
+                        defs.push((name.clone(), target.node.name.get_span_pos()));
+                    }
+                }
+            }
+        }
+        let mut collector = NameCollector::default();
+        collector.walk_module(module);
+        self.defs.insert(module.filename.clone(), defs);
+        self.used_names.extend(collector.names);
+    }
+
+    fn check_scope(&mut self, handler: &mut Handler, _ctx: &mut LintContext, scope: &Scope) {
+        if let ScopeKind::Package(files) = &scope.kind {
+            for file in files {
+                for (name, range) in self.defs.swap_remove(file).unwrap_or_default() {
+                    if !self.used_names.contains(&name) {
+                        handler.add_warning(
+                            WarningKind::UnusedVariableWarning,
+                            &[Message {
+                                range,
+                                style: Style::Line,
+                                message: format!("Private variable '{name}' is defined but unused"),
+                                note: Some("Consider removing this variable".to_string()),
+                                suggested_replacement: None,
+                            }],
+                        );
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// The 'unused_schema_attr' lint detects private schema attributes that are never used.
+///
+/// ### Example
+///
+/// ```kcl
+/// schema Person:
+///     name: str
+///     _age: int = 1
+///
+/// ```
+/// ### Explanation
+///
+/// Private schema attributes are not output, so a private attribute that is never referenced
+/// in the program is dead code.
+pub static UNUSED_SCHEMA_ATTR: &Lint = &Lint {
+    name: stringify!("UNUSED_SCHEMA_ATTR"),
+    level: Level::Warning,
+    desc: "Check for unused private schema attributes",
+    code: "W0613",
+    note: Some("Consider removing this attribute"),
+};
+
+/// The attribute definitions are collected in `check_module` and reported in `check_scope`
+/// of the package scope, because private attributes can be referenced by sub-schemas and
+/// schema instances in other files.
+#[derive(Default)]
+pub struct UnusedSchemaAttr {
+    /// Private schema attribute definitions in each file: (schema name, attribute name, range).
+    defs: IndexMap<String, Vec<(String, String, Range)>>,
+    /// Used names in the whole program.
+    used_names: IndexSet<String>,
+}
+
+impl_lint_pass!(UnusedSchemaAttr => [UNUSED_SCHEMA_ATTR]);
+
+impl LintPass for UnusedSchemaAttr {
+    fn check_module(
+        &mut self,
+        _handler: &mut Handler,
+        _ctx: &mut LintContext,
+        module: &ast::Module,
+    ) {
+        let mut defs = vec![];
+        for stmt in &module.body {
+            if let ast::Stmt::Schema(schema_stmt) = &stmt.node {
+                for stmt in &schema_stmt.body {
+                    if let ast::Stmt::SchemaAttr(schema_attr) = &stmt.node
+                        && is_private_name(&schema_attr.name.node)
+                    {
+                        defs.push((
+                            schema_stmt.name.node.clone(),
+                            schema_attr.name.node.clone(),
+                            schema_attr.name.get_span_pos(),
+                        ));
+                    }
+                }
+            }
+        }
+        let mut collector = NameCollector::default();
+        collector.walk_module(module);
+        self.defs.insert(module.filename.clone(), defs);
+        self.used_names.extend(collector.names);
+    }
+
+    fn check_scope(&mut self, handler: &mut Handler, _ctx: &mut LintContext, scope: &Scope) {
+        if let ScopeKind::Package(files) = &scope.kind {
+            for file in files {
+                for (schema, name, range) in self.defs.swap_remove(file).unwrap_or_default() {
+                    if !self.used_names.contains(&name) {
+                        handler.add_warning(
+                            WarningKind::UnusedSchemaAttrWarning,
+                            &[Message {
+                                range,
+                                style: Style::Line,
+                                message: format!(
+                                    "Private attribute '{name}' of schema '{schema}' is defined but unused"
+                                ),
+                                note: Some("Consider removing this attribute".to_string()),
+                                suggested_replacement: None,
+                            }],
+                        );
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// Returns whether the variable or attribute name is private, e.g., `_name`.
+fn is_private_name(name: &str) -> bool {
+    name.starts_with('_')
+}
+
+/// The 'shadowed_loop_variable' lint detects loop variables of comprehensions and quantifier
+/// expressions that shadow the loop variables of the enclosing loops.
+///
+/// ### Example
+///
+/// ```kcl
+/// matrix = [[1, 2], [3, 4]]
+/// data = [[x * 2 for x in x] for x in matrix]
+///
+/// ```
+/// ### Explanation
+///
+/// The inner loop variable hides the outer one, so the outer one can not be referenced in the
+/// inner loop, which is confusing and error-prone. Consider renaming one of the loop variables.
+pub static SHADOWED_LOOP_VARIABLE: &Lint = &Lint {
+    name: stringify!("SHADOWED_LOOP_VARIABLE"),
+    level: Level::Warning,
+    desc: "Check for loop variables that shadow the variables of enclosing loops",
+    code: "W0621",
+    note: Some("Consider renaming this loop variable"),
+};
+
+declare_lint_pass!(ShadowedLoopVariable => [SHADOWED_LOOP_VARIABLE]);
+
+impl LintPass for ShadowedLoopVariable {
+    fn check_module(
+        &mut self,
+        handler: &mut Handler,
+        _ctx: &mut LintContext,
+        module: &ast::Module,
+    ) {
+        let mut checker = LoopVariableChecker {
+            handler,
+            loop_vars: vec![],
+        };
+        checker.walk_module(module);
+    }
+}
+
+/// Walks the loops and records the variables of the enclosing loops.
+struct LoopVariableChecker<'a> {
+    handler: &'a mut Handler,
+    loop_vars: Vec<String>,
+}
+
+impl LoopVariableChecker<'_> {
+    /// Checks the loop variables and pushes them into the loop variable stack.
+    fn enter_loop(&mut self, targets: &[ast::NodeRef<ast::Identifier>]) {
+        for target in targets {
+            let name = target.node.get_name();
+            if self.loop_vars.contains(&name) {
+                self.handler.add_warning(
+                    WarningKind::ShadowedLoopVariableWarning,
+                    &[Message {
+                        range: target.get_span_pos(),
+                        style: Style::Line,
+                        message: format!(
+                            "Loop variable '{name}' shadows the variable of an enclosing loop"
+                        ),
+                        note: Some("Consider renaming this loop variable".to_string()),
+                        suggested_replacement: None,
+                    }],
+                );
+            }
+        }
+        for target in targets {
+            self.loop_vars.push(target.node.get_name());
+        }
+    }
+
+    /// Walks the generators and then the loop body with the loop variables of the generators.
+    fn walk_generators(
+        &mut self,
+        generators: &[ast::NodeRef<ast::CompClause>],
+        body: impl FnOnce(&mut Self),
+    ) {
+        let len = self.loop_vars.len();
+        for generator in generators {
+            self.walk_expr(&generator.node.iter.node);
+            self.enter_loop(&generator.node.targets);
+            for if_expr in &generator.node.ifs {
+                self.walk_expr(&if_expr.node);
+            }
+        }
+        body(self);
+        self.loop_vars.truncate(len);
+    }
+}
+
+impl MutSelfWalker for LoopVariableChecker<'_> {
+    fn walk_list_comp(&mut self, list_comp: &ast::ListComp) {
+        self.walk_generators(&list_comp.generators, |checker| {
+            checker.walk_expr(&list_comp.elt.node)
+        });
+    }
+
+    fn walk_dict_comp(&mut self, dict_comp: &ast::DictComp) {
+        self.walk_generators(&dict_comp.generators, |checker| {
+            if let Some(key) = &dict_comp.entry.key {
+                checker.walk_expr(&key.node);
+            }
+            checker.walk_expr(&dict_comp.entry.value.node);
+        });
+    }
+
+    fn walk_quant_expr(&mut self, quant_expr: &ast::QuantExpr) {
+        self.walk_expr(&quant_expr.target.node);
+        let len = self.loop_vars.len();
+        self.enter_loop(&quant_expr.variables);
+        self.walk_expr(&quant_expr.test.node);
+        if let Some(if_cond) = &quant_expr.if_cond {
+            self.walk_expr(&if_cond.node);
+        }
+        self.loop_vars.truncate(len);
+    }
+}
+
+/// The 'none_comparison' lint detects comparisons to `None` or `Undefined` using `==` or `!=`.
+///
+/// ### Example
+///
+/// ```kcl
+/// a = None
+/// b = a == None
+///
+/// ```
+/// ### Explanation
+///
+/// Use `is` or `is not` to check whether a value is `None` or `Undefined`. The operators
+/// `==` and `!=` compare the values, e.g., `None == Undefined` is `True`.
+pub static NONE_COMPARISON: &Lint = &Lint {
+    name: stringify!("NONE_COMPARISON"),
+    level: Level::Warning,
+    desc: "Check for comparisons to None or Undefined using == or !=",
+    code: "W0121",
+    note: Some("Consider using 'is' or 'is not' instead"),
+};
+
+declare_lint_pass!(NoneComparison => [NONE_COMPARISON]);
+
+impl LintPass for NoneComparison {
+    fn check_module(
+        &mut self,
+        handler: &mut Handler,
+        _ctx: &mut LintContext,
+        module: &ast::Module,
+    ) {
+        let mut checker = NoneComparisonChecker { handler };
+        checker.walk_module(module);
+    }
+}
+
+struct NoneComparisonChecker<'a> {
+    handler: &'a mut Handler,
+}
+
+impl MutSelfWalker for NoneComparisonChecker<'_> {
+    fn walk_compare(&mut self, compare: &ast::Compare) {
+        let mut left = &compare.left;
+        for (op, right) in compare.ops.iter().zip(&compare.comparators) {
+            let (op_symbol, suggested_op) = match op {
+                ast::CmpOp::Eq => (op.symbol(), ast::CmpOp::Is.symbol()),
+                ast::CmpOp::NotEq => (op.symbol(), ast::CmpOp::IsNot.symbol()),
+                _ => {
+                    left = right;
+                    continue;
+                }
+            };
+            if let Some(constant) = none_constant(left).or_else(|| none_constant(right)) {
+                self.handler.add_warning(
+                    WarningKind::NoneComparisonWarning,
+                    &[Message {
+                        // The range between the operands, which contains the operator.
+                        range: (left.get_end_pos(), right.get_pos()),
+                        style: Style::Line,
+                        message: format!(
+                            "Comparison to {constant} should use '{suggested_op}' instead of '{op_symbol}'"
+                        ),
+                        note: Some("Consider using 'is' or 'is not' instead".to_string()),
+                        suggested_replacement: Some(vec![format!(" {suggested_op} ")]),
+                    }],
+                );
+            }
+            left = right;
+        }
+        self.walk_expr(&compare.left.node);
+        for comparator in &compare.comparators {
+            self.walk_expr(&comparator.node);
+        }
+    }
+}
+
+/// Returns the symbol of the expression if it is `None` or `Undefined`.
+fn none_constant(expr: &ast::NodeRef<ast::Expr>) -> Option<&'static str> {
+    match &expr.node {
+        ast::Expr::NameConstantLit(lit)
+            if matches!(
+                lit.value,
+                ast::NameConstant::None | ast::NameConstant::Undefined
+            ) =>
+        {
+            Some(lit.value.symbol())
+        }
+        _ => None,
+    }
+}
+
+/// The 'overridden_schema_attr' lint detects default values of schema attributes that are always
+/// overridden by an assignment in the schema body.
+///
+/// ### Example
+///
+/// ```kcl
+/// schema Person:
+///     name: str = "Alice"
+///     name = "Bob"
+///
+/// ```
+/// ### Explanation
+///
+/// The default value is never used because the attribute is unconditionally reassigned in
+/// the schema body. Consider removing the default value or the assignment.
+pub static OVERRIDDEN_SCHEMA_ATTR: &Lint = &Lint {
+    name: stringify!("OVERRIDDEN_SCHEMA_ATTR"),
+    level: Level::Warning,
+    desc: "Check for schema attribute default values that are always overridden",
+    code: "W0614",
+    note: Some("Consider removing the default value or the assignment"),
+};
+
+declare_lint_pass!(OverriddenSchemaAttr => [OVERRIDDEN_SCHEMA_ATTR]);
+
+impl LintPass for OverriddenSchemaAttr {
+    fn check_module(
+        &mut self,
+        handler: &mut Handler,
+        _ctx: &mut LintContext,
+        module: &ast::Module,
+    ) {
+        for stmt in &module.body {
+            let ast::Stmt::Schema(schema_stmt) = &stmt.node else {
+                continue;
+            };
+            // Attribute name -> the range of its default value.
+            let mut defaults = IndexMap::<String, Range>::default();
+            for stmt in &schema_stmt.body {
+                match &stmt.node {
+                    ast::Stmt::SchemaAttr(schema_attr) => match &schema_attr.value {
+                        Some(value) if matches!(schema_attr.op, Some(ast::AugOp::Assign)) => {
+                            defaults.insert(schema_attr.name.node.clone(), value.get_span_pos());
+                        }
+                        _ => {
+                            defaults.swap_remove(&schema_attr.name.node);
+                        }
+                    },
+                    ast::Stmt::Assign(assign_stmt) => {
+                        let mut collector = NameCollector::default();
+                        collector.walk_expr(&assign_stmt.value.node);
+                        for target in &assign_stmt.targets {
+                            let name = &target.node.name.node;
+                            if !target.node.paths.is_empty() || collector.names.contains(name) {
+                                defaults.swap_remove(name);
+                                continue;
+                            }
+                            if let Some(range) = defaults.swap_remove(name) {
+                                handler.add_warning(
+                                    WarningKind::OverriddenSchemaAttrWarning,
+                                    &[Message {
+                                        range,
+                                        style: Style::Line,
+                                        message: format!(
+                                            "The default value of attribute '{}' is always overridden at line {}",
+                                            name, target.line
+                                        ),
+                                        note: Some(
+                                            "Consider removing the default value or the assignment"
+                                                .to_string(),
+                                        ),
+                                        suggested_replacement: None,
+                                    }],
+                                );
+                            }
+                        }
+                    }
+                    _ => {
+                        // The attribute may be used by other statements before it is reassigned.
+                        let mut collector = NameCollector::default();
+                        collector.walk_stmt(&stmt.node);
+                        defaults.retain(|name, _| !collector.names.contains(name));
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// The 'constant_check' lint detects check expressions whose test is a constant.
+///
+/// ### Example
+///
+/// ```kcl
+/// schema Person:
+///     name: str
+///
+///     check:
+///         True
+///
+/// ```
+/// ### Explanation
+///
+/// A constant check expression always succeeds or always fails regardless of the schema
+/// attributes, which is usually a mistake.
+pub static CONSTANT_CHECK: &Lint = &Lint {
+    name: stringify!("CONSTANT_CHECK"),
+    level: Level::Warning,
+    desc: "Check for check expressions with constant conditions",
+    code: "W0125",
+    note: Some("Consider checking the schema attributes"),
+};
+
+declare_lint_pass!(ConstantCheck => [CONSTANT_CHECK]);
+
+impl LintPass for ConstantCheck {
+    fn check_module(
+        &mut self,
+        handler: &mut Handler,
+        _ctx: &mut LintContext,
+        module: &ast::Module,
+    ) {
+        for stmt in &module.body {
+            let checks = match &stmt.node {
+                ast::Stmt::Schema(schema_stmt) => &schema_stmt.checks,
+                ast::Stmt::Rule(rule_stmt) => &rule_stmt.checks,
+                _ => continue,
+            };
+            for check in checks {
+                if let Some(value) = constant_truth_value(&check.node.test.node) {
+                    let result = if value { "succeeds" } else { "fails" };
+                    handler.add_warning(
+                        WarningKind::ConstantCheckWarning,
+                        &[Message {
+                            range: check.node.test.get_span_pos(),
+                            style: Style::Line,
+                            message: format!(
+                                "The check expression is a constant and always {result}"
+                            ),
+                            note: Some("Consider checking the schema attributes".to_string()),
+                            suggested_replacement: None,
+                        }],
+                    );
+                }
+            }
+        }
+    }
+}
+
+/// Returns the truth value of the expression if it is a constant literal.
+fn constant_truth_value(expr: &ast::Expr) -> Option<bool> {
+    match expr {
+        ast::Expr::NameConstantLit(lit) => Some(matches!(lit.value, ast::NameConstant::True)),
+        ast::Expr::NumberLit(lit) => Some(match lit.value {
+            ast::NumberLitValue::Int(v) => v != 0,
+            ast::NumberLitValue::Float(v) => v != 0.0,
+        }),
+        ast::Expr::StringLit(lit) => Some(!lit.value.is_empty()),
+        ast::Expr::Paren(paren_expr) => constant_truth_value(&paren_expr.expr.node),
+        _ => None,
+    }
+}
@@ -22,6 +22,10 @@
 //!    }
 //!    ```
 //!
+//!    The lint code e.g., `W0413` should also be registered with its `WarningKind` in `kcl_error`, so that
+//!    the reported diagnostics carry the code, which is used by the `[lint]` section of `kcl.mod` and the
+//!    inline `# kcl-lint: allow(W0413)` comments to change the lint level or suppress the lint.
+//!
 //! 2. Define a lintpass, which is used to implement the checking process，e.g.,
 //!    
 //!     ```ignore
@@ -84,7 +88,14 @@ mod types;
 use kcl_ast::ast;
 use kcl_ast::walker::MutSelfWalker;
 
-pub use self::{combinedlintpass::CombinedLintPass, lintpass::LintPass, types::LintContext};
+pub use self::{
+    combinedlintpass::CombinedLintPass,
+    lintpass::LintPass,
+    types::{LintContext, LintLevel},
+};
+
+/// The prefix of the inline lint suppression comment e.g., `# kcl-lint: allow(W0411)`.
+const LINT_COMMENT_PREFIX: &str = "kcl-lint:";
 
 /// The struct `Linter` is used to traverse the AST and call the `check_*` method defined in `CombinedLintPass`.
 pub struct Linter<T: LintPass> {
@@ -99,8 +110,46 @@ impl LintContext {
             filename: "".to_string(),
             start_pos: Position::dummy_pos(),
             end_pos: Position::dummy_pos(),
+            levels: Default::default(),
+            suppressions: Default::default(),
+        }
+    }
+
+    /// Records the lint codes suppressed by the `# kcl-lint: allow(W0411, ...)` comments in the
+    /// module. A suppression comment applies to its own line and the next line.
+    pub fn add_suppressions(&mut self, module: &ast::Module) {
+        for comment in &module.comments {
+            let text = comment.node.text.trim_start_matches('#').trim();
+            let Some(codes) = text
+                .strip_prefix(LINT_COMMENT_PREFIX)
+                .map(|s| s.trim())
+                .and_then(|s| s.strip_prefix("allow("))
+                .and_then(|s| s.strip_suffix(')'))
+            else {
+                continue;
+            };
+            let lines = self
+                .suppressions
+                .entry(module.filename.clone())
+                .or_default();
+            for line in [comment.line, comment.end_line + 1] {
+                lines.entry(line).or_default().extend(
+                    codes
+                        .split(',')
+                        .map(|code| code.trim().to_string())
+                        .filter(|code| !code.is_empty()),
+                );
+            }
         }
     }
+
+    /// Returns whether the lint code is suppressed at the position.
+    pub fn is_suppressed(&self, code: &str, pos: &Position) -> bool {
+        self.suppressions
+            .get(&pos.filename)
+            .and_then(|lines| lines.get(&pos.line))
+            .is_some_and(|codes| codes.contains(code))
+    }
 }
 
 impl Default for Linter<CombinedLintPass> {
@@ -127,6 +176,7 @@ impl Resolver<'_> {
     /// Iterate the module and run lint checks, generating diagnostics and save them in `lint.handler`
     pub fn lint_check_module(&mut self, module: &ast::Module) {
         self.linter.ctx.filename = module.filename.clone();
+        self.linter.ctx.add_suppressions(module);
         self.linter.walk_module(module);
     }
     /// Recursively iterate the scope and its child scope, run lint checks, generating diagnostics and save them in `lint.handler`
@@ -1,4 +1,5 @@
 use kcl_error::{Level, Position};
+use kcl_primitives::{IndexMap, IndexSet};
 
 /// Record the information at `LintContext` when traversing the AST for analysis across AST nodes, e.g., record
 /// used importstmt(used_import_names) when traversing `ast::Identifier` and `ast::SchemaAttr`, and detect unused
@@ -10,6 +11,21 @@ pub struct LintContext {
     pub start_pos: Position,
     /// Are we resolving the ast node end position.
     pub end_pos: Position,
+    /// Lint levels by lint code e.g., `W0411`, which override the default lint levels.
+    pub levels: IndexMap<String, LintLevel>,
+    /// Lint codes suppressed by the inline `# kcl-lint: allow(...)` comments, by filename and line.
+    pub suppressions: IndexMap<String, IndexMap<u64, IndexSet<String>>>,
+}
+
+/// The level of a lint configured by the `[lint]` section of `kcl.mod`.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum LintLevel {
+    /// The lint is not reported.
+    Allow,
+    /// The lint is reported as a warning.
+    Warn,
+    /// The lint is reported as an error.
+    Deny,
 }
 
 /// Definition of `Lint` struct
@@ -299,6 +299,7 @@ mod tests {
     use crate::core::symbol::SymbolKind;
     use kcl_parser::ParseSession;
     use kcl_parser::load_program;
+    use std::collections::HashSet;
     use std::sync::Arc;
 
     #[test]
@@ -374,4 +375,58 @@ mod tests {
             );
         }
     }
+
+    #[test]
+    fn test_reverse_dependencies() {
+        let sess = Arc::new(ParseSession::default());
+        let program = load_program(
+            sess.clone(),
+            &["./src/namer/test_data/schema_symbols.k"],
+            None,
+            None,
+        )
+        .unwrap()
+        .program;
+        let mut gs = GlobalState::default();
+        Namer::find_symbols(&program, &mut gs);
+
+        let filename = |pkg: &str| {
+            gs.get_packages()
+                .get_package_info(pkg)
+                .unwrap()
+                .get_kfile_paths()
+                .first()
+                .unwrap()
+                .clone()
+        };
+        let a = filename("import_test.a");
+        let main = filename("__main__");
+
+        let expected_a: HashSet<String> = ["import_test.a", "__main__"]
+            .iter()
+            .map(|s| s.to_string())
+            .collect();
+        assert_eq!(gs.get_reverse_dependencies(&a), expected_a);
+        let expected: HashSet<String> = ["__main__".to_string()].into_iter().collect();
+        assert_eq!(gs.get_reverse_dependencies(&main), expected);
+        assert!(gs.get_reverse_dependencies("not_exist.k").is_empty());
+
+        gs.new_or_invalidate_pkgs.clear();
+        gs.invalidate_module(&a, &program);
+        assert!(gs.new_or_invalidate_pkgs.contains("import_test.a"));
+        assert!(gs.new_or_invalidate_pkgs.contains("__main__"));
+        assert!(!gs.new_or_invalidate_pkgs.contains("pkg"));
+
+        // A new module of the program invalidates its package and the reverse dependencies.
+        let mut program = program;
+        let new_module = a.replace(".k", "_new.k");
+        program
+            .pkgs
+            .get_mut("import_test.a")
+            .unwrap()
+            .push(new_module.clone());
+        gs.new_or_invalidate_pkgs.clear();
+        gs.invalidate_module(&new_module, &program);
+        assert_eq!(gs.new_or_invalidate_pkgs, expected_a);
+    }
 }
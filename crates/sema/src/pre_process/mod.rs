@@ -5,6 +5,7 @@ mod multi_assign;
 
 use kcl_ast::ast;
 use kcl_primitives::IndexMap;
+use kcl_utils::parallel::par_map;
 
 #[cfg(test)]
 mod tests;
@@ -17,11 +18,16 @@ pub use multi_assign::transform_multi_assign;
 use crate::resolver::Options;
 
 /// Pre-process AST program.
+///
+/// The modules are transformed independently of each other, so they are transformed
+/// concurrently. Note that the resolver still checks the packages sequentially, because
+/// the package scopes are shared through `Rc<RefCell<Scope>>` references.
 pub fn pre_process_program(program: &mut ast::Program, opts: &Options) {
-    for (pkgpath, modules) in program.pkgs.iter() {
+    let mut modules = vec![];
+    for (pkgpath, pkg_modules) in program.pkgs.iter() {
         let mut import_names = IndexMap::default();
         if pkgpath == kcl_ast::MAIN_PKG {
-            for module in modules.iter() {
+            for module in pkg_modules.iter() {
                 let module = program
                     .get_module(module)
                     .expect("Failed to acquire module lock")
@@ -34,21 +40,36 @@ pub fn pre_process_program(program: &mut ast::Program, opts: &Options) {
                 }
             }
         }
-        for module in modules.iter() {
-            let mut module = program
-                .get_module_mut(module)
-                .expect("Failed to acquire module lock")
+        for module in pkg_modules.iter() {
+            let module_ref = program
+                .modules
+                .get(module)
+                .or(program.modules_not_imported.get(module))
                 .unwrap_or_else(|| panic!("module {:?} not found in program", module));
-            if pkgpath != kcl_ast::MAIN_PKG {
-                import_names.clear();
+            if pkgpath == kcl_ast::MAIN_PKG {
+                // The import names of the main package modules accumulate in the module order.
+                let module = module_ref.read().expect("Failed to acquire module lock");
+                for stmt in &module.body {
+                    if let ast::Stmt::Import(import_stmt) = &stmt.node {
+                        import_names
+                            .insert(import_stmt.name.clone(), import_stmt.path.node.clone());
+                    }
+                }
+                modules.push((module_ref.clone(), import_names.clone()));
+            } else {
+                modules.push((module_ref.clone(), IndexMap::default()));
             }
-            // First we should transform the raw identifier to avoid raw identifier that happens to be a package path.
-            fix_raw_identifier_prefix(&mut module);
-            fix_qualified_identifier(&mut module, &mut import_names);
-            fix_config_expr_nest_attr(&mut module);
-            fix_lit_ty_default_value(&mut module);
         }
     }
+    par_map(&modules, |(module, import_names)| {
+        let mut module = module.write().expect("Failed to acquire module lock");
+        let mut import_names = import_names.clone();
+        // First we should transform the raw identifier to avoid raw identifier that happens to be a package path.
+        fix_raw_identifier_prefix(&mut module);
+        fix_qualified_identifier(&mut module, &mut import_names);
+        fix_config_expr_nest_attr(&mut module);
+        fix_lit_ty_default_value(&mut module);
+    });
     if opts.merge_program {
         merge_program(program);
     }
@@ -24,7 +24,7 @@ use kcl_primitives::{IndexMap, IndexSet};
 use std::sync::Arc;
 use std::{cell::RefCell, rc::Rc};
 
-use crate::lint::{CombinedLintPass, Linter};
+use crate::lint::{CombinedLintPass, LintLevel, Linter};
 use crate::pre_process::pre_process_program;
 use crate::resolver::scope::ScopeObject;
 use crate::resolver::ty_alias::type_alias_pass;
@@ -55,6 +55,8 @@ impl<'ctx> Resolver<'ctx> {
     pub fn new(program: &'ctx Program, options: Options) -> Self {
         let builtin_scope = Rc::new(RefCell::new(builtin_scope()));
         let scope = Rc::clone(&builtin_scope);
+        let mut linter = Linter::<CombinedLintPass>::new();
+        linter.ctx.levels = options.lint_levels.clone();
         Resolver {
             program,
             scope_map: IndexMap::default(),
@@ -65,7 +67,7 @@ impl<'ctx> Resolver<'ctx> {
             ctx: Context::default(),
             options,
             handler: Handler::default(),
-            linter: Linter::<CombinedLintPass>::new(),
+            linter,
         }
     }
 
@@ -166,12 +168,14 @@ pub struct Context {
 /// Resolve options.
 /// - lint_check: whether to run lint passes
 /// - resolve_val: whether to resolve and print their AST to value for some nodes.
+/// - lint_levels: lint levels by lint code e.g., `W0411`, usually loaded from the `[lint]` section of `kcl.mod`.
 #[derive(Clone, Debug)]
 pub struct Options {
     pub lint_check: bool,
     pub resolve_val: bool,
     pub merge_program: bool,
     pub type_erasure: bool,
+    pub lint_levels: IndexMap<String, LintLevel>,
 }
 
 impl Default for Options {
@@ -181,6 +185,7 @@ impl Default for Options {
             resolve_val: false,
             merge_program: true,
             type_erasure: true,
+            lint_levels: IndexMap::default(),
         }
     }
 }
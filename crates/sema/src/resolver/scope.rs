@@ -564,7 +564,9 @@ impl DependencyGraph {
         invalidate_pkg_modules: &Option<HashSet<String>>,
     ) -> Result<HashSet<String>, String> {
         let mut new_modules = HashMap::new();
-        for (pkgpath, modules) in program.pkgs.iter() {
+        // Packages not imported by the main package are cached too, so their changes must be
+        // tracked to invalidate them.
+        for (pkgpath, modules) in program.pkgs.iter().chain(program.pkgs_not_imported.iter()) {
             if pkgpath == kcl_ast::MAIN_PKG {
                 continue;
             }
@@ -225,8 +225,14 @@ fn test_resolve_program_redefine() {
 fn test_resolve_program_mismatch_type_fail() {
     let mut program = parse_program("./src/resolver/test_fail_data/config_expr.k").unwrap();
     let scope = resolve_program(&mut program);
-    assert_eq!(scope.handler.diagnostics.len(), 1);
-    let diag = &scope.handler.diagnostics[0];
+    let errors: Vec<_> = scope
+        .handler
+        .diagnostics
+        .iter()
+        .filter(|diag| diag.level == Level::Error)
+        .collect();
+    assert_eq!(errors.len(), 1);
+    let diag = errors[0];
     assert_eq!(diag.code, Some(DiagnosticId::Error(ErrorKind::TypeError)));
     assert_eq!(diag.messages.len(), 1);
     assert_eq!(
@@ -257,6 +263,8 @@ fn test_resolve_program_cycle_reference_fail() {
         "There is a circular reference between schemas file1.A, file1.B, file1.C",
         "There is a circular reference between schemas file1.A, file1.B, file1.C",
         "There is a circular reference between schemas file1.A, file1.B, file1.C",
+        "The check expression is a constant and always succeeds",
+        "The check expression is a constant and always succeeds",
         "Module 'file1' imported but unused",
         "Module 'file2' imported but unused",
     ];
@@ -448,7 +456,7 @@ fn test_lint() {
             style: Style::Line,
             message: "Module 'a' is reimported multiple times".to_string(),
             note: Some("Consider removing this statement".to_string()),
-            suggested_replacement: None,
+            suggested_replacement: Some(vec!["".to_string()]),
         }],
     );
     handler.add_warning(
@@ -469,7 +477,7 @@ fn test_lint() {
             style: Style::Line,
             message: "Module 'a' imported but unused".to_string(),
             note: Some("Consider removing this statement".to_string()),
-            suggested_replacement: None,
+            suggested_replacement: Some(vec!["".to_string()]),
         }],
     );
     for (d1, d2) in resolver
@@ -638,6 +646,24 @@ fn test_system_package() {
             .ty
             .is_func()
     );
+
+    assert!(main_scope.lookup("toml").unwrap().borrow().ty.is_module());
+    assert!(
+        main_scope
+            .lookup("toml_encode")
+            .unwrap()
+            .borrow()
+            .ty
+            .is_func()
+    );
+    assert!(
+        main_scope
+            .lookup("toml_decode")
+            .unwrap()
+            .borrow()
+            .ty
+            .is_func()
+    );
 }
 
 #[test]
@@ -736,8 +762,14 @@ fn test_resolve_lambda_assignment_diagnostic() {
     .unwrap()
     .program;
     let scope = resolve_program(&mut program);
-    assert_eq!(scope.handler.diagnostics.len(), 1);
-    let diag = &scope.handler.diagnostics[0];
+    let errors: Vec<_> = scope
+        .handler
+        .diagnostics
+        .iter()
+        .filter(|diag| diag.level == Level::Error)
+        .collect();
+    assert_eq!(errors.len(), 1);
+    let diag = errors[0];
     assert_eq!(diag.code, Some(DiagnosticId::Error(ErrorKind::TypeError)));
     assert_eq!(diag.messages.len(), 1);
     assert_eq!(
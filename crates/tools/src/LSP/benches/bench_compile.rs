@@ -0,0 +1,129 @@
+use std::path::Path;
+use std::sync::{Arc, Mutex};
+
+use criterion::{BatchSize, BenchmarkId, Criterion, criterion_group, criterion_main};
+
+use kcl_driver::{lookup_compile_workspace, toolchain};
+use kcl_language_server::compile::{Params, compile};
+use kcl_parser::KCLModuleCache;
+use kcl_sema::core::global_state::GlobalState;
+use kcl_sema::resolver::scope::KCLScopeCache;
+use kcl_utils::path::PathPrefix;
+
+const PKG_COUNT: usize = 40;
+const SCHEMA_COUNT: usize = 20;
+
+/// Generate a workspace with the packages imported by the main file, where `pkg1` imports
+/// `pkg0`. Returns the main file and the file of `pkg0`, which is changed between the
+/// incremental compilations.
+fn gen_workspace(root: &Path) -> (String, String) {
+    let mut main = String::new();
+    for pkg in 0..PKG_COUNT {
+        let pkg_dir = root.join(format!("pkg{pkg}"));
+        std::fs::create_dir_all(&pkg_dir).unwrap();
+        let mut code = String::new();
+        if pkg == 1 {
+            code.push_str("import pkg0\n\nfrom_pkg0 = pkg0.value0\n");
+        }
+        for schema in 0..SCHEMA_COUNT {
+            code.push_str(&format!(
+                r#"schema Schema{schema}:
+    name: str = "{schema}"
+    items: [int] = [x * {schema} for x in range(10)]
+
+    check:
+        len(name) > 0
+
+value{schema} = Schema{schema} {{}}
+"#
+            ));
+        }
+        std::fs::write(pkg_dir.join("pkg.k"), code).unwrap();
+        main.push_str(&format!("import pkg{pkg}\nv{pkg} = pkg{pkg}.value0\n"));
+    }
+    std::fs::write(root.join("kcl.mod"), "[package]\nname = \"bench\"\n").unwrap();
+    std::fs::write(root.join("main.k"), main).unwrap();
+    let canonicalize = |path: &Path| {
+        path.canonicalize()
+            .unwrap()
+            .display()
+            .to_string()
+            .adjust_canonicalization()
+    };
+    (
+        canonicalize(&root.join("main.k")),
+        canonicalize(&root.join("pkg0").join("pkg.k")),
+    )
+}
+
+/// The caches kept by the language server between the compilations.
+#[derive(Default, Clone)]
+struct Caches {
+    module_cache: KCLModuleCache,
+    scope_cache: KCLScopeCache,
+    gs_cache: Arc<Mutex<GlobalState>>,
+}
+
+fn compile_workspace(file: &str, main_file: &str, caches: &Caches) {
+    let (mut files, opts, _) = lookup_compile_workspace(&toolchain::default(), main_file, true);
+    let (_, result) = compile(
+        Params {
+            file: Some(file.to_string()),
+            module_cache: Some(caches.module_cache.clone()),
+            scope_cache: Some(caches.scope_cache.clone()),
+            vfs: None,
+            gs_cache: Some(caches.gs_cache.clone()),
+        },
+        &mut files,
+        opts,
+    );
+    result.unwrap();
+}
+
+pub fn criterion_benchmark(c: &mut Criterion) {
+    let root = tempfile::tempdir().unwrap();
+    let (main_file, changed_file) = gen_workspace(root.path());
+    let code = std::fs::read_to_string(&changed_file).unwrap();
+    let mut group = c.benchmark_group("lsp_compile");
+    group.sample_size(20);
+    // A full compilation of the workspace with the empty caches, e.g., the first compilation
+    // after the language server starts.
+    group.bench_with_input(
+        BenchmarkId::new("full", PKG_COUNT),
+        &main_file,
+        |b, main_file| {
+            b.iter_batched(
+                Caches::default,
+                |caches| compile_workspace(main_file, main_file, &caches),
+                BatchSize::PerIteration,
+            )
+        },
+    );
+    // A compilation of the workspace with the warm caches after `pkg0` is changed, only
+    // `pkg0` and the packages importing it are resolved again.
+    let caches = Caches::default();
+    compile_workspace(&main_file, &main_file, &caches);
+    let mut version = 0;
+    group.bench_with_input(
+        BenchmarkId::new("incremental", PKG_COUNT),
+        &main_file,
+        |b, main_file| {
+            b.iter_batched(
+                || {
+                    version += 1;
+                    std::fs::write(
+                        &changed_file,
+                        format!("{code}\nschema Added{version}:\n    name: str\n"),
+                    )
+                    .unwrap();
+                },
+                |_| compile_workspace(&changed_file, main_file, &caches),
+                BatchSize::PerIteration,
+            )
+        },
+    );
+    group.finish();
+}
+
+criterion_group!(benches, criterion_benchmark);
+criterion_main!(benches);
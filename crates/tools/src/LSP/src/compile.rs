@@ -16,6 +16,7 @@ use kcl_sema::{
     resolver::{resolve_program_with_opts, scope::KCLScopeCache},
     ty::SchemaType,
 };
+use kcl_tools::lint::load_lint_levels;
 use std::collections::HashSet;
 use std::path::PathBuf;
 
@@ -119,11 +120,13 @@ pub fn compile(
         cached_scope.invalidate_pkg_modules = Some(invalidate_pkg_modules);
     }
 
+    let lint_levels = load_lint_levels(&program.root);
     let prog_scope = resolve_program_with_opts(
         &mut program,
         kcl_sema::resolver::Options {
             merge_program: false,
             type_erasure: false,
+            lint_levels,
             ..Default::default()
         },
         params.scope_cache.clone(),
@@ -153,6 +156,11 @@ pub fn compile(
         None => HashSet::new(),
     };
 
+    // Only invalidate the changed module and its reverse dependencies, the scope cache may
+    // be locked by another compilation and return no invalidated packages.
+    if let Some(file) = &params.file {
+        gs.invalidate_module(file, &program);
+    }
     gs.new_or_invalidate_pkgs
         .extend(program.pkgs_not_imported.keys().cloned());
 
@@ -1389,6 +1389,8 @@ mod tests {
                 "template",
                 "runtime",
                 "base32",
+                "toml",
+                "semver",
             ]
             .iter()
             .map(|name| KCLCompletionItem {
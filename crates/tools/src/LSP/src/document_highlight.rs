@@ -0,0 +1,111 @@
+use kcl_error::Position as KCLPos;
+use kcl_sema::core::global_state::GlobalState;
+use lsp_types::{DocumentHighlight, DocumentHighlightKind, Range};
+
+use crate::to_lsp::lsp_pos;
+
+/// Returns the highlights of the symbol at the position in the current file. The definition
+/// is highlighted as a write and all the references are highlighted as reads.
+pub fn document_highlight(kcl_pos: &KCLPos, gs: &GlobalState) -> Option<Vec<DocumentHighlight>> {
+    let symbol_ref = gs.look_up_exact_symbol(kcl_pos)?;
+    let symbol = gs.get_symbols().get_symbol(symbol_ref)?;
+    let def_ref = symbol.get_definition()?;
+    let def = gs.get_symbols().get_symbol(def_ref)?;
+
+    let mut highlights: Vec<DocumentHighlight> = vec![];
+    let mut push = |(start, end): (KCLPos, KCLPos), kind: DocumentHighlightKind| {
+        // filter current file symbols
+        if start.filename != kcl_pos.filename {
+            return;
+        }
+        let range = Range {
+            start: lsp_pos(&start),
+            end: lsp_pos(&end),
+        };
+        if !highlights.iter().any(|h| h.range == range) {
+            highlights.push(DocumentHighlight {
+                range,
+                kind: Some(kind),
+            });
+        }
+    };
+    push(def.get_range(), DocumentHighlightKind::WRITE);
+    push(symbol.get_range(), DocumentHighlightKind::READ);
+    for reference in def.get_references() {
+        if let Some(reference) = gs.get_symbols().get_symbol(reference) {
+            push(reference.get_range(), DocumentHighlightKind::READ);
+        }
+    }
+    highlights.sort_by_key(|h| (h.range.start.line, h.range.start.character));
+    Some(highlights)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::document_highlight;
+    use crate::tests::compile_test_file;
+    use kcl_error::Position as KCLPos;
+    use lsp_types::DocumentHighlight;
+
+    fn fmt_resp(resp: &Option<Vec<DocumentHighlight>>) -> String {
+        match resp {
+            Some(resp) => {
+                let mut res = String::new();
+                for highlight in resp {
+                    res.push_str(&format!(
+                        "range: {:?}, kind: {:?}\n",
+                        highlight.range, highlight.kind
+                    ));
+                }
+                res
+            }
+            None => "None".to_string(),
+        }
+    }
+
+    #[macro_export]
+    macro_rules! document_highlight_test_snapshot {
+        ($name:ident, $file:expr, $line:expr, $column: expr) => {
+            #[test]
+            fn $name() {
+                let (file, _program, _, gs, _) = compile_test_file($file);
+
+                let pos = KCLPos {
+                    filename: file.clone(),
+                    line: $line,
+                    column: Some($column),
+                };
+                let res = document_highlight(&pos, &gs);
+                insta::assert_snapshot!(fmt_resp(&res));
+            }
+        };
+    }
+
+    document_highlight_test_snapshot!(
+        document_highlight_variable_def_test,
+        "src/test_data/document_highlight/main.k",
+        1,
+        0
+    );
+
+    document_highlight_test_snapshot!(
+        document_highlight_variable_ref_test,
+        "src/test_data/document_highlight/main.k",
+        3,
+        4
+    );
+
+    document_highlight_test_snapshot!(
+        document_highlight_schema_attr_test,
+        "src/test_data/document_highlight/main.k",
+        6,
+        5
+    );
+
+    document_highlight_test_snapshot!(
+        document_highlight_none_test,
+        "src/test_data/document_highlight/main.k",
+        4,
+        0
+    );
+}
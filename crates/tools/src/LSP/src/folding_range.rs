@@ -0,0 +1,197 @@
+use std::cmp::Reverse;
+
+use kcl_ast::ast::{Expr, Module, Node, Program, Stmt};
+use kcl_ast::pos::GetPos;
+use kcl_error::Position as KCLPos;
+use kcl_error::diagnostic::Range;
+use lsp_types::{FoldingRange, FoldingRangeKind};
+
+use crate::util::{SpanNode, walk_module_spans};
+
+/// Returns the folding ranges of the file, including compound statements (schema, rule, if),
+/// multi-line expressions (config, list, comprehension, lambda and call), multi-line strings,
+/// docstrings, consecutive comments and consecutive import statements. The source `src` of the
+/// file is used to locate the `else` keywords which are not recorded in the AST.
+pub fn folding_range(file: &str, src: &str, program: &Program) -> Option<Vec<FoldingRange>> {
+    let module = program.get_module(file).ok()??;
+    let mut ranges: Vec<FoldingRange> = vec![];
+
+    if let Some(doc) = &module.doc {
+        push_range(
+            &mut ranges,
+            doc.get_span_pos(),
+            Some(FoldingRangeKind::Comment),
+        );
+    }
+    imports_folding_ranges(&module, &mut ranges);
+    comments_folding_ranges(&module, &mut ranges);
+
+    walk_module_spans(&module, |node, range| {
+        match node {
+            SpanNode::Stmt(Stmt::Schema(schema_stmt)) => {
+                push_range(&mut ranges, range, None);
+                if let Some(doc) = &schema_stmt.doc {
+                    push_range(
+                        &mut ranges,
+                        doc.get_span_pos(),
+                        Some(FoldingRangeKind::Comment),
+                    );
+                }
+            }
+            SpanNode::Stmt(Stmt::Rule(rule_stmt)) => {
+                push_range(&mut ranges, range, None);
+                if let Some(doc) = &rule_stmt.doc {
+                    push_range(
+                        &mut ranges,
+                        doc.get_span_pos(),
+                        Some(FoldingRangeKind::Comment),
+                    );
+                }
+            }
+            SpanNode::Stmt(Stmt::If(if_stmt)) => {
+                // Fold the if body and the else body separately, `elif` branches are
+                // nested if statements and fold themselves.
+                if let Some(last) = if_stmt.body.last() {
+                    push_range(&mut ranges, (range.0.clone(), last.get_end_pos()), None);
+                }
+                if let (Some(first), Some(last)) = (if_stmt.orelse.first(), if_stmt.orelse.last())
+                    && !matches!(first.node, Stmt::If(_))
+                {
+                    let body_end = match if_stmt.body.last() {
+                        Some(stmt) => stmt.get_end_pos(),
+                        None => if_stmt.cond.get_end_pos(),
+                    };
+                    if let Some(start) = else_keyword_pos(src, &body_end, &first.get_pos()) {
+                        push_range(&mut ranges, (start, last.get_end_pos()), None);
+                    }
+                }
+            }
+            SpanNode::Expr(
+                Expr::Config(_)
+                | Expr::List(_)
+                | Expr::ListComp(_)
+                | Expr::DictComp(_)
+                | Expr::Lambda(_)
+                | Expr::Call(_)
+                | Expr::ConfigIfEntry(_)
+                | Expr::ListIfItem(_),
+            ) => push_range(&mut ranges, range, None),
+            SpanNode::Expr(Expr::StringLit(string_lit)) if string_lit.is_long_string => {
+                push_range(&mut ranges, range, None)
+            }
+            SpanNode::Expr(Expr::JoinedString(joined_string)) if joined_string.is_long_string => {
+                push_range(&mut ranges, range, None)
+            }
+            _ => {}
+        }
+        true
+    });
+
+    // Keep the outermost range for each start line, clients only fold one range per line.
+    ranges.sort_by_key(|r| (r.start_line, Reverse(r.end_line)));
+    ranges.dedup_by_key(|r| r.start_line);
+    Some(ranges)
+}
+
+/// Returns the position of the `else` keyword between the end of the if body `body_end` and
+/// the start of the else body `orelse_start`, only whitespaces and comments can surround it.
+fn else_keyword_pos(src: &str, body_end: &KCLPos, orelse_start: &KCLPos) -> Option<KCLPos> {
+    let lines: Vec<&str> = src.lines().collect();
+    for line in body_end.line..=orelse_start.line {
+        let text: Vec<char> = lines.get(line.checked_sub(1)? as usize)?.chars().collect();
+        let start = if line == body_end.line {
+            body_end.column.unwrap_or_default() as usize
+        } else {
+            0
+        };
+        let end = if line == orelse_start.line {
+            orelse_start.column.unwrap_or_default() as usize
+        } else {
+            text.len()
+        };
+        let code: String = text
+            .get(start..end.min(text.len()))?
+            .iter()
+            .take_while(|c| **c != '#')
+            .collect();
+        if let Some(column) = code.find("else") {
+            return Some(KCLPos {
+                filename: body_end.filename.clone(),
+                line,
+                column: Some((start + code[..column].chars().count()) as u64),
+            });
+        }
+    }
+    None
+}
+
+/// Folds consecutive top-level import statements.
+fn imports_folding_ranges(module: &Module, ranges: &mut Vec<FoldingRange>) {
+    let mut group: Vec<&Node<Stmt>> = vec![];
+    for stmt in module.body.iter().map(|s| s.as_ref()) {
+        if matches!(stmt.node, Stmt::Import(_)) {
+            group.push(stmt);
+        } else {
+            push_group(&group, ranges, FoldingRangeKind::Imports);
+            group.clear();
+        }
+    }
+    push_group(&group, ranges, FoldingRangeKind::Imports);
+}
+
+/// Folds comments on consecutive lines.
+fn comments_folding_ranges(module: &Module, ranges: &mut Vec<FoldingRange>) {
+    let mut group: Vec<&Node<kcl_ast::ast::Comment>> = vec![];
+    for comment in module.comments.iter().map(|c| c.as_ref()) {
+        if let Some(last) = group.last()
+            && last.end_line + 1 != comment.line
+        {
+            push_group(&group, ranges, FoldingRangeKind::Comment);
+            group.clear();
+        }
+        group.push(comment);
+    }
+    push_group(&group, ranges, FoldingRangeKind::Comment);
+}
+
+fn push_group<T>(group: &[&Node<T>], ranges: &mut Vec<FoldingRange>, kind: FoldingRangeKind) {
+    if let (Some(first), Some(last)) = (group.first(), group.last()) {
+        push_range(ranges, (first.get_pos(), last.get_end_pos()), Some(kind));
+    }
+}
+
+/// Pushes the range into the result if it spans multiple lines.
+fn push_range(ranges: &mut Vec<FoldingRange>, range: Range, kind: Option<FoldingRangeKind>) {
+    let (start, end) = range;
+    if end.line <= start.line {
+        return;
+    }
+    ranges.push(FoldingRange {
+        start_line: start.line.saturating_sub(1) as u32,
+        start_character: start.column.map(|c| c as u32),
+        end_line: end.line.saturating_sub(1) as u32,
+        end_character: end.column.map(|c| c as u32),
+        kind,
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::folding_range;
+    use crate::tests::compile_test_file;
+
+    #[test]
+    fn folding_range_test() {
+        let (file, program, _, _, _) = compile_test_file("src/test_data/folding_range/main.k");
+        let src = std::fs::read_to_string(&file).unwrap();
+        let res = folding_range(&file, &src, &program).unwrap();
+        let mut got = String::new();
+        for r in res {
+            got.push_str(&format!(
+                "{}:{:?} -> {}:{:?} {:?}\n",
+                r.start_line, r.start_character, r.end_line, r.end_character, r.kind
+            ));
+        }
+        insta::assert_snapshot!(got);
+    }
+}
@@ -26,26 +26,33 @@ pub(crate) fn kcl_pos(file: &str, pos: Position) -> KCLPos {
 
 /// Converts the given lsp range to `Range`
 pub(crate) fn text_range(text: &str, range: lsp_types::Range) -> Range<usize> {
-    let mut lines_length = vec![];
-    let lines_text: Vec<&str> = text.split('\n').collect();
-    let mut pre_total_length = 0;
-    // range line base-zeror
-    for i in 0..range.end.line + 1 {
-        let i = i as usize;
-        if i < lines_text.len() {
-            let line = lines_text.get(i).unwrap();
-            lines_length.push(pre_total_length);
-            pre_total_length += line.len() + "\n".len();
-        } else {
-            lines_length.push(pre_total_length);
+    let start = text_offset(text, range.start);
+    let end = text_offset(text, range.end).max(start);
+    Range { start, end }
+}
+
+/// Converts the lsp position to the byte offset in the text. The character of the lsp position
+/// is counted in UTF-16 code units, characters beyond the line end continue into the following
+/// line break as the previous line index did, and positions beyond the text are clamped to its end.
+pub(crate) fn text_offset(text: &str, pos: Position) -> usize {
+    let mut line_start = 0;
+    for _ in 0..pos.line {
+        match text[line_start..].find('\n') {
+            Some(i) => line_start += i + 1,
+            None => return text.len(),
         }
     }
-
-    let start =
-        lines_length.get(range.start.line as usize).unwrap() + range.start.character as usize;
-    let end = lines_length.get(range.end.line as usize).unwrap() + range.end.character as usize;
-
-    Range { start, end }
+    let line_end = text[line_start..]
+        .find('\n')
+        .map_or(text.len(), |i| line_start + i);
+    let mut character = 0;
+    for (i, c) in text[line_start..line_end].char_indices() {
+        if character >= pos.character as usize {
+            return line_start + i;
+        }
+        character += c.len_utf16();
+    }
+    (line_end + (pos.character as usize).saturating_sub(character)).min(text.len())
 }
 
 /// Converts the specified `url` to a utf8 encoded file path string. Returns an error if the url could not be
@@ -1,8 +1,10 @@
 pub mod analysis;
 pub mod capabilities;
 pub mod completion;
+pub mod document_highlight;
 pub mod document_symbol;
 pub mod find_refs;
+pub mod folding_range;
 pub mod formatting;
 pub mod goto_def;
 pub mod hover;
@@ -10,8 +12,10 @@ pub mod inlay_hints;
 pub mod quick_fix;
 pub mod rename;
 pub mod request;
+pub mod selection_range;
 pub mod semantic_token;
 pub mod signature_help;
+pub mod workspace_symbol;
 
 pub mod app;
 pub mod compile;
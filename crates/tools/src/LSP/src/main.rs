@@ -4,9 +4,11 @@ mod capabilities;
 mod compile;
 mod completion;
 mod dispatcher;
+mod document_highlight;
 mod document_symbol;
 mod error;
 mod find_refs;
+mod folding_range;
 mod formatting;
 mod from_lsp;
 mod goto_def;
@@ -15,12 +17,14 @@ mod inlay_hints;
 mod notification;
 mod quick_fix;
 mod request;
+mod selection_range;
 mod semantic_token;
 mod signature_help;
 mod state;
 mod to_lsp;
 mod util;
 mod word_index;
+mod workspace_symbol;
 
 #[cfg(test)]
 mod tests;
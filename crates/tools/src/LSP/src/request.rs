@@ -5,7 +5,7 @@ use kcl_driver::WorkSpaceKind;
 use kcl_sema::info::is_valid_kcl_name;
 use lsp_types::{Location, SemanticTokensResult, TextEdit};
 use ra_ap_vfs::VfsPath;
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::sync::Arc;
 use std::time::Instant;
 
@@ -13,18 +13,22 @@ use crate::{
     analysis::{AnalysisDatabase, DBState},
     completion::completion,
     dispatcher::RequestDispatcher,
+    document_highlight::document_highlight,
     document_symbol::document_symbol,
     error::LSPError,
     find_refs::find_refs,
+    folding_range::folding_range,
     formatting::format,
     from_lsp::{self, file_path_from_url, kcl_pos},
     goto_def::goto_def,
     hover,
     inlay_hints::inlay_hints,
     quick_fix,
+    selection_range::selection_range,
     semantic_token::semantic_tokens_full,
     signature_help::signature_help,
     state::{LanguageServerSnapshot, LanguageServerState, Task, log_message},
+    workspace_symbol::{sort_symbols, workspace_symbol},
 };
 
 impl LanguageServerState {
@@ -54,6 +58,9 @@ impl LanguageServerState {
             })?
             .on::<lsp_types::request::GotoDefinition>(handle_goto_definition)?
             .on::<lsp_types::request::References>(handle_reference)?
+            .on::<lsp_types::request::DocumentHighlightRequest>(handle_document_highlight)?
+            .on::<lsp_types::request::FoldingRangeRequest>(handle_folding_range)?
+            .on::<lsp_types::request::SelectionRangeRequest>(handle_selection_range)?
             .on::<lsp_types::request::HoverRequest>(handle_hover)?
             .on::<lsp_types::request::DocumentSymbolRequest>(handle_document_symbol)?
             .on::<lsp_types::request::CodeActionRequest>(handle_code_action)?
@@ -63,6 +70,7 @@ impl LanguageServerState {
             .on::<lsp_types::request::SemanticTokensFullRequest>(handle_semantic_tokens_full)?
             .on::<lsp_types::request::InlayHintRequest>(handle_inlay_hint)?
             .on::<lsp_types::request::SignatureHelpRequest>(handle_signature_help)?
+            .on::<lsp_types::request::WorkspaceSymbol>(handle_workspace_symbol)?
             .on_maybe_retry::<lsp_types::request::Completion>(handle_completion)?
             .finish();
 
@@ -301,6 +309,86 @@ pub(crate) fn handle_reference(
     Ok(res)
 }
 
+/// Called when a `textDocument/documentHighlight` request was received.
+pub(crate) fn handle_document_highlight(
+    snapshot: LanguageServerSnapshot,
+    params: lsp_types::DocumentHighlightParams,
+    sender: Sender<Task>,
+) -> anyhow::Result<Option<Vec<lsp_types::DocumentHighlight>>> {
+    let file = file_path_from_url(&params.text_document_position_params.text_document.uri)?;
+    let path = from_lsp::abs_path(&params.text_document_position_params.text_document.uri)?;
+    if !snapshot.verify_request_path(&path.clone().into(), &sender) {
+        return Ok(None);
+    }
+    let db = match snapshot.try_get_db(&path.clone().into(), &sender) {
+        Ok(option_db) => match option_db {
+            Some(db) => db,
+            None => return Err(anyhow!(LSPError::Retry)),
+        },
+        Err(_) => return Ok(None),
+    };
+    let pos = kcl_pos(&file, params.text_document_position_params.position);
+    let res = document_highlight(&pos, &db.gs);
+    Ok(res)
+}
+
+/// Called when a `textDocument/foldingRange` request was received.
+pub(crate) fn handle_folding_range(
+    snapshot: LanguageServerSnapshot,
+    params: lsp_types::FoldingRangeParams,
+    sender: Sender<Task>,
+) -> anyhow::Result<Option<Vec<lsp_types::FoldingRange>>> {
+    let file = file_path_from_url(&params.text_document.uri)?;
+    let path = from_lsp::abs_path(&params.text_document.uri)?;
+    if !snapshot.verify_request_path(&path.clone().into(), &sender) {
+        return Ok(None);
+    }
+    let db = match snapshot.try_get_db(&path.clone().into(), &sender) {
+        Ok(option_db) => match option_db {
+            Some(db) => db,
+            None => return Err(anyhow!(LSPError::Retry)),
+        },
+        Err(_) => return Ok(None),
+    };
+    let src = {
+        let vfs = snapshot.vfs.read();
+        let file_id = vfs
+            .file_id(&path.into())
+            .ok_or(anyhow::anyhow!("Already checked that the file_id exists!"))?;
+
+        String::from_utf8(vfs.file_contents(file_id).to_vec())?
+    };
+    let res = folding_range(&file, &src, &db.prog);
+    Ok(res)
+}
+
+/// Called when a `textDocument/selectionRange` request was received.
+pub(crate) fn handle_selection_range(
+    snapshot: LanguageServerSnapshot,
+    params: lsp_types::SelectionRangeParams,
+    sender: Sender<Task>,
+) -> anyhow::Result<Option<Vec<lsp_types::SelectionRange>>> {
+    let file = file_path_from_url(&params.text_document.uri)?;
+    let path = from_lsp::abs_path(&params.text_document.uri)?;
+    if !snapshot.verify_request_path(&path.clone().into(), &sender) {
+        return Ok(None);
+    }
+    let db = match snapshot.try_get_db(&path.clone().into(), &sender) {
+        Ok(option_db) => match option_db {
+            Some(db) => db,
+            None => return Err(anyhow!(LSPError::Retry)),
+        },
+        Err(_) => return Ok(None),
+    };
+    let positions: Vec<_> = params
+        .positions
+        .into_iter()
+        .map(|pos| kcl_pos(&file, pos))
+        .collect();
+    let res = selection_range(&file, &db.prog, &positions);
+    Ok(res)
+}
+
 /// Called when a `textDocument/completion` request was received.
 pub(crate) fn handle_completion(
     snapshot: LanguageServerSnapshot,
@@ -495,3 +583,30 @@ pub(crate) fn handle_signature_help(
 
     Ok(res)
 }
+
+/// Called when a `workspace/symbol` request was received.
+pub(crate) fn handle_workspace_symbol(
+    snapshot: LanguageServerSnapshot,
+    params: lsp_types::WorkspaceSymbolParams,
+    _sender: Sender<Task>,
+) -> anyhow::Result<Option<Vec<lsp_types::SymbolInformation>>> {
+    let mut symbols = vec![];
+    let mut locations = HashSet::new();
+    for state in snapshot.workspaces.read().values() {
+        let db = match state {
+            DBState::Ready(db) | DBState::Compiling(db) => db,
+            DBState::Init | DBState::Failed(_) => continue,
+        };
+        // Packages shared by several workspaces are reported once.
+        for (score, symbol) in workspace_symbol(&params.query, &db.gs) {
+            let start = symbol.location.range.start;
+            if locations.insert((symbol.location.uri.to_string(), start.line, start.character)) {
+                symbols.push((score, symbol));
+            }
+        }
+    }
+    sort_symbols(&mut symbols);
+    Ok(Some(
+        symbols.into_iter().map(|(_, symbol)| symbol).collect(),
+    ))
+}
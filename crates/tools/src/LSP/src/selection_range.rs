@@ -0,0 +1,115 @@
+use kcl_ast::ast::Program;
+use kcl_ast::pos::ContainsPos;
+use kcl_error::Position as KCLPos;
+use lsp_types::{Range, SelectionRange};
+
+use crate::to_lsp::lsp_pos;
+use crate::util::walk_module_spans;
+
+/// Returns the selection ranges for each position, from the innermost AST node containing
+/// the position to the outermost statement.
+pub fn selection_range(
+    file: &str,
+    program: &Program,
+    positions: &[KCLPos],
+) -> Option<Vec<SelectionRange>> {
+    let module = program.get_module(file).ok()??;
+    let mut res = vec![];
+    for pos in positions {
+        let mut ranges: Vec<Range> = vec![];
+        walk_module_spans(&module, |_, range| {
+            if !range.contains_pos(pos) {
+                return false;
+            }
+            let range = Range {
+                start: lsp_pos(&range.0),
+                end: lsp_pos(&range.1),
+            };
+            if ranges.last() != Some(&range) {
+                ranges.push(range);
+            }
+            true
+        });
+        let mut selection: Option<SelectionRange> = None;
+        for range in ranges {
+            selection = Some(SelectionRange {
+                range,
+                parent: selection.map(Box::new),
+            });
+        }
+        res.push(selection.unwrap_or_else(|| {
+            let pos = lsp_pos(pos);
+            SelectionRange {
+                range: Range {
+                    start: pos,
+                    end: pos,
+                },
+                parent: None,
+            }
+        }));
+    }
+    Some(res)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::selection_range;
+    use crate::tests::compile_test_file;
+    use kcl_error::Position as KCLPos;
+    use lsp_types::SelectionRange;
+
+    fn fmt_resp(selection: &SelectionRange) -> String {
+        let mut res = String::new();
+        let mut selection = Some(selection);
+        while let Some(s) = selection {
+            res.push_str(&format!(
+                "{}:{} -> {}:{}\n",
+                s.range.start.line,
+                s.range.start.character,
+                s.range.end.line,
+                s.range.end.character
+            ));
+            selection = s.parent.as_deref();
+        }
+        res
+    }
+
+    #[macro_export]
+    macro_rules! selection_range_test_snapshot {
+        ($name:ident, $file:expr, $line:expr, $column: expr) => {
+            #[test]
+            fn $name() {
+                let (file, program, _, _, _) = compile_test_file($file);
+
+                let pos = KCLPos {
+                    filename: file.clone(),
+                    line: $line,
+                    column: Some($column),
+                };
+                let res = selection_range(&file, &program, &[pos]).unwrap();
+                insta::assert_snapshot!(fmt_resp(&res[0]));
+            }
+        };
+    }
+
+    selection_range_test_snapshot!(
+        selection_range_binary_expr_test,
+        "src/test_data/selection_range/main.k",
+        4,
+        25
+    );
+
+    selection_range_test_snapshot!(
+        selection_range_schema_expr_test,
+        "src/test_data/selection_range/main.k",
+        8,
+        12
+    );
+
+    selection_range_test_snapshot!(
+        selection_range_empty_line_test,
+        "src/test_data/selection_range/main.k",
+        6,
+        0
+    );
+}
@@ -0,0 +1,4 @@
+[package]
+name = "workspace_symbol"
+edition = "v0.11.0"
+version = "0.0.1"
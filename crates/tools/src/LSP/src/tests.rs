@@ -73,6 +73,7 @@ use proc_macro_crate::bench_test;
 
 use lsp_server::{Connection, Message, Notification, Request};
 
+use crate::compile::compile;
 use crate::compile::compile_with_params;
 use crate::from_lsp::file_path_from_url;
 
@@ -293,7 +294,7 @@ fn build_expect_diags() -> Vec<Diagnostic> {
             Some(DiagnosticSeverity::WARNING),
             vec![],
             Some(NumberOrString::String("UnusedImportWarning".to_string())),
-            None,
+            Some(serde_json::json!({ "suggested_replacement": "" })),
         ),
     ];
     expected_diags
@@ -399,14 +400,24 @@ fn test_apply_document_changes() {
     apply_document_changes(&mut text, change![0, 0; 0, 0 => "a"]);
     assert_eq!(text, "a❤️");
 
-    // todo: Non-ASCII char
-    // text = String::from("a\nb");
-    // apply_document_changes(&mut text, change![0, 1; 1, 0 => "\nțc", 0, 1; 1, 1 => "d"]);
-    // assert_eq!(text, "adcb");
+    // Non-ASCII char
+    text = String::from("a\nb");
+    apply_document_changes(&mut text, change![0, 1; 1, 0 => "\nțc", 0, 1; 1, 1 => "d"]);
+    assert_eq!(text, "adcb");
+
+    text = String::from("a\nb");
+    apply_document_changes(&mut text, change![0, 1; 1, 0 => "ț\nc", 0, 2; 0, 2 => "c"]);
+    assert_eq!(text, "ațc\ncb");
 
-    // text = String::from("a\nb");
-    // apply_document_changes(&mut text, change![0, 1; 1, 0 => "ț\nc", 0, 2; 0, 2 => "c"]);
-    // assert_eq!(text, "ațc\ncb");
+    // UTF-16 code units, "❤️" is 2 chars and "𝕂" is 2 code units
+    text = String::from("❤️𝕂b");
+    apply_document_changes(&mut text, change![0, 4; 0, 4 => "c"]);
+    assert_eq!(text, "❤️𝕂cb");
+
+    // Out of range positions are clamped
+    text = String::from("ab");
+    apply_document_changes(&mut text, change![0, 10; 3, 0 => "c"]);
+    assert_eq!(text, "abc");
 }
 
 #[test]
@@ -1943,3 +1954,145 @@ fn aug_assign_without_define() {
         compile_test_file("src/test_data/error_code/aug_assign/aug_assign.k");
     assert_eq!(diags.len(), 1);
 }
+
+/// Generates a workspace whose main package imports `pkg_count` packages, the package `pkg1`
+/// imports `pkg0` as well. Returns the root, the main file and the file of `pkg0`.
+fn gen_incremental_workspace(name: &str, pkg_count: usize) -> (PathBuf, String, String) {
+    let root = env::temp_dir().join(format!("{name}_{}", std::process::id()));
+    let _ = std::fs::remove_dir_all(&root);
+    std::fs::create_dir_all(&root).unwrap();
+    std::fs::write(root.join("kcl.mod"), "[package]\nname = \"bench\"\n").unwrap();
+    let mut main_code = String::new();
+    for i in 0..pkg_count {
+        let pkg = root.join(format!("pkg{i}"));
+        std::fs::create_dir_all(&pkg).unwrap();
+        let mut code = String::new();
+        if i == 1 {
+            code.push_str("import pkg0\n\nfrom_pkg0 = pkg0.value0\n");
+        }
+        for j in 0..20 {
+            code.push_str(&format!(
+                "schema Schema{j}:\n    name: str = \"{j}\"\n    items: [int] = [x * {j} for x in range(10)]\n\n    check:\n        len(name) > 0\n\nvalue{j} = Schema{j} {{}}\n"
+            ));
+        }
+        std::fs::write(pkg.join("pkg.k"), code).unwrap();
+        main_code.push_str(&format!("import pkg{i}\nv{i} = pkg{i}.value0\n"));
+    }
+    std::fs::write(root.join("main.k"), main_code).unwrap();
+    let canonicalize = |path: PathBuf| {
+        path.canonicalize()
+            .unwrap()
+            .display()
+            .to_string()
+            .adjust_canonicalization()
+    };
+    let main_file = canonicalize(root.join("main.k"));
+    let changed_file = canonicalize(root.join("pkg0").join("pkg.k"));
+    (root, main_file, changed_file)
+}
+
+fn compile_incremental_workspace(
+    file: &str,
+    main_file: &str,
+    gs_cache: KCLGlobalStateCache,
+    scope_cache: KCLScopeCache,
+) -> GlobalState {
+    let (mut files, opts, _) = lookup_compile_workspace(&toolchain::default(), main_file, true);
+    let (_, res) = compile(
+        Params {
+            file: Some(file.to_string()),
+            module_cache: Some(KCLModuleCache::default()),
+            scope_cache: Some(scope_cache),
+            vfs: Some(KCLVfs::default()),
+            gs_cache: Some(gs_cache),
+        },
+        &mut files,
+        opts,
+    );
+    res.unwrap().2
+}
+
+#[test]
+fn incremental_compile_invalidation_test() {
+    let (root, main_file, changed_file) = gen_incremental_workspace("kcl_lsp_invalidation", 4);
+    let gs_cache = KCLGlobalStateCache::default();
+    let scope_cache = KCLScopeCache::default();
+    let gs = compile_incremental_workspace(
+        &main_file,
+        &main_file,
+        gs_cache.clone(),
+        scope_cache.clone(),
+    );
+    let symbol = |gs: &GlobalState, name: &str| {
+        gs.get_symbols()
+            .get_symbol_by_fully_qualified_name(name)
+            .unwrap()
+    };
+    let names = [
+        "pkg0.Schema0",
+        "pkg1.Schema0",
+        "pkg2.Schema0",
+        "pkg3.Schema0",
+        "__main__.v0",
+    ];
+    let before: Vec<_> = names.iter().map(|name| symbol(&gs, name)).collect();
+
+    // Change `pkg0` and recompile with the warm caches.
+    let mut code = std::fs::read_to_string(&changed_file).unwrap();
+    code.push_str("\nschema Added:\n    name: str\n");
+    std::fs::write(&changed_file, code).unwrap();
+    let gs = compile_incremental_workspace(
+        &changed_file,
+        &main_file,
+        gs_cache.clone(),
+        scope_cache.clone(),
+    );
+
+    let expected: HashSet<String> = ["pkg0", "pkg1", "__main__"]
+        .iter()
+        .map(|s| s.to_string())
+        .collect();
+    assert_eq!(gs.get_reverse_dependencies(&changed_file), expected);
+    assert!(
+        gs.get_symbols()
+            .get_symbol_by_fully_qualified_name("pkg0.Added")
+            .is_some()
+    );
+    // Only the changed package and the packages importing it are resolved again, the symbols
+    // of the other packages are kept in the cache.
+    for (name, before) in names.iter().zip(before) {
+        let pkg = name.split('.').next().unwrap();
+        assert_eq!(
+            symbol(&gs, name) == before,
+            !expected.contains(pkg),
+            "unexpected resolution of the package {pkg}"
+        );
+    }
+
+    // A new module is not known by the global state yet, its package is resolved again.
+    let before: Vec<_> = names.iter().map(|name| symbol(&gs, name)).collect();
+    let new_file = root.join("pkg2").join("new.k");
+    std::fs::write(&new_file, "schema New:\n    name: str\n").unwrap();
+    let new_file = new_file
+        .canonicalize()
+        .unwrap()
+        .display()
+        .to_string()
+        .adjust_canonicalization();
+    let gs = compile_incremental_workspace(&new_file, &main_file, gs_cache, scope_cache);
+    let _ = std::fs::remove_dir_all(&root);
+    assert!(
+        gs.get_symbols()
+            .get_symbol_by_fully_qualified_name("pkg2.New")
+            .is_some()
+    );
+    let expected = ["pkg2", "__main__"];
+    for (name, before) in names.iter().zip(before) {
+        let pkg = name.split('.').next().unwrap();
+        assert_eq!(
+            symbol(&gs, name) == before,
+            !expected.contains(&pkg),
+            "unexpected resolution of the package {pkg}"
+        );
+    }
+}
@@ -1,11 +1,14 @@
 use kcl_ast::ast::{
-    ConfigEntry, Expr, Identifier, MemberOrIndex, Node, NodeRef, PosTuple, Program, SchemaStmt,
-    Stmt, Type,
+    AssignStmt, CallExpr, ConfigEntry, ConfigExpr, ConfigIfEntryExpr, Expr, Identifier, Keyword,
+    MemberOrIndex, Module, Node, NodeRef, PosTuple, Program, SchemaExpr, SchemaStmt, Stmt, Target,
+    Type,
 };
-use kcl_ast::node_ref;
-use kcl_ast::pos::ContainsPos;
+use kcl_ast::pos::{ContainsPos, GetPos};
+use kcl_ast::walker::MutSelfWalker;
+use kcl_ast::{node_ref, walk_node_if, walk_node_list};
 
 use kcl_error::Position as KCLPos;
+use kcl_error::diagnostic::Range;
 use kcl_parser::entry::get_dir_files;
 
 use crate::from_lsp;
@@ -665,3 +668,151 @@ fn build_identifier_from_ty_string(ty: &NodeRef<Type>, pos: &KCLPos) -> Option<N
         Type::Function(_) => None,
     }
 }
+
+/// The AST node kind passed to the callback of [`walk_module_spans`].
+pub(crate) enum SpanNode<'a> {
+    Stmt(&'a Stmt),
+    Expr(&'a Expr),
+    /// Identifier names, targets, keywords, config entries and other auxiliary nodes.
+    Other,
+}
+
+/// Walks the AST nodes of the module in pre-order and calls `f` with each node and its span.
+/// The children of a node are walked only when `f` returns `true` for it.
+pub(crate) fn walk_module_spans<F>(module: &Module, f: F)
+where
+    F: FnMut(SpanNode, Range) -> bool,
+{
+    let mut walker = SpanWalker { f };
+    walker.walk_module(module);
+}
+
+/// Returns the span of the statement. The spans of schema and rule statements end at the
+/// start of the next statement, so trim them to the end of their last child.
+fn stmt_span(stmt: &Node<Stmt>) -> Range {
+    let (start, end) = stmt.get_span_pos();
+    let last_end = match &stmt.node {
+        Stmt::Schema(schema_stmt) => schema_stmt
+            .body
+            .iter()
+            .map(|s| s.get_end_pos())
+            .chain(schema_stmt.checks.iter().map(|c| c.get_end_pos()))
+            .chain(schema_stmt.doc.iter().map(|d| d.get_end_pos()))
+            .max_by_key(|p| (p.line, p.column)),
+        Stmt::Rule(rule_stmt) => rule_stmt
+            .checks
+            .iter()
+            .map(|c| c.get_end_pos())
+            .chain(rule_stmt.doc.iter().map(|d| d.get_end_pos()))
+            .max_by_key(|p| (p.line, p.column)),
+        _ => None,
+    };
+    match last_end {
+        Some(last_end) if last_end.less_equal(&end) => (start, last_end),
+        _ => (start, end),
+    }
+}
+
+/// Collects the spans on the default AST traversal, the walk methods overridden besides
+/// the statement and expression nodes only add the spans of their auxiliary nodes.
+struct SpanWalker<F> {
+    f: F,
+}
+
+impl<F> SpanWalker<F>
+where
+    F: FnMut(SpanNode, Range) -> bool,
+{
+    fn visit<T>(&mut self, node: &Node<T>) -> bool {
+        (self.f)(SpanNode::Other, node.get_span_pos())
+    }
+
+    fn walk_config_entries(&mut self, entries: &[NodeRef<ConfigEntry>]) {
+        for entry in entries {
+            if self.visit(entry) {
+                walk_node_if!(self, walk_expr_node, entry.node.key);
+                self.walk_expr_node(&entry.node.value);
+            }
+        }
+    }
+
+    fn walk_keywords(&mut self, keywords: &[NodeRef<Keyword>]) {
+        for keyword in keywords {
+            if self.visit(keyword) {
+                self.visit(&keyword.node.arg);
+                walk_node_if!(self, walk_expr_node, keyword.node.value);
+            }
+        }
+    }
+}
+
+impl<F> MutSelfWalker for SpanWalker<F>
+where
+    F: FnMut(SpanNode, Range) -> bool,
+{
+    fn walk_stmt_node(&mut self, stmt: &Node<Stmt>) {
+        if (self.f)(SpanNode::Stmt(&stmt.node), stmt_span(stmt)) {
+            self.walk_stmt(&stmt.node);
+        }
+    }
+
+    fn walk_expr_node(&mut self, expr: &Node<Expr>) {
+        if (self.f)(SpanNode::Expr(&expr.node), expr.get_span_pos()) {
+            self.walk_expr(&expr.node);
+        }
+    }
+
+    fn walk_assign_stmt(&mut self, assign_stmt: &AssignStmt) {
+        for target in &assign_stmt.targets {
+            if self.visit(target) {
+                self.walk_target(&target.node);
+            }
+        }
+        self.walk_expr_node(&assign_stmt.value);
+    }
+
+    fn walk_call_expr(&mut self, call_expr: &CallExpr) {
+        self.walk_expr_node(&call_expr.func);
+        walk_node_list!(self, walk_expr_node, call_expr.args);
+        self.walk_keywords(&call_expr.keywords);
+    }
+
+    fn walk_schema_expr(&mut self, schema_expr: &SchemaExpr) {
+        if self.visit(&schema_expr.name) {
+            self.walk_identifier(&schema_expr.name.node);
+        }
+        walk_node_list!(self, walk_expr_node, schema_expr.args);
+        self.walk_keywords(&schema_expr.kwargs);
+        self.walk_expr_node(&schema_expr.config);
+    }
+
+    fn walk_config_expr(&mut self, config_expr: &ConfigExpr) {
+        self.walk_config_entries(&config_expr.items);
+    }
+
+    fn walk_config_if_entry_expr(&mut self, config_if_entry_expr: &ConfigIfEntryExpr) {
+        self.walk_expr_node(&config_if_entry_expr.if_cond);
+        self.walk_config_entries(&config_if_entry_expr.items);
+        walk_node_if!(self, walk_expr_node, config_if_entry_expr.orelse);
+    }
+
+    fn walk_identifier(&mut self, identifier: &Identifier) {
+        if identifier.names.len() > 1 {
+            for name in &identifier.names {
+                self.visit(name);
+            }
+        }
+    }
+
+    fn walk_target(&mut self, target: &Target) {
+        self.visit(&target.name);
+        for path in &target.paths {
+            match path {
+                MemberOrIndex::Member(member) => {
+                    self.visit(member);
+                }
+                MemberOrIndex::Index(index) => self.walk_expr_node(index),
+            }
+        }
+    }
+}
@@ -0,0 +1,186 @@
+use kcl_sema::core::global_state::GlobalState;
+use kcl_sema::core::symbol::SymbolKind as KCLSymbolKind;
+use kcl_sema::ty::TypeKind;
+use lsp_types::{SymbolInformation, SymbolKind};
+
+use crate::to_lsp::lsp_location;
+
+/// Returns the schemas, rules, lambdas and top-level variables of all the compiled
+/// non-system packages whose names fuzzy match the query, together with their package path.
+/// The result is sorted by the match score, the best match first.
+pub fn workspace_symbol(query: &str, gs: &GlobalState) -> Vec<(i64, SymbolInformation)> {
+    let mut symbols = vec![];
+    for pkgpath in gs.get_scopes().get_root_scope_map().keys() {
+        if gs
+            .get_packages()
+            .get_package_info(pkgpath)
+            .is_none_or(|info| info.is_system())
+        {
+            continue;
+        }
+        let Some(pkg) = gs
+            .get_symbols()
+            .get_symbol_by_fully_qualified_name(pkgpath)
+            .and_then(|pkg| gs.get_symbols().get_symbol(pkg))
+        else {
+            continue;
+        };
+        for member in pkg.get_all_attributes(gs.get_symbols(), None) {
+            let Some(symbol) = gs.get_symbols().get_symbol(member) else {
+                continue;
+            };
+            let kind = match member.get_kind() {
+                KCLSymbolKind::Schema => SymbolKind::STRUCT,
+                KCLSymbolKind::Rule => SymbolKind::FUNCTION,
+                KCLSymbolKind::Value => match &symbol.get_sema_info().ty {
+                    Some(ty) if matches!(ty.kind, TypeKind::Function(_)) => SymbolKind::FUNCTION,
+                    _ => SymbolKind::VARIABLE,
+                },
+                _ => continue,
+            };
+            let name = symbol.get_name();
+            let Some(score) = fuzzy_match(query, &name) else {
+                continue;
+            };
+            let (start, end) = symbol.get_range();
+            let Some(location) = lsp_location(start.filename.clone(), &start, &end) else {
+                continue;
+            };
+            #[allow(deprecated)]
+            symbols.push((
+                score,
+                SymbolInformation {
+                    name,
+                    kind,
+                    tags: None,
+                    deprecated: None,
+                    location,
+                    container_name: Some(pkgpath.clone()),
+                },
+            ));
+        }
+    }
+    sort_symbols(&mut symbols);
+    symbols
+}
+
+/// Sorts the scored symbols, the best match first.
+pub(crate) fn sort_symbols(symbols: &mut [(i64, SymbolInformation)]) {
+    symbols.sort_by(|(score1, s1), (score2, s2)| {
+        score2
+            .cmp(score1)
+            .then_with(|| s1.name.cmp(&s2.name))
+            .then_with(|| s1.container_name.cmp(&s2.container_name))
+    });
+}
+
+/// Matches the query against the name as a case-insensitive subsequence and returns the
+/// match score, or `None` if the name does not match. Consecutive characters, matches at
+/// word boundaries (start, after `_` or a lowercase to uppercase change) and exact case
+/// are rewarded, unmatched characters are penalized. An empty query matches everything.
+pub(crate) fn fuzzy_match(query: &str, name: &str) -> Option<i64> {
+    let query: Vec<char> = query.chars().filter(|c| !c.is_whitespace()).collect();
+    let name: Vec<char> = name.chars().collect();
+    if query.is_empty() {
+        return Some(0);
+    }
+    let char_score = |q: char, i: usize| -> Option<i64> {
+        let c = name[i];
+        if !q.to_lowercase().eq(c.to_lowercase()) {
+            return None;
+        }
+        let mut score = 1;
+        if i == 0 || name[i - 1] == '_' || (name[i - 1].is_lowercase() && c.is_uppercase()) {
+            score += 10;
+        }
+        if q == c {
+            score += 1;
+        }
+        Some(score)
+    };
+    // best[i] is the best score of the query prefix whose last character is matched at name[i].
+    let mut best: Vec<Option<i64>> = vec![None; name.len()];
+    for (qi, q) in query.iter().enumerate() {
+        let mut next = vec![None; name.len()];
+        let mut gap_best: Option<i64> = None;
+        for i in 0..name.len() {
+            if i >= 2 {
+                gap_best = gap_best.max(best[i - 2]);
+            }
+            let Some(score) = char_score(*q, i) else {
+                continue;
+            };
+            next[i] = if qi == 0 {
+                Some(score)
+            } else {
+                let consecutive = i
+                    .checked_sub(1)
+                    .and_then(|j| best[j])
+                    .map(|s| s + score + 5);
+                consecutive.max(gap_best.map(|s| s + score))
+            };
+        }
+        best = next;
+    }
+    let score = best.into_iter().flatten().max()?;
+    Some(score - (name.len() - query.len()) as i64)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{fuzzy_match, workspace_symbol};
+    use crate::tests::compile_test_file;
+
+    #[test]
+    fn fuzzy_match_test() {
+        assert!(fuzzy_match("", "Person").is_some());
+        assert!(fuzzy_match("psn", "Person").is_some());
+        assert!(fuzzy_match("PS", "PersonSpec").is_some());
+        assert!(fuzzy_match("xyz", "Person").is_none());
+        assert!(fuzzy_match("personn", "Person").is_none());
+        // Word boundary and consecutive matches rank first.
+        assert!(fuzzy_match("ps", "PersonSpec") > fuzzy_match("ps", "Persons"));
+        assert!(fuzzy_match("per", "Person") > fuzzy_match("per", "Upper"));
+        assert!(fuzzy_match("person", "Person") > fuzzy_match("person", "PersonSpec"));
+    }
+
+    #[test]
+    fn workspace_symbol_test() {
+        let (_, _, _, gs, _) = compile_test_file("src/test_data/workspace_symbol/main.k");
+        let mut got = String::new();
+        for (_, symbol) in workspace_symbol("", &gs) {
+            let filename = symbol
+                .location
+                .uri
+                .path_segments()
+                .and_then(|mut s| s.next_back().map(|s| s.to_string()))
+                .unwrap_or_default();
+            got.push_str(&format!(
+                "{} {:?} {} {}:{:?}\n",
+                symbol.name,
+                symbol.kind,
+                symbol.container_name.unwrap_or_default(),
+                filename,
+                symbol.location.range.start
+            ));
+        }
+        insta::assert_snapshot!(got);
+    }
+
+    #[test]
+    fn workspace_symbol_fuzzy_test() {
+        let (_, _, _, gs, _) = compile_test_file("src/test_data/workspace_symbol/main.k");
+        let names: Vec<String> = workspace_symbol("pers", &gs)
+            .into_iter()
+            .map(|(_, s)| format!("{} {}", s.name, s.container_name.unwrap_or_default()))
+            .collect();
+        assert_eq!(
+            names,
+            vec![
+                "Person pkg".to_string(),
+                "persons __main__".to_string(),
+                "PersonCheck pkg".to_string(),
+            ]
+        );
+    }
+}
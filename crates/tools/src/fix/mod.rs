@@ -3,7 +3,8 @@ mod replace;
 mod tests;
 use anyhow::{Error, ensure};
 use kcl_error::{Diagnostic, diagnostic::Range as KCLRange};
-use std::collections::HashMap;
+use similar::TextDiff;
+use std::collections::{BTreeMap, HashMap};
 use std::fs;
 use std::ops::Range;
 
@@ -53,6 +54,9 @@ pub struct Snippet {
     pub range: Range<usize>,
 }
 
+/// Converts the machine-applicable suggested replacements of the diagnostic into suggestions.
+/// Messages without any suggested replacement are skipped, and only the first replacement
+/// of each message is used. An empty replacement removes the code in the message range.
 pub fn diag_to_suggestion(
     diag: Diagnostic,
     files: &mut HashMap<String, String>,
@@ -60,17 +64,19 @@ pub fn diag_to_suggestion(
     let mut suggestions = vec![];
 
     for msg in &diag.messages {
-        let replacements = msg
+        let Some(replacement_str) = msg
             .suggested_replacement
-            .clone()
-            .unwrap_or_else(|| vec!["".to_string()]);
-        let replacement_str = replacements.first().cloned().unwrap_or_default();
+            .as_ref()
+            .and_then(|replacements| replacements.first().cloned())
+        else {
+            continue;
+        };
 
         let file_name = msg.range.0.filename.clone();
         let src = match files.get(&file_name) {
             Some(src) => src.clone(),
             None => {
-                let src = fs::read_to_string(&file_name).expect("Unable to read file");
+                let src = fs::read_to_string(&file_name)?;
                 files.insert(file_name.clone(), src.clone());
                 src
             }
@@ -142,30 +148,78 @@ pub(crate) fn text_range(text: &str, range: &KCLRange) -> anyhow::Result<Range<u
     Ok(Range { start, end })
 }
 
-pub fn fix(diags: Vec<Diagnostic>) -> Result<(), Error> {
+/// The fix result of a source file.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FileFix {
+    /// The source file name.
+    pub filename: String,
+    /// The original source code.
+    pub source: String,
+    /// The fixed source code.
+    pub fixed: String,
+    /// The number of the applied suggestions.
+    pub applied: usize,
+}
+
+impl FileFix {
+    /// Returns the unified diff between the original and the fixed source code.
+    pub fn diff(&self) -> String {
+        TextDiff::from_lines(&self.source, &self.fixed)
+            .unified_diff()
+            .context_radius(3)
+            .header(&self.filename, &self.filename)
+            .to_string()
+    }
+}
+
+/// Applies the machine-applicable suggestions of the diagnostics to the source code without
+/// writing the files, and returns the fix results sorted by the file name. Files without any
+/// applied suggestion are not returned.
+pub fn apply_fixes(diags: Vec<Diagnostic>) -> Result<Vec<FileFix>, Error> {
     let mut suggestions = vec![];
     let mut source_code = HashMap::new();
     for diag in diags {
         suggestions.extend(diag_to_suggestion(diag, &mut source_code)?)
     }
 
-    let mut files = HashMap::new();
+    let mut files = BTreeMap::new();
     for suggestion in suggestions {
         let file = suggestion.replacement.snippet.file_name.clone();
         files.entry(file).or_insert_with(Vec::new).push(suggestion);
     }
 
-    for (source_file, suggestions) in &files {
-        println!("fix file: {:?}", source_file);
-        let source = fs::read_to_string(source_file)?;
+    let mut fixes = vec![];
+    for (source_file, suggestions) in files {
+        let source = match source_code.remove(&source_file) {
+            Some(source) => source,
+            None => fs::read_to_string(&source_file)?,
+        };
         let mut fix = CodeFix::new(&source);
+        let mut applied = 0;
         for suggestion in suggestions.iter() {
-            if let Err(e) = fix.apply(suggestion) {
-                eprintln!("Failed to apply suggestion to {}: {}", source_file, e);
+            match fix.apply(suggestion) {
+                Ok(_) => applied += 1,
+                Err(e) => eprintln!("Failed to apply suggestion to {}: {}", source_file, e),
             }
         }
-        let fixes = fix.finish()?;
-        fs::write(source_file, fixes)?;
+        let fixed = fix.finish()?;
+        if applied > 0 {
+            fixes.push(FileFix {
+                filename: source_file,
+                source,
+                fixed,
+                applied,
+            });
+        }
+    }
+    Ok(fixes)
+}
+
+/// Applies the machine-applicable suggestions of the diagnostics to the source files in place.
+pub fn fix(diags: Vec<Diagnostic>) -> Result<(), Error> {
+    for file_fix in apply_fixes(diags)? {
+        println!("fix file: {:?}", file_fix.filename);
+        fs::write(&file_fix.filename, file_fix.fixed)?;
     }
     Ok(())
 }
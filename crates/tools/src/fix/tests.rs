@@ -2,7 +2,7 @@ use std::fs;
 
 use crate::lint::lint_files;
 
-use super::fix;
+use super::{apply_fixes, fix};
 
 #[test]
 fn test_lint() {
@@ -40,3 +40,25 @@ a = math.pow(1, 1)"#,
         Err(e) => panic!("fix failed: {:?}", e),
     }
 }
+
+#[test]
+fn test_apply_fixes() {
+    let file = "./src/fix/test_data/none_comparison.k";
+    let (errors, warnings) = lint_files(&[file], None);
+    assert_eq!(errors.len(), 0);
+    let fixes = apply_fixes(warnings.into_iter().collect()).unwrap();
+    assert_eq!(fixes.len(), 1);
+    let file_fix = &fixes[0];
+    assert_eq!(file_fix.applied, 2);
+    assert_eq!(
+        file_fix.fixed,
+        "a = None\nb = a is None\nc = a is not None\nd = a is None\n"
+    );
+    // Fixes are not written to the file.
+    assert_eq!(fs::read_to_string(file).unwrap(), file_fix.source);
+    let diff = file_fix.diff();
+    assert!(
+        diff.contains("-b = a == None\n-c = a != None\n+b = a is None\n+c = a is not None\n"),
+        "{diff}"
+    );
+}
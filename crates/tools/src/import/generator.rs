@@ -0,0 +1,748 @@
+use std::collections::{HashMap, HashSet};
+
+use anyhow::{Result, bail};
+use kcl_ast::ast::{
+    BasicType, CallExpr, CheckExpr, CmpOp, Compare, ConfigEntry, ConfigEntryOperation, ConfigExpr,
+    DictType, Expr, ExprContext, Identifier, ImportStmt, IntLiteralType, ListExpr, ListType,
+    LiteralType, Module, NameConstant, NameConstantLit, Node, NodeRef, NumberLit, NumberLitValue,
+    SchemaAttr, SchemaIndexSignature, SchemaStmt, Stmt, StringLit, Type, UnionType,
+};
+use kcl_span::symbol::reserved::is_reserved_word;
+use serde_yaml::Value;
+
+const REGEX_MODULE: &str = "regex";
+
+/// `SchemaGenerator` generates the schema statements from the object schemas of a JSON
+/// Schema document. The nested object schemas are generated as separate schemas named
+/// after the parent schema and the attribute e.g., `DeploymentSpec`, and the local
+/// `$ref`s are resolved against the document root.
+pub(crate) struct SchemaGenerator<'a> {
+    root: &'a Value,
+    /// The generated schemas in the order of definition, the slot of a schema is
+    /// reserved before its nested schemas are generated.
+    schemas: Vec<Option<SchemaStmt>>,
+    /// All the used schema names.
+    names: HashSet<String>,
+    /// The schema names of the resolved `$ref` pointers.
+    refs: HashMap<String, String>,
+    /// The `$ref` pointers being resolved to break the reference cycles of non-object
+    /// schemas.
+    resolving: HashSet<String>,
+    /// Whether the `regex` module is used by the checks.
+    pub(crate) uses_regex: bool,
+}
+
+impl<'a> SchemaGenerator<'a> {
+    pub(crate) fn new(root: &'a Value) -> Self {
+        Self {
+            root,
+            schemas: vec![],
+            names: HashSet::new(),
+            refs: HashMap::new(),
+            resolving: HashSet::new(),
+            uses_regex: false,
+        }
+    }
+
+    /// Returns the generated schema statements.
+    pub(crate) fn finish(self) -> Vec<SchemaStmt> {
+        self.schemas.into_iter().flatten().collect()
+    }
+
+    /// Generate the object schemas under the `/` separated `path` of the document root,
+    /// e.g., `$defs` or `components/schemas`.
+    pub(crate) fn gen_definitions(&mut self, doc: &Value, path: &str) -> Result<()> {
+        let mut defs = Some(doc);
+        for key in path.split('/') {
+            defs = defs.and_then(|defs| defs.get(key));
+        }
+        let Some(defs) = defs.and_then(Value::as_mapping) else {
+            return Ok(());
+        };
+        for (key, schema) in defs {
+            let Some(key) = key.as_str() else {
+                continue;
+            };
+            if is_object_schema(schema) {
+                self.ref_type(&format!("#/{path}/{}", escape_pointer(key)))?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Generate the schema statement named `name` from the object `schema` and returns
+    /// the unique schema name.
+    pub(crate) fn gen_schema(&mut self, name: &str, schema: &Value) -> Result<String> {
+        let name = self.unique_name(name);
+        self.gen_named_schema(name, schema)
+    }
+
+    /// Generate the schema statement with the reserved unique `name`.
+    fn gen_named_schema(&mut self, name: String, schema: &Value) -> Result<String> {
+        let index = self.schemas.len();
+        self.schemas.push(None);
+        let (parent_name, merged) = self.inherit_all_of(schema)?;
+        let schema = merged.as_ref().unwrap_or(schema);
+
+        let required: HashSet<&str> = schema
+            .get("required")
+            .and_then(Value::as_sequence)
+            .map(|required| required.iter().filter_map(Value::as_str).collect())
+            .unwrap_or_default();
+        let mut body = vec![];
+        let mut checks = vec![];
+        let mut attr_docs = vec![];
+        for (key, property) in properties(schema) {
+            let ty = self.type_of(property, &format!("{name}{}", schema_name(key)))?;
+            let is_optional = !required.contains(key);
+            let default = property.get("default");
+            let attr_name = attr_name(key);
+            if is_identifier(&attr_name) {
+                checks.extend(self.checks(&attr_name, property, is_optional));
+            }
+            let mut attr_doc = format!("{key} : {ty}");
+            if let Some(default) = default.and_then(value_expr) {
+                attr_doc.push_str(&format!(
+                    ", default is {}",
+                    kcl_ast_pretty::print_ast_node(kcl_ast_pretty::ASTNode::Expr(&default))
+                ));
+            }
+            attr_doc.push_str(if is_optional {
+                ", optional"
+            } else {
+                ", required"
+            });
+            attr_docs.push((attr_doc, description(property)));
+            body.push(node(Stmt::SchemaAttr(SchemaAttr {
+                doc: String::new(),
+                name: node(attr_name),
+                op: default.map(|_| kcl_ast::ast::AugOp::Assign),
+                value: default.and_then(value_expr),
+                is_optional,
+                decorators: vec![],
+                ty: node(ty),
+            })));
+        }
+        let index_signature = match schema.get("additionalProperties") {
+            Some(Value::Bool(true)) => Some(Type::Any),
+            Some(value @ Value::Mapping(_)) => Some(self.type_of(value, &format!("{name}Value"))?),
+            _ if is_true(schema, "x-kubernetes-preserve-unknown-fields") => Some(Type::Any),
+            _ => None,
+        }
+        .map(|value_ty| {
+            node(SchemaIndexSignature {
+                key_name: None,
+                value: None,
+                any_other: true,
+                key_ty: node(Type::Basic(BasicType::Str)),
+                value_ty: node(value_ty),
+            })
+        });
+        let doc = schema_doc(description(schema), &attr_docs);
+        self.schemas[index] = Some(SchemaStmt {
+            doc: doc.map(node),
+            name: node(name.clone()),
+            parent_name: parent_name.map(|name| node(identifier(&name))),
+            for_host_name: None,
+            is_mixin: false,
+            is_protocol: false,
+            args: None,
+            mixins: vec![],
+            body,
+            decorators: vec![],
+            checks,
+            index_signature,
+        });
+        Ok(name)
+    }
+
+    /// Returns the KCL type of the JSON `schema`, the nested object schemas are generated
+    /// with the `name` hint.
+    fn type_of(&mut self, schema: &Value, name: &str) -> Result<Type> {
+        if let Some(pointer) = schema.get("$ref").and_then(Value::as_str) {
+            return self.ref_type(pointer);
+        }
+        if let Some(values) = schema.get("enum").and_then(Value::as_sequence) {
+            let types = values.iter().filter_map(literal_type).collect();
+            return Ok(union_type(types));
+        }
+        if let Some(value) = schema.get("const") {
+            return Ok(literal_type(value).unwrap_or(Type::Any));
+        }
+        if is_true(schema, "x-kubernetes-int-or-string") {
+            return Ok(union_type(vec![
+                Type::Basic(BasicType::Int),
+                Type::Basic(BasicType::Str),
+            ]));
+        }
+        for key in ["anyOf", "oneOf"] {
+            if let Some(items) = schema.get(key).and_then(Value::as_sequence) {
+                let mut types = vec![];
+                for item in items {
+                    types.push(self.type_of(item, name)?);
+                }
+                return Ok(union_type(types));
+            }
+        }
+        if let Some(items) = schema.get("allOf").and_then(Value::as_sequence) {
+            if let [item] = items.as_slice() {
+                return self.type_of(item, name);
+            }
+            return self.object_type(schema, name);
+        }
+        let types: Vec<&str> = match schema.get("type") {
+            Some(Value::String(ty)) => vec![ty.as_str()],
+            Some(Value::Sequence(types)) => types
+                .iter()
+                .filter_map(Value::as_str)
+                .filter(|ty| *ty != "null")
+                .collect(),
+            _ if schema.get("properties").is_some() => vec!["object"],
+            _ if schema.get("items").is_some() => vec!["array"],
+            _ => vec![],
+        };
+        let mut result = vec![];
+        for ty in types {
+            result.push(match ty {
+                "object" => self.object_type(schema, name)?,
+                "array" => {
+                    let item_ty = match schema.get("items") {
+                        Some(items) => self.type_of(items, &format!("{name}Item"))?,
+                        None => Type::Any,
+                    };
+                    Type::List(ListType {
+                        inner_type: Some(node(item_ty)),
+                    })
+                }
+                "string" => Type::Basic(BasicType::Str),
+                "integer" => Type::Basic(BasicType::Int),
+                "number" => Type::Basic(BasicType::Float),
+                "boolean" => Type::Basic(BasicType::Bool),
+                _ => Type::Any,
+            });
+        }
+        Ok(union_type(result))
+    }
+
+    /// Returns the schema type of the object schema with properties, or the dict type
+    /// of the object schema without properties.
+    fn object_type(&mut self, schema: &Value, name: &str) -> Result<Type> {
+        if is_object_schema(schema) {
+            let name = self.gen_schema(name, schema)?;
+            return Ok(named_type(&name));
+        }
+        let value_ty = match schema.get("additionalProperties") {
+            Some(value @ Value::Mapping(_)) => self.type_of(value, &format!("{name}Value"))?,
+            _ => Type::Any,
+        };
+        Ok(Type::Dict(DictType {
+            key_type: Some(node(Type::Basic(BasicType::Str))),
+            value_type: Some(node(value_ty)),
+        }))
+    }
+
+    /// Returns the type of the local `$ref` pointer e.g., `#/$defs/Name`. The referenced
+    /// object schemas are generated once, and other schemas are inlined.
+    fn ref_type(&mut self, pointer: &str) -> Result<Type> {
+        if let Some(name) = self.refs.get(pointer) {
+            return Ok(named_type(name));
+        }
+        let Some(schema) = resolve_pointer(self.root, pointer) else {
+            bail!("Failed to resolve the schema reference '{pointer}'");
+        };
+        let ref_name = pointer.rsplit('/').next().unwrap_or_default();
+        let ref_name = schema_name(&ref_name.replace("~1", "/").replace("~0", "~"));
+        if is_object_schema(schema) {
+            // Register the name before generating the schema for the self references.
+            let name = self.unique_name(&ref_name);
+            self.refs.insert(pointer.to_string(), name.clone());
+            self.gen_named_schema(name.clone(), schema)?;
+            Ok(named_type(&name))
+        } else if self.resolving.insert(pointer.to_string()) {
+            let ty = self.type_of(schema, &ref_name);
+            self.resolving.remove(pointer);
+            ty
+        } else {
+            Ok(Type::Any)
+        }
+    }
+
+    /// Returns the parent schema name and the merged object schema of the `allOf` schemas.
+    /// The only referenced object schema of `allOf` is generated as the parent schema,
+    /// and the properties and the required attributes of other schemas are merged.
+    fn inherit_all_of(&mut self, schema: &Value) -> Result<(Option<String>, Option<Value>)> {
+        let Some(items) = schema.get("allOf").and_then(Value::as_sequence) else {
+            return Ok((None, None));
+        };
+        let refs: Vec<&str> = items
+            .iter()
+            .filter_map(|item| item.get("$ref").and_then(Value::as_str))
+            .filter(|pointer| resolve_pointer(self.root, pointer).is_some_and(is_object_schema))
+            .collect();
+        let parent = match refs.as_slice() {
+            [pointer] => Some(*pointer),
+            _ => None,
+        };
+        let mut properties = serde_yaml::Mapping::new();
+        let mut required = vec![];
+        let mut merged = serde_yaml::Mapping::new();
+        let mut sources = vec![schema];
+        for item in items {
+            match item.get("$ref").and_then(Value::as_str) {
+                Some(pointer) if Some(pointer) == parent => {}
+                Some(pointer) => sources.extend(resolve_pointer(self.root, pointer)),
+                None => sources.push(item),
+            }
+        }
+        for source in sources {
+            for (key, property) in properties_mapping(source) {
+                properties.insert(key.clone(), property.clone());
+            }
+            if let Some(items) = source.get("required").and_then(Value::as_sequence) {
+                required.extend(items.iter().cloned());
+            }
+            for key in ["description", "additionalProperties"] {
+                if let Some(value) = source.get(key) {
+                    merged.entry(key.into()).or_insert_with(|| value.clone());
+                }
+            }
+        }
+        merged.insert("type".into(), "object".into());
+        merged.insert("properties".into(), Value::Mapping(properties));
+        merged.insert("required".into(), Value::Sequence(required));
+        let parent = match parent {
+            Some(pointer) => Some(self.ref_type(pointer)?.to_string()),
+            None => None,
+        };
+        Ok((parent, Some(Value::Mapping(merged))))
+    }
+
+    /// Returns the check expressions of the validation keywords of the attribute. The
+    /// checks of optional attributes only apply when the attribute is set.
+    fn checks(&mut self, attr: &str, schema: &Value, is_optional: bool) -> Vec<NodeRef<CheckExpr>> {
+        let mut tests = vec![];
+        let (lower, upper) = bounds(
+            schema,
+            ("minimum", "exclusiveMinimum"),
+            ("maximum", "exclusiveMaximum"),
+        );
+        tests.extend(bound_check(|| ident_expr(attr), lower, upper));
+        for (min, max) in [
+            ("minLength", "maxLength"),
+            ("minItems", "maxItems"),
+            ("minProperties", "maxProperties"),
+        ] {
+            let lower = schema.get(min).and_then(number_expr).map(|n| (n, false));
+            let upper = schema.get(max).and_then(number_expr).map(|n| (n, false));
+            tests.extend(bound_check(
+                || call_expr(&["len"], vec![ident_expr(attr)]),
+                lower,
+                upper,
+            ));
+        }
+        if let Some(pattern) = schema.get("pattern").and_then(Value::as_str) {
+            self.uses_regex = true;
+            tests.push(call_expr(
+                &[REGEX_MODULE, "match"],
+                vec![ident_expr(attr), node(Expr::StringLit(raw_string(pattern)))],
+            ));
+        }
+        if let Some(n) = schema.get("multipleOf").and_then(Value::as_i64) {
+            tests.push(call_expr(
+                &["multiplyof"],
+                vec![ident_expr(attr), int_expr(n)],
+            ));
+        }
+        if is_true(schema, "uniqueItems") {
+            tests.push(call_expr(&["isunique"], vec![ident_expr(attr)]));
+        }
+        tests
+            .into_iter()
+            .map(|test| {
+                node(CheckExpr {
+                    test,
+                    if_cond: is_optional.then(|| is_set_expr(attr)),
+                    msg: None,
+                })
+            })
+            .collect()
+    }
+
+    /// Returns a schema name not used yet by appending a number to the name.
+    fn unique_name(&mut self, name: &str) -> String {
+        let mut unique = name.to_string();
+        let mut i = 1;
+        while self.names.contains(&unique) {
+            unique = format!("{name}{i}");
+            i += 1;
+        }
+        self.names.insert(unique.clone());
+        unique
+    }
+}
+
+/// Returns the module of the schema statements, which imports the `regex` module when
+/// used by the checks.
+pub(crate) fn module(schemas: Vec<SchemaStmt>, uses_regex: bool) -> Module {
+    let mut stmts = vec![];
+    if uses_regex {
+        stmts.push(Stmt::Import(ImportStmt {
+            path: Node::dummy_node(REGEX_MODULE.to_string()),
+            rawpath: REGEX_MODULE.to_string(),
+            name: REGEX_MODULE.to_string(),
+            asname: None,
+            pkg_name: kcl_ast::MAIN_PKG.to_string(),
+        }));
+    }
+    stmts.extend(schemas.into_iter().map(Stmt::Schema));
+    Module {
+        filename: String::new(),
+        doc: None,
+        body: stmts.into_iter().map(node).collect(),
+        comments: vec![],
+    }
+}
+
+/// Returns the node without the position, the nodes without positions are printed in the
+/// compact layout of the printer e.g., the list elements are printed in one line.
+fn node<T>(node: T) -> NodeRef<T> {
+    NodeRef::new(Node::new(node, String::new(), 0, 0, 0, 0))
+}
+
+/// Returns whether the JSON schema is an object schema with properties, which is
+/// generated as a KCL schema.
+pub(crate) fn is_object_schema(schema: &Value) -> bool {
+    let is_object = match schema.get("type") {
+        Some(Value::String(ty)) => ty == "object",
+        Some(Value::Sequence(types)) => types.iter().any(|ty| ty.as_str() == Some("object")),
+        _ => true,
+    };
+    is_object
+        && (properties(schema).next().is_some() || schema.get("allOf").is_some())
+        && schema.get("$ref").is_none()
+}
+
+/// Convert the name to a PascalCase schema name e.g., `io.k8s.api.core.v1.Pod` to
+/// `IoK8sApiCoreV1Pod`.
+pub(crate) fn schema_name(name: &str) -> String {
+    let mut result = String::new();
+    for part in name.split(|c: char| !c.is_ascii_alphanumeric()) {
+        let mut chars = part.chars();
+        if let Some(first) = chars.next() {
+            result.push(first.to_ascii_uppercase());
+            result.push_str(chars.as_str());
+        }
+    }
+    if !result.starts_with(|c: char| c.is_ascii_alphabetic()) {
+        result.insert_str(0, "Schema");
+    }
+    result
+}
+
+/// Returns the attribute name, the reserved words are prefixed with `$`.
+fn attr_name(key: &str) -> String {
+    if is_reserved_word(key) {
+        format!("${key}")
+    } else {
+        key.to_string()
+    }
+}
+
+fn is_identifier(name: &str) -> bool {
+    let name = name.strip_prefix('$').unwrap_or(name);
+    name.starts_with(|c: char| c.is_ascii_alphabetic() || c == '_')
+        && name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_')
+}
+
+fn is_true(schema: &Value, key: &str) -> bool {
+    schema.get(key).and_then(Value::as_bool).unwrap_or(false)
+}
+
+fn description(schema: &Value) -> Option<&str> {
+    schema
+        .get("description")
+        .or_else(|| schema.get("title"))
+        .and_then(Value::as_str)
+        .map(str::trim)
+        .filter(|description| !description.is_empty())
+}
+
+fn properties_mapping(schema: &Value) -> impl Iterator<Item = (&Value, &Value)> {
+    schema
+        .get("properties")
+        .and_then(Value::as_mapping)
+        .into_iter()
+        .flatten()
+}
+
+fn properties(schema: &Value) -> impl Iterator<Item = (&str, &Value)> {
+    properties_mapping(schema).filter_map(|(key, value)| key.as_str().map(|key| (key, value)))
+}
+
+/// Resolve the local JSON pointer e.g., `#/components/schemas/Name` in the root.
+fn resolve_pointer<'v>(root: &'v Value, pointer: &str) -> Option<&'v Value> {
+    let path = pointer.strip_prefix('#')?;
+    let mut value = root;
+    for key in path.split('/').filter(|key| !key.is_empty()) {
+        let key = key.replace("~1", "/").replace("~0", "~");
+        value = match value {
+            Value::Sequence(items) => items.get(key.parse::<usize>().ok()?)?,
+            _ => value.get(key.as_str())?,
+        };
+    }
+    Some(value)
+}
+
+fn escape_pointer(key: &str) -> String {
+    key.replace('~', "~0").replace('/', "~1")
+}
+
+/// Returns the union of the types without duplicates, which is `any` when any of the
+/// types is `any` or there are no types.
+fn union_type(types: Vec<Type>) -> Type {
+    let mut elements: Vec<Type> = vec![];
+    for ty in types {
+        if matches!(ty, Type::Any) {
+            return Type::Any;
+        }
+        // The AST nodes are compared by their printed types without the node ids.
+        if !elements
+            .iter()
+            .any(|element| element.to_string() == ty.to_string())
+        {
+            elements.push(ty);
+        }
+    }
+    match elements.len() {
+        0 => Type::Any,
+        1 => elements.remove(0),
+        _ => Type::Union(UnionType {
+            type_elements: elements.into_iter().map(node).collect(),
+        }),
+    }
+}
+
+fn named_type(name: &str) -> Type {
+    Type::Named(identifier(name))
+}
+
+fn identifier(name: &str) -> Identifier {
+    Identifier {
+        names: name
+            .split('.')
+            .map(|name| Node::dummy_node(name.to_string()))
+            .collect(),
+        pkgpath: String::new(),
+        ctx: ExprContext::Load,
+    }
+}
+
+fn literal_type(value: &Value) -> Option<Type> {
+    let literal = match value {
+        Value::Bool(v) => LiteralType::Bool(*v),
+        Value::Number(n) => match n.as_i64() {
+            Some(v) => LiteralType::Int(IntLiteralType {
+                value: v,
+                suffix: None,
+            }),
+            None => LiteralType::Float(n.as_f64()?),
+        },
+        Value::String(v) => LiteralType::Str(v.clone()),
+        _ => return None,
+    };
+    Some(Type::Literal(literal))
+}
+
+/// Returns the lower and upper bounds of the value with whether they are exclusive.
+/// Both the numeric exclusive keywords of JSON Schema and the boolean exclusive flags
+/// of OpenAPI v3.0 are supported.
+type Bound = Option<(NodeRef<Expr>, bool)>;
+
+fn bounds(schema: &Value, min: (&str, &str), max: (&str, &str)) -> (Bound, Bound) {
+    let bound = |(inclusive, exclusive): (&str, &str)| match schema.get(exclusive) {
+        Some(Value::Number(_)) => schema
+            .get(exclusive)
+            .and_then(number_expr)
+            .map(|n| (n, true)),
+        Some(Value::Bool(exclusive)) => schema
+            .get(inclusive)
+            .and_then(number_expr)
+            .map(|n| (n, *exclusive)),
+        _ => schema
+            .get(inclusive)
+            .and_then(number_expr)
+            .map(|n| (n, false)),
+    };
+    (bound(min), bound(max))
+}
+
+/// Returns the comparison of the value and the bounds, e.g., `0 <= value < 10`.
+fn bound_check(
+    value: impl Fn() -> NodeRef<Expr>,
+    lower: Bound,
+    upper: Bound,
+) -> Option<NodeRef<Expr>> {
+    let op = |exclusive: bool, lt: CmpOp, lte: CmpOp| if exclusive { lt } else { lte };
+    let compare = match (lower, upper) {
+        (Some((lower, lower_ex)), Some((upper, upper_ex))) => Compare {
+            left: lower,
+            ops: vec![
+                op(lower_ex, CmpOp::Lt, CmpOp::LtE),
+                op(upper_ex, CmpOp::Lt, CmpOp::LtE),
+            ],
+            comparators: vec![value(), upper],
+        },
+        (Some((lower, exclusive)), None) => Compare {
+            left: value(),
+            ops: vec![op(exclusive, CmpOp::Gt, CmpOp::GtE)],
+            comparators: vec![lower],
+        },
+        (None, Some((upper, exclusive))) => Compare {
+            left: value(),
+            ops: vec![op(exclusive, CmpOp::Lt, CmpOp::LtE)],
+            comparators: vec![upper],
+        },
+        (None, None) => return None,
+    };
+    Some(node(Expr::Compare(compare)))
+}
+
+fn ident_expr(name: &str) -> NodeRef<Expr> {
+    node(Expr::Identifier(identifier(name)))
+}
+
+/// Returns whether the optional attribute is set, e.g., `attr not in [None, Undefined]`.
+/// The falsy values such as `0` are set values, so the attribute itself is not used as
+/// the condition.
+fn is_set_expr(attr: &str) -> NodeRef<Expr> {
+    let constant = |value| node(Expr::NameConstantLit(NameConstantLit { value }));
+    node(Expr::Compare(Compare {
+        left: ident_expr(attr),
+        ops: vec![CmpOp::NotIn],
+        comparators: vec![node(Expr::List(ListExpr {
+            elts: vec![
+                constant(NameConstant::None),
+                constant(NameConstant::Undefined),
+            ],
+            ctx: ExprContext::Load,
+        }))],
+    }))
+}
+
+fn call_expr(func: &[&str], args: Vec<NodeRef<Expr>>) -> NodeRef<Expr> {
+    node(Expr::Call(CallExpr {
+        func: ident_expr(&func.join(".")),
+        args,
+        keywords: vec![],
+    }))
+}
+
+fn int_expr(value: i64) -> NodeRef<Expr> {
+    node(Expr::NumberLit(NumberLit {
+        binary_suffix: None,
+        value: NumberLitValue::Int(value),
+    }))
+}
+
+fn number_expr(value: &Value) -> Option<NodeRef<Expr>> {
+    let Value::Number(n) = value else {
+        return None;
+    };
+    match n.as_i64() {
+        Some(v) => Some(int_expr(v)),
+        None => Some(node(Expr::NumberLit(NumberLit {
+            binary_suffix: None,
+            value: NumberLitValue::Float(n.as_f64()?),
+        }))),
+    }
+}
+
+/// Returns the raw string literal of the regular expression pattern.
+fn raw_string(value: &str) -> StringLit {
+    let mut lit = StringLit::from(value.to_string());
+    if !value.contains('"') && !value.ends_with('\\') {
+        lit.raw_value = format!("r\"{value}\"");
+    }
+    lit
+}
+
+/// Convert the JSON value to the KCL expression, the objects are converted to dict
+/// expressions.
+fn value_expr(value: &Value) -> Option<NodeRef<Expr>> {
+    let expr = match value {
+        Value::Null => Expr::NameConstantLit(NameConstantLit {
+            value: NameConstant::None,
+        }),
+        Value::Bool(v) => Expr::NameConstantLit(NameConstantLit {
+            value: if *v {
+                NameConstant::True
+            } else {
+                NameConstant::False
+            },
+        }),
+        Value::Number(_) => return number_expr(value),
+        Value::String(v) => Expr::StringLit(StringLit::from(v.clone())),
+        Value::Sequence(items) => Expr::List(ListExpr {
+            elts: items.iter().map(value_expr).collect::<Option<Vec<_>>>()?,
+            ctx: ExprContext::Load,
+        }),
+        Value::Mapping(items) => Expr::Config(ConfigExpr {
+            items: items
+                .iter()
+                .map(|(key, value)| {
+                    Some(node(ConfigEntry {
+                        key: Some(value_expr(key)?),
+                        value: value_expr(value)?,
+                        operation: ConfigEntryOperation::Union,
+                    }))
+                })
+                .collect::<Option<Vec<_>>>()?,
+        }),
+        Value::Tagged(tagged) => return value_expr(&tagged.value),
+    };
+    Some(node(expr))
+}
+
+/// Returns the schema document string including the description and the `Attributes`
+/// section in the KCL document style.
+fn schema_doc(description: Option<&str>, attrs: &[(String, Option<&str>)]) -> Option<String> {
+    const INDENT: &str = "    ";
+    if description.is_none() && attrs.is_empty() {
+        return None;
+    }
+    let mut lines: Vec<String> = vec![];
+    if let Some(description) = description {
+        lines.extend(description.lines().map(|line| line.trim_end().to_string()));
+    }
+    if !attrs.is_empty() {
+        if !lines.is_empty() {
+            lines.push(String::new());
+        }
+        lines.push("Attributes".to_string());
+        lines.push("----------".to_string());
+        for (attr, description) in attrs {
+            lines.push(attr.clone());
+            if let Some(description) = description {
+                lines.extend(
+                    description
+                        .lines()
+                        .map(|line| format!("{INDENT}{}", line.trim_end())),
+                );
+            }
+        }
+    }
+    let content = lines
+        .iter()
+        .map(|line| {
+            if line.trim().is_empty() {
+                String::new()
+            } else {
+                format!("{INDENT}{}", line.replace("\"\"\"", "'''"))
+            }
+        })
+        .collect::<Vec<String>>()
+        .join("\n");
+    // Use the raw string to keep the backslashes in the description.
+    let prefix = if content.contains('\\') { "r" } else { "" };
+    Some(format!("{prefix}\"\"\"\n{content}\n{INDENT}\"\"\""))
+}
@@ -0,0 +1,295 @@
+//! [kcl_tools::import] module generates KCL schema definitions from JSON Schema,
+//! OpenAPI v3 and Kubernetes CRD documents, the main API function is `import_source`,
+//! which accepts the document source and the import options.
+//!
+//! The object schemas of the document are mapped to [kcl_ast::ast::SchemaStmt] nodes,
+//! which are printed as source code string by [kcl_ast_pretty::print_ast_module].
+use std::path::Path;
+use std::str::FromStr;
+
+use anyhow::{Context, Result, bail};
+use kcl_ast::ast::Module;
+use kcl_ast_pretty::print_ast_module;
+use serde::Deserialize;
+use serde_yaml::{Mapping, Value};
+
+use generator::SchemaGenerator;
+
+mod generator;
+#[cfg(test)]
+mod tests;
+
+const CRD_KIND: &str = "CustomResourceDefinition";
+const DEFAULT_ROOT_NAME: &str = "Root";
+
+/// The kind of the imported document.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum ImportMode {
+    /// Detect the kind from the document content.
+    #[default]
+    Auto,
+    /// A JSON Schema document whose root and `$defs` (or `definitions`) are imported.
+    JsonSchema,
+    /// An OpenAPI v3 (or Swagger v2) document whose `components.schemas` (or
+    /// `definitions`) are imported.
+    OpenApi,
+    /// One or more Kubernetes `CustomResourceDefinition` documents whose version
+    /// schemas are imported.
+    Crd,
+}
+
+impl FromStr for ImportMode {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s.to_lowercase().as_str() {
+            "" | "auto" => Ok(ImportMode::Auto),
+            "jsonschema" | "json_schema" | "json-schema" => Ok(ImportMode::JsonSchema),
+            "openapi" | "swagger" => Ok(ImportMode::OpenApi),
+            "crd" => Ok(ImportMode::Crd),
+            _ => bail!(
+                "Invalid import mode '{s}', expected 'auto', 'jsonschema', 'openapi' or 'crd'"
+            ),
+        }
+    }
+}
+
+/// ImportOptions contains the options of importing schemas:
+/// - mode: the kind of the imported document.
+/// - name: the schema name of a JSON Schema root, which defaults to its `title`.
+#[derive(Debug, Default, Clone)]
+pub struct ImportOptions {
+    pub mode: ImportMode,
+    pub name: Option<String>,
+}
+
+/// Imports a JSON Schema, OpenAPI or CRD file in the JSON or YAML format and returns
+/// the KCL code of the generated schemas. The root schema of a JSON Schema file without
+/// a `title` is named after the file.
+///
+/// # Examples
+///
+/// ```no_run
+/// use kcl_tools::import::{import_file, ImportOptions};
+///
+/// let code = import_file("crontab.crd.yaml", &ImportOptions::default()).unwrap();
+/// std::fs::write("crontab.k", code).unwrap();
+/// ```
+pub fn import_file<P: AsRef<Path>>(path: P, opts: &ImportOptions) -> Result<String> {
+    let path = path.as_ref();
+    let src = std::fs::read_to_string(path)
+        .with_context(|| format!("Failed to read the file '{}'", path.display()))?;
+    let mut opts = opts.clone();
+    if opts.name.is_none() {
+        opts.name = path
+            .file_name()
+            .and_then(|name| name.to_str())
+            .and_then(|name| name.split('.').next())
+            .map(generator::schema_name);
+    }
+    import_source(&src, &opts)
+}
+
+/// Imports a JSON Schema, OpenAPI or CRD document source in the JSON or YAML format and
+/// returns the KCL code of the generated schemas.
+///
+/// # Examples
+///
+/// ```
+/// use kcl_tools::import::{import_source, ImportOptions};
+///
+/// let src = r#"{
+///     "title": "Person",
+///     "type": "object",
+///     "properties": {
+///         "name": {"type": "string", "minLength": 1},
+///         "age": {"type": "integer", "minimum": 0}
+///     },
+///     "required": ["name"]
+/// }"#;
+/// let code = import_source(src, &ImportOptions::default()).unwrap();
+/// assert!(code.contains("schema Person:"));
+/// assert!(code.contains("age?: int"));
+/// ```
+pub fn import_source(src: &str, opts: &ImportOptions) -> Result<String> {
+    Ok(print_ast_module(&import_module(src, opts)?))
+}
+
+/// Imports a JSON Schema, OpenAPI or CRD document source and returns the AST module
+/// of the generated schemas.
+pub fn import_module(src: &str, opts: &ImportOptions) -> Result<Module> {
+    let docs = serde_yaml::Deserializer::from_str(src)
+        .map(Value::deserialize)
+        .filter(|doc| !matches!(doc, Ok(Value::Null)))
+        .collect::<std::result::Result<Vec<Value>, _>>()
+        .context("Failed to parse the document")?;
+    let Some(first) = docs.first() else {
+        bail!("The document is empty")
+    };
+    let mode = match opts.mode {
+        ImportMode::Auto => detect_mode(first),
+        mode => mode,
+    };
+    let mut schemas = vec![];
+    let mut uses_regex = false;
+    for doc in &docs {
+        let mut generator = SchemaGenerator::new(doc);
+        match mode {
+            ImportMode::Auto | ImportMode::JsonSchema => {
+                let name = opts
+                    .name
+                    .clone()
+                    .or_else(|| {
+                        doc.get("title")
+                            .and_then(Value::as_str)
+                            .map(generator::schema_name)
+                    })
+                    .unwrap_or_else(|| DEFAULT_ROOT_NAME.to_string());
+                if generator::is_object_schema(doc) {
+                    generator.gen_schema(&name, doc)?;
+                }
+                for defs in ["$defs", "definitions"] {
+                    generator.gen_definitions(doc, defs)?;
+                }
+            }
+            ImportMode::OpenApi => {
+                generator.gen_definitions(doc, "components/schemas")?;
+                generator.gen_definitions(doc, "definitions")?;
+            }
+            ImportMode::Crd => {
+                for (name, schema) in crd_schemas(doc)? {
+                    generator.gen_schema(&name, &schema)?;
+                }
+            }
+        }
+        uses_regex |= generator.uses_regex;
+        schemas.extend(generator.finish());
+    }
+    if schemas.is_empty() {
+        bail!("No schema definitions found in the document");
+    }
+    Ok(generator::module(schemas, uses_regex))
+}
+
+/// Detect the import mode from the document content.
+fn detect_mode(doc: &Value) -> ImportMode {
+    if doc.get("kind").and_then(Value::as_str) == Some(CRD_KIND) {
+        ImportMode::Crd
+    } else if doc.get("openapi").is_some() || doc.get("swagger").is_some() {
+        ImportMode::OpenApi
+    } else {
+        ImportMode::JsonSchema
+    }
+}
+
+/// Returns the schema names and the OpenAPI v3 schemas of the versions in the CRD. The
+/// schema is named after the kind when there is only one version, otherwise the version is
+/// appended to the kind e.g., `CronTabV1beta1`. The `apiVersion` and `kind` attributes are
+/// fixed to the values of the version.
+fn crd_schemas(doc: &Value) -> Result<Vec<(String, Value)>> {
+    if doc.get("kind").and_then(Value::as_str) != Some(CRD_KIND) {
+        bail!("The document is not a {CRD_KIND}");
+    }
+    let spec = doc
+        .get("spec")
+        .with_context(|| format!("The {CRD_KIND} spec is missing"))?;
+    let group = spec
+        .get("group")
+        .and_then(Value::as_str)
+        .unwrap_or_default();
+    let kind = spec
+        .get("names")
+        .and_then(|names| names.get("kind"))
+        .and_then(Value::as_str)
+        .with_context(|| format!("The {CRD_KIND} kind name is missing"))?;
+    let mut versions = vec![];
+    match spec.get("versions").and_then(Value::as_sequence) {
+        Some(items) if !items.is_empty() => {
+            for item in items {
+                let version = item.get("name").and_then(Value::as_str).unwrap_or_default();
+                // The `apiextensions.k8s.io/v1beta1` CRDs share the validation schema.
+                let schema = item
+                    .get("schema")
+                    .or_else(|| spec.get("validation"))
+                    .and_then(|schema| schema.get("openAPIV3Schema"));
+                if let Some(schema) = schema {
+                    versions.push((version, schema));
+                }
+            }
+        }
+        _ => {
+            let version = spec
+                .get("version")
+                .and_then(Value::as_str)
+                .unwrap_or_default();
+            if let Some(schema) = spec
+                .get("validation")
+                .and_then(|schema| schema.get("openAPIV3Schema"))
+            {
+                versions.push((version, schema));
+            }
+        }
+    }
+    if versions.is_empty() {
+        bail!("No openAPIV3Schema found in the {CRD_KIND} '{kind}'");
+    }
+    let single = versions.len() == 1;
+    Ok(versions
+        .into_iter()
+        .map(|(version, schema)| {
+            let name = if single {
+                generator::schema_name(kind)
+            } else {
+                generator::schema_name(&format!("{kind}_{version}"))
+            };
+            let api_version = if group.is_empty() {
+                version.to_string()
+            } else {
+                format!("{group}/{version}")
+            };
+            (name, crd_version_schema(schema, &api_version, kind))
+        })
+        .collect())
+}
+
+/// Fix the `apiVersion` and `kind` attributes of the CRD version schema to the constant
+/// values and map the `metadata` attribute to a dict.
+fn crd_version_schema(schema: &Value, api_version: &str, kind: &str) -> Value {
+    let mut schema = schema.clone();
+    let Some(schema_map) = schema.as_mapping_mut() else {
+        return schema;
+    };
+    let properties = schema_map
+        .entry("properties".into())
+        .or_insert_with(|| Value::Mapping(Mapping::new()));
+    if let Some(properties) = properties.as_mapping_mut() {
+        for (key, value) in [("apiVersion", api_version), ("kind", kind)] {
+            let mut property = Mapping::new();
+            if let Some(description) = properties
+                .get(key)
+                .and_then(|property| property.get("description"))
+            {
+                property.insert("description".into(), description.clone());
+            }
+            property.insert("type".into(), "string".into());
+            property.insert("enum".into(), Value::Sequence(vec![value.into()]));
+            property.insert("default".into(), value.into());
+            properties.insert(key.into(), Value::Mapping(property));
+        }
+        properties.insert(
+            "metadata".into(),
+            serde_yaml::from_str("type: object").unwrap(),
+        );
+    }
+    let required = schema_map
+        .entry("required".into())
+        .or_insert_with(|| Value::Sequence(vec![]));
+    if let Some(required) = required.as_sequence_mut() {
+        for key in ["apiVersion", "kind"] {
+            if !required.iter().any(|item| item.as_str() == Some(key)) {
+                required.push(key.into());
+            }
+        }
+    }
+    schema
+}
@@ -0,0 +1,137 @@
+use std::path::{Path, PathBuf};
+
+use kcl_parser::ParseSessionRef;
+use kcl_runner::{ExecProgramArgs, exec_program};
+
+use super::{ImportMode, ImportOptions, import_file, import_source};
+use crate::format::{FormatOptions, format_source};
+
+fn test_data(name: &str) -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR"))
+        .join("src")
+        .join("import")
+        .join("test_data")
+        .join(name)
+}
+
+/// Run the generated code with the instance code and returns the error message.
+fn exec_with_instance(code: &str, instance: &str) -> String {
+    // The generated code is formatted.
+    let (formatted, _) = format_source("main.k", code, &FormatOptions::default()).unwrap();
+    assert_eq!(formatted, code);
+    let args = ExecProgramArgs {
+        k_filename_list: vec!["main.k".to_string()],
+        k_code_list: vec![format!("{code}\n{instance}\n")],
+        ..Default::default()
+    };
+    exec_program(ParseSessionRef::default(), &args)
+        .unwrap()
+        .err_message
+}
+
+#[test]
+fn test_import_json_schema() {
+    let code = import_file(test_data("person.schema.json"), &ImportOptions::default()).unwrap();
+    insta::assert_snapshot!(code);
+    let person = r#"person = Person {
+    name = "Alice"
+    age = 18
+    email = "alice@example.com"
+    address.street = "Main Street"
+    address.location = {lat = 30.5, lng = 120}
+    contacts = [{kind = "email", value = "alice@example.com"}]
+    id = "a1"
+    type = "person"
+}"#;
+    assert_eq!(exec_with_instance(&code, person), "");
+    let invalid = person.replace("age = 18", "age = 150");
+    assert!(exec_with_instance(&code, &invalid).contains("Check failed"));
+    let invalid = person.replace("alice@example.com\"\n", "alice\"\n");
+    assert!(exec_with_instance(&code, &invalid).contains("Check failed"));
+}
+
+#[test]
+fn test_import_openapi() {
+    let code = import_file(
+        test_data("petstore.openapi.yaml"),
+        &ImportOptions::default(),
+    )
+    .unwrap();
+    insta::assert_snapshot!(code);
+    let pet = r#"pet = NewPet {
+    id = 1
+    name = "kitty"
+    owner = "alice"
+    photoUrls = ["https://example.com/kitty.png"]
+    category.id = 2
+}"#;
+    assert_eq!(exec_with_instance(&code, pet), "");
+    assert!(exec_with_instance(&code, &pet.replace("id = 1", "id = 0")).contains("Check failed"));
+}
+
+#[test]
+fn test_import_crd() {
+    let opts = ImportOptions {
+        mode: ImportMode::Crd,
+        ..Default::default()
+    };
+    let code = import_file(test_data("crontab.crd.yaml"), &opts).unwrap();
+    insta::assert_snapshot!(code);
+    let crontab = r#"crontab = CronTab {
+    metadata.name = "crontab"
+    spec = {
+        cronSpec = "*/5 * * * *"
+        image = "busybox"
+        port = "http"
+        config.command = ["date"]
+    }
+}"#;
+    assert_eq!(exec_with_instance(&code, crontab), "");
+    assert!(exec_with_instance(&code, &crontab.replace("*/5", "every 5")).contains("Check failed"));
+}
+
+#[test]
+fn test_import_source() {
+    let src = r#"
+type: object
+properties:
+  name:
+    type: string
+"#;
+    let opts = ImportOptions {
+        name: Some("Config".to_string()),
+        ..Default::default()
+    };
+    assert_eq!(
+        import_source(src, &opts).unwrap(),
+        r#"schema Config:
+    """
+    Attributes
+    ----------
+    name : str, optional
+    """
+    name?: str
+"#
+    );
+    assert_eq!(
+        import_source("", &opts).unwrap_err().to_string(),
+        "The document is empty"
+    );
+    assert_eq!(
+        import_source("type: string", &opts)
+            .unwrap_err()
+            .to_string(),
+        "No schema definitions found in the document"
+    );
+    assert!(
+        import_source(r##"{"properties": {"a": {"$ref": "#/$defs/A"}}}"##, &opts)
+            .unwrap_err()
+            .to_string()
+            .contains("Failed to resolve the schema reference '#/$defs/A'")
+    );
+    assert!("graphql".parse::<ImportMode>().is_err());
+    assert_eq!(
+        "openapi".parse::<ImportMode>().unwrap(),
+        ImportMode::OpenApi
+    );
+}
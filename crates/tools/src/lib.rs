@@ -1,5 +1,6 @@
 pub mod fix;
 pub mod format;
+pub mod import;
 pub mod lint;
 pub mod testing;
 pub mod util;
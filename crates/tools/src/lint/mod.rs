@@ -1,9 +1,11 @@
 use std::sync::Arc;
 
-use kcl_error::{Diagnostic, Handler};
+use kcl_config::modfile::{ModFile, load_mod_file};
+use kcl_error::{Diagnostic, DiagnosticFormat, Handler};
 use kcl_parser::{LoadProgramOptions, ParseSession, load_program};
-use kcl_primitives::IndexSet;
+use kcl_primitives::{IndexMap, IndexSet};
 use kcl_runtime::PanicInfo;
+use kcl_sema::lint::LintLevel;
 use kcl_sema::resolver::resolve_program_with_opts;
 #[cfg(test)]
 mod tests;
@@ -82,11 +84,13 @@ pub fn lint_files(
                 .classification();
         }
     };
+    let lint_levels = load_lint_levels(&program.root);
     sess.append_diagnostic(
         resolve_program_with_opts(
             &mut program,
             kcl_sema::resolver::Options {
                 merge_program: false,
+                lint_levels,
                 ..Default::default()
             },
             None,
@@ -96,3 +100,55 @@ pub fn lint_files(
     )
     .classification()
 }
+
+/// Load the lint levels by lint code from the `[lint]` section of the `kcl.mod` file in the
+/// package root. Returns empty levels if the `kcl.mod` file or the `[lint]` section is not found.
+///
+/// ```toml
+/// [lint]
+/// allow = ["W0413"]
+/// warn = ["W0404"]
+/// deny = ["W0411"]
+/// ```
+pub fn load_lint_levels(root: &str) -> IndexMap<String, LintLevel> {
+    let mut levels = IndexMap::default();
+    if let Ok(ModFile {
+        lint: Some(lint), ..
+    }) = load_mod_file(root)
+    {
+        for (codes, level) in [
+            (lint.allow, LintLevel::Allow),
+            (lint.warn, LintLevel::Warn),
+            (lint.deny, LintLevel::Deny),
+        ] {
+            for code in codes.unwrap_or_default() {
+                levels.insert(code, level);
+            }
+        }
+    }
+    levels
+}
+
+/// KCL Lint tools API, check a set of files like [`lint_files`] and emit the error and warning
+/// diagnostics to a string in the given format. The JSON Lines and SARIF formats can be consumed
+/// by CI systems such as code scanning dashboards.
+///
+/// # Examples
+///
+/// ```no_run
+/// use kcl_error::DiagnosticFormat;
+/// use kcl_tools::lint::lint_files_with_format;
+/// let sarif = lint_files_with_format(&["test.k"], None, DiagnosticFormat::Sarif).unwrap();
+/// ```
+pub fn lint_files_with_format(
+    files: &[&str],
+    opts: Option<LoadProgramOptions>,
+    format: DiagnosticFormat,
+) -> anyhow::Result<String> {
+    let (errors, warnings) = lint_files(files, opts);
+    let mut handler = Handler::default();
+    for diag in errors.into_iter().chain(warnings) {
+        handler.add_diagnostic(diag);
+    }
+    handler.emit_to_string_with_format(format)
+}
@@ -0,0 +1,8 @@
+[package]
+name = "lint_config"
+edition = "v0.12.3"
+version = "0.0.1"
+
+[lint]
+allow = ["W0413"]
+deny = ["W0411"]
@@ -1,4 +1,5 @@
-use super::lint_files;
+use super::{lint_files, lint_files_with_format};
+use kcl_error::DiagnosticFormat;
 use std::path::PathBuf;
 
 #[test]
@@ -68,3 +69,130 @@ fn test_unused_check_for_each_file() {
         path.to_str().unwrap().to_string()
     );
 }
+
+#[test]
+fn test_lint_with_format() {
+    let file = "./src/lint/test_data/unused_check_for_each_file/a.k";
+    let json = lint_files_with_format(&[file], None, DiagnosticFormat::Json).unwrap();
+    let lines: Vec<serde_json::Value> = json
+        .lines()
+        .map(|line| serde_json::from_str(line).unwrap())
+        .collect();
+    assert_eq!(lines.len(), 1);
+    assert_eq!(lines[0]["level"], "warning");
+    assert_eq!(lines[0]["name"], "UnusedImportWarning");
+    assert_eq!(
+        lines[0]["messages"][0]["message"],
+        "Module 'math' imported but unused"
+    );
+    assert_eq!(
+        lines[0]["messages"][0]["note"],
+        "Consider removing this statement"
+    );
+    assert_eq!(lines[0]["messages"][0]["range"]["start"]["line"], 1);
+
+    let sarif = lint_files_with_format(&[file], None, DiagnosticFormat::Sarif).unwrap();
+    let sarif: serde_json::Value = serde_json::from_str(&sarif).unwrap();
+    assert_eq!(sarif["version"], "2.1.0");
+    let run = &sarif["runs"][0];
+    assert_eq!(run["tool"]["driver"]["name"], "kcl");
+    assert_eq!(
+        run["tool"]["driver"]["rules"][0]["name"],
+        "UnusedImportWarning"
+    );
+    let result = &run["results"][0];
+    assert_eq!(result["ruleIndex"], 0);
+    assert_eq!(result["level"], "warning");
+    assert_eq!(
+        result["message"]["text"],
+        "Module 'math' imported but unused\nnote: Consider removing this statement"
+    );
+    let location = &result["locations"][0]["physicalLocation"];
+    assert!(
+        location["artifactLocation"]["uri"]
+            .as_str()
+            .unwrap()
+            .ends_with("unused_check_for_each_file/a.k")
+    );
+    assert_eq!(location["region"]["startLine"], 1);
+}
+
+#[test]
+fn test_lint_with_config() {
+    let (errors, warnings) = lint_files(&["./src/lint/test_data/lint_config/main.k"], None);
+    // W0413 is allowed in kcl.mod and W0411 is denied, except for suppressed lines.
+    assert_eq!(warnings.len(), 1);
+    assert_eq!(
+        warnings[0].messages[0].message,
+        "Module 'math' is reimported multiple times"
+    );
+    let msgs = [
+        "Module 'math' imported but unused",
+        "Module 'net' imported but unused",
+    ];
+    assert_eq!(errors.len(), msgs.len());
+    for (diag, m) in errors.iter().zip(msgs.iter()) {
+        assert_eq!(diag.level, kcl_error::Level::Error);
+        assert_eq!(diag.messages[0].message, m.to_string());
+    }
+}
+
+#[test]
+fn test_new_lints() {
+    let (errors, warnings) = lint_files(&["./src/lint/test_data/new_lints/main.k"], None);
+    assert_eq!(errors.len(), 0);
+    let mut msgs: Vec<(String, String, u64)> = warnings
+        .iter()
+        .map(|diag| {
+            (
+                diag.code.as_ref().unwrap().code(),
+                diag.messages[0].message.clone(),
+                diag.messages[0].range.0.line,
+            )
+        })
+        .collect();
+    msgs.sort_by_key(|(_, _, line)| *line);
+    let expected = [
+        (
+            "W0612",
+            "Private variable '_unused' is defined but unused",
+            1,
+        ),
+        (
+            "W0614",
+            "The default value of attribute 'name' is always overridden at line 11",
+            7,
+        ),
+        (
+            "W0613",
+            "Private attribute '_secret' of schema 'Person' is defined but unused",
+            9,
+        ),
+        (
+            "W0125",
+            "The check expression is a constant and always succeeds",
+            15,
+        ),
+        (
+            "W0621",
+            "Loop variable 'x' shadows the variable of an enclosing loop",
+            20,
+        ),
+        (
+            "W0121",
+            "Comparison to None should use 'is' instead of '=='",
+            22,
+        ),
+    ];
+    assert_eq!(
+        msgs.len(),
+        expected.len(),
+        "{:?}",
+        msgs.iter().map(|(_, m, _)| m).collect::<Vec<_>>()
+    );
+    for ((code, msg, line), (e_code, e_msg, e_line)) in msgs.iter().zip(expected.iter()) {
+        assert_eq!(code, e_code);
+        assert_eq!(msg, e_msg);
+        assert_eq!(line, e_line);
+    }
+}
@@ -3,17 +3,38 @@
 //! The basic principle of the testing tool is to search for test files in the KCL package
 //! that have the suffix "_test.k" and do not start with "_". These test files will be regard
 //! as test suites. Within these files, any lambda literals starting with "test_" will be
-//! considered as test cases, but these lambda functions should not have any parameters.
+//! considered as test cases.
 //! To perform the testing, the tool compiles the test suite file and its dependencies into an
-//! [kcl_runner::Artifact], which is regard as a new compilation entry point. Then,
-//! it executes each test case separately and collects information about the test cases,
-//! such as the execution time and whether the test passes or fails.
-pub use crate::testing::suite::{TestSuite, load_test_suites};
+//! [kcl_runner::Artifact] only once, which is regard as a new compilation entry point. Then,
+//! it executes the test cases concurrently with the shared compiled program (see
+//! [run_test_suites] and [TestOptions::jobs]) and collects information about the test cases,
+//! such as the execution time and whether the test passes or fails. When the coverage is
+//! enabled, the line and branch coverage of the non-test files is also collected, which can
+//! be emitted as an LCOV tracefile or as a summary per file. The test results of the test
+//! suites can be collected into a [TestReport] and emitted as a JUnit XML or JSON report for
+//! CI systems.
+//!
+//! A test lambda with parameters is a parameterised test, whose cases are supplied by the
+//! `<name>_cases` list in the test files or the `testdata/<name>.cases.yaml` file, and every
+//! case is run and reported separately e.g., `test_add[0]`. A parameterised test without cases
+//! is reported as an error when the suite is loaded. A case is a list of positional
+//! arguments or a dict of keyword arguments, and the arguments not in the case are taken from
+//! the dict returned by the `setup_suite` lambda, which is evaluated once per suite. The
+//! `assert_golden(name, value)` function compares the YAML of the value with the
+//! `testdata/<name>.golden.yaml` snapshot, which is written instead when
+//! [TestOptions::update_golden] is enabled. It is generated only when the test files call
+//! it and the package does not define it.
+pub use crate::testing::report::{TestReport, TestReportFormat};
+pub use crate::testing::suite::{
+    TestCase, TestCasesSource, TestSuite, load_test_suites, run_test_suites,
+};
 use anyhow::{Error, Result};
 use kcl_primitives::IndexMap;
 use kcl_runner::ExecProgramArgs;
+pub use kcl_runner::{BranchCoverage, Coverage, CoverageSpan, FileCoverage};
 use std::time::Duration;
 
+mod report;
 mod suite;
 
 #[cfg(test)]
@@ -33,6 +54,8 @@ pub trait TestRun {
 pub struct TestResult {
     /// This field stores test case information in an [IndexMap], where the key is a [String] and the value is a [TestCaseInfo] struct.
     pub info: IndexMap<String, TestCaseInfo>,
+    /// This field stores the line and branch coverage of the non-test files, if the coverage is enabled.
+    pub coverage: Option<Coverage>,
 }
 
 /// Represents information about a test case.
@@ -55,4 +78,12 @@ pub struct TestOptions {
     pub run_regexp: String,
     /// This field determines whether the test run should stop on the first failure.
     pub fail_fast: bool,
+    /// This field determines whether to collect the line and branch coverage of the non-test files.
+    pub coverage: bool,
+    /// This field stores the maximum number of test cases run concurrently, where `0` means
+    /// the available parallelism of the machine or the `KCL_THREADS` environment variable.
+    pub jobs: usize,
+    /// This field determines whether to update the golden files with the values of the
+    /// `assert_golden` calls instead of comparing them.
+    pub update_golden: bool,
 }
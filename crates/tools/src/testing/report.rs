@@ -0,0 +1,232 @@
+use std::fmt::Write;
+use std::str::FromStr;
+use std::time::Duration;
+
+use anyhow::{Result, bail};
+use serde::Serialize;
+
+use crate::testing::{TestCaseInfo, TestResult};
+
+/// The report format of the test results for CI systems.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub enum TestReportFormat {
+    /// JUnit XML, one `testsuite` per package and one `testcase` per test case.
+    #[default]
+    JUnit,
+    /// A JSON document of the packages and their test cases.
+    Json,
+}
+
+impl FromStr for TestReportFormat {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s.to_lowercase().as_str() {
+            "junit" | "xml" => Ok(TestReportFormat::JUnit),
+            "json" => Ok(TestReportFormat::Json),
+            _ => bail!("Invalid test report format '{s}', expected 'junit' or 'json'"),
+        }
+    }
+}
+
+/// Collects the test results of the test suites, keyed by the package path, and emits
+/// them as a JUnit XML or JSON report.
+#[derive(Debug, Default)]
+pub struct TestReport {
+    /// The package path and the test result of each test suite in the run order.
+    pub suites: Vec<(String, TestResult)>,
+}
+
+#[derive(Serialize)]
+struct JsonReport<'a> {
+    tests: usize,
+    failures: usize,
+    time: f64,
+    suites: Vec<JsonSuite<'a>>,
+}
+
+#[derive(Serialize)]
+struct JsonSuite<'a> {
+    pkg: &'a str,
+    tests: usize,
+    failures: usize,
+    time: f64,
+    cases: Vec<JsonCase<'a>>,
+}
+
+#[derive(Serialize)]
+struct JsonCase<'a> {
+    name: &'a str,
+    pkg: &'a str,
+    time: f64,
+    passed: bool,
+    failure: Option<String>,
+    log_message: &'a str,
+}
+
+impl TestReport {
+    /// Add the test result of the test suite in the package `pkg`.
+    pub fn add(&mut self, pkg: impl Into<String>, result: TestResult) {
+        self.suites.push((pkg.into(), result));
+    }
+
+    /// Returns the number of the test cases.
+    pub fn tests(&self) -> usize {
+        self.suites
+            .iter()
+            .map(|(_, result)| result.info.len())
+            .sum()
+    }
+
+    /// Returns the number of the failed test cases.
+    pub fn failures(&self) -> usize {
+        self.suites.iter().map(|(_, result)| failures(result)).sum()
+    }
+
+    /// Returns the total duration of the test cases.
+    pub fn duration(&self) -> Duration {
+        self.suites.iter().map(|(_, result)| duration(result)).sum()
+    }
+
+    /// Returns the report in the given format.
+    pub fn emit(&self, format: TestReportFormat) -> Result<String> {
+        match format {
+            TestReportFormat::JUnit => Ok(self.to_junit()),
+            TestReportFormat::Json => self.to_json(),
+        }
+    }
+
+    /// Returns the report as a JUnit XML document. The failed test cases have a `failure`
+    /// element whose message is the first line of the error, and the captured `print` output
+    /// is written in the `system-out` element.
+    pub fn to_junit(&self) -> String {
+        let mut xml = String::new();
+        let _ = writeln!(xml, r#"<?xml version="1.0" encoding="UTF-8"?>"#);
+        let _ = writeln!(
+            xml,
+            r#"<testsuites name="kcl" tests="{}" failures="{}" errors="0" time="{}">"#,
+            self.tests(),
+            self.failures(),
+            seconds(self.duration())
+        );
+        for (pkg, result) in &self.suites {
+            let pkg = escape_xml(pkg);
+            let _ = writeln!(
+                xml,
+                r#"  <testsuite name="{pkg}" tests="{}" failures="{}" errors="0" time="{}">"#,
+                result.info.len(),
+                failures(result),
+                seconds(duration(result))
+            );
+            for (name, info) in &result.info {
+                let _ = write!(
+                    xml,
+                    r#"    <testcase name="{}" classname="{pkg}" time="{}""#,
+                    escape_xml(name),
+                    seconds(info.duration)
+                );
+                if info.error.is_none() && info.log_message.is_empty() {
+                    let _ = writeln!(xml, "/>");
+                    continue;
+                }
+                let _ = writeln!(xml, ">");
+                if let Some(error) = failure(info) {
+                    let message = error.lines().next().unwrap_or_default();
+                    let _ = writeln!(
+                        xml,
+                        r#"      <failure message="{}">{}</failure>"#,
+                        escape_xml(message),
+                        escape_xml(&error)
+                    );
+                }
+                if !info.log_message.is_empty() {
+                    let _ = writeln!(
+                        xml,
+                        "      <system-out>{}</system-out>",
+                        escape_xml(&info.log_message)
+                    );
+                }
+                let _ = writeln!(xml, "    </testcase>");
+            }
+            let _ = writeln!(xml, "  </testsuite>");
+        }
+        let _ = writeln!(xml, "</testsuites>");
+        xml
+    }
+
+    /// Returns the report as a JSON document.
+    pub fn to_json(&self) -> Result<String> {
+        let report = JsonReport {
+            tests: self.tests(),
+            failures: self.failures(),
+            time: seconds(self.duration()),
+            suites: self
+                .suites
+                .iter()
+                .map(|(pkg, result)| JsonSuite {
+                    pkg,
+                    tests: result.info.len(),
+                    failures: failures(result),
+                    time: seconds(duration(result)),
+                    cases: result
+                        .info
+                        .iter()
+                        .map(|(name, info)| JsonCase {
+                            name,
+                            pkg,
+                            time: seconds(info.duration),
+                            passed: info.error.is_none(),
+                            failure: failure(info),
+                            log_message: &info.log_message,
+                        })
+                        .collect(),
+                })
+                .collect(),
+        };
+        Ok(serde_json::to_string_pretty(&report)?)
+    }
+}
+
+#[inline]
+fn failures(result: &TestResult) -> usize {
+    result
+        .info
+        .values()
+        .filter(|info| info.error.is_some())
+        .count()
+}
+
+#[inline]
+fn duration(result: &TestResult) -> Duration {
+    result.info.values().map(|info| info.duration).sum()
+}
+
+#[inline]
+fn failure(info: &TestCaseInfo) -> Option<String> {
+    info.error.as_ref().map(|err| err.to_string())
+}
+
+#[inline]
+fn seconds(duration: Duration) -> f64 {
+    // Round to microseconds to keep the reports readable.
+    (duration.as_micros() as f64) / 1_000_000.0
+}
+
+/// Escape the text for the XML attributes and contents, the characters that are not
+/// allowed in XML 1.0 e.g., the ANSI escape codes are removed.
+fn escape_xml(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&apos;"),
+            '\t' | '\n' | '\r' => escaped.push(c),
+            c if (c as u32) < 0x20 => {}
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
@@ -1,14 +1,18 @@
 use std::{fs::remove_file, path::Path};
 
 use crate::testing::{TestCaseInfo, TestOptions, TestResult, TestRun};
-use anyhow::{Result, anyhow};
+use anyhow::{Result, anyhow, bail};
 use kcl_ast::ast;
+use kcl_ast::walker::MutSelfWalker;
 use kcl_driver::get_pkg_list;
 use kcl_parser::get_kcl_files;
 use kcl_parser::{ParseSessionRef, parse_file_force_errors};
 use kcl_primitives::{DefaultHashBuilder, IndexMap};
-use kcl_runner::ExecProgramArgs;
-use kcl_runner::exec_program;
+use kcl_runner::runner::{FastRunner, RunnerOptions};
+use kcl_runner::{Coverage, ExecProgramArgs, build_program};
+use kcl_utils::parallel::par_map_with_threads;
+use std::collections::{HashMap, HashSet};
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::time::Instant;
 
 /// File suffix for test files.
@@ -16,13 +20,57 @@ pub const TEST_FILE_SUFFIX: &str = "_test.k";
 /// Prefix for test suite names.
 pub const TEST_SUITE_PREFIX: &str = "test_";
 
+/// Suffix of the variable names which supply the cases of the parameterised tests.
+pub const TEST_CASES_SUFFIX: &str = "_cases";
+/// Name of the suite setup lambda or value, whose values are passed to the test parameters.
+pub const TEST_SETUP_NAME: &str = "setup_suite";
+/// Directory of the case files and golden files in the package.
+pub const TEST_DATA_DIR: &str = "testdata";
+/// File suffixes of the case files of the parameterised tests.
+pub const TEST_CASES_FILE_SUFFIXES: &[&str] = &[".cases.yaml", ".cases.yml", ".cases.json"];
+
 const TEST_MAIN_FILE: &str = "_kcl_test.k";
-const TEST_CASE_RUN_OPTION: &str = "_kcl_test_case_run";
+const TEST_CASE_RUN_OPTION: &str = "__kcl_test_case_run";
+const TEST_CASE_INDEX_OPTION: &str = "__kcl_test_case_index";
+const TEST_UPDATE_GOLDEN_OPTION: &str = "__kcl_test_update_golden";
+const TEST_SETUP_OPTION: &str = "__kcl_test_setup";
+const TEST_CASE_COUNTS: &str = "__kcl_test_case_counts";
+/// Name of the golden file assertion lambda, which is only generated in the test main file
+/// when the test files call it and the package does not define it.
+const TEST_GOLDEN_NAME: &str = "assert_golden";
+/// The generated definitions of the test main file use the reserved `__kcl_test_` prefix,
+/// which do not conflict with the names in the package.
 const TEST_MAIN_FILE_PREFIX: &str = r#"
 # Auto generated by the kcl test tool; DO NOT EDIT!
+import file as __kcl_test_file
+import yaml as __kcl_test_yaml
 
-_kcl_test_case_run = option("_kcl_test_case_run", type="str", default="")
+__kcl_test_case_run = option("__kcl_test_case_run", type="str", default="")
+__kcl_test_case_index = option("__kcl_test_case_index", type="int", default=0)
 
+__kcl_test_arg = lambda case: any, index: int, name: str {
+    if typeof(case) == "list":
+        _value = case[index] if index < len(case) else __kcl_test_setup[name]
+    else:
+        _value = case[name] if name in case else __kcl_test_setup[name]
+    _value
+}
+"#;
+const TEST_MAIN_FILE_GOLDEN: &str = r#"
+__kcl_test_update_golden = option("__kcl_test_update_golden", type="bool", default=False)
+
+assert_golden = lambda name: str, value: any {
+    _filepath = __kcl_test_data_dir + "/" + name + ".golden.yaml"
+    _got = __kcl_test_yaml.encode(value)
+    if __kcl_test_update_golden:
+        __kcl_test_file.mkdir(__kcl_test_data_dir, exists=True)
+        __kcl_test_file.write(_filepath, _got)
+    else:
+        assert __kcl_test_file.exists(_filepath), "golden file ${_filepath} not found, run the tests in the update mode to create it"
+        _expected = __kcl_test_file.read(_filepath)
+        assert _got == _expected, "golden file ${_filepath} mismatch\nexpected:\n${_expected}\ngot:\n${_got}"
+    True
+}
 "#;
 
 pub struct TestSuite {
@@ -34,6 +82,11 @@ pub struct TestSuite {
     pub test_files: Vec<String>,
     // Map of test cases in the test suite.
     pub cases: IndexMap<String, TestCase>,
+    /// The KCL expression of the suite setup values, e.g. `setup_suite()`.
+    pub setup: Option<String>,
+    /// Whether the `assert_golden` lambda is generated, i.e., the test files call it and the
+    /// package does not define it.
+    pub golden: bool,
     // Flag indicating whether the test suite should be skipped.
     pub skip: bool,
 }
@@ -44,10 +97,153 @@ impl TestRun for TestSuite {
 
     /// Run the test suite with the given options and return the result.
     fn run(&self, opts: &Self::Options) -> Result<Self::Result> {
-        let mut result = TestResult::default();
+        let mut results = run_test_suites(std::slice::from_ref(self), opts)?;
+        Ok(results.pop().unwrap_or_default())
+    }
+}
+
+/// The program of a test suite which is parsed and resolved once, and shared by all
+/// the test cases of the suite.
+struct CompiledSuite {
+    program: ast::Program,
+    args: ExecProgramArgs,
+    runs: Vec<CaseRun>,
+    /// The JSON of the suite setup values, which are evaluated once and passed to every
+    /// parameterised test case.
+    setup: Option<String>,
+}
+
+/// A test case to run, the parameterised tests run once per case.
+struct CaseRun {
+    /// The reported name, e.g. `test_add[0]` for the first case of `test_add`.
+    name: String,
+    /// The test lambda name.
+    func: String,
+    /// The index of the parameterised case.
+    index: Option<usize>,
+    /// The error reported without running the case, e.g. the cases can not be loaded.
+    error: Option<String>,
+}
+
+/// Run the test suites with the given options and return the result of each suite in order.
+///
+/// Every suite is compiled only once, then all the test cases of the suites are executed
+/// concurrently on at most [TestOptions::jobs] threads with the compiled programs. When the
+/// `fail_fast` option is enabled, the cases not started yet are skipped after a failure.
+pub fn run_test_suites(suites: &[TestSuite], opts: &TestOptions) -> Result<Vec<TestResult>> {
+    let compiled = par_map_with_threads(suites, opts.jobs, |suite| suite.compile(opts))
+        .into_iter()
+        .collect::<Result<Vec<Option<CompiledSuite>>>>()?;
+    let runs: Vec<(usize, &CaseRun)> = compiled
+        .iter()
+        .enumerate()
+        .flat_map(|(index, compiled)| {
+            compiled
+                .iter()
+                .flat_map(move |compiled| compiled.runs.iter().map(move |run| (index, run)))
+        })
+        .collect();
+    let failed = AtomicBool::new(false);
+    let case_results = par_map_with_threads(&runs, opts.jobs, |(index, run)| -> Result<_> {
+        if opts.fail_fast && failed.load(Ordering::Relaxed) {
+            return Ok(None);
+        }
+        let compiled = compiled[*index]
+            .as_ref()
+            .expect("only the compiled suites have cases to run");
+        let result = run_test_case(compiled, run)?;
+        if result.0.error.is_some() {
+            failed.store(true, Ordering::Relaxed);
+        }
+        Ok(Some(result))
+    });
+    let mut results: Vec<TestResult> = suites.iter().map(|_| TestResult::default()).collect();
+    for ((index, run), case_result) in runs.into_iter().zip(case_results) {
+        let Some((info, coverage)) = case_result? else {
+            continue;
+        };
+        let result = &mut results[index];
+        // Merge the coverage of the non-test files.
+        if let Some(coverage) = coverage {
+            result
+                .coverage
+                .get_or_insert_with(Default::default)
+                .merge(coverage);
+        }
+        result.info.insert(run.name.clone(), info);
+    }
+    Ok(results)
+}
+
+/// Run the test case with the compiled program of the test suite.
+fn run_test_case(
+    compiled: &CompiledSuite,
+    run: &CaseRun,
+) -> Result<(TestCaseInfo, Option<Coverage>)> {
+    if let Some(error) = &run.error {
+        return Ok((
+            TestCaseInfo {
+                error: Some(anyhow!("{error}")),
+                ..Default::default()
+            },
+            None,
+        ));
+    }
+    let mut args = compiled.args.clone();
+    args.args.insert(
+        0,
+        ast::Argument {
+            name: TEST_CASE_RUN_OPTION.into(),
+            value: format!("{:?}", run.func),
+        },
+    );
+    if let Some(index) = run.index {
+        args.args.insert(
+            1,
+            ast::Argument {
+                name: TEST_CASE_INDEX_OPTION.into(),
+                value: index.to_string(),
+            },
+        );
+    }
+    if let Some(setup) = &compiled.setup {
+        args.args.push(ast::Argument {
+            name: TEST_SETUP_OPTION.into(),
+            value: setup.clone(),
+        });
+    }
+    let start = Instant::now();
+    let mut exec_result = FastRunner::new(Some(RunnerOptions {
+        plugin_agent_ptr: args.plugin_agent,
+    }))
+    .run(&compiled.program, &args)?;
+    let coverage = exec_result.coverage.take().map(|mut coverage| {
+        coverage.retain_files(|file| !is_test_file(file));
+        coverage
+    });
+    // Check if there was an error.
+    let error = if exec_result.err_message.is_empty() {
+        None
+    } else {
+        Some(anyhow!("{}", exec_result.err_message))
+    };
+    Ok((
+        TestCaseInfo {
+            log_message: exec_result.log_message,
+            duration: Instant::now() - start,
+            error,
+        },
+        coverage,
+    ))
+}
+
+impl TestSuite {
+    /// Parse and resolve the test suite with the generated test main entry file, returns
+    /// [None] if the test suite is skipped or there are no test cases.
+    fn compile(&self, opts: &TestOptions) -> Result<Option<CompiledSuite>> {
         // Skip test suite if marked as skipped or if there are no test cases.
         if self.skip || self.cases.is_empty() {
-            return Ok(result);
+            return Ok(None);
         }
         // Generate the test main entry file.
         let main_file = self.gen_test_main_file()?;
@@ -56,57 +252,138 @@ impl TestRun for TestSuite {
             k_filename_list: self.get_input_files(&main_file),
             overrides: vec![],
             disable_yaml_result: true,
+            coverage: opts.coverage,
             ..opts.exec_args.clone()
         };
-        // Save the user argument options.
-        let user_args = args.args;
-        // Test every case in the suite.
-        for (name, _) in &self.cases {
-            args.args = vec![ast::Argument {
-                name: TEST_CASE_RUN_OPTION.into(),
-                value: format!("{:?}", name),
-            }];
-            args.args.append(&mut user_args.clone());
-            let start = Instant::now();
-            // Check if is the fast eval mode.
-            let exec_result = exec_program(ParseSessionRef::default(), &args)?;
-            // Check if there was an error.
-            let error = if exec_result.err_message.is_empty() {
-                None
-            } else {
-                Some(anyhow!("{}", exec_result.err_message))
-            };
-            // Check if the fail_fast option is enabled and there was an error.
-            let fail_fast = error.is_some() && opts.fail_fast;
-            // Add test case information to the result.
-            result.info.insert(
-                name.clone(),
-                TestCaseInfo {
-                    log_message: exec_result.log_message.clone(),
-                    duration: Instant::now() - start,
-                    error,
-                },
-            );
-            if fail_fast {
-                break;
-            }
+        if opts.update_golden && self.golden {
+            args.args.push(ast::Argument {
+                name: TEST_UPDATE_GOLDEN_OPTION.into(),
+                value: "true".into(),
+            });
         }
-        // Remove the temp test main file
+        let program = build_program::<&str>(ParseSessionRef::default(), &args, None);
+        // Remove the temp test main file, which is not needed after the suite is compiled.
         if opts.exec_args.debug == 0 {
             remove_file(main_file)?;
         }
-        Ok(result)
+        let program = program?.program();
+        let (runs, setup) = self.case_runs(&program, &args)?;
+        Ok(Some(CompiledSuite {
+            program,
+            args,
+            runs,
+            setup,
+        }))
+    }
+
+    /// Returns the test case runs of the suite and the JSON of the suite setup values. The
+    /// parameterised tests run once per case, and the number of the cases and the setup values
+    /// are evaluated once with the compiled program.
+    fn case_runs(
+        &self,
+        program: &ast::Program,
+        args: &ExecProgramArgs,
+    ) -> Result<(Vec<CaseRun>, Option<String>)> {
+        let has_cases = self.cases.values().any(|case| case.source.is_some());
+        let evaluated: Result<(HashMap<String, usize>, Option<String>), String> = if has_cases {
+            // The evaluated values are hidden variables of the test main file.
+            let args = ExecProgramArgs {
+                show_hidden: true,
+                ..args.clone()
+            };
+            let result = FastRunner::new(Some(RunnerOptions {
+                plugin_agent_ptr: args.plugin_agent,
+            }))
+            .run(program, &args)?;
+            if result.err_message.is_empty() {
+                let output: serde_json::Value = serde_json::from_str(&result.json_result)?;
+                let counts = serde_json::from_value(output[TEST_CASE_COUNTS].clone())?;
+                let setup = match self.setup {
+                    Some(_) => Some(serde_json::to_string(&output[TEST_SETUP_OPTION])?),
+                    None => None,
+                };
+                Ok((counts, setup))
+            } else {
+                Err(result.err_message)
+            }
+        } else {
+            Ok((HashMap::new(), None))
+        };
+        let mut runs = vec![];
+        for (name, case) in &self.cases {
+            let run = |run_name: String, index: Option<usize>, error: Option<String>| CaseRun {
+                name: run_name,
+                func: name.clone(),
+                index,
+                error,
+            };
+            match &evaluated {
+                _ if case.params.is_empty() => runs.push(run(name.clone(), None, None)),
+                Ok((counts, _)) => {
+                    for index in 0..counts.get(name).copied().unwrap_or_default() {
+                        runs.push(run(format!("{name}[{index}]"), Some(index), None));
+                    }
+                }
+                Err(err) => runs.push(run(name.clone(), None, Some(err.clone()))),
+            }
+        }
+        Ok((runs, evaluated.ok().and_then(|(_, setup)| setup)))
     }
-}
 
-impl TestSuite {
     fn gen_test_main_file(&self) -> Result<String> {
-        let test_codes = self
+        // The setup values are only evaluated without running a test case, and then passed
+        // to the test cases with the option.
+        let mut code = format!(
+            "{TEST_MAIN_FILE_PREFIX}\n{TEST_SETUP_OPTION} = option({TEST_SETUP_OPTION:?}, default={{}}) if {TEST_CASE_RUN_OPTION} else {}\n",
+            self.setup.as_deref().unwrap_or("{}"),
+        );
+        if self.golden {
+            let data_dir = Path::new(&self.pkg).canonicalize()?.join(TEST_DATA_DIR);
+            code.push_str(&format!(
+                "\n__kcl_test_data_dir = {:?}\n{TEST_MAIN_FILE_GOLDEN}",
+                data_dir.display().to_string().replace('\\', "/"),
+            ));
+        }
+        let counts = self
             .cases
-            .keys()
-            .map(|c| format!("if {} == '{}': {}()", TEST_CASE_RUN_OPTION, c, c))
+            .iter()
+            .filter_map(|(name, case)| {
+                case.source
+                    .as_ref()
+                    .map(|source| format!("{name:?}: len({})", source.to_expr()))
+            })
             .collect::<Vec<String>>();
-        let code = format!("{}{}", TEST_MAIN_FILE_PREFIX, test_codes.join("\n"));
+        if !counts.is_empty() {
+            code.push_str(&format!(
+                "\nif {TEST_CASE_RUN_OPTION} == \"\":\n    {TEST_CASE_COUNTS} = {{{}}}\n",
+                counts.join(", ")
+            ));
+        }
+        for (name, case) in &self.cases {
+            if case.params.is_empty() {
+                code.push_str(&format!(
+                    "\nif {TEST_CASE_RUN_OPTION} == {name:?}: {name}()\n"
+                ));
+                continue;
+            }
+            // The parameterised tests without cases are reported when the suite is loaded.
+            let Some(source) = &case.source else {
+                continue;
+            };
+            let case_expr = format!("{}[{TEST_CASE_INDEX_OPTION}]", source.to_expr());
+            let args = case
+                .params
+                .iter()
+                .enumerate()
+                .map(|(index, param)| {
+                    format!("{param}=__kcl_test_arg(__kcl_test_case, {index}, {param:?})")
+                })
+                .collect::<Vec<String>>();
+            code.push_str(&format!(
+                "\nif {TEST_CASE_RUN_OPTION} == {name:?}:\n    __kcl_test_case = {case_expr}\n    {name}({})\n",
+                args.join(", ")
+            ));
+        }
         let path = Path::new(&self.pkg).join(TEST_MAIN_FILE);
         let test_main_file = path
             .to_str()
@@ -127,7 +404,41 @@ impl TestSuite {
     }
 }
 
-pub struct TestCase;
+/// A test lambda of the test suite.
+#[derive(Debug, Default, Clone)]
+pub struct TestCase {
+    /// The parameter names of the test lambda, the arguments are supplied by the cases
+    /// and the suite setup values.
+    pub params: Vec<String>,
+    /// The source of the cases of the parameterised test, which runs once per case.
+    pub source: Option<TestCasesSource>,
+}
+
+/// The source of the cases of a parameterised test. Every case is a list of the positional
+/// arguments or a dict of the keyword arguments of the test lambda, and the missing arguments
+/// are taken from the suite setup values.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TestCasesSource {
+    /// The cases list variable in the test files, e.g. `test_add_cases` of `test_add`.
+    Variable(String),
+    /// The YAML or JSON cases file in the `testdata` directory, e.g. `testdata/test_add.cases.yaml`.
+    File(String),
+}
+
+impl TestCasesSource {
+    /// Returns the KCL expression of the cases list.
+    fn to_expr(&self) -> String {
+        match self {
+            TestCasesSource::Variable(name) => name.clone(),
+            TestCasesSource::File(path) => {
+                format!(
+                    "__kcl_test_yaml.decode(__kcl_test_file.read({:?}))",
+                    path.replace('\\', "/")
+                )
+            }
+        }
+    }
+}
 
 /// Load test suite from path
 pub fn load_test_suites<P: AsRef<str>>(path: P, opts: &TestOptions) -> Result<Vec<TestSuite>> {
@@ -136,26 +447,108 @@ pub fn load_test_suites<P: AsRef<str>>(path: P, opts: &TestOptions) -> Result<Ve
     for pkg in &pkg_list {
         let (normal_files, test_files) = get_test_files(pkg)?;
         let mut cases = IndexMap::with_hasher(DefaultHashBuilder::default());
+        let mut names = HashSet::new();
+        let mut setup = None;
+        let mut golden = false;
         for file in &test_files {
             let module = parse_file_force_errors(file, None)?;
+            golden |= references(&module, TEST_GOLDEN_NAME);
             for stmt in &module.body {
-                if let ast::Stmt::Assign(assign_stmt) = &stmt.node
-                    && let ast::Expr::Lambda(_lambda_expr) = &assign_stmt.value.node
-                {
+                if let ast::Stmt::Assign(assign_stmt) = &stmt.node {
                     for target in &assign_stmt.targets {
-                        let func_name = target.node.get_name();
-                        if is_test_suite(func_name) && should_run(&opts.run_regexp, func_name) {
-                            cases.insert(func_name.to_string(), TestCase {});
+                        let name = target.node.get_name();
+                        names.insert(name.to_string());
+                        match &assign_stmt.value.node {
+                            ast::Expr::Lambda(lambda_expr)
+                                if name == TEST_SETUP_NAME && lambda_expr.args.is_none() =>
+                            {
+                                setup = Some(format!("{TEST_SETUP_NAME}()"));
+                            }
+                            // The setup lambda with parameters can not be called.
+                            ast::Expr::Lambda(_) if name == TEST_SETUP_NAME => {}
+                            _ if name == TEST_SETUP_NAME => setup = Some(name.to_string()),
+                            ast::Expr::Lambda(lambda_expr)
+                                if is_test_suite(name) && should_run(&opts.run_regexp, name) =>
+                            {
+                                let params = lambda_expr
+                                    .args
+                                    .as_ref()
+                                    .map(|args| {
+                                        args.node
+                                            .args
+                                            .iter()
+                                            .map(|arg| arg.node.get_name().to_string())
+                                            .collect()
+                                    })
+                                    .unwrap_or_default();
+                                cases.insert(
+                                    name.to_string(),
+                                    TestCase {
+                                        params,
+                                        source: None,
+                                    },
+                                );
+                            }
+                            _ => {}
                         }
                     }
                 }
             }
         }
+        // The `assert_golden` lambda defined in the package is called instead.
+        if golden {
+            for file in &normal_files {
+                let module = parse_file_force_errors(file, None)?;
+                names.extend(module.body.iter().flat_map(|stmt| {
+                    match &stmt.node {
+                        ast::Stmt::Assign(assign_stmt) => assign_stmt
+                            .targets
+                            .iter()
+                            .map(|target| target.node.get_name().to_string())
+                            .collect(),
+                        _ => vec![],
+                    }
+                }));
+            }
+            golden = !names.contains(TEST_GOLDEN_NAME);
+        }
+        for (name, case) in cases.iter_mut() {
+            if case.params.is_empty() {
+                continue;
+            }
+            let var = format!("{name}{TEST_CASES_SUFFIX}");
+            case.source = if names.contains(&var) {
+                Some(TestCasesSource::Variable(var.clone()))
+            } else {
+                TEST_CASES_FILE_SUFFIXES
+                    .iter()
+                    .map(|suffix| {
+                        Path::new(pkg)
+                            .join(TEST_DATA_DIR)
+                            .join(format!("{name}{suffix}"))
+                    })
+                    .find(|path| path.is_file())
+                    .map(|path| -> Result<TestCasesSource> {
+                        Ok(TestCasesSource::File(
+                            path.canonicalize()?.display().to_string(),
+                        ))
+                    })
+                    .transpose()?
+            };
+            if case.source.is_none() {
+                bail!(
+                    "parameterised test has no cases: expected `{var}` or `{TEST_DATA_DIR}/{name}{}`",
+                    TEST_CASES_FILE_SUFFIXES[0]
+                );
+            }
+        }
         suites.push(TestSuite {
             pkg: pkg.clone(),
             cases,
             normal_files,
             test_files,
+            setup,
+            golden,
             skip: false,
         });
     }
@@ -178,6 +571,36 @@ fn get_test_files<P: AsRef<Path>>(pkg: P) -> Result<(Vec<String>, Vec<String>)>
     Ok((normal_files, test_files))
 }
 
+#[inline]
+fn is_test_file(file: &str) -> bool {
+    let name = Path::new(file)
+        .file_name()
+        .and_then(|name| name.to_str())
+        .unwrap_or(file);
+    name.ends_with(TEST_FILE_SUFFIX) || name == TEST_MAIN_FILE
+}
+
+/// Returns whether the module references the name, e.g., calls the lambda with the name.
+fn references(module: &ast::Module, name: &str) -> bool {
+    struct NameFinder<'a> {
+        name: &'a str,
+        found: bool,
+    }
+
+    impl MutSelfWalker for NameFinder<'_> {
+        fn walk_identifier(&mut self, identifier: &ast::Identifier) {
+            self.found |= identifier
+                .names
+                .first()
+                .is_some_and(|name| name.node == self.name);
+        }
+    }
+
+    let mut finder = NameFinder { name, found: false };
+    finder.walk_module(module);
+    finder.found
+}
+
 #[inline]
 fn is_test_suite(name: &str) -> bool {
     name.starts_with(TEST_SUITE_PREFIX)
@@ -3,11 +3,23 @@ use kcl_runner::ExecProgramArgs;
 
 use crate::testing::TestRun;
 
-use super::{TestOptions, load_test_suites};
+use super::{
+    TestCasesSource, TestOptions, TestReport, TestReportFormat, load_test_suites, run_test_suites,
+};
 use std::path::Path;
+use std::sync::{Mutex, MutexGuard};
+
+/// The test main file is generated in the package directory, so the tests which run the
+/// same test data packages are serialized.
+static TEST_DATA: Mutex<()> = Mutex::new(());
+
+fn lock_test_data() -> MutexGuard<'static, ()> {
+    TEST_DATA.lock().unwrap_or_else(|err| err.into_inner())
+}
 
 #[test]
 fn test_load_test_suites_and_run() {
+    let _guard = lock_test_data();
     let opts = TestOptions {
         exec_args: ExecProgramArgs {
             args: vec![Argument {
@@ -49,3 +61,310 @@ fn test_load_test_suites_and_run() {
         test_result.info[2].error
     );
 }
+
+#[test]
+fn test_run_with_coverage() {
+    let _guard = lock_test_data();
+    let opts = TestOptions {
+        coverage: true,
+        ..Default::default()
+    };
+    let suites = load_test_suites(
+        Path::new(".")
+            .join("src")
+            .join("testing")
+            .join("test_data")
+            .join("module")
+            .join("coverage")
+            .to_str()
+            .unwrap(),
+        &opts,
+    )
+    .unwrap();
+    assert_eq!(suites.len(), 1);
+    let test_result = suites[0].run(&opts).unwrap();
+    assert!(test_result.info[0].error.is_none());
+    let coverage = test_result.coverage.unwrap();
+    // Only the non-test files are reported.
+    assert_eq!(coverage.files.len(), 1);
+    let (filename, file) = coverage.files.iter().next().unwrap();
+    assert!(filename.ends_with("policy.k"), "{filename}");
+    let unhit_lines: Vec<u64> = file
+        .lines
+        .iter()
+        .filter(|(_, count)| **count == 0)
+        .map(|(line, _)| *line)
+        .collect();
+    assert_eq!(unhit_lines, [5]);
+    let lcov = coverage.to_lcov();
+    assert!(lcov.starts_with(&format!("TN:\nSF:{filename}\n")), "{lcov}");
+    assert!(lcov.contains("DA:5,0\n"), "{lcov}");
+    // The if condition and the check condition are branch points.
+    assert_eq!(file.branches_found(), 4);
+    assert!(lcov.contains("BRF:4\n"), "{lcov}");
+    let summary = coverage.summary();
+    assert!(summary.contains("TOTAL"), "{summary}");
+    assert!(
+        summary.contains(&format!("{:.1}%", coverage.percent())),
+        "{summary}"
+    );
+    assert!(
+        summary.contains(&format!("{:.1}%", coverage.branch_percent())),
+        "{summary}"
+    );
+}
+
+#[test]
+fn test_test_report() {
+    let _guard = lock_test_data();
+    let opts = TestOptions {
+        exec_args: ExecProgramArgs {
+            args: vec![Argument {
+                name: "a".to_string(),
+                value: "\"a\"".to_string(),
+            }],
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+    let pkg = Path::new(".")
+        .join("src")
+        .join("testing")
+        .join("test_data")
+        .join("module")
+        .join("pkg");
+    let pkg = pkg.to_str().unwrap();
+    let suites = load_test_suites(pkg, &opts).unwrap();
+    let mut report = TestReport::default();
+    for suite in &suites {
+        report.add(&suite.pkg, suite.run(&opts).unwrap());
+    }
+    assert_eq!((report.tests(), report.failures()), (3, 1));
+
+    let junit = report.emit(TestReportFormat::JUnit).unwrap();
+    assert!(
+        junit.starts_with("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n<testsuites name=\"kcl\" tests=\"3\" failures=\"1\""),
+        "{junit}"
+    );
+    assert!(
+        junit.contains(&format!(
+            "<testsuite name=\"{pkg}\" tests=\"3\" failures=\"1\""
+        )),
+        "{junit}"
+    );
+    assert!(
+        junit.contains(&format!(
+            "<testcase name=\"test_func_0\" classname=\"{pkg}\" time=\""
+        )),
+        "{junit}"
+    );
+    assert_eq!(junit.matches("<failure message=\"").count(), 1, "{junit}");
+    assert!(junit.contains("<system-out>a\n</system-out>"), "{junit}");
+
+    let json: serde_json::Value =
+        serde_json::from_str(&report.emit(TestReportFormat::Json).unwrap()).unwrap();
+    assert_eq!(json["tests"], 3);
+    assert_eq!(json["failures"], 1);
+    let cases = &json["suites"][0]["cases"];
+    assert_eq!(cases[0]["name"], "test_func_0");
+    assert_eq!(cases[0]["pkg"], pkg);
+    assert_eq!(cases[0]["passed"], true);
+    assert!(cases[0]["failure"].is_null());
+    assert_eq!(cases[1]["passed"], false);
+    assert!(cases[1]["failure"].as_str().unwrap().contains("Error"));
+    assert_eq!(cases[2]["log_message"], "a\n");
+
+    assert_eq!(
+        "JUnit".parse::<TestReportFormat>().unwrap(),
+        TestReportFormat::JUnit
+    );
+    assert!("html".parse::<TestReportFormat>().is_err());
+}
+
+#[test]
+fn test_run_test_suites_concurrently() {
+    let _guard = lock_test_data();
+    let opts = TestOptions {
+        exec_args: ExecProgramArgs {
+            args: vec![Argument {
+                name: "a".to_string(),
+                value: "\"a\"".to_string(),
+            }],
+            ..Default::default()
+        },
+        jobs: 4,
+        ..Default::default()
+    };
+    let path = Path::new(".")
+        .join("src")
+        .join("testing")
+        .join("test_data")
+        .join("module")
+        .join("...");
+    let suites = load_test_suites(path.to_str().unwrap(), &opts).unwrap();
+    assert_eq!(suites.len(), 4);
+    let results = run_test_suites(&suites, &opts).unwrap();
+    let serial_results = run_test_suites(
+        &suites,
+        &TestOptions {
+            jobs: 1,
+            ..opts.clone()
+        },
+    )
+    .unwrap();
+    for (result, serial_result) in results.iter().zip(&serial_results) {
+        // The cases are reported in order regardless of the scheduling.
+        let names: Vec<&String> = result.info.keys().collect();
+        let serial_names: Vec<&String> = serial_result.info.keys().collect();
+        assert_eq!(names, serial_names);
+        for (info, serial_info) in result.info.values().zip(serial_result.info.values()) {
+            assert_eq!(info.error.is_some(), serial_info.error.is_some());
+            assert_eq!(info.log_message, serial_info.log_message);
+        }
+    }
+    // The parameterised tests are reported once per case.
+    assert_eq!(results.iter().map(|r| r.info.len()).sum::<usize>(), 14);
+    // The cases not started yet are skipped after a failure.
+    let results = run_test_suites(
+        &suites,
+        &TestOptions {
+            jobs: 1,
+            fail_fast: true,
+            ..opts.clone()
+        },
+    )
+    .unwrap();
+    let failed = results
+        .iter()
+        .flat_map(|r| r.info.values())
+        .position(|info| info.error.is_some())
+        .unwrap();
+    assert_eq!(
+        results.iter().map(|r| r.info.len()).sum::<usize>(),
+        failed + 1
+    );
+}
+
+#[test]
+fn test_parameterised_tests_and_golden_files() {
+    let _guard = lock_test_data();
+    let opts = TestOptions::default();
+    let pkg = Path::new(".")
+        .join("src")
+        .join("testing")
+        .join("test_data")
+        .join("module")
+        .join("params");
+    let suites = load_test_suites(pkg.to_str().unwrap(), &opts).unwrap();
+    assert_eq!(suites[0].setup.as_deref(), Some("setup_suite()"));
+    assert!(matches!(
+        &suites[0].cases["test_mul"].source,
+        Some(TestCasesSource::File(path)) if path.ends_with("test_mul.cases.yaml")
+    ));
+    let result = suites[0].run(&opts).unwrap();
+    // Every parameterised case is reported separately.
+    let names: Vec<&str> = result.info.keys().map(|name| name.as_str()).collect();
+    assert_eq!(
+        names,
+        [
+            "test_add[0]",
+            "test_add[1]",
+            "test_add[2]",
+            "test_mul[0]",
+            "test_mul[1]",
+            "test_factor[0]",
+            "test_golden[0]"
+        ]
+    );
+    let failed: Vec<&str> = result
+        .info
+        .iter()
+        .filter(|(_, info)| info.error.is_some())
+        .map(|(name, _)| name.as_str())
+        .collect();
+    assert_eq!(failed, ["test_add[2]"]);
+    let err = result.info["test_add[2]"]
+        .error
+        .as_ref()
+        .unwrap()
+        .to_string();
+    assert!(err.contains("2 + 2 != 5"), "{err}");
+
+    // Create and update the golden files in a copy of the package.
+    let dir = std::env::temp_dir().join("kcl_test_parameterised_tests_and_golden_files");
+    let _ = std::fs::remove_dir_all(&dir);
+    std::fs::create_dir_all(dir.join("testdata")).unwrap();
+    std::fs::write(dir.join("kcl.mod"), "").unwrap();
+    for file in ["calc.k", "calc_test.k", "testdata/test_mul.cases.yaml"] {
+        std::fs::copy(pkg.join(file), dir.join(file)).unwrap();
+    }
+    let opts = TestOptions {
+        run_regexp: "test_golden".to_string(),
+        ..Default::default()
+    };
+    let suites = load_test_suites(dir.to_str().unwrap(), &opts).unwrap();
+    let result = suites[0].run(&opts).unwrap();
+    let err = result.info["test_golden[0]"]
+        .error
+        .as_ref()
+        .unwrap()
+        .to_string();
+    assert!(err.contains("config.golden.yaml not found"), "{err}");
+    let update_opts = TestOptions {
+        update_golden: true,
+        ..opts.clone()
+    };
+    let result = suites[0].run(&update_opts).unwrap();
+    assert!(result.info["test_golden[0]"].error.is_none());
+    assert_eq!(
+        std::fs::read_to_string(dir.join("testdata").join("config.golden.yaml")).unwrap(),
+        std::fs::read_to_string(pkg.join("testdata").join("config.golden.yaml")).unwrap()
+    );
+    std::fs::write(
+        dir.join("testdata").join("config.golden.yaml"),
+        "replicas: 1\n",
+    )
+    .unwrap();
+    let result = suites[0].run(&opts).unwrap();
+    let err = result.info["test_golden[0]"]
+        .error
+        .as_ref()
+        .unwrap()
+        .to_string();
+    assert!(err.contains("config.golden.yaml mismatch"), "{err}");
+
+    // The parameterised tests without cases are reported when the suite is loaded.
+    std::fs::remove_file(dir.join("testdata").join("test_mul.cases.yaml")).unwrap();
+    let err = match load_test_suites(dir.to_str().unwrap(), &TestOptions::default()) {
+        Ok(_) => panic!("expected the suite without cases to fail"),
+        Err(err) => err.to_string(),
+    };
+    assert_eq!(
+        err,
+        "parameterised test has no cases: expected `test_mul_cases` or `testdata/test_mul.cases.yaml`"
+    );
+    std::fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn test_names_and_setup_of_suite() {
+    let _guard = lock_test_data();
+    let opts = TestOptions::default();
+    let pkg = Path::new(".")
+        .join("src")
+        .join("testing")
+        .join("test_data")
+        .join("module")
+        .join("names");
+    let suites = load_test_suites(pkg.to_str().unwrap(), &opts).unwrap();
+    // The `assert_golden` lambda of the package is not generated.
+    assert!(!suites[0].golden);
+    let result = suites[0].run(&opts).unwrap();
+    let names: Vec<&str> = result.info.keys().map(|name| name.as_str()).collect();
+    assert_eq!(names, ["test_scale[0]", "test_scale[1]", "test_names[0]"]);
+    for (name, info) in &result.info {
+        assert!(info.error.is_none(), "{name}: {:?}", info.error);
+        // The setup values are evaluated once before running the cases.
+        assert_eq!(info.log_message, "", "{name}");
+    }
+}
@@ -5,17 +5,25 @@ use compiler_base_span::{BytePos, FilePathMapping, SourceMap, span::new_byte_pos
 use json_spanned_value::{self as jsv, spanned};
 use kcl_ast::ast::PosTuple;
 use located_yaml::YamlLoader;
+use serde::de::{Deserialize, Deserializer, MapAccess, SeqAccess, Visitor};
 
 pub(crate) trait Loader<T> {
     fn load(&self) -> Result<T>;
 }
 
 /// Types of verifiable files currently supported by KCL-Vet,
-/// currently only YAML files and Json files are supported.
+/// currently YAML files, Json files, JSON Lines files, TOML files and CSV files are supported.
 #[derive(Clone, Copy)]
 pub enum LoaderKind {
+    /// YAML files, which may contain multiple documents separated by `---`.
     YAML,
     JSON,
+    /// JSON Lines files, which contain one Json document per line.
+    JSONL,
+    TOML,
+    /// CSV files, where the first record is the header and each of the other records is a
+    /// document.
+    CSV,
 }
 
 /// DataLoader for Json or Yaml
@@ -138,6 +146,51 @@ impl Loader<located_yaml::Yaml> for DataLoader {
     }
 }
 
+/// Load all the documents of the YAML stream with span.
+impl Loader<Vec<located_yaml::Yaml>> for DataLoader {
+    fn load(&self) -> Result<Vec<located_yaml::Yaml>> {
+        match self.kind {
+            LoaderKind::YAML => Ok(YamlLoader::load_from_str(self.get_data())
+                .with_context(|| format!("Failed to String '{}' to Yaml", self.get_data()))?
+                .docs),
+            _ => {
+                bail!("Failed to String to Yaml Value")
+            }
+        }
+    }
+}
+
+/// Load the Json documents of the JSON Lines with span, the spans of the documents are
+/// relative to the lines, so the byte offsets of the lines are returned with the documents.
+/// Blank lines are skipped.
+impl Loader<Vec<(usize, spanned::Value)>> for DataLoader {
+    fn load(&self) -> Result<Vec<(usize, spanned::Value)>> {
+        match self.kind {
+            LoaderKind::JSONL => {
+                let mut docs = vec![];
+                let mut offset = 0;
+                for (i, line) in self.get_data().split_inclusive('\n').enumerate() {
+                    if !line.trim().is_empty() {
+                        let doc = jsv::from_str(line).with_context(|| {
+                            format!(
+                                "Failed to String '{}' to Json at line {}",
+                                line.trim(),
+                                i + 1
+                            )
+                        })?;
+                        docs.push((offset, doc));
+                    }
+                    offset += line.len();
+                }
+                Ok(docs)
+            }
+            _ => {
+                bail!("Failed to String to Json Lines")
+            }
+        }
+    }
+}
+
 impl Loader<serde_yaml::Value> for DataLoader {
     /// Load data into Yaml value.
     fn load(&self) -> Result<serde_yaml::Value> {
@@ -152,3 +205,221 @@ impl Loader<serde_yaml::Value> for DataLoader {
         Ok(v)
     }
 }
+
+/// TOML value with the byte spans of the values in the file. Note that the tables defined
+/// by the table headers and the arrays of tables have no spans, i.e., the spans are `0..0`.
+pub(crate) type TomlSpanned = toml::Spanned<TomlValue>;
+
+/// The key used by the TOML deserializer to pass a datetime as a map.
+const TOML_DATETIME_KEY: &str = "$__toml_private_datetime";
+
+#[derive(Debug)]
+pub(crate) enum TomlValue {
+    String(String),
+    Integer(i64),
+    Float(f64),
+    Boolean(bool),
+    /// The datetime string in RFC 3339 format.
+    Datetime(String),
+    Array(Vec<TomlSpanned>),
+    /// The key-value pairs of the table in the document order.
+    Table(Vec<(String, TomlSpanned)>),
+}
+
+impl<'de> Deserialize<'de> for TomlValue {
+    fn deserialize<D>(deserializer: D) -> std::result::Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        struct TomlValueVisitor;
+
+        impl<'de> Visitor<'de> for TomlValueVisitor {
+            type Value = TomlValue;
+
+            fn expecting(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
+                formatter.write_str("a TOML value")
+            }
+
+            fn visit_bool<E>(self, value: bool) -> std::result::Result<TomlValue, E> {
+                Ok(TomlValue::Boolean(value))
+            }
+
+            fn visit_i64<E>(self, value: i64) -> std::result::Result<TomlValue, E> {
+                Ok(TomlValue::Integer(value))
+            }
+
+            fn visit_f64<E>(self, value: f64) -> std::result::Result<TomlValue, E> {
+                Ok(TomlValue::Float(value))
+            }
+
+            fn visit_str<E>(self, value: &str) -> std::result::Result<TomlValue, E> {
+                Ok(TomlValue::String(value.to_string()))
+            }
+
+            fn visit_string<E>(self, value: String) -> std::result::Result<TomlValue, E> {
+                Ok(TomlValue::String(value))
+            }
+
+            fn visit_seq<A>(self, mut seq: A) -> std::result::Result<TomlValue, A::Error>
+            where
+                A: SeqAccess<'de>,
+            {
+                let mut values = vec![];
+                while let Some(value) = seq.next_element()? {
+                    values.push(value);
+                }
+                Ok(TomlValue::Array(values))
+            }
+
+            fn visit_map<A>(self, mut map: A) -> std::result::Result<TomlValue, A::Error>
+            where
+                A: MapAccess<'de>,
+            {
+                let mut entries = vec![];
+                while let Some(key) = map.next_key::<String>()? {
+                    if key == TOML_DATETIME_KEY {
+                        return Ok(TomlValue::Datetime(map.next_value()?));
+                    }
+                    entries.push((key, map.next_value()?));
+                }
+                Ok(TomlValue::Table(entries))
+            }
+        }
+
+        deserializer.deserialize_any(TomlValueVisitor)
+    }
+}
+
+/// Load data into TOML value with span.
+impl Loader<TomlSpanned> for DataLoader {
+    fn load(&self) -> Result<TomlSpanned> {
+        match self.kind {
+            LoaderKind::TOML => Ok(toml::from_str(self.get_data())
+                .with_context(|| format!("Failed to String '{}' to TOML", self.get_data()))?),
+            _ => {
+                bail!("Failed to String to TOML Value")
+            }
+        }
+    }
+}
+
+/// A field of a CSV record with the byte span of the field in the file.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct CsvField {
+    pub(crate) value: String,
+    /// Whether the field is enclosed in double quotes.
+    pub(crate) quoted: bool,
+    pub(crate) span: (usize, usize),
+}
+
+/// Load the records of the CSV with the spans of the fields. The fields are separated
+/// by `,` and may be enclosed in double quotes, where a double quote is escaped by
+/// another one. Blank lines are skipped.
+impl Loader<Vec<Vec<CsvField>>> for DataLoader {
+    fn load(&self) -> Result<Vec<Vec<CsvField>>> {
+        match self.kind {
+            LoaderKind::CSV => parse_csv(self.get_data()),
+            _ => {
+                bail!("Failed to String to CSV Records")
+            }
+        }
+    }
+}
+
+fn parse_csv(data: &str) -> Result<Vec<Vec<CsvField>>> {
+    let bytes = data.as_bytes();
+    let mut records = vec![];
+    let mut record = vec![];
+    let mut pos = 0;
+    let mut line = 1;
+    while pos < bytes.len() {
+        // Skip blank lines between records.
+        if record.is_empty() && matches!(bytes[pos], b'\n' | b'\r') {
+            if bytes[pos] == b'\n' {
+                line += 1;
+            }
+            pos += 1;
+            continue;
+        }
+        let start = pos;
+        let field = if bytes[pos] == b'"' {
+            let start_line = line;
+            let mut value = String::new();
+            pos += 1;
+            loop {
+                match data[pos..].find('"') {
+                    Some(i) => {
+                        value.push_str(&data[pos..pos + i]);
+                        line += data[pos..pos + i].matches('\n').count();
+                        pos += i + 1;
+                        if bytes.get(pos) == Some(&b'"') {
+                            value.push('"');
+                            pos += 1;
+                        } else {
+                            break;
+                        }
+                    }
+                    None => bail!(
+                        "Failed to String to CSV, unterminated quoted field at line {start_line}"
+                    ),
+                }
+            }
+            if !matches!(bytes.get(pos), None | Some(b',' | b'\n' | b'\r')) {
+                bail!(
+                    "Failed to String to CSV, unexpected character after the quoted field at line {line}"
+                );
+            }
+            CsvField {
+                value,
+                quoted: true,
+                span: (start, pos),
+            }
+        } else {
+            let end = data[pos..]
+                .find([',', '\n', '\r'])
+                .map_or(bytes.len(), |i| pos + i);
+            pos = end;
+            CsvField {
+                value: data[start..end].to_string(),
+                quoted: false,
+                span: (start, end),
+            }
+        };
+        record.push(field);
+        match bytes.get(pos) {
+            Some(b',') => {
+                pos += 1;
+                // A trailing separator at the end of the data ends the record with an
+                // empty field, which is parsed as the next field in other cases.
+                if pos == bytes.len() {
+                    record.push(CsvField {
+                        value: String::new(),
+                        quoted: false,
+                        span: (pos, pos),
+                    });
+                }
+            }
+            _ => {
+                if bytes.get(pos) == Some(&b'\r') {
+                    pos += 1;
+                }
+                if bytes.get(pos) == Some(&b'\n') {
+                    pos += 1;
+                    line += 1;
+                }
+                records.push(std::mem::take(&mut record));
+            }
+        }
+    }
+    if !record.is_empty() {
+        records.push(record);
+    }
+    // The spaces around the unquoted header names are not part of the keys, e.g.,
+    // `name, image` has the keys `name` and `image`.
+    if let Some(header) = records.first_mut() {
+        for field in header.iter_mut().filter(|field| !field.quoted) {
+            field.value = field.value.trim().to_string();
+        }
+    }
+    Ok(records)
+}
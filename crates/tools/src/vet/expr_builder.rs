@@ -8,7 +8,7 @@ use kcl_ast::{
 };
 use serde_json::json;
 
-use crate::util::loader::{DataLoader, Loader, LoaderKind};
+use crate::util::loader::{CsvField, DataLoader, Loader, LoaderKind, TomlSpanned, TomlValue};
 use anyhow::{Context, Result, bail};
 
 const FAIL_LOAD_VALIDATED_ERR_MSG: &str = "Failed to load the validated file";
@@ -17,8 +17,9 @@ trait ExprGenerator<T> {
     fn generate(&self, value: &T, schema_name: &Option<String>) -> Result<NodeRef<Expr>>;
 }
 
-/// `ExprBuilder` will generate ast expr from Json/Yaml.
-/// `Object` in Json and `Mapping` in Yaml is mapped to `Schema Expr`.
+/// `ExprBuilder` will generate ast expr from Json/Yaml/TOML/CSV.
+/// `Object` in Json, `Mapping` in Yaml, `Table` in TOML and the records of CSV are mapped
+/// to `Schema Expr`.
 /// You should set `schema_name` for `Schema Expr` before using `ExprBuilder`.
 pub(crate) struct ExprBuilder {
     loader: DataLoader,
@@ -59,10 +60,137 @@ impl ExprBuilder {
                     .generate(&value, &schema_name)
                     .with_context(|| "Failed to Load YAML".to_string())?)
             }
+            LoaderKind::JSONL => {
+                // A JSON Lines file with many documents must be built with `build_documents`.
+                let mut docs = self.build_documents()?;
+                if docs.len() != 1 {
+                    bail!(
+                        "Failed to Load JSON Lines, found {} documents, use `build_documents` instead",
+                        docs.len()
+                    );
+                }
+                let expr = docs.remove(0);
+                Ok(match &schema_name {
+                    Some(schema_name) => with_schema_name(expr, schema_name),
+                    None => expr,
+                })
+            }
+            LoaderKind::TOML => {
+                let value = <DataLoader as Loader<TomlSpanned>>::load(&self.loader)
+                    .with_context(|| "Failed to Load TOML".to_string())?;
+                Ok(self
+                    .generate(&value, &schema_name)
+                    .with_context(|| "Failed to Load TOML".to_string())?)
+            }
+            LoaderKind::CSV => {
+                let records = <DataLoader as Loader<Vec<Vec<CsvField>>>>::load(&self.loader)
+                    .with_context(|| "Failed to Load CSV".to_string())?;
+                Ok(self
+                    .generate(&records, &schema_name)
+                    .with_context(|| "Failed to Load CSV".to_string())?)
+            }
+        }
+    }
+
+    /// Generate an ast expr for each document of the validated file, which are the
+    /// documents of a multi-document YAML stream, the lines of JSON Lines or the records
+    /// of CSV. A Json or TOML file contains only one document. The empty documents of a
+    /// YAML stream are skipped.
+    ///
+    /// The `Object` in Json and `Mapping` in Yaml is mapped to `Config Expr`, use
+    /// [`with_schema_name`] to map it to the `Schema Expr` of a document.
+    pub(crate) fn build_documents(&self) -> Result<Vec<NodeRef<Expr>>> {
+        match self.loader.get_kind() {
+            LoaderKind::JSON | LoaderKind::TOML => Ok(vec![self.build(None)?]),
+            LoaderKind::CSV => {
+                let records = <DataLoader as Loader<Vec<Vec<CsvField>>>>::load(&self.loader)
+                    .with_context(|| "Failed to Load CSV".to_string())?;
+                match records.split_first() {
+                    Some((header, records)) if !records.is_empty() => records
+                        .iter()
+                        .map(|record| {
+                            self.generate_csv_record(header, record)
+                                .with_context(|| "Failed to Load CSV".to_string())
+                        })
+                        .collect(),
+                    _ => bail!("Failed to Load CSV, no record found"),
+                }
+            }
+            LoaderKind::YAML => {
+                let docs = <DataLoader as Loader<Vec<located_yaml::Yaml>>>::load(&self.loader)
+                    .with_context(|| "Failed to Load YAML".to_string())?;
+                if docs.is_empty() {
+                    bail!("Failed to Load YAML");
+                }
+                let multi_docs = docs.len() > 1;
+                docs.iter()
+                    .filter(|doc| !(multi_docs && matches!(doc.yaml, located_yaml::YamlElt::Null)))
+                    .map(|doc| {
+                        self.generate(doc, &None)
+                            .with_context(|| "Failed to Load YAML".to_string())
+                    })
+                    .collect()
+            }
+            LoaderKind::JSONL => {
+                let docs = <DataLoader as Loader<
+                    Vec<(
+                        usize,
+                        json_spanned_value::Spanned<json_spanned_value::Value>,
+                    )>,
+                >>::load(&self.loader)
+                .with_context(|| "Failed to Load JSON Lines".to_string())?;
+                if docs.is_empty() {
+                    bail!("Failed to Load JSON Lines, no document found");
+                }
+                docs.iter()
+                    .map(|(offset, doc)| {
+                        self.generate_json(doc, &None, *offset)
+                            .with_context(|| "Failed to Load JSON Lines".to_string())
+                    })
+                    .collect()
+            }
         }
     }
 }
 
+/// Map the `Config Expr` generated from a document to the `Schema Expr` with the schema
+/// name, the `Config Expr` items of a `List Expr` are mapped as well.
+pub(crate) fn with_schema_name(expr: NodeRef<Expr>, schema_name: &str) -> NodeRef<Expr> {
+    match &expr.node {
+        Expr::Config(_) => {
+            let loc = expr.pos();
+            let iden = node_ref!(
+                Identifier {
+                    names: vec![Node::new_with_pos(schema_name.to_string(), loc.clone())],
+                    pkgpath: String::new(),
+                    ctx: ExprContext::Load
+                },
+                loc.clone()
+            );
+            node_ref!(
+                Expr::Schema(SchemaExpr {
+                    name: iden,
+                    config: expr,
+                    args: vec![],
+                    kwargs: vec![]
+                }),
+                loc
+            )
+        }
+        Expr::List(_) => {
+            let mut expr = expr;
+            if let Expr::List(list) = &mut expr.node {
+                list.elts = std::mem::take(&mut list.elts)
+                    .into_iter()
+                    .map(|elt| with_schema_name(elt, schema_name))
+                    .collect();
+            }
+            expr
+        }
+        _ => expr,
+    }
+}
+
 impl ExprGenerator<serde_yaml::Value> for ExprBuilder {
     fn generate(
         &self,
@@ -337,10 +465,23 @@ impl ExprGenerator<json_spanned_value::Spanned<json_spanned_value::Value>> for E
         &self,
         value: &json_spanned_value::Spanned<json_spanned_value::Value>,
         schema_name: &Option<String>,
+    ) -> Result<NodeRef<Expr>> {
+        self.generate_json(value, schema_name, 0)
+    }
+}
+
+impl ExprBuilder {
+    /// Generate ast expr from Json with span, where `offset` is the byte offset of the Json
+    /// document in the file e.g., the offset of a line in the JSON Lines.
+    fn generate_json(
+        &self,
+        value: &json_spanned_value::Spanned<json_spanned_value::Value>,
+        schema_name: &Option<String>,
+        offset: usize,
     ) -> Result<NodeRef<Expr>> {
         let loc = self.loader.byte_pos_to_pos_in_sourcemap(
-            new_byte_pos(value.span().0 as u32),
-            new_byte_pos(value.span().1 as u32),
+            new_byte_pos((offset + value.span().0) as u32),
+            new_byte_pos((offset + value.span().1) as u32),
         );
         match value.get_ref() {
             json_spanned_value::Value::Null => Ok(node_ref!(
@@ -406,7 +547,7 @@ impl ExprGenerator<json_spanned_value::Spanned<json_spanned_value::Value>> for E
                 let mut j_arr_ast_nodes: Vec<NodeRef<Expr>> = Vec::new();
                 for j_arr_item in j_arr {
                     j_arr_ast_nodes.push(
-                        self.generate(j_arr_item, schema_name)
+                        self.generate_json(j_arr_item, schema_name, offset)
                             .with_context(|| FAIL_LOAD_VALIDATED_ERR_MSG)?,
                     );
                 }
@@ -425,7 +566,7 @@ impl ExprGenerator<json_spanned_value::Spanned<json_spanned_value::Value>> for E
                     let k_span = k.span();
                     let k = From::from(k.to_string());
                     let v = self
-                        .generate(v, &None)
+                        .generate_json(v, &None, offset)
                         .with_context(|| FAIL_LOAD_VALIDATED_ERR_MSG)?;
 
                     let config_entry = node_ref!(
@@ -433,8 +574,8 @@ impl ExprGenerator<json_spanned_value::Spanned<json_spanned_value::Value>> for E
                             key: Some(node_ref!(
                                 Expr::StringLit(k),
                                 self.loader.byte_pos_to_pos_in_sourcemap(
-                                    new_byte_pos(k_span.0 as u32),
-                                    new_byte_pos(k_span.1 as u32)
+                                    new_byte_pos((offset + k_span.0) as u32),
+                                    new_byte_pos((offset + k_span.1) as u32)
                                 )
                             )),
                             value: v,
@@ -596,3 +737,208 @@ impl ExprGenerator<serde_json::Value> for ExprBuilder {
         }
     }
 }
+
+/// `ExprBuilder` will generate ast expr from TOML with span.
+impl ExprGenerator<TomlSpanned> for ExprBuilder {
+    fn generate(&self, value: &TomlSpanned, schema_name: &Option<String>) -> Result<NodeRef<Expr>> {
+        let expr = self.generate_toml(value)?;
+        Ok(match schema_name {
+            Some(schema_name) => with_schema_name(expr, schema_name),
+            None => expr,
+        })
+    }
+}
+
+impl ExprBuilder {
+    /// Generate ast expr from TOML with span. The keys of TOML have no spans, so the keys
+    /// are located at their values.
+    fn generate_toml(&self, value: &TomlSpanned) -> Result<NodeRef<Expr>> {
+        let (lo, hi) = toml_span(value);
+        let loc = self
+            .loader
+            .byte_pos_to_pos_in_sourcemap(new_byte_pos(lo as u32), new_byte_pos(hi as u32));
+        match value.get_ref() {
+            TomlValue::String(t_string) | TomlValue::Datetime(t_string) => {
+                let str_lit = From::from(t_string.to_string());
+                Ok(node_ref!(Expr::StringLit(str_lit), loc))
+            }
+            TomlValue::Integer(t_int) => Ok(node_ref!(
+                Expr::NumberLit(NumberLit {
+                    binary_suffix: None,
+                    value: NumberLitValue::Int(*t_int)
+                }),
+                loc
+            )),
+            TomlValue::Float(t_float) => {
+                if !t_float.is_finite() {
+                    bail!("{FAIL_LOAD_VALIDATED_ERR_MSG}, Unsupported Number Type");
+                }
+                Ok(node_ref!(
+                    Expr::NumberLit(NumberLit {
+                        binary_suffix: None,
+                        value: NumberLitValue::Float(*t_float)
+                    }),
+                    loc
+                ))
+            }
+            TomlValue::Boolean(t_bool) => {
+                let name_const = match NameConstant::try_from(*t_bool) {
+                    Ok(nc) => nc,
+                    Err(err) => {
+                        bail!("{FAIL_LOAD_VALIDATED_ERR_MSG}, {err}")
+                    }
+                };
+                Ok(node_ref!(
+                    Expr::NameConstantLit(NameConstantLit { value: name_const }),
+                    loc
+                ))
+            }
+            TomlValue::Array(t_arr) => {
+                let mut t_arr_ast_nodes: Vec<NodeRef<Expr>> = Vec::new();
+                for t_arr_item in t_arr {
+                    t_arr_ast_nodes.push(
+                        self.generate_toml(t_arr_item)
+                            .with_context(|| FAIL_LOAD_VALIDATED_ERR_MSG)?,
+                    );
+                }
+                Ok(node_ref!(
+                    Expr::List(ListExpr {
+                        ctx: ExprContext::Load,
+                        elts: t_arr_ast_nodes
+                    }),
+                    loc
+                ))
+            }
+            TomlValue::Table(t_table) => {
+                let mut config_entries: Vec<NodeRef<ConfigEntry>> = Vec::new();
+                for (k, v) in t_table {
+                    let v = self
+                        .generate_toml(v)
+                        .with_context(|| FAIL_LOAD_VALIDATED_ERR_MSG)?;
+                    let k = node_ref!(Expr::StringLit(From::from(k.to_string())), v.pos());
+                    config_entries.push(node_ref!(
+                        ConfigEntry {
+                            key: Some(k),
+                            value: v,
+                            operation: ConfigEntryOperation::Union,
+                        },
+                        loc.clone()
+                    ));
+                }
+                Ok(node_ref!(
+                    Expr::Config(ConfigExpr {
+                        items: config_entries
+                    }),
+                    loc
+                ))
+            }
+        }
+    }
+}
+
+/// Returns the span of the TOML value. The tables defined by the table headers and the
+/// arrays of tables have no spans, they are located at their first value with a span.
+fn toml_span(value: &TomlSpanned) -> (usize, usize) {
+    if value.span() != (0, 0) {
+        return value.span();
+    }
+    let children: Vec<&TomlSpanned> = match value.get_ref() {
+        TomlValue::Array(values) => values.iter().collect(),
+        TomlValue::Table(entries) => entries.iter().map(|(_, v)| v).collect(),
+        _ => vec![],
+    };
+    children
+        .into_iter()
+        .map(toml_span)
+        .find(|span| *span != (0, 0))
+        .unwrap_or((0, 0))
+}
+
+/// `ExprBuilder` will generate a `List Expr` from the CSV records, where the first record
+/// is the header and each of the other records is mapped to a `Config Expr`.
+impl ExprGenerator<Vec<Vec<CsvField>>> for ExprBuilder {
+    fn generate(
+        &self,
+        records: &Vec<Vec<CsvField>>,
+        schema_name: &Option<String>,
+    ) -> Result<NodeRef<Expr>> {
+        let mut elts = vec![];
+        if let Some((header, records)) = records.split_first() {
+            for record in records {
+                elts.push(self.generate_csv_record(header, record)?);
+            }
+        }
+        let loc = self
+            .loader
+            .byte_pos_to_pos_in_sourcemap(new_byte_pos(0), new_byte_pos(0));
+        let expr = node_ref!(
+            Expr::List(ListExpr {
+                ctx: ExprContext::Load,
+                elts
+            }),
+            loc
+        );
+        Ok(match schema_name {
+            Some(schema_name) => with_schema_name(expr, schema_name),
+            None => expr,
+        })
+    }
+}
+
+impl ExprBuilder {
+    /// Generate a `Config Expr` from a CSV record with the header. The fields are mapped
+    /// to strings, or `None` for the unquoted empty fields.
+    fn generate_csv_record(
+        &self,
+        header: &[CsvField],
+        record: &[CsvField],
+    ) -> Result<NodeRef<Expr>> {
+        let record_loc = |field: &CsvField| {
+            self.loader.byte_pos_to_pos_in_sourcemap(
+                new_byte_pos(field.span.0 as u32),
+                new_byte_pos(field.span.1 as u32),
+            )
+        };
+        let loc = record_loc(&record[0]);
+        if record.len() != header.len() {
+            bail!(
+                "{FAIL_LOAD_VALIDATED_ERR_MSG}, expected {} fields but got {} at line {}",
+                header.len(),
+                record.len(),
+                loc.1
+            );
+        }
+        let mut config_entries: Vec<NodeRef<ConfigEntry>> = Vec::new();
+        for (k, v) in header.iter().zip(record) {
+            let v_loc = record_loc(v);
+            let k = node_ref!(Expr::StringLit(From::from(k.value.clone())), v_loc.clone());
+            let v = node_ref!(csv_field_expr(v), v_loc);
+            config_entries.push(node_ref!(
+                ConfigEntry {
+                    key: Some(k),
+                    value: v,
+                    operation: ConfigEntryOperation::Union,
+                },
+                loc.clone()
+            ));
+        }
+        Ok(node_ref!(
+            Expr::Config(ConfigExpr {
+                items: config_entries
+            }),
+            loc
+        ))
+    }
+}
+
+/// CSV has no types, so the fields are mapped to strings except the unquoted empty
+/// fields, which are mapped to `None`.
+fn csv_field_expr(field: &CsvField) -> Expr {
+    if !field.quoted && field.value.is_empty() {
+        Expr::NameConstantLit(NameConstantLit {
+            value: NameConstant::None,
+        })
+    } else {
+        Expr::StringLit(From::from(field.value.clone()))
+    }
+}
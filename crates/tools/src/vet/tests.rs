@@ -230,6 +230,78 @@ mod test_expr_builder {
         }
     }
 
+    #[test]
+    /// Test `expr_builder.build_documents()` with multi-document yaml and json lines.
+    fn test_build_documents() {
+        let yaml = "a: 1\n---\n---\nb: 2\n";
+        let expr_builder = ExprBuilder::new_with_str(LoaderKind::YAML, yaml.to_string()).unwrap();
+        let docs = expr_builder.build_documents().unwrap();
+        assert_eq!(docs.len(), 2);
+        assert_eq!(docs[1].line, 4);
+
+        let jsonl = "{\"a\": 1}\n\n{\"b\": [2]}\n";
+        let expr_builder = ExprBuilder::new_with_str(LoaderKind::JSONL, jsonl.to_string()).unwrap();
+        let docs = expr_builder.build_documents().unwrap();
+        assert_eq!(docs.len(), 2);
+        assert_eq!((docs[1].line, docs[1].column), (3, 0));
+        let err = format!("{:?}", expr_builder.build(None).unwrap_err());
+        assert!(err.contains("found 2 documents"), "{err}");
+        let expr_builder =
+            ExprBuilder::new_with_str(LoaderKind::JSONL, "{\"a\": 1}\n".to_string()).unwrap();
+        assert!(expr_builder.build(None).is_ok());
+
+        let expr_builder =
+            ExprBuilder::new_with_str(LoaderKind::JSONL, "{}\n{\n".to_string()).unwrap();
+        let err = format!("{:?}", expr_builder.build_documents().unwrap_err());
+        assert!(
+            err.contains("Failed to String '{' to Json at line 2"),
+            "{err}"
+        );
+    }
+
+    #[test]
+    /// Test `expr_builder.build_documents()` with toml and csv.
+    fn test_build_documents_with_toml_and_csv() {
+        let toml = "name = \"a\"\n[server]\nport = 80\n";
+        let expr_builder = ExprBuilder::new_with_str(LoaderKind::TOML, toml.to_string()).unwrap();
+        assert_eq!(expr_builder.build_documents().unwrap().len(), 1);
+
+        let csv = "name,count\r\n\"a,\"\"b\"\"\",1\r\n\r\nc,\r\n";
+        let expr_builder = ExprBuilder::new_with_str(LoaderKind::CSV, csv.to_string()).unwrap();
+        let docs = expr_builder.build_documents().unwrap();
+        assert_eq!(docs.len(), 2);
+        assert_eq!((docs[0].line, docs[1].line), (2, 4));
+
+        // The fields are strings except the unquoted empty fields, and the spaces around
+        // the header names are trimmed.
+        let csv = "name, image ,count,tag\nweb,nginx:1.25,01,\"\"\n";
+        let expr_builder = ExprBuilder::new_with_str(LoaderKind::CSV, csv.to_string()).unwrap();
+        let docs = expr_builder.build_documents().unwrap();
+        let got = kcl_ast_pretty::print_ast_node(kcl_ast_pretty::ASTNode::Expr(&docs[0]));
+        assert_eq!(
+            got.replace(['\n', ' '], ""),
+            r#"{"name":"web","image":"nginx:1.25","count":"01","tag":""}"#
+        );
+        let expr_builder =
+            ExprBuilder::new_with_str(LoaderKind::CSV, "a,b\ntrue,\n".to_string()).unwrap();
+        let docs = expr_builder.build_documents().unwrap();
+        let got = kcl_ast_pretty::print_ast_node(kcl_ast_pretty::ASTNode::Expr(&docs[0]));
+        assert_eq!(got.replace(['\n', ' '], ""), r#"{"a":"true","b":None}"#);
+
+        let expr_builder =
+            ExprBuilder::new_with_str(LoaderKind::CSV, "a,b\n1\n".to_string()).unwrap();
+        let err = format!("{:?}", expr_builder.build_documents().unwrap_err());
+        assert!(
+            err.contains("expected 2 fields but got 1 at line 2"),
+            "{err}"
+        );
+
+        let expr_builder =
+            ExprBuilder::new_with_str(LoaderKind::CSV, "a\n\"1\n".to_string()).unwrap();
+        let err = format!("{:?}", expr_builder.build_documents().unwrap_err());
+        assert!(err.contains("unterminated quoted field at line 2"), "{err}");
+    }
+
     #[test]
     /// Test `expr_builder.build()` with files that do not exist.
     fn test_build_with_noexist_file() {
@@ -324,7 +396,7 @@ mod test_validater {
         util::loader::LoaderKind,
         vet::{
             tests::deal_windows_filepath,
-            validator::{ValidateOption, validate},
+            validator::{ValidateOption, validate, validate_documents},
         },
     };
 
@@ -586,6 +658,185 @@ mod test_validater {
         }
     }
 
+    #[test]
+    fn test_validate_multi_documents() {
+        let kcl_path = construct_full_path(
+            &Path::new("multi_doc_cases")
+                .join("manifests.k")
+                .display()
+                .to_string(),
+        )
+        .unwrap();
+        for (kind, suffix) in [(LoaderKind::YAML, "yaml"), (LoaderKind::JSONL, "jsonl")] {
+            let validate_file = |name: &str| {
+                let validated_file_path = construct_full_path(&format!(
+                    "{}.{}",
+                    Path::new("multi_doc_cases").join(name).display(),
+                    suffix
+                ))
+                .unwrap();
+                validate(ValidateOption::new(
+                    None,
+                    "value".to_string(),
+                    validated_file_path,
+                    kind,
+                    Some(kcl_path.clone()),
+                    None,
+                    Default::default(),
+                ))
+            };
+            // Each document is validated with the schema named after its kind.
+            assert!(validate_file("manifests.k").unwrap());
+            // All the failing documents are reported with their positions.
+            let err = validate_file("invalid_manifests.k")
+                .unwrap_err()
+                .to_string();
+            assert!(
+                err.starts_with("2 of 3 documents failed validation"),
+                "{err}"
+            );
+            assert!(err.contains("replicas must be at least 1"), "{err}");
+            assert!(err.contains("the name must not be empty"), "{err}");
+            assert!(!err.contains("document 2 at"), "{err}");
+            // The documents of an unknown kind are not validated with another schema.
+            let err = validate_file("unknown_kind.k").unwrap_err().to_string();
+            assert!(
+                err.starts_with("1 of 2 documents failed validation"),
+                "{err}"
+            );
+            assert!(err.contains("document 2 at"), "{err}");
+            assert!(err.contains("no schema for kind ConfigMap"), "{err}");
+        }
+    }
+
+    #[test]
+    fn test_validate_collect_check_failures() {
+        let case = |name: &str| {
+            construct_full_path(
+                &Path::new("collect_check_cases")
+                    .join(name)
+                    .display()
+                    .to_string(),
+            )
+            .unwrap()
+        };
+        let opt = || {
+            ValidateOption::new(
+                Some("Team".to_string()),
+                "value".to_string(),
+                case("team.k.json"),
+                LoaderKind::JSON,
+                Some(case("team.k")),
+                None,
+                Default::default(),
+            )
+        };
+        // Stop at the first failing check by default.
+        let result = validate_documents(opt()).unwrap();
+        assert_eq!(result.failures.len(), 1);
+        assert_eq!(result.diagnostics().len(), 1);
+        // Report all the failing checks and type mismatches in the schema tree.
+        let result = validate_documents(opt().collect_check_failures(true)).unwrap();
+        assert!(!result.is_valid());
+        let messages: Vec<(String, u64)> = result
+            .diagnostics()
+            .iter()
+            .map(|diag| {
+                let msg = diag.messages.last().unwrap();
+                (msg.message.clone(), msg.range.0.line)
+            })
+            .collect();
+        assert_eq!(
+            messages,
+            vec![
+                (
+                    "Check failed on the condition: the name must not be empty".to_string(),
+                    15
+                ),
+                (
+                    "Check failed on the condition: the member must be an adult".to_string(),
+                    16
+                ),
+                (
+                    "Check failed on the condition: the member must be an adult".to_string(),
+                    16
+                ),
+                (
+                    "Check failed on the condition: a team has at most 2 members".to_string(),
+                    7
+                ),
+            ]
+        );
+        let err = validate(opt().collect_check_failures(true))
+            .unwrap_err()
+            .to_string();
+        assert!(err.contains("a team has at most 2 members"), "{err}");
+        assert!(err.contains("the name must not be empty"), "{err}");
+    }
+
+    #[test]
+    fn test_validate_toml_and_csv() {
+        let case = |name: &str| {
+            construct_full_path(&Path::new("toml_csv_cases").join(name).display().to_string())
+                .unwrap()
+        };
+        let opt = |kcl: &str, data: &str, kind: LoaderKind| {
+            ValidateOption::new(
+                None,
+                "value".to_string(),
+                case(data),
+                kind,
+                Some(case(kcl)),
+                None,
+                Default::default(),
+            )
+            .collect_check_failures(true)
+        };
+        // The errors point at the lines of the values in the TOML file.
+        assert!(validate(opt("service.k", "service.k.toml", LoaderKind::TOML)).unwrap());
+        let result =
+            validate_documents(opt("service.k", "invalid_service.k.toml", LoaderKind::TOML))
+                .unwrap();
+        let messages: Vec<String> = result
+            .diagnostics()
+            .iter()
+            .map(|diag| diag.messages.last().unwrap().message.clone())
+            .collect();
+        assert_eq!(
+            messages,
+            vec![
+                "Check failed on the condition: the email is invalid",
+                "Check failed on the condition: the weight must be positive",
+                "Check failed on the condition: the port must be in [1, 65535]",
+            ]
+        );
+        let instance_pos = &result.diagnostics()[2].messages[0].range.0;
+        assert!(instance_pos.filename.ends_with("invalid_service.k.toml"));
+        assert_eq!(instance_pos.line, 1);
+        // Each CSV record is a document located at its line.
+        assert!(validate(opt("inventory.k", "inventory.k.csv", LoaderKind::CSV)).unwrap());
+        let result = validate_documents(opt(
+            "inventory.k",
+            "invalid_inventory.k.csv",
+            LoaderKind::CSV,
+        ))
+        .unwrap();
+        assert_eq!(result.documents, 3);
+        let failures: Vec<(usize, u64)> = result
+            .failures
+            .iter()
+            .map(|failure| (failure.index, failure.line))
+            .collect();
+        assert_eq!(failures, vec![(1, 3), (2, 4)]);
+        let err = result.err_message().unwrap();
+        assert!(
+            err.starts_with("2 of 3 documents failed validation"),
+            "{err}"
+        );
+        assert!(err.contains("the cpus must be positive"), "{err}");
+        assert!(err.contains("the memory must be at least 0.5 GiB"), "{err}");
+    }
+
     fn test_validate_with_invalid_kcl_path() {
         let opt = ValidateOption::new(
             None,
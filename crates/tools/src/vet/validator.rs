@@ -64,18 +64,34 @@
 //!         name == "Alice"
 //!         age > 10
 //! ```
+//!
+//! A YAML file may contain multiple documents separated by `---`, and a JSON Lines file
+//! contains one Json document per line. Each document is validated separately with the
+//! schema named after its `kind` (e.g., the rendered Kubernetes manifests), and all the
+//! failing documents are reported with their positions.
+//!
+//! By default, the validation of a document stops at its first failing check. With
+//! [ValidateOption::collect_check_failures], all the failing checks and attribute type
+//! mismatches of the schema tree are reported, see [validate_documents] to get them as a
+//! structured list of diagnostics.
 
 use std::collections::HashMap;
+use std::sync::{Arc, RwLock};
 
-use super::expr_builder::ExprBuilder;
+use super::expr_builder::{ExprBuilder, with_schema_name};
 pub use crate::util::loader::LoaderKind;
 use anyhow::Result;
 use kcl_ast::{
-    ast::{AssignStmt, Expr, Node, NodeRef, Program, SchemaStmt, Stmt, Target},
+    ast::{
+        AssignStmt, Expr, LiteralType, Module, Node, NodeRef, Program, SchemaStmt, Stmt, Target,
+        Type,
+    },
     node_ref,
 };
+use kcl_error::{Diagnostic, Level};
 use kcl_parser::{LoadProgramOptions, ParseSessionRef};
-use kcl_runner::{ExecProgramArgs, MapErrorResult, execute};
+use kcl_runner::{ExecProgramArgs, execute};
+use kcl_runtime::PanicInfo;
 
 const TMP_FILE: &str = "validationTempKCLCode.k";
 
@@ -176,6 +192,17 @@ const TMP_FILE: &str = "validationTempKCLCode.k";
 /// }
 /// ```
 pub fn validate(val_opt: ValidateOption) -> Result<bool> {
+    match validate_documents(val_opt)?.err_message() {
+        Some(msg) => Err(anyhow::anyhow!(msg)),
+        None => Ok(true),
+    }
+}
+
+/// Validate all the documents of the file like [validate], and returns the validation
+/// failures of the documents as a structured list instead of an error message.
+///
+/// The loading errors of the KCL code and the validated file are still returned as errors.
+pub fn validate_documents(val_opt: ValidateOption) -> Result<ValidateResult> {
     let k_path = val_opt.kcl_path.unwrap_or_else(|| TMP_FILE.to_string());
     let k_code = val_opt.kcl_code.map_or_else(Vec::new, |code| vec![code]);
 
@@ -197,43 +224,259 @@ pub fn validate(val_opt: ValidateOption) -> Result<bool> {
     )?;
 
     let schemas = filter_schema_stmt_from_prog(&compile_res.program);
-    let schema_name = match val_opt.schema_name {
-        Some(name) => Some(name),
-        None => schemas.first().map(|schema| schema.name.node.clone()),
-    };
 
     let expr_builder =
         ExprBuilder::new_with_file_path(val_opt.validated_file_kind, val_opt.validated_file_path)?;
+    let documents = expr_builder.build_documents()?;
+    let args = ExecProgramArgs {
+        collect_check_failures: val_opt.collect_check_failures,
+        ..Default::default()
+    };
+
+    // Each document is validated with its own program, so that all the failing documents
+    // are reported instead of the first one.
+    let mut failures = vec![];
+    for (index, document) in documents.iter().enumerate() {
+        let pos = document.pos();
+        let schema_name = match &val_opt.schema_name {
+            Some(name) => Some(name.clone()),
+            None => match select_schema(&schemas, document) {
+                Ok(schema_name) => schema_name,
+                Err(err_message) => {
+                    failures.push(DocumentFailure {
+                        index,
+                        filename: pos.0,
+                        line: pos.1,
+                        err_message,
+                        diagnostics: vec![],
+                    });
+                    continue;
+                }
+            },
+        };
+        let validated_expr = match &schema_name {
+            Some(schema_name) => with_schema_name(document.clone(), schema_name),
+            None => document.clone(),
+        };
+        if let Err((err_message, diagnostics)) = validate_expr(
+            clone_program(&compile_res.program),
+            &val_opt.attribute_name,
+            validated_expr,
+            &args,
+        ) {
+            failures.push(DocumentFailure {
+                index,
+                filename: pos.0,
+                line: pos.1,
+                err_message,
+                diagnostics,
+            });
+        }
+    }
+    Ok(ValidateResult {
+        documents: documents.len(),
+        failures,
+    })
+}
 
-    let validated_expr = expr_builder.build(schema_name)?;
+/// The validation result of all the documents in the validated file.
+#[derive(Debug, Default)]
+pub struct ValidateResult {
+    /// The number of the validated documents.
+    pub documents: usize,
+    /// The failing documents in the order of the file.
+    pub failures: Vec<DocumentFailure>,
+}
+
+/// The validation failure of a document.
+#[derive(Debug)]
+pub struct DocumentFailure {
+    /// The index of the document in the validated file starting from 0.
+    pub index: usize,
+    /// The filename of the document.
+    pub filename: String,
+    /// The line where the document starts.
+    pub line: u64,
+    /// The error message of the document.
+    pub err_message: String,
+    /// The error diagnostics of the document e.g., one for each failing check when the
+    /// check failures are collected.
+    pub diagnostics: Vec<Diagnostic>,
+}
+
+impl ValidateResult {
+    /// Whether all the documents are valid.
+    #[inline]
+    pub fn is_valid(&self) -> bool {
+        self.failures.is_empty()
+    }
+
+    /// Returns the error diagnostics of all the failing documents.
+    pub fn diagnostics(&self) -> Vec<Diagnostic> {
+        self.failures
+            .iter()
+            .flat_map(|failure| failure.diagnostics.iter().cloned())
+            .collect()
+    }
+
+    /// Returns the error message of the failing documents if any. The error message of a
+    /// single document is kept as it is.
+    pub fn err_message(&self) -> Option<String> {
+        match self.failures.as_slice() {
+            [] => None,
+            [failure] if self.documents == 1 => Some(failure.err_message.clone()),
+            failures => {
+                let mut msg = format!(
+                    "{} of {} documents failed validation",
+                    failures.len(),
+                    self.documents
+                );
+                for failure in failures {
+                    msg.push_str(&format!(
+                        "\n\ndocument {} at {}:{}:\n{}",
+                        failure.index + 1,
+                        failure.filename,
+                        failure.line,
+                        failure.err_message
+                    ));
+                }
+                Some(msg)
+            }
+        }
+    }
+}
 
-    let assign_stmt = build_assign(&val_opt.attribute_name, validated_expr);
+/// Validate the expression by assigning it to the attribute in the main module of the
+/// program and executing the program. Returns the error message and the error diagnostics
+/// when the validation fails.
+fn validate_expr(
+    program: Program,
+    attr_name: &str,
+    validated_expr: NodeRef<Expr>,
+    args: &ExecProgramArgs,
+) -> std::result::Result<(), (String, Vec<Diagnostic>)> {
+    let assign_stmt = build_assign(attr_name, validated_expr);
 
-    match compile_res.program.pkgs.get(kcl_ast::MAIN_PKG) {
+    match program.pkgs.get(kcl_ast::MAIN_PKG) {
         Some(pkg) => {
             if let Some(module) = pkg.first() {
-                let mut m = compile_res
-                    .program
+                let mut m = program
                     .get_module_mut(module)
                     .expect("Failed to acquire module lock")
                     .unwrap_or_else(|| panic!("module {:?} not found in program", module));
                 m.body.push(assign_stmt);
             } else {
-                return Err(anyhow::anyhow!("No main module found"));
+                return Err(("No main module found".to_string(), vec![]));
             }
         }
         None => {
-            return Err(anyhow::anyhow!("No main package found"));
+            return Err(("No main package found".to_string(), vec![]));
+        }
+    }
+
+    let sess = ParseSessionRef::default();
+    let err_message = match execute(sess.clone(), program, args) {
+        Ok(result) if result.err_message.is_empty() => return Ok(()),
+        Ok(result) => result.err_message,
+        Err(err) => err.to_string(),
+    };
+    let mut diagnostics: Vec<Diagnostic> = sess
+        .1
+        .read()
+        .diagnostics
+        .iter()
+        .filter(|diag| diag.level == Level::Error)
+        .cloned()
+        .collect();
+    if diagnostics.is_empty() {
+        diagnostics.push(PanicInfo::from(err_message.as_str()).into());
+    }
+    Err((err_message, diagnostics))
+}
+
+/// Deep clone the program to validate the documents with their own modules.
+fn clone_program(prog: &Program) -> Program {
+    let clone_modules = |modules: &HashMap<String, Arc<RwLock<Module>>>| {
+        modules
+            .iter()
+            .map(|(name, module)| {
+                let module = module
+                    .read()
+                    .expect("Failed to acquire module lock")
+                    .clone();
+                (name.clone(), Arc::new(RwLock::new(module)))
+            })
+            .collect()
+    };
+    Program {
+        root: prog.root.clone(),
+        pkgs: prog.pkgs.clone(),
+        pkgs_not_imported: prog.pkgs_not_imported.clone(),
+        modules: clone_modules(&prog.modules),
+        modules_not_imported: clone_modules(&prog.modules_not_imported),
+    }
+}
+
+/// Select the schema of the document when the schema name is omitted. The schema named
+/// after the document `kind` is selected when its `apiVersion` attribute matches the
+/// document `apiVersion`, e.g., the `Deployment` schema for a Kubernetes manifest
+/// `{"apiVersion": "apps/v1", "kind": "Deployment"}`. The first schema is used only when
+/// the document has no `kind` or there is a single schema, otherwise an error message is
+/// returned for the document.
+fn select_schema(
+    schemas: &[SchemaStmt],
+    document: &NodeRef<Expr>,
+) -> std::result::Result<Option<String>, String> {
+    let first = || schemas.first().map(|schema| schema.name.node.clone());
+    let Some(kind) = config_str_value(document, "kind") else {
+        return Ok(first());
+    };
+    let api_version = config_str_value(document, "apiVersion");
+    let schema = schemas.iter().find(|schema| {
+        schema.name.node == kind
+            && match (&api_version, schema_str_attr(schema, "apiVersion")) {
+                (Some(api_version), Some(schema_api_version)) => *api_version == schema_api_version,
+                _ => true,
+            }
+    });
+    match schema {
+        Some(schema) => Ok(Some(schema.name.node.clone())),
+        None if schemas.len() <= 1 => Ok(first()),
+        None => Err(format!("no schema for kind {kind}")),
+    }
+}
+
+/// Returns the string value of the key in the config expression.
+fn config_str_value(expr: &NodeRef<Expr>, key: &str) -> Option<String> {
+    if let Expr::Config(config) = &expr.node {
+        for item in &config.items {
+            if let (Some(Expr::StringLit(k)), Expr::StringLit(v)) = (
+                item.node.key.as_ref().map(|k| &k.node),
+                &item.node.value.node,
+            ) && k.value == key
+            {
+                return Some(v.value.clone());
+            }
         }
     }
+    None
+}
 
-    execute(
-        ParseSessionRef::default(),
-        compile_res.program,
-        &ExecProgramArgs::default(),
-    )
-    .map_err_to_result()
-    .map(|_| true)
+/// Returns the string literal type or the string default value of the schema attribute.
+fn schema_str_attr(schema: &SchemaStmt, attr: &str) -> Option<String> {
+    schema.body.iter().find_map(|stmt| match &stmt.node {
+        Stmt::SchemaAttr(schema_attr) if schema_attr.name.node == attr => {
+            match (
+                &schema_attr.ty.node,
+                schema_attr.value.as_ref().map(|v| &v.node),
+            ) {
+                (Type::Literal(LiteralType::Str(value)), _) => Some(value.clone()),
+                (_, Some(Expr::StringLit(value))) => Some(value.value.clone()),
+                _ => None,
+            }
+        }
+        _ => None,
+    })
 }
 
 fn build_assign(attr_name: &str, node: NodeRef<Expr>) -> NodeRef<Stmt> {
@@ -275,6 +518,7 @@ pub struct ValidateOption {
     kcl_path: Option<String>,
     kcl_code: Option<String>,
     package_maps: HashMap<String, String>,
+    collect_check_failures: bool,
 }
 
 impl ValidateOption {
@@ -295,6 +539,13 @@ impl ValidateOption {
             kcl_path,
             kcl_code,
             package_maps,
+            collect_check_failures: false,
         }
     }
+
+    /// Set whether to report all the failing checks of a document instead of the first one.
+    pub fn collect_check_failures(mut self, collect: bool) -> Self {
+        self.collect_check_failures = collect;
+        self
+    }
 }
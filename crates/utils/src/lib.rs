@@ -1,3 +1,4 @@
 pub mod fslock;
+pub mod parallel;
 pub mod path;
 pub mod pkgpath;
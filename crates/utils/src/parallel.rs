@@ -0,0 +1,132 @@
+//! Run independent tasks concurrently on scoped threads.
+//!
+//! The number of threads defaults to the available parallelism of the machine and can be
+//! limited with the `KCL_THREADS` environment variable, where `KCL_THREADS=1` runs all the
+//! tasks sequentially on the current thread.
+
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+/// The environment variable to limit the number of threads.
+pub const KCL_THREADS_ENV: &str = "KCL_THREADS";
+
+/// Returns the maximum number of threads used to run the tasks concurrently.
+pub fn num_threads() -> usize {
+    if cfg!(target_arch = "wasm32") {
+        return 1;
+    }
+    match std::env::var(KCL_THREADS_ENV)
+        .ok()
+        .and_then(|threads| threads.trim().parse::<usize>().ok())
+    {
+        Some(threads) if threads > 0 => threads,
+        _ => std::thread::available_parallelism()
+            .map(|n| n.get())
+            .unwrap_or(1),
+    }
+}
+
+/// Map the items with `f` concurrently and returns the results in the order of the items,
+/// so the results are deterministic regardless of the scheduling of the threads. The items
+/// are mapped on the current thread when there are not enough items or threads.
+///
+/// # Examples
+///
+/// ```
+/// use kcl_utils::parallel::par_map;
+///
+/// let squares = par_map(&[1, 2, 3, 4], |n| n * n);
+/// assert_eq!(squares, vec![1, 4, 9, 16]);
+/// ```
+pub fn par_map<T, R, F>(items: &[T], f: F) -> Vec<R>
+where
+    T: Sync,
+    R: Send,
+    F: Fn(&T) -> R + Sync,
+{
+    par_map_with_threads(items, num_threads(), f)
+}
+
+/// Map the items with `f` concurrently on at most `threads` threads, see [par_map]. The
+/// number of threads defaults to [num_threads] when `threads` is zero.
+///
+/// # Examples
+///
+/// ```
+/// use kcl_utils::parallel::par_map_with_threads;
+///
+/// let squares = par_map_with_threads(&[1, 2, 3, 4], 2, |n| n * n);
+/// assert_eq!(squares, vec![1, 4, 9, 16]);
+/// ```
+pub fn par_map_with_threads<T, R, F>(items: &[T], threads: usize, f: F) -> Vec<R>
+where
+    T: Sync,
+    R: Send,
+    F: Fn(&T) -> R + Sync,
+{
+    let threads = if threads == 0 { num_threads() } else { threads };
+    let threads = if cfg!(target_arch = "wasm32") {
+        1
+    } else {
+        threads.min(items.len())
+    };
+    if threads <= 1 {
+        return items.iter().map(f).collect();
+    }
+    let next = AtomicUsize::new(0);
+    let mut results: Vec<Option<R>> = std::iter::repeat_with(|| None).take(items.len()).collect();
+    std::thread::scope(|scope| {
+        let workers: Vec<_> = (0..threads)
+            .map(|_| {
+                scope.spawn(|| {
+                    let mut done = vec![];
+                    loop {
+                        let index = next.fetch_add(1, Ordering::Relaxed);
+                        match items.get(index) {
+                            Some(item) => done.push((index, f(item))),
+                            None => break done,
+                        }
+                    }
+                })
+            })
+            .collect();
+        for worker in workers {
+            match worker.join() {
+                Ok(done) => {
+                    for (index, result) in done {
+                        results[index] = Some(result);
+                    }
+                }
+                Err(err) => std::panic::resume_unwind(err),
+            }
+        }
+    });
+    results
+        .into_iter()
+        .map(|result| result.expect("every item is mapped by a worker"))
+        .collect()
+}
+
+#[test]
+fn test_par_map_in_order() {
+    let items: Vec<usize> = (0..1000).collect();
+    let results = par_map(&items, |n| {
+        // Unbalanced tasks finish out of order.
+        if n % 7 == 0 {
+            std::thread::yield_now();
+        }
+        n * 2
+    });
+    assert_eq!(results, items.iter().map(|n| n * 2).collect::<Vec<_>>());
+    assert!(par_map(&[] as &[usize], |n| *n).is_empty());
+}
+
+#[test]
+#[should_panic(expected = "task failed")]
+fn test_par_map_panic() {
+    par_map(&[1, 2, 3, 4], |n| {
+        if *n == 3 {
+            panic!("task failed");
+        }
+        *n
+    });
+}